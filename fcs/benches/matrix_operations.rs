@@ -23,10 +23,10 @@ fn generate_compensation_matrix(n: usize) -> Array2<f32> {
         for j in 0..n {
             if i == j {
                 // Diagonal: make it dominant
-                matrix[[i, j]] = 1.0 + rng.gen_range(0.0..0.1);
+                matrix[[i, j]] = 1.0 + rng.random_range(0.0..0.1);
             } else {
                 // Off-diagonal: small values
-                matrix[[i, j]] = rng.gen_range(-0.1..0.1);
+                matrix[[i, j]] = rng.random_range(-0.1..0.1);
             }
         }
     }
@@ -44,7 +44,7 @@ fn generate_channel_data(n_channels: usize, n_events: usize) -> Vec<Vec<f32>> {
     let mut data = Vec::with_capacity(n_channels);
 
     for _ in 0..n_channels {
-        let channel: Vec<f32> = (0..n_events).map(|_| rng.gen_range(0.0..1000.0)).collect();
+        let channel: Vec<f32> = (0..n_events).map(|_| rng.random_range(0.0..1000.0)).collect();
         data.push(channel);
     }
 
@@ -99,6 +99,35 @@ fn bench_batch_matvec_cpu(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_matrix_inversion, bench_batch_matvec_cpu);
+fn bench_compensate_parameters_end_to_end(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compensate_parameters_end_to_end");
+
+    // The realistic hot path: invert + matrixmultiply/BLAS-backed `dot`, at a full-spectrum
+    // 30-color panel size to confirm the matmul kernel keeps up on multi-million-event files
+    let test_cases = vec![(30, 1_000_000), (30, 5_000_000)];
+
+    for &(n_channels, n_events) in &test_cases {
+        let matrix = generate_compensation_matrix(n_channels);
+        let channel_data = generate_channel_data(n_channels, n_events);
+
+        group.throughput(Throughput::Elements((n_channels * n_events) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("CPU", format!("{}ch_{}ev", n_channels, n_events)),
+            &(&matrix, &channel_data),
+            |b, (m, d)| {
+                b.iter(|| black_box(MatrixOps::compensate_parameters(m, d)).unwrap());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_matrix_inversion,
+    bench_batch_matvec_cpu,
+    bench_compensate_parameters_end_to_end
+);
 
 criterion_main!(benches);
@@ -0,0 +1,116 @@
+//! Typed accessors for acquisition and instrument metadata
+//!
+//! Wraps the handful of keywords that describe *when* and *on what* a file was acquired into a
+//! single [`AcquisitionInfo`] struct, so callers don't have to fetch each keyword by name and
+//! juggle the FCS 3.2 vs. legacy naming split themselves.
+
+use crate::file::Fcs;
+use crate::keyword::StringableKeyword;
+use crate::metadata::Metadata;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Acquisition and instrument metadata parsed from a file's keywords
+///
+/// Every field is `None` if the corresponding keyword is absent or doesn't parse; a missing
+/// value isn't treated as an error, since most of these keywords are optional in the standard.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AcquisitionInfo {
+    /// Acquisition start, from `$BEGINDATETIME` (FCS 3.2+) or `$DATE`+`$BTIM` (legacy)
+    pub begin: Option<NaiveDateTime>,
+    /// Acquisition end, from `$ENDDATETIME` (FCS 3.2+) or `$DATE`+`$ETIM` (legacy)
+    pub end: Option<NaiveDateTime>,
+    /// Flow cytometer type/model (`$CYT`)
+    pub cytometer: Option<String>,
+    /// Operator name (`$OP`)
+    pub operator: Option<String>,
+    /// Sample carrier identifier: `$CARRIERID` (FCS 3.2+) or `$PLATEID` (legacy)
+    pub carrier_id: Option<String>,
+    /// Location within the carrier: `$LOCATIONID` (FCS 3.2+) or `$WELLID` (legacy)
+    pub location_id: Option<String>,
+    /// Sample volume, in microliters (`$VOL`)
+    pub volume_ul: Option<f32>,
+    /// Flow rate, in microliters/second (`$FLOWRATE`)
+    pub flow_rate_ul_per_s: Option<f32>,
+}
+
+impl AcquisitionInfo {
+    /// Parses acquisition/instrument metadata from `fcs`, preferring FCS 3.2 keywords and
+    /// falling back to their legacy 2.0/3.0/3.1 equivalents where one exists.
+    #[must_use]
+    pub fn from_fcs(fcs: &Fcs) -> Self {
+        let metadata = &fcs.metadata;
+
+        let begin = get_string(metadata, "$BEGINDATETIME")
+            .and_then(|value| parse_iso_datetime(&value))
+            .or_else(|| combine_legacy_datetime(metadata, "$BTIM"));
+        let end = get_string(metadata, "$ENDDATETIME")
+            .and_then(|value| parse_iso_datetime(&value))
+            .or_else(|| combine_legacy_datetime(metadata, "$ETIM"));
+
+        Self {
+            begin,
+            end,
+            cytometer: get_string(metadata, "$CYT"),
+            operator: get_string(metadata, "$OP"),
+            carrier_id: get_string(metadata, "$CARRIERID")
+                .or_else(|| get_string(metadata, "$PLATEID")),
+            location_id: get_string(metadata, "$LOCATIONID")
+                .or_else(|| get_string(metadata, "$WELLID")),
+            volume_ul: get_string(metadata, "$VOL").and_then(|value| value.parse().ok()),
+            flow_rate_ul_per_s: get_string(metadata, "$FLOWRATE")
+                .and_then(|value| value.parse().ok()),
+        }
+    }
+}
+
+/// Look up any string keyword's value by key, regardless of which `StringKeyword` variant it
+/// parsed into
+fn get_string(metadata: &Metadata, key: &str) -> Option<String> {
+    metadata
+        .get_string_keyword(key)
+        .ok()
+        .map(|value| value.get_str().into_owned())
+}
+
+/// Parses an FCS 3.2 `$BEGINDATETIME`/`$ENDDATETIME` ISO-8601 value
+fn parse_iso_datetime(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+}
+
+/// Combines the legacy `$DATE` (`dd-mmm-yyyy`) keyword with `$BTIM`/`$ETIM` (`hh:mm:ss[.cc]`)
+/// into a single datetime
+fn combine_legacy_datetime(metadata: &Metadata, time_key: &str) -> Option<NaiveDateTime> {
+    let date = parse_legacy_date(&get_string(metadata, "$DATE")?)?;
+    let time = parse_legacy_time(&get_string(metadata, time_key)?)?;
+    Some(NaiveDateTime::new(date, time))
+}
+
+fn parse_legacy_date(date: &str) -> Option<NaiveDate> {
+    let mut parts = date.split('-');
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = match parts.next()?.to_ascii_uppercase().as_str() {
+        "JAN" => 1,
+        "FEB" => 2,
+        "MAR" => 3,
+        "APR" => 4,
+        "MAY" => 5,
+        "JUN" => 6,
+        "JUL" => 7,
+        "AUG" => 8,
+        "SEP" => 9,
+        "OCT" => 10,
+        "NOV" => 11,
+        "DEC" => 12,
+        _ => return None,
+    };
+    let year: i32 = parts.next()?.parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+fn parse_legacy_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+        .ok()
+}
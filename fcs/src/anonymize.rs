@@ -0,0 +1,86 @@
+//! Anonymization of identifying metadata for external sharing
+//!
+//! [`anonymize`] strips the keywords that most commonly identify who or where a file came from -
+//! operator name, source filename, acquisition dates, and free-text experiment/source labels -
+//! and regenerates `$GUID`, so a cleaned file can be handed to a collaborator or uploaded to a
+//! public repository without carrying that back-reference. Event data and every keyword needed
+//! to interpret it ($PAR, $PnN, compensation, transforms, etc.) are left untouched.
+
+use crate::metadata::Metadata;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Which categories of identifying keywords [`anonymize`] strips. All default to `true`.
+#[derive(Debug, Clone, Copy)]
+pub struct AnonymizeOptions {
+    /// Strip `$OP` (operator name)
+    pub strip_operator: bool,
+    /// Strip `$FIL` (original filename)
+    pub strip_filename: bool,
+    /// Strip `$DATE`, `$BTIM`, `$ETIM`, `$BEGINDATETIME`, `$ENDDATETIME`
+    pub strip_dates: bool,
+    /// Strip `$SRC` (source/specimen label), `$EXP` (experiment name), and `$PROJ` (project name)
+    pub strip_source_labels: bool,
+    /// Replace `$GUID` with a freshly generated one, so the anonymized file can't be matched
+    /// back to the original via a shared identifier
+    pub regenerate_guid: bool,
+}
+
+impl Default for AnonymizeOptions {
+    fn default() -> Self {
+        Self {
+            strip_operator: true,
+            strip_filename: true,
+            strip_dates: true,
+            strip_source_labels: true,
+            regenerate_guid: true,
+        }
+    }
+}
+
+/// Record of what [`anonymize`] actually changed, for a manifest or `--verbose` log
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnonymizeReport {
+    /// Keywords that were present and removed
+    pub stripped_keywords: Vec<String>,
+    /// The freshly generated `$GUID`, if [`AnonymizeOptions::regenerate_guid`] was set
+    pub new_guid: Option<String>,
+}
+
+const OPERATOR_KEYWORDS: [&str; 1] = ["$OP"];
+const FILENAME_KEYWORDS: [&str; 1] = ["$FIL"];
+const DATE_KEYWORDS: [&str; 5] = ["$DATE", "$BTIM", "$ETIM", "$BEGINDATETIME", "$ENDDATETIME"];
+const SOURCE_LABEL_KEYWORDS: [&str; 3] = ["$SRC", "$EXP", "$PROJ"];
+
+/// Strip identifying keywords from `metadata` per `options`, returning a report of what changed
+pub fn anonymize(metadata: &mut Metadata, options: &AnonymizeOptions) -> AnonymizeReport {
+    let mut report = AnonymizeReport::default();
+
+    let mut groups: Vec<&[&str]> = Vec::new();
+    if options.strip_operator {
+        groups.push(&OPERATOR_KEYWORDS);
+    }
+    if options.strip_filename {
+        groups.push(&FILENAME_KEYWORDS);
+    }
+    if options.strip_dates {
+        groups.push(&DATE_KEYWORDS);
+    }
+    if options.strip_source_labels {
+        groups.push(&SOURCE_LABEL_KEYWORDS);
+    }
+
+    for keyword in groups.into_iter().flatten() {
+        if metadata.remove_keyword(keyword).unwrap_or(false) {
+            report.stripped_keywords.push((*keyword).to_string());
+        }
+    }
+
+    if options.regenerate_guid {
+        let new_guid = Uuid::new_v4().to_string();
+        metadata.insert_string_keyword("GUID".to_string(), new_guid.clone());
+        report.new_guid = Some(new_guid);
+    }
+
+    report
+}
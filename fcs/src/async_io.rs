@@ -0,0 +1,87 @@
+//! Async, non-blocking FCS file loading (requires the `tokio` feature)
+//!
+//! [`Fcs::open`] performs blocking file I/O and CPU-bound DataFrame construction directly
+//! on the calling thread, which is fine for CLI tools but stalls an async runtime's worker
+//! threads when called from a server or Tauri backend. [`open_async`] and [`load_batch`]
+//! move that work onto tokio's blocking thread pool instead, so callers can await file
+//! loads without blocking the reactor.
+
+use crate::file::Fcs;
+use anyhow::{Result, anyhow};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Opens and parses an FCS file without blocking the async runtime
+///
+/// Runs [`Fcs::open`] via `tokio::task::spawn_blocking`, since file I/O and DataFrame
+/// construction are both blocking/CPU-bound.
+/// # Errors
+/// Will return `Err` under the same conditions as [`Fcs::open`], or if the blocking task
+/// panics or is cancelled.
+pub async fn open_async(path: impl Into<String>) -> Result<Fcs> {
+    let path = path.into();
+    tokio::task::spawn_blocking(move || Fcs::open(&path))
+        .await
+        .map_err(|e| anyhow!("Blocking load task panicked or was cancelled: {e}"))?
+}
+
+/// Loads many FCS files concurrently, bounded by `concurrency` and a total `memory_budget`
+/// (in bytes)
+///
+/// Useful for server and Tauri backends that need to load an entire directory of files
+/// without spawning unbounded concurrent tasks or exceeding available memory. Each file's
+/// on-disk size is reserved against `memory_budget` before it starts loading, and released
+/// once loaded, so a directory of large files may run with less than `concurrency`
+/// parallelism rather than exhausting memory.
+///
+/// Returns one `Result<Fcs>` per input path, in the same order as `paths`; a failure
+/// loading one file does not prevent the others from loading.
+pub async fn load_batch(
+    paths: Vec<String>,
+    concurrency: usize,
+    memory_budget: u64,
+) -> Vec<Result<Fcs>> {
+    let concurrency_limiter = Arc::new(Semaphore::new(concurrency.max(1)));
+    let total_memory_permits =
+        u32::try_from(memory_budget.max(1).min(u64::from(u32::MAX))).unwrap_or(u32::MAX);
+    let memory_limiter = Arc::new(Semaphore::new(total_memory_permits as usize));
+
+    let handles: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let concurrency_limiter = Arc::clone(&concurrency_limiter);
+            let memory_limiter = Arc::clone(&memory_limiter);
+            tokio::spawn(async move {
+                let _concurrency_permit = concurrency_limiter
+                    .acquire()
+                    .await
+                    .expect("concurrency semaphore is never closed");
+
+                // Reserve permits proportional to the file's on-disk size, clamped to the
+                // semaphore's total capacity so a single huge file can't deadlock the batch.
+                let file_size = tokio::fs::metadata(&path)
+                    .await
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                let permits = u32::try_from(file_size)
+                    .unwrap_or(u32::MAX)
+                    .clamp(1, total_memory_permits);
+                let _memory_permit = memory_limiter
+                    .acquire_many(permits)
+                    .await
+                    .expect("memory semaphore is never closed");
+
+                open_async(path).await
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(match handle.await {
+            Ok(result) => result,
+            Err(e) => Err(anyhow!("Load task panicked or was cancelled: {e}")),
+        });
+    }
+    results
+}
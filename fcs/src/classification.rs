@@ -0,0 +1,26 @@
+//! Classification columns: per-event categorical labels
+//!
+//! A standard naming convention for attaching integer category codes - gate membership,
+//! cluster ID, QC pass/fail flag - as ordinary DataFrame columns. Prefixing the channel name
+//! with [`CLASSIFICATION_PREFIX`] lets consumers tell a classification column apart from a
+//! physical measurement channel at a glance, without needing a separate storage mechanism:
+//! classification columns are built by [`crate::write::add_classification_column`] as regular
+//! parameters, so they ride through [`crate::write::filter_events`] and
+//! [`crate::write::concatenate_events`] and export to FCS/CSV/Parquet like any other channel.
+
+/// Prefix marking a `$PnN` channel name as a classification column rather than a physical
+/// measurement
+pub const CLASSIFICATION_PREFIX: &str = "class:";
+
+/// Builds the `$PnN` channel name for a classification column named `name`
+#[must_use]
+pub fn classification_channel_name(name: &str) -> String {
+    format!("{CLASSIFICATION_PREFIX}{name}")
+}
+
+/// Whether `channel_name` is a classification column, by the [`CLASSIFICATION_PREFIX`] naming
+/// convention
+#[must_use]
+pub fn is_classification_channel(channel_name: &str) -> bool {
+    channel_name.starts_with(CLASSIFICATION_PREFIX)
+}
@@ -0,0 +1,299 @@
+//! Named compensation/spillover matrix editing
+//!
+//! Wraps a compensation matrix with channel-name-indexed rows/columns so callers can read
+//! and edit individual spillover coefficients by channel name, validate the result before
+//! applying it, and diff it against the matrix stored in a file's `$SPILLOVER` keyword. This
+//! is the building block an interactive compensation editor would sit on top of; the actual
+//! event-data math still goes through [`Fcs::apply_compensation`].
+
+use crate::file::Fcs;
+use crate::matrix::MatrixOps;
+use crate::parameter::EventDataFrame;
+use anyhow::{Result, anyhow};
+use ndarray::Array2;
+
+/// A named compensation/spillover matrix
+///
+/// Rows and columns share the same channel ordering: `matrix[[i, j]]` is the fraction of
+/// `channels[i]`'s true signal that spills into `channels[j]`'s detector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompensationMatrix {
+    channels: Vec<String>,
+    matrix: Array2<f32>,
+}
+
+/// A single changed coefficient between two [`CompensationMatrix`] instances
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompensationDiff {
+    pub from_channel: String,
+    pub into_channel: String,
+    pub old_value: f32,
+    pub new_value: f32,
+}
+
+impl CompensationMatrix {
+    /// Creates a new matrix; `matrix` must be square with one row/column per channel name.
+    ///
+    /// # Errors
+    /// Will return `Err` if `matrix` is not `channels.len()` x `channels.len()`.
+    pub fn new(channels: Vec<String>, matrix: Array2<f32>) -> Result<Self> {
+        let n = channels.len();
+        if matrix.nrows() != n || matrix.ncols() != n {
+            return Err(anyhow!(
+                "Compensation matrix must be {n}x{n} to match {n} channel names, got {rows}x{cols}",
+                n = n,
+                rows = matrix.nrows(),
+                cols = matrix.ncols()
+            ));
+        }
+        Ok(Self { channels, matrix })
+    }
+
+    /// Builds an identity matrix (no compensation applied) for the given channels
+    #[must_use]
+    pub fn identity(channels: Vec<String>) -> Self {
+        let n = channels.len();
+        Self {
+            channels,
+            matrix: Array2::eye(n),
+        }
+    }
+
+    /// Reads the file's `$SPILLOVER`/`$SPILL`/`$COMP` matrix, if present
+    ///
+    /// # Errors
+    /// Will return `Err` if the keyword exists but is malformed (see
+    /// [`Fcs::get_spillover_matrix`]).
+    pub fn from_fcs(fcs: &Fcs) -> Result<Option<Self>> {
+        Ok(fcs
+            .get_spillover_matrix()?
+            .map(|(matrix, channels)| Self { channels, matrix }))
+    }
+
+    /// Channel names, in matrix row/column order
+    #[must_use]
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// The underlying coefficient matrix
+    #[must_use]
+    pub fn as_array(&self) -> &Array2<f32> {
+        &self.matrix
+    }
+
+    fn index_of(&self, channel: &str) -> Result<usize> {
+        self.channels
+            .iter()
+            .position(|c| c == channel)
+            .ok_or_else(|| anyhow!("Unknown channel {channel} in compensation matrix"))
+    }
+
+    /// Returns the spillover coefficient from `from_channel` into `into_channel`
+    ///
+    /// # Errors
+    /// Will return `Err` if either channel isn't in this matrix.
+    pub fn get(&self, from_channel: &str, into_channel: &str) -> Result<f32> {
+        let i = self.index_of(from_channel)?;
+        let j = self.index_of(into_channel)?;
+        Ok(self.matrix[[i, j]])
+    }
+
+    /// Sets the spillover coefficient from `from_channel` into `into_channel`
+    ///
+    /// # Errors
+    /// Will return `Err` if either channel isn't in this matrix.
+    pub fn set(&mut self, from_channel: &str, into_channel: &str, value: f32) -> Result<()> {
+        let i = self.index_of(from_channel)?;
+        let j = self.index_of(into_channel)?;
+        self.matrix[[i, j]] = value;
+        Ok(())
+    }
+
+    /// Validates the matrix is usable as a compensation matrix: every diagonal coefficient
+    /// is (approximately) 1.0, and the matrix is invertible
+    ///
+    /// # Errors
+    /// Will return `Err` describing the first violation found: a non-unity diagonal entry,
+    /// or a singular (non-invertible) matrix.
+    pub fn validate(&self) -> Result<()> {
+        for (i, channel) in self.channels.iter().enumerate() {
+            let diagonal = self.matrix[[i, i]];
+            if (diagonal - 1.0).abs() > 1e-6 {
+                return Err(anyhow!(
+                    "Diagonal coefficient for {channel} must be 1.0, got {diagonal}"
+                ));
+            }
+        }
+
+        MatrixOps::invert_matrix(&self.matrix)
+            .map(|_| ())
+            .map_err(|e| anyhow!("Compensation matrix is not invertible: {e}"))
+    }
+
+    /// Compares this matrix against the file's `$SPILLOVER` matrix, returning every
+    /// coefficient that changed
+    ///
+    /// Channels present in `self` but not in the file's matrix (or vice versa) are skipped
+    /// rather than treated as a difference, since there's no prior value to compare against.
+    ///
+    /// # Errors
+    /// Will return `Err` if the file has no `$SPILLOVER` matrix to diff against, or if it
+    /// exists but is malformed.
+    pub fn diff_against_file(&self, fcs: &Fcs) -> Result<Vec<CompensationDiff>> {
+        let original = Self::from_fcs(fcs)?
+            .ok_or_else(|| anyhow!("File has no $SPILLOVER matrix to diff against"))?;
+
+        let mut diffs = Vec::new();
+        for from_channel in &self.channels {
+            for into_channel in &self.channels {
+                let new_value = self.get(from_channel, into_channel)?;
+                let Ok(old_value) = original.get(from_channel, into_channel) else {
+                    continue;
+                };
+                if (new_value - old_value).abs() > f32::EPSILON {
+                    diffs.push(CompensationDiff {
+                        from_channel: from_channel.clone(),
+                        into_channel: into_channel.clone(),
+                        old_value,
+                        new_value,
+                    });
+                }
+            }
+        }
+        Ok(diffs)
+    }
+
+    /// Applies this matrix to `fcs`'s event data via [`Fcs::apply_compensation`]
+    ///
+    /// # Errors
+    /// Will return `Err` if the matrix is singular or a channel doesn't exist in `fcs`.
+    pub fn apply(&self, fcs: &Fcs) -> Result<EventDataFrame> {
+        let channel_refs: Vec<&str> = self.channels.iter().map(String::as_str).collect();
+        fcs.apply_compensation(&self.matrix, &channel_refs)
+    }
+}
+
+/// Computes a [`CompensationMatrix`] from a set of single-stained control files plus an
+/// unstained control, following the approach flowCore's `spillover()` uses
+///
+/// For each `(channel, control)` pair, the unstained control's median and MAD on `channel`
+/// establish a positive/negative threshold; the spillover coefficient into every other
+/// channel is then the ratio of the positive/negative median difference on that channel to
+/// the positive/negative median difference on `channel` itself. This is a median-based
+/// slope estimate, not a full AutoSpill iterative refinement.
+///
+/// # Errors
+/// Will return `Err` if `controls` is empty, a channel name doesn't exist in its control
+/// file or the unstained control, or a control's primary channel has no events on one side
+/// of the positive/negative threshold (making the slope undefined).
+pub fn spillover_from_controls(
+    unstained: &Fcs,
+    controls: &[(String, &Fcs)],
+) -> Result<CompensationMatrix> {
+    if controls.is_empty() {
+        return Err(anyhow!(
+            "At least one single-stain control is required to compute spillover"
+        ));
+    }
+
+    let channels: Vec<String> = controls.iter().map(|(channel, _)| channel.clone()).collect();
+    let n = channels.len();
+    let mut matrix = Array2::<f32>::eye(n);
+
+    for (i, (primary_channel, control)) in controls.iter().enumerate() {
+        let unstained_primary = unstained.get_parameter_events_slice(primary_channel)?;
+        let (background_median, background_mad) = median_mad(unstained_primary);
+        let threshold = background_median + 3.0 * background_mad;
+
+        let primary_data = control.get_parameter_events_slice(primary_channel)?;
+        let (positive, negative): (Vec<f32>, Vec<f32>) =
+            primary_data.iter().copied().partition(|&v| v > threshold);
+        if positive.is_empty() || negative.is_empty() {
+            return Err(anyhow!(
+                "Control for {primary_channel} has no events on one side of the positive/negative threshold ({} positive, {} negative)",
+                positive.len(),
+                negative.len()
+            ));
+        }
+
+        let (primary_positive_median, _) = median_mad(&positive);
+        let (primary_negative_median, _) = median_mad(&negative);
+        let primary_spread = primary_positive_median - primary_negative_median;
+
+        for (j, spill_channel) in channels.iter().enumerate() {
+            if i == j {
+                continue; // diagonal stays 1.0
+            }
+
+            let spill_data = control.get_parameter_events_slice(spill_channel)?;
+            let positive_spill: Vec<f32> = primary_data
+                .iter()
+                .zip(spill_data)
+                .filter(|&(&primary, _)| primary > threshold)
+                .map(|(_, &spill)| spill)
+                .collect();
+            let negative_spill: Vec<f32> = primary_data
+                .iter()
+                .zip(spill_data)
+                .filter(|&(&primary, _)| primary <= threshold)
+                .map(|(_, &spill)| spill)
+                .collect();
+
+            let (spill_positive_median, _) = median_mad(&positive_spill);
+            let (spill_negative_median, _) = median_mad(&negative_spill);
+
+            matrix[[i, j]] = (spill_positive_median - spill_negative_median) / primary_spread;
+        }
+    }
+
+    CompensationMatrix::new(channels, matrix)
+}
+
+/// Extracts a per-channel autofluorescence signature from an unstained control, suitable as
+/// an extra component in a spectral unmixing matrix (see
+/// [`Fcs::apply_spectral_unmixing_with_autofluorescence`](crate::file::Fcs::apply_spectral_unmixing_with_autofluorescence))
+///
+/// The unstained control's own median per-channel signal *is* its autofluorescence
+/// spectrum, so unmixing can treat "how much AF" as just another abundance to solve for
+/// alongside the labeled fluorophores - standard practice in spectral cytometry.
+///
+/// # Errors
+/// Will return `Err` if a channel doesn't exist in `unstained`.
+pub fn extract_autofluorescence_spectrum(
+    unstained: &Fcs,
+    channel_names: &[&str],
+) -> Result<Vec<f32>> {
+    channel_names
+        .iter()
+        .map(|&channel| {
+            let data = unstained.get_parameter_events_slice(channel)?;
+            Ok(median_mad(data).0)
+        })
+        .collect()
+}
+
+/// Median and median absolute deviation of `data`; returns `(0.0, 0.0)` for an empty slice
+pub(crate) fn median_mad(data: &[f32]) -> (f32, f32) {
+    if data.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let median = |values: &mut [f32]| -> f32 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        if values.len() % 2 == 0 {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    };
+
+    let mut sorted = data.to_vec();
+    let center = median(&mut sorted);
+
+    let mut deviations: Vec<f32> = data.iter().map(|v| (v - center).abs()).collect();
+    let mad = median(&mut deviations);
+
+    (center, mad)
+}
@@ -0,0 +1,102 @@
+//! CRC-16 checksum computation and validation for FCS 3.x files
+//!
+//! FCS 3.x files may end with an 8-byte ASCII-encoded CRC-16 checksum covering the
+//! HEADER, TEXT, and DATA segments, used to detect corruption introduced after
+//! acquisition (e.g. a truncated transfer). Not all files include one, so validation
+//! is best-effort: a missing or non-numeric trailing field is treated as "no CRC to check"
+//! rather than a failure.
+
+use anyhow::{Result, anyhow};
+
+/// Computes the CRC-16/XMODEM checksum (poly `0x1021`, init `0x0000`) of `data`
+#[must_use]
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0x0000;
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x1021
+            };
+        }
+    }
+    crc
+}
+
+/// Formats a CRC-16 value as the zero-padded, 8-byte ASCII decimal field FCS expects
+#[must_use]
+pub fn format_crc(crc: u16) -> String {
+    format!("{crc:08}")
+}
+
+/// Result of validating an FCS file's optional trailing CRC-16 checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Whether the last 8 bytes of the file parsed as a CRC field
+    pub crc_present: bool,
+    /// The CRC value recorded in the file, if `crc_present`
+    pub recorded_crc: Option<u16>,
+    /// The CRC recomputed from the file's preceding bytes, if `crc_present`
+    pub computed_crc: Option<u16>,
+}
+
+impl VerificationReport {
+    /// Whether the file passes CRC validation: either it has no CRC field to check, or the
+    /// recorded and recomputed checksums match
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        match (self.recorded_crc, self.computed_crc) {
+            (Some(recorded), Some(computed)) => recorded == computed,
+            _ => true,
+        }
+    }
+
+    /// Returns `Err` with a descriptive message if the CRC is present and does not match
+    /// # Errors
+    /// Will return `Err` if `crc_present` is true and the recorded/computed CRCs differ
+    pub fn into_result(self) -> Result<Self> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(anyhow!(
+                "CRC mismatch: file records {:?} but bytes checksum to {:?}. File may be corrupted.",
+                self.recorded_crc,
+                self.computed_crc
+            ))
+        }
+    }
+}
+
+/// Validates the trailing CRC-16 field of a complete FCS file's bytes, if present
+///
+/// Treats the last 8 bytes of `file_bytes` as the CRC field and everything before it as
+/// the checksummed body. If those 8 bytes don't parse as an ASCII decimal `u16`, assumes
+/// the file has no CRC and reports `crc_present: false`.
+#[must_use]
+pub fn verify_crc(file_bytes: &[u8]) -> VerificationReport {
+    let not_present = VerificationReport {
+        crc_present: false,
+        recorded_crc: None,
+        computed_crc: None,
+    };
+
+    if file_bytes.len() < 8 {
+        return not_present;
+    }
+
+    let (body, crc_field) = file_bytes.split_at(file_bytes.len() - 8);
+    let Ok(crc_str) = std::str::from_utf8(crc_field) else {
+        return not_present;
+    };
+    let Ok(recorded_crc) = crc_str.trim().parse::<u16>() else {
+        return not_present;
+    };
+
+    VerificationReport {
+        crc_present: true,
+        recorded_crc: Some(recorded_crc),
+        computed_crc: Some(crc16(body)),
+    }
+}
@@ -15,19 +15,19 @@ pub enum FcsDataType {
     F,
     /// Double-precision floating point (f64)
     D,
-    /// ASCII-encoded string (not supported)
+    /// ASCII-encoded decimal integer, stored as fixed-width text
     A,
 }
 impl FcsDataType {
     /// Matches the string pattern and returns the corresponding data type
     /// # Errors
-    /// Will return `Err` if `data_type` is not a valid data type (ASCII-encoded strings are not supported, but binary integers, single-precision floating point, and double-precision floating point are supported)
+    /// Will return `Err` if `data_type` is not one of `I`, `F`, `D`, or `A`
     pub fn from_keyword_str(data_type: &str) -> Result<Self> {
         match data_type {
             "I" => Ok(Self::I),
             "F" => Ok(Self::F),
             "D" => Ok(Self::D),
-            "A" => Err(anyhow!("ASCII-encoded string data type not supported")),
+            "A" => Ok(Self::A),
             _ => Err(anyhow!("Invalid data type")),
         }
     }
@@ -42,25 +42,26 @@ impl FcsDataType {
         }
     }
 
-    /// Returns the number of bytes for the data type based on the number of bits
+    /// Returns the number of bytes for the data type based on `$PnB`
     ///
     /// This is used in conjunction with `$PnB` to determine the actual bytes per parameter.
     /// For `I` (integer) type, the actual bytes depend on `$PnB` (e.g., 16 bits = 2 bytes, 32 bits = 4 bytes).
     /// For `F` (float32), always 4 bytes.
     /// For `D` (float64), always 8 bytes.
+    /// For `A` (ASCII), `$PnB` is already a byte/character count, not a bit count.
     ///
     /// # Arguments
-    /// * `bits` - Number of bits from `$PnB` keyword
+    /// * `bits` - Raw `$PnB` value for the parameter
     ///
     /// # Returns
-    /// Number of bytes for this data type with the given bit width
+    /// Number of bytes for this data type with the given `$PnB` value
     #[must_use]
     pub fn get_bytes_for_bits(&self, bits: usize) -> usize {
         match self {
             Self::I => (bits + 7) / 8, // Convert bits to bytes, rounding up
             Self::F => 4,
             Self::D => 8,
-            Self::A => 0,
+            Self::A => bits,
         }
     }
 }
@@ -0,0 +1,244 @@
+//! Structural diffing between two FCS files
+//!
+//! [`diff`] compares two [`Fcs`] files' headers, keywords, and parameters (and, optionally, a
+//! sample of event data) and returns a machine-readable [`DiffReport`] of everything that
+//! differs - useful for confirming a write→read round trip, or a pipeline step, didn't
+//! silently change anything it shouldn't have.
+
+use crate::file::Fcs;
+use crate::keyword::{Keyword, StringableKeyword};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Which part of a file a [`Difference`] was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffCategory {
+    Header,
+    Keyword,
+    Parameter,
+    Data,
+}
+
+/// A single difference found by [`diff`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Difference {
+    pub category: DiffCategory,
+    /// What differs, e.g. a keyword name or `$PnN` channel name
+    pub field: String,
+    /// The value in file `a`, or `None` if `field` isn't present in `a`
+    pub a: Option<String>,
+    /// The value in file `b`, or `None` if `field` isn't present in `b`
+    pub b: Option<String>,
+}
+
+/// Machine-readable report of every difference [`diff`] found between two files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiffReport {
+    pub differences: Vec<Difference>,
+}
+
+impl DiffReport {
+    /// Whether the two files are identical over everything [`diff`] checked
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    fn push(
+        &mut self,
+        category: DiffCategory,
+        field: impl Into<String>,
+        a: Option<String>,
+        b: Option<String>,
+    ) {
+        self.differences.push(Difference {
+            category,
+            field: field.into(),
+            a,
+            b,
+        });
+    }
+}
+
+/// Options controlling [`diff`]'s optional event-data comparison
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiffOptions {
+    /// Compare event data too: `Some(n)` samples at most `n` evenly-spaced events per shared
+    /// channel rather than comparing every event (cheaper for large files); `None` skips data
+    /// comparison entirely.
+    pub sample_events: Option<usize>,
+}
+
+/// Compares two FCS files' headers, keywords, parameters, and (optionally) sampled event data
+///
+/// # Errors
+/// Propagates errors from reading either file's event data during the optional data
+/// comparison.
+pub fn diff(a: &Fcs, b: &Fcs, options: DiffOptions) -> Result<DiffReport> {
+    let mut report = DiffReport::default();
+
+    diff_header(&mut report, a, b);
+    diff_keywords(&mut report, a, b);
+    diff_parameters(&mut report, a, b);
+    if let Some(sample_events) = options.sample_events {
+        diff_data(&mut report, a, b, sample_events)?;
+    }
+
+    Ok(report)
+}
+
+fn diff_header(report: &mut DiffReport, a: &Fcs, b: &Fcs) {
+    let a_version = a.header.version.to_string();
+    let b_version = b.header.version.to_string();
+    if a_version != b_version {
+        report.push(
+            DiffCategory::Header,
+            "version",
+            Some(a_version),
+            Some(b_version),
+        );
+    }
+    if a.header.text_offset != b.header.text_offset {
+        report.push(
+            DiffCategory::Header,
+            "text_offset",
+            Some(format!("{:?}", a.header.text_offset)),
+            Some(format!("{:?}", b.header.text_offset)),
+        );
+    }
+    if a.header.data_offset != b.header.data_offset {
+        report.push(
+            DiffCategory::Header,
+            "data_offset",
+            Some(format!("{:?}", a.header.data_offset)),
+            Some(format!("{:?}", b.header.data_offset)),
+        );
+    }
+    if a.header.analysis_offset != b.header.analysis_offset {
+        report.push(
+            DiffCategory::Header,
+            "analysis_offset",
+            Some(format!("{:?}", a.header.analysis_offset)),
+            Some(format!("{:?}", b.header.analysis_offset)),
+        );
+    }
+}
+
+fn diff_keywords(report: &mut DiffReport, a: &Fcs, b: &Fcs) {
+    let mut keys: Vec<&String> = a
+        .metadata
+        .keywords
+        .keys()
+        .chain(b.metadata.keywords.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    let value_of = |keyword: &Keyword| match keyword {
+        Keyword::Int(kw) => kw.get_str().into_owned(),
+        Keyword::Float(kw) => kw.get_str().into_owned(),
+        Keyword::String(kw) => kw.get_str().into_owned(),
+        Keyword::Byte(kw) => kw.get_str().into_owned(),
+        Keyword::Mixed(kw) => kw.get_str().into_owned(),
+    };
+
+    for key in keys {
+        let a_value = a.metadata.keywords.get(key).map(value_of);
+        let b_value = b.metadata.keywords.get(key).map(value_of);
+        if a_value != b_value {
+            report.push(DiffCategory::Keyword, key.clone(), a_value, b_value);
+        }
+    }
+}
+
+fn diff_parameters(report: &mut DiffReport, a: &Fcs, b: &Fcs) {
+    let mut names: Vec<&crate::parameter::ChannelName> = a
+        .parameters
+        .keys()
+        .chain(b.parameters.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        let a_param = a.parameters.get(name);
+        let b_param = b.parameters.get(name);
+        match (a_param, b_param) {
+            (Some(a_param), Some(b_param)) => {
+                if a_param.label_name != b_param.label_name {
+                    report.push(
+                        DiffCategory::Parameter,
+                        format!("{name}:label_name"),
+                        Some(a_param.label_name.to_string()),
+                        Some(b_param.label_name.to_string()),
+                    );
+                }
+                let a_transform = format!("{:?}", a_param.transform);
+                let b_transform = format!("{:?}", b_param.transform);
+                if a_transform != b_transform {
+                    report.push(
+                        DiffCategory::Parameter,
+                        format!("{name}:transform"),
+                        Some(a_transform),
+                        Some(b_transform),
+                    );
+                }
+                if a_param.parameter_number != b_param.parameter_number {
+                    report.push(
+                        DiffCategory::Parameter,
+                        format!("{name}:parameter_number"),
+                        Some(a_param.parameter_number.to_string()),
+                        Some(b_param.parameter_number.to_string()),
+                    );
+                }
+            }
+            (a_param, b_param) => {
+                report.push(
+                    DiffCategory::Parameter,
+                    name.to_string(),
+                    a_param.map(|_| "present".to_string()),
+                    b_param.map(|_| "present".to_string()),
+                );
+            }
+        }
+    }
+}
+
+fn diff_data(report: &mut DiffReport, a: &Fcs, b: &Fcs, sample_events: usize) -> Result<()> {
+    let mut shared_channels: Vec<&crate::parameter::ChannelName> = a
+        .parameters
+        .keys()
+        .filter(|name| b.parameters.contains_key(name.as_ref()))
+        .collect();
+    shared_channels.sort();
+
+    for channel in shared_channels {
+        let a_values = a.get_parameter_events_slice(channel)?;
+        let b_values = b.get_parameter_events_slice(channel)?;
+
+        if a_values.len() != b_values.len() {
+            report.push(
+                DiffCategory::Data,
+                format!("{channel}:event_count"),
+                Some(a_values.len().to_string()),
+                Some(b_values.len().to_string()),
+            );
+            continue;
+        }
+
+        let n_events = a_values.len();
+        let step = n_events.div_ceil(sample_events.max(1)).max(1);
+        for index in (0..n_events).step_by(step) {
+            if a_values[index] != b_values[index] {
+                report.push(
+                    DiffCategory::Data,
+                    format!("{channel}[{index}]"),
+                    Some(a_values[index].to_string()),
+                    Some(b_values[index].to_string()),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Typed errors for the FCS file-opening pipeline
+//!
+//! Every other fallible operation in this crate returns [`anyhow::Result`], which is the
+//! right default for one-shot analysis code. The open pipeline is different: it's the first
+//! thing every caller runs against untrusted file input, so callers benefit from knowing
+//! which stage failed - "the file couldn't be memory-mapped" is a different problem to
+//! recover from than "a required keyword is missing from the TEXT segment" - without having
+//! to pattern-match an error string. [`FcsError`] gives each stage of the open pipeline
+//! (see [`Fcs::open`](crate::Fcs::open)) its own variant, wrapping the underlying failure as
+//! its `source` so no diagnostic detail is lost.
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// What went wrong while opening an FCS file, and which stage of the pipeline failed
+#[derive(Debug, Error)]
+pub enum FcsError {
+    /// The file couldn't be opened or memory-mapped
+    #[error("failed to access file: {source}")]
+    FileAccess {
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// The path doesn't have a `.fcs` extension
+    #[error("invalid file extension: {path:?}")]
+    InvalidExtension { path: Option<PathBuf> },
+
+    /// The FCS HEADER segment couldn't be parsed
+    #[error("failed to parse HEADER segment: {source}")]
+    HeaderParse {
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// A required keyword was missing or malformed in the TEXT segment
+    #[error("invalid TEXT segment: {source}")]
+    TextSegment {
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// The parameter map (one entry per `$PnN`) couldn't be built from the TEXT segment
+    #[error("failed to read parameter definitions: {source}")]
+    ParameterMap {
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// The DATA segment couldn't be decoded into a `DataFrame`
+    #[error("failed to read DATA segment: {source}")]
+    DataSegment {
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// Result alias for the open pipeline; see [`FcsError`]
+pub type FcsResult<T> = std::result::Result<T, FcsError>;
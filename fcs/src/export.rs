@@ -0,0 +1,214 @@
+//! Exporting event data to interchange and plain-text formats
+//!
+//! [`Fcs::data_frame`](crate::file::Fcs::data_frame) already lives in Polars' Arrow-backed
+//! columnar format, so handing it off to Python/R or a data lake is mostly a matter of picking
+//! a container. [`Fcs::to_parquet`] and [`Fcs::to_arrow_ipc`] also attach each channel's `$PnS`
+//! label and configured transform as key/value metadata, keyed `flow.parameter.<channel>.label`
+//! / `.transform`. Polars' writer API only exposes file-level (not per-`Field`) custom metadata,
+//! so that's the granularity these exports offer; a reader that needs it per-column can still
+//! recover it by parsing the channel name back out of each key.
+//!
+//! [`Fcs::to_csv`] covers the lowest-common-denominator case: a delimited text file of a
+//! chosen subset of channels, optionally compensated and/or run through each channel's
+//! configured [`crate::TransformType`], with either `$PnN` channel names or `$PnS` stain
+//! labels as the header row.
+
+use crate::file::Fcs;
+use crate::parameter::Parameter;
+use crate::transform::Transformable;
+use anyhow::{Result, anyhow};
+use polars::prelude::*;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Options controlling [`Fcs::to_parquet`]
+#[derive(Debug, Clone)]
+pub struct ParquetExportOptions {
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetExportOptions {
+    fn default() -> Self {
+        Self {
+            compression: ParquetCompression::Zstd(None),
+        }
+    }
+}
+
+/// Which name to use for each column's header in [`Fcs::to_csv`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvHeaderStyle {
+    /// The `$PnN` channel name (e.g. "FL1-A")
+    #[default]
+    ChannelName,
+    /// The `$PnS` stain label (e.g. "CD8"), falling back to the channel name if unset
+    StainLabel,
+}
+
+/// Options controlling [`Fcs::to_csv`]
+#[derive(Debug, Clone, Copy)]
+pub struct CsvExportOptions {
+    /// Apply the file's `$SPILLOVER` compensation before exporting; see
+    /// [`crate::file::Fcs::apply_file_compensation`]
+    pub compensate: bool,
+    /// Apply each channel's configured [`crate::TransformType`] before exporting
+    pub apply_transform: bool,
+    pub header: CsvHeaderStyle,
+    /// Field separator byte: `b','` for CSV, `b'\t'` for TSV
+    pub delimiter: u8,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            compensate: false,
+            apply_transform: false,
+            header: CsvHeaderStyle::default(),
+            delimiter: b',',
+        }
+    }
+}
+
+impl Fcs {
+    /// Exports event data to a Parquet file, attaching each channel's label and transform as
+    /// file-level key/value metadata (see [`crate::export`])
+    /// # Errors
+    /// Will return `Err` if the file cannot be created or the DataFrame cannot be written
+    pub fn to_parquet(&self, path: impl AsRef<Path>, options: ParquetExportOptions) -> Result<()> {
+        let file = File::create(path)?;
+        let mut df = (*self.data_frame).clone();
+
+        ParquetWriter::new(file)
+            .with_compression(options.compression)
+            .with_key_value_metadata(Some(KeyValueMetadata::from_static(
+                self.channel_metadata_pairs(),
+            )))
+            .finish(&mut df)?;
+
+        Ok(())
+    }
+
+    /// Exports event data to an Arrow IPC (Feather) file, attaching each channel's label and
+    /// transform as schema-level key/value metadata (see [`crate::export`])
+    /// # Errors
+    /// Will return `Err` if the file cannot be created or the DataFrame cannot be written
+    pub fn to_arrow_ipc(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = File::create(path)?;
+        let mut df = (*self.data_frame).clone();
+
+        let custom_metadata = self
+            .channel_metadata_pairs()
+            .into_iter()
+            .map(|(key, value)| (PlSmallStr::from_string(key), PlSmallStr::from_string(value)))
+            .collect();
+        let mut writer = IpcWriter::new(file);
+        writer.set_custom_schema_metadata(Arc::new(custom_metadata));
+        writer.finish(&mut df)?;
+
+        Ok(())
+    }
+
+    /// Exports selected channels to a delimited text file (see [`crate::export`])
+    ///
+    /// `channels` selects and orders the exported columns by `$PnN` channel name; `None` exports
+    /// every parameter, ordered by `$PnN` (parameter number).
+    /// # Errors
+    /// Will return `Err` if a requested channel does not exist, is not `f32`-typed, compensation
+    /// fails, or the file cannot be created or written
+    pub fn to_csv(
+        &self,
+        path: impl AsRef<Path>,
+        channels: Option<&[&str]>,
+        options: CsvExportOptions,
+    ) -> Result<()> {
+        let source_df = if options.compensate {
+            self.apply_file_compensation()?
+        } else {
+            self.data_frame.clone()
+        };
+        let mut df = (*source_df).clone();
+
+        let selected_channels: Vec<String> = match channels {
+            Some(channels) => channels
+                .iter()
+                .map(|channel| (*channel).to_string())
+                .collect(),
+            None => {
+                let mut parameters: Vec<&Parameter> = self.parameters.values().collect();
+                parameters.sort_by_key(|parameter| parameter.parameter_number);
+                parameters
+                    .into_iter()
+                    .map(|parameter| parameter.channel_name.to_string())
+                    .collect()
+            }
+        };
+        df = df.select(selected_channels.iter().map(String::as_str))?;
+
+        if options.apply_transform {
+            use rayon::prelude::*;
+            for channel_name in &selected_channels {
+                let parameter = self
+                    .parameters
+                    .get(channel_name.as_str())
+                    .ok_or_else(|| anyhow!("Parameter {channel_name} not found"))?;
+                let transform = parameter.transform.clone();
+
+                let column = df
+                    .column(channel_name)
+                    .map_err(|e| anyhow!("Parameter {channel_name} not found: {e}"))?;
+                let series = column.as_materialized_series();
+                let float_chunk = series
+                    .f32()
+                    .map_err(|e| anyhow!("Parameter {channel_name} is not f32: {e}"))?;
+                let transformed: Vec<f32> = float_chunk
+                    .cont_slice()?
+                    .par_iter()
+                    .map(|value| transform.transform(value))
+                    .collect();
+                let new_series = Series::new(channel_name.into(), transformed);
+                df.replace(channel_name, new_series)?;
+            }
+        }
+
+        if options.header == CsvHeaderStyle::StainLabel {
+            for channel_name in &selected_channels {
+                let parameter = self
+                    .parameters
+                    .get(channel_name.as_str())
+                    .ok_or_else(|| anyhow!("Parameter {channel_name} not found"))?;
+                if !parameter.label_name.is_empty() {
+                    df.rename(channel_name, parameter.label_name.as_ref().into())?;
+                }
+            }
+        }
+
+        let file = File::create(path)?;
+        CsvWriter::new(file)
+            .include_header(true)
+            .with_separator(options.delimiter)
+            .finish(&mut df)?;
+
+        Ok(())
+    }
+
+    /// Builds `flow.parameter.<channel>.label` / `.transform` key/value pairs for every
+    /// parameter, for attaching as file-level export metadata
+    fn channel_metadata_pairs(&self) -> Vec<(String, String)> {
+        self.parameters
+            .values()
+            .flat_map(|parameter| {
+                [
+                    (
+                        format!("flow.parameter.{}.label", parameter.channel_name),
+                        parameter.label_name.to_string(),
+                    ),
+                    (
+                        format!("flow.parameter.{}.transform", parameter.channel_name),
+                        format!("{:?}", parameter.transform),
+                    ),
+                ]
+            })
+            .collect()
+    }
+}
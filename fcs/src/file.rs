@@ -2,17 +2,22 @@
 use crate::{
     FcsDataType, TransformType, Transformable,
     byteorder::ByteOrder,
+    crc::{VerificationReport, verify_crc},
+    error::{FcsError, FcsResult},
     header::Header,
-    keyword::{IntegerableKeyword, StringableKeyword},
+    keyword::{IntegerKeyword, IntegerableKeyword, Keyword, StringableKeyword},
+    matrix::MatrixOps,
     metadata::Metadata,
-    parameter::{EventDataFrame, EventDatum, Parameter, ParameterBuilder, ParameterMap},
+    parameter::{ChannelName, EventDataFrame, EventDatum, Parameter, ParameterBuilder, ParameterMap},
+    repair::{RecoveryOptions, RepairReport},
+    validate::{ComplianceReport, ValidationLevel, validate as validate_fcs},
 };
 // Standard library imports
 use std::borrow::Cow;
 use std::fs::File;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 // External crate imports
 use anyhow::{Result, anyhow};
@@ -30,16 +35,13 @@ use rayon::prelude::*;
 /// - Int16/Int32/Float64: Use parallel for datasets with ≥400k values
 const PARALLEL_THRESHOLD: usize = 400_000;
 
-/// A shareable wrapper around the file path and memory-map
-///
-/// Uses Arc<Mmap> to share the memory mapping across clones without creating
-/// new file descriptors or memory mappings. This is more efficient than cloning
-/// the underlying file descriptor and re-mapping.
+/// The byte source backing an [`AccessWrapper`]: either a memory-mapped file or
+/// an owned in-memory buffer (e.g. bytes fetched over HTTP or pulled from a zip
+/// archive), so [`Fcs::open`] and [`Fcs::from_bytes`] can share the same parsing
+/// pipeline regardless of where the bytes came from.
 #[derive(Debug, Clone)]
-pub struct AccessWrapper {
-    /// An owned, mutable path to the file on disk
-    pub path: PathBuf,
-    /// The memory-mapped file, shared via Arc for efficient cloning
+pub enum FileBacking {
+    /// A memory-mapped file, shared via Arc for efficient cloning
     ///
     /// # Safety
     /// The Mmap is created from a File handle and remains valid as long as:
@@ -51,7 +53,30 @@ pub struct AccessWrapper {
     /// - FCS files are read-only once opened (we never write back to them)
     /// - The file remains open (via File handle) for the lifetime of the Mmap
     /// - We only drop the Mmap when the FCS file is no longer needed
-    pub mmap: Arc<Mmap>,
+    Mapped(Arc<Mmap>),
+    /// An owned buffer of bytes with no backing file, shared via Arc for efficient cloning
+    Owned(Arc<Vec<u8>>),
+}
+
+impl Deref for FileBacking {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A shareable wrapper around the file path (if any) and underlying bytes
+#[derive(Debug, Clone)]
+pub struct AccessWrapper {
+    /// The path to the file on disk, or `None` when the bytes did not come from a file
+    /// (e.g. [`Fcs::from_bytes`]/[`Fcs::from_reader`])
+    pub path: Option<PathBuf>,
+    /// The underlying bytes, either memory-mapped from a file or held in memory
+    pub bytes: FileBacking,
 }
 
 impl AccessWrapper {
@@ -70,17 +95,26 @@ impl AccessWrapper {
         let mmap = unsafe { MmapOptions::new().map(&file)? };
 
         Ok(Self {
-            path,
-            mmap: Arc::new(mmap),
+            path: Some(path),
+            bytes: FileBacking::Mapped(Arc::new(mmap)),
         })
     }
+
+    /// Creates a new `AccessWrapper` from an owned, in-memory byte buffer, with no backing path
+    #[must_use]
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            path: None,
+            bytes: FileBacking::Owned(Arc::new(bytes)),
+        }
+    }
 }
 
 impl Deref for AccessWrapper {
-    type Target = Mmap;
+    type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        &self.mmap
+        &self.bytes
     }
 }
 
@@ -106,6 +140,146 @@ pub struct Fcs {
 
     /// A wrapper around the file, path, and memory-map
     pub file_access: AccessWrapper,
+
+    /// Cache for [`Fcs::channel_ranges`], keyed against the `data_frame` allocation it was
+    /// computed from. Not meant to be read or written directly - always go through
+    /// `channel_ranges()`, which recomputes it whenever `data_frame` points somewhere new.
+    pub channel_range_cache: ChannelRangeCache,
+}
+
+/// A channel's numeric range: what the file's `$PnR` keyword declares (if present) and what
+/// the data itself actually spans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelRange {
+    /// The channel's `$PnR` keyword value, if present - the acquisition range the instrument
+    /// declared, not necessarily what the data actually reaches
+    pub keyword_range: Option<f32>,
+    /// The minimum value actually observed across this channel's events
+    pub observed_min: f32,
+    /// The maximum value actually observed across this channel's events
+    pub observed_max: f32,
+}
+
+impl ChannelRange {
+    /// The range to recommend for display: the `$PnR` keyword range widened to cover the
+    /// observed data if the data exceeds it, since compensation and unmixing can push values
+    /// past the instrument's declared acquisition range
+    #[must_use]
+    pub fn display_range(&self) -> (f32, f32) {
+        match self.keyword_range {
+            Some(range) => (self.observed_min.min(0.0), range.max(self.observed_max)),
+            None => (self.observed_min, self.observed_max),
+        }
+    }
+}
+
+/// Keyword-declared and observed ranges for every channel in an [`Fcs`], keyed by channel name
+pub type ChannelRanges = rustc_hash::FxHashMap<ChannelName, ChannelRange>;
+
+/// Shared, lazily-populated cache backing [`Fcs::channel_ranges`]. Wrapped in `Arc<RwLock<_>>`
+/// (rather than a plain field) so it survives behind `&self`, matching how [`Fcs`] otherwise
+/// only ever hands out shared references to its data.
+pub type ChannelRangeCache = Arc<RwLock<Option<(EventDataFrame, ChannelRanges)>>>;
+
+/// Algorithm used by [`Fcs::apply_spectral_unmixing_with_method`] to solve for each event's
+/// unmixed fluorophore abundances
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmixingMethod {
+    /// Ordinary least squares via a single matrix inversion, shared across all events
+    /// (equivalent to [`Fcs::apply_spectral_unmixing`])
+    Ols,
+    /// Weighted least squares with Poisson (shot-noise) weights, recomputed per event as
+    /// `1 / max(signal, 1.0)` on each spectral channel - appropriate when channel variance
+    /// scales with intensity, as it does for photon-counting detectors
+    Wls,
+    /// Non-negative least squares (Lawson-Hanson active set method): fluorophore
+    /// abundances cannot be physically negative, so this clamps the solution to the
+    /// non-negative orthant instead of allowing OLS/WLS's unconstrained negative values
+    Nnls,
+}
+
+/// Result of [`Fcs::apply_spectral_unmixing_with_method`]: the unmixed data plus a
+/// per-event residual for QC (large residuals indicate a poor spectral fit, e.g. an
+/// unaccounted-for fluorophore or autofluorescence component)
+pub struct UnmixingResult {
+    /// Unmixed (and re-transformed) event data, same shape as [`Fcs::apply_spectral_unmixing`]'s output
+    pub data: EventDataFrame,
+    /// Euclidean norm of `unmixing_matrix * abundances - signal` for each event, in the
+    /// same (linear, pre-transform) scale the unmixing was solved in
+    pub residuals: Vec<f32>,
+}
+
+/// Method used to select a representative subset of events, see [`Fcs::subsample`]
+#[derive(Debug, Clone, Copy)]
+pub enum SubsampleMethod {
+    /// Uniform random sample without replacement, seeded for reproducibility
+    Random { seed: u64 },
+    /// The first `n` events, in original order
+    First,
+    /// Every `n_events / n`-th event, evenly spaced through the file
+    EveryKth,
+    /// Density-preserving downsampling: events are binned across every numeric channel and
+    /// sampled with probability inversely proportional to their bin's occupancy, so rare
+    /// populations aren't diluted away the way uniform random sampling would dilute them
+    DensityPreserving { seed: u64 },
+}
+
+/// A borrowed, multi-channel window of events; see [`Fcs::iter_chunks`]
+pub struct EventChunk<'a> {
+    channel_names: Vec<ChannelName>,
+    columns: Vec<&'a [f32]>,
+}
+
+impl<'a> EventChunk<'a> {
+    /// Number of events covered by this chunk
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    /// Whether this chunk covers zero events
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The borrowed slice for one of this chunk's channels, or `None` if `channel_name` was
+    /// not one of the channels passed to [`Fcs::iter_chunks`]
+    #[must_use]
+    pub fn channel(&self, channel_name: &str) -> Option<&'a [f32]> {
+        self.channel_names
+            .iter()
+            .position(|name| name.as_ref() == channel_name)
+            .map(|index| self.columns[index])
+    }
+}
+
+/// Iterator over an [`Fcs`]'s events in fixed-size, multi-channel chunks of borrowed slices;
+/// see [`Fcs::iter_chunks`]
+pub struct EventChunks<'a> {
+    channel_names: Vec<ChannelName>,
+    columns: Vec<&'a [f32]>,
+    chunk_size: usize,
+    next_event: usize,
+    number_of_events: usize,
+}
+
+impl<'a> Iterator for EventChunks<'a> {
+    type Item = EventChunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_event >= self.number_of_events {
+            return None;
+        }
+        let start = self.next_event;
+        let end = (start + self.chunk_size).min(self.number_of_events);
+        self.next_event = end;
+
+        Some(EventChunk {
+            channel_names: self.channel_names.clone(),
+            columns: self.columns.iter().map(|column| &column[start..end]).collect(),
+        })
+    }
 }
 
 impl Fcs {
@@ -124,6 +298,7 @@ impl Fcs {
             parameters: ParameterMap::default(),
             data_frame: Arc::new(DataFrame::empty()),
             file_access: AccessWrapper::new("")?,
+            channel_range_cache: Arc::new(RwLock::new(None)),
         })
     }
 
@@ -159,23 +334,71 @@ impl Fcs {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn open(path: &str) -> Result<Self> {
+        Self::open_with_range_mask(path, true)
+    }
+
+    /// Opens and parses an FCS file from the given path, with control over `$PnR` range masking
+    ///
+    /// Identical to [`Fcs::open`], except it allows disabling the `$PnR` bit-mask that is
+    /// normally applied to `$DATATYPE=I` (integer) parameters. See
+    /// [`Metadata::get_bytes_per_parameter`] for why `$PnR` (rather than `$PnB`) determines
+    /// the number of bits actually in use, and mask out any acquisition artifacts in the
+    /// unused high bits accordingly - matching `flowCore`'s `read.FCS` behavior.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the FCS file (must have `.fcs` extension)
+    /// * `apply_range_mask` - Whether to mask integer parameter values to their `$PnR` bit width
+    ///
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`]
+    pub fn open_with_range_mask(path: &str, apply_range_mask: bool) -> Result<Self> {
+        Ok(Self::open_with_options(path, apply_range_mask, false)?)
+    }
+
+    /// Opens and parses an FCS file, storing `$DATATYPE=I` parameters that fit in 16 bits as
+    /// `UInt16` Polars columns instead of `Float32`, halving that column's steady-state memory
+    ///
+    /// Identical to [`Fcs::open`] otherwise. Use [`Fcs::get_parameter_events_f32`] rather than
+    /// [`Fcs::get_parameter_events_slice`] to read a parameter's events regardless of which
+    /// storage layout it ended up in: the former casts a compact column to `f32` on demand,
+    /// while the latter only ever returns a zero-copy slice and errors on a non-`Float32` column.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the FCS file (must have `.fcs` extension)
+    /// * `apply_range_mask` - Whether to mask integer parameter values to their `$PnR` bit width
+    ///
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`]
+    pub fn open_with_compact_integer_storage(path: &str, apply_range_mask: bool) -> Result<Self> {
+        Ok(Self::open_with_options(path, apply_range_mask, true)?)
+    }
+
+    fn open_with_options(
+        path: &str,
+        apply_range_mask: bool,
+        compact_integer_storage: bool,
+    ) -> FcsResult<Self> {
         use tracing::debug;
 
         // Attempt to open the file path
-        let file_access = AccessWrapper::new(path).expect("Should be able make new access wrapper");
+        let file_access =
+            AccessWrapper::new(path).map_err(|source| FcsError::FileAccess { source })?;
 
         // Validate the file extension
-        Self::validate_fcs_extension(&file_access.path)
-            .expect("Should have a valid file extension");
+        Self::validate_fcs_extension(file_access.path.as_deref()).map_err(|_| {
+            FcsError::InvalidExtension {
+                path: file_access.path.clone(),
+            }
+        })?;
 
         // Create header and metadata structs from a memory map of the file
-        let header = Header::from_mmap(&file_access.mmap)
-            .expect("Should be able to create header from mmap");
-        let mut metadata = Metadata::from_mmap(&file_access.mmap, &header);
+        let header = Header::from_mmap(&file_access.bytes)
+            .map_err(|source| FcsError::HeaderParse { source })?;
+        let mut metadata = Metadata::from_mmap(&file_access.bytes, &header);
 
         metadata
             .validate_text_segment_keywords(&header)
-            .expect("Should have valid text segment keywords");
+            .map_err(|source| FcsError::TextSegment { source })?;
         // metadata.validate_number_of_parameters()?;
         metadata.validate_guid();
 
@@ -187,12 +410,19 @@ impl Fcs {
 
         let fcs = Self {
             parameters: Self::generate_parameter_map(&metadata)
-                .expect("Should be able to generate parameter map"),
-            data_frame: Self::store_raw_data_as_dataframe(&header, &file_access.mmap, &metadata)
-                .expect("Should be able to store raw data as DataFrame"),
+                .map_err(|source| FcsError::ParameterMap { source })?,
+            data_frame: Self::store_raw_data_as_dataframe(
+                &header,
+                &file_access.bytes,
+                &metadata,
+                apply_range_mask,
+                compact_integer_storage,
+            )
+            .map_err(|source| FcsError::DataSegment { source })?,
             file_access,
             header,
             metadata,
+            channel_range_cache: Arc::new(RwLock::new(None)),
         };
 
         // Log DataFrame event count and compare to $TOT
@@ -210,6 +440,16 @@ impl Fcs {
             }
         }
 
+        // Warn (but don't fail) on a CRC mismatch; use `Fcs::open_strict` to reject instead
+        let crc_report = fcs.verify();
+        if !crc_report.is_valid() {
+            tracing::warn!(
+                "CRC mismatch: file records {:?} but bytes checksum to {:?}. File may be corrupted.",
+                crc_report.recorded_crc,
+                crc_report.computed_crc
+            );
+        }
+
         // Log compensation status
         let has_compensation = fcs.has_compensation();
         debug!(
@@ -236,10 +476,409 @@ impl Fcs {
         Ok(fcs)
     }
 
+    /// Opens and parses an FCS file, rejecting it if its trailing CRC-16 checksum is present
+    /// but does not match its bytes
+    ///
+    /// Identical to [`Fcs::open`], except a CRC mismatch is treated as an error instead of a
+    /// warning. A file with no CRC field at all still opens successfully.
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`], or if the CRC is present
+    /// and does not match.
+    pub fn open_strict(path: &str) -> Result<Self> {
+        let fcs = Self::open(path)?;
+        fcs.verify().into_result()?;
+        Ok(fcs)
+    }
+
+    /// Opens and parses a possibly-malformed FCS file, repairing what `options` allows instead
+    /// of failing
+    ///
+    /// Unlike [`Fcs::open`], never panics on a bad offset or a `$TOT`/DATA-segment mismatch:
+    /// [`Self::resolve_data_segment`] already falls back to the `$BEGINDATA`/`$ENDDATA`
+    /// keywords when the HEADER offsets are zero, and this additionally recomputes the event
+    /// count from the DATA segment's actual length when it disagrees with `$TOT`, trimming any
+    /// partial trailing event rather than erroring. Every repair made is recorded in the
+    /// returned [`RepairReport`].
+    /// # Errors
+    /// Will return `Err` if the file cannot be opened, has an invalid `.fcs` extension, is
+    /// missing keywords required to locate the DATA segment at all, or if a mismatch is found
+    /// that `options` does not permit repairing.
+    pub fn open_with_recovery(
+        path: &str,
+        options: RecoveryOptions,
+    ) -> Result<(Self, RepairReport)> {
+        let mut report = RepairReport::default();
+
+        let file_access = AccessWrapper::new(path)?;
+        Self::validate_fcs_extension(file_access.path.as_deref())?;
+
+        let header = Header::from_mmap(&file_access.bytes)?;
+        let mut metadata = Metadata::from_mmap(&file_access.bytes, &header);
+
+        if let Err(e) = metadata.validate_text_segment_keywords(&header) {
+            report.push(
+                "missing_keyword",
+                format!("Continuing despite invalid TEXT segment keywords: {e}"),
+            );
+        }
+        metadata.validate_guid();
+
+        if *header.data_offset.start() == 0 || *header.data_offset.end() == 0 {
+            report.push(
+                "offset_fallback",
+                "HEADER DATA offsets are zero; falling back to $BEGINDATA/$ENDDATA keywords",
+            );
+        }
+
+        Self::reconcile_event_count(
+            &header,
+            &file_access.bytes,
+            &mut metadata,
+            options,
+            &mut report,
+        )?;
+
+        let fcs = Self {
+            parameters: Self::generate_parameter_map(&metadata)?,
+            data_frame: Self::store_raw_data_as_dataframe(
+                &header,
+                &file_access.bytes,
+                &metadata,
+                options.apply_range_mask,
+                false,
+            )?,
+            file_access,
+            header,
+            metadata,
+            channel_range_cache: Arc::new(RwLock::new(None)),
+        };
+
+        Ok((fcs, report))
+    }
+
+    /// Reconciles `$TOT` against the DATA segment's actual byte length, recording and applying
+    /// whatever repair `options` permits by overwriting the `$TOT` keyword in `metadata` in place
+    /// # Errors
+    /// Will return `Err` if the DATA segment cannot be located at all, or if it disagrees with
+    /// `$TOT` in a way `options` does not permit repairing
+    fn reconcile_event_count(
+        header: &Header,
+        mmap: &[u8],
+        metadata: &mut Metadata,
+        options: RecoveryOptions,
+        report: &mut RepairReport,
+    ) -> Result<()> {
+        let data_bytes = Self::resolve_data_segment(header, mmap, metadata)?;
+        let bytes_per_event = metadata.calculate_bytes_per_event()?;
+        let recorded_events = *metadata.get_number_of_events()?;
+
+        let available_events = data_bytes.len() / bytes_per_event;
+        let remainder = data_bytes.len() % bytes_per_event;
+
+        if remainder != 0 {
+            if !options.trim_partial_events {
+                return Err(anyhow!(
+                    "DATA segment is {} bytes, which is not a multiple of the {} bytes/event; \
+                     {} trailing bytes form a partial event",
+                    data_bytes.len(),
+                    bytes_per_event,
+                    remainder
+                ));
+            }
+            report.push(
+                "partial_event_trimmed",
+                format!("Dropped {remainder} trailing bytes that formed a partial event"),
+            );
+        }
+
+        if available_events != recorded_events {
+            if !options.infer_event_count {
+                return Err(anyhow!(
+                    "$TOT says {} events, but the DATA segment only holds {}",
+                    recorded_events,
+                    available_events
+                ));
+            }
+            report.push(
+                "event_count_inferred",
+                format!(
+                    "$TOT claimed {recorded_events} events, but the DATA segment only holds {available_events}; using {available_events}"
+                ),
+            );
+            metadata.keywords.insert(
+                "$TOT".to_string(),
+                Keyword::Int(IntegerKeyword::TOT(available_events)),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Parses an FCS file already held in memory, e.g. bytes fetched over HTTP, extracted
+    /// from a zip archive, or otherwise not backed by a file on disk
+    ///
+    /// Runs the same parsing pipeline as [`Fcs::open`] against an owned copy of `bytes`
+    /// instead of a memory-mapped file, so `file_access.path` on the returned [`Fcs`] is `None`.
+    /// Skips the `.fcs` extension check, since there is no path to check.
+    ///
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`], aside from the
+    /// file-extension and file-opening checks.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_range_mask(bytes, true)
+    }
+
+    /// Identical to [`Fcs::from_bytes`], with control over `$PnR` range masking; see
+    /// [`Fcs::open_with_range_mask`]
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::from_bytes`]
+    pub fn from_bytes_with_range_mask(bytes: &[u8], apply_range_mask: bool) -> Result<Self> {
+        let file_access = AccessWrapper::from_bytes(bytes.to_vec());
+
+        let header = Header::from_mmap(&file_access.bytes)
+            .map_err(|source| FcsError::HeaderParse { source })?;
+        let mut metadata = Metadata::from_mmap(&file_access.bytes, &header);
+
+        metadata
+            .validate_text_segment_keywords(&header)
+            .map_err(|source| FcsError::TextSegment { source })?;
+        metadata.validate_guid();
+
+        Ok(Self {
+            parameters: Self::generate_parameter_map(&metadata)
+                .map_err(|source| FcsError::ParameterMap { source })?,
+            data_frame: Self::store_raw_data_as_dataframe(
+                &header,
+                &file_access.bytes,
+                &metadata,
+                apply_range_mask,
+                false,
+            )
+            .map_err(|source| FcsError::DataSegment { source })?,
+            file_access,
+            header,
+            metadata,
+            channel_range_cache: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Parses an FCS file from any reader, e.g. a `Cursor` over a downloaded buffer or a
+    /// file entry read out of a zip archive, by reading it fully into memory and delegating
+    /// to [`Fcs::from_bytes`]
+    /// # Errors
+    /// Will return `Err` if `reader` cannot be read to completion, or under the same
+    /// conditions as [`Fcs::from_bytes`]
+    pub fn from_reader<R: std::io::Read + std::io::Seek>(mut reader: R) -> Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Builds an in-memory `Fcs` from a Polars `DataFrame` of already-acquired event data,
+    /// e.g. data reconstructed from a CSV/Parquet export or generated by a simulation
+    ///
+    /// Every column must be castable to `f32` and becomes one linear-scale parameter, named
+    /// and ordered after the DataFrame's own column order. The result has no backing file
+    /// (`file_access.path` is `None`); write it out with [`crate::write::write_fcs_file`] to
+    /// get a real FCS file with populated HEADER offsets.
+    ///
+    /// # Errors
+    /// Will return `Err` if the DataFrame has no columns or rows, or if any column cannot be
+    /// cast to `f32`
+    pub fn from_dataframe(df: DataFrame) -> Result<Self> {
+        if df.height() == 0 {
+            return Err(anyhow!(
+                "Cannot build an Fcs from a DataFrame with 0 events"
+            ));
+        }
+        let column_names: Vec<String> = df
+            .get_column_names()
+            .into_iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        if column_names.is_empty() {
+            return Err(anyhow!(
+                "Cannot build an Fcs from a DataFrame with 0 parameters"
+            ));
+        }
+
+        let mut df = df;
+        for column_name in &column_names {
+            let casted = df
+                .column(column_name)?
+                .cast(&DataType::Float32)
+                .map_err(|e| anyhow!("Parameter {column_name} is not castable to f32: {e}"))?;
+            df.replace(column_name, casted.take_materialized_series())?;
+        }
+
+        let mut metadata = Metadata::new();
+        let n_events = df.height();
+        let n_params = column_names.len();
+
+        metadata.insert_string_keyword("$MODE".to_string(), "L".to_string());
+        metadata.insert_string_keyword("$DATATYPE".to_string(), "F".to_string());
+        metadata.insert_string_keyword("$BYTEORD".to_string(), "1,2,3,4".to_string());
+
+        use crate::keyword::{KeywordCreationResult, match_and_parse_keyword};
+        if let KeywordCreationResult::Int(int_kw) =
+            match_and_parse_keyword("$PAR", &n_params.to_string())
+        {
+            metadata
+                .keywords
+                .insert("$PAR".to_string(), Keyword::Int(int_kw));
+        }
+        if let KeywordCreationResult::Int(int_kw) =
+            match_and_parse_keyword("$TOT", &n_events.to_string())
+        {
+            metadata
+                .keywords
+                .insert("$TOT".to_string(), Keyword::Int(int_kw));
+        }
+
+        for (index, column_name) in column_names.iter().enumerate() {
+            let parameter_number = index + 1;
+            metadata.insert_string_keyword(format!("$P{parameter_number}N"), column_name.clone());
+            if let KeywordCreationResult::Int(int_kw) =
+                match_and_parse_keyword(&format!("$P{parameter_number}B"), "32")
+            {
+                metadata
+                    .keywords
+                    .insert(format!("$P{parameter_number}B"), Keyword::Int(int_kw));
+            }
+            if let KeywordCreationResult::Int(int_kw) =
+                match_and_parse_keyword(&format!("$P{parameter_number}R"), "262144")
+            {
+                metadata
+                    .keywords
+                    .insert(format!("$P{parameter_number}R"), Keyword::Int(int_kw));
+            }
+            metadata.insert_string_keyword(format!("$P{parameter_number}E"), "0,0".to_string());
+        }
+        metadata.validate_guid();
+
+        Ok(Self {
+            parameters: Self::generate_parameter_map(&metadata)?,
+            data_frame: Arc::new(df),
+            file_access: AccessWrapper::from_bytes(Vec::new()),
+            header: Header::new(),
+            metadata,
+            channel_range_cache: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Opens every dataset contained in an FCS file, following the `$NEXTDATA` chain
+    ///
+    /// Some FCS files (e.g. those produced by plate-based acquisition or Beckman
+    /// LMD exports) concatenate multiple datasets, each with its own HEADER/TEXT/DATA
+    /// segments, in a single file. Each dataset's `$NEXTDATA` keyword gives the
+    /// absolute byte offset to the next dataset's HEADER segment, or `0` when it is
+    /// the last one. This walks that chain and returns every dataset found, sharing
+    /// a single memory map across them.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the FCS file (must have `.fcs` extension)
+    ///
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`], for any dataset
+    /// encountered while walking the chain.
+    pub fn open_all(path: &str) -> Result<Vec<Self>> {
+        // The HEADER segment is always the first 58 bytes of a dataset; anything shorter can't
+        // possibly hold one, so this is the minimum a `$NEXTDATA` offset must leave room for.
+        const HEADER_LEN: usize = 58;
+        // Backstop against a `$NEXTDATA` chain that (accidentally or maliciously) never
+        // terminates: no legitimate multi-dataset FCS file needs anywhere near this many.
+        const MAX_DATASETS: usize = 256;
+
+        let file_access = AccessWrapper::new(path)?;
+        Self::validate_fcs_extension(file_access.path.as_deref())?;
+
+        let mut datasets = Vec::new();
+        let mut offset = 0;
+        let mut visited_offsets = std::collections::HashSet::new();
+
+        loop {
+            if !visited_offsets.insert(offset) {
+                return Err(anyhow!(
+                    "$NEXTDATA chain loops back to an already-visited offset ({offset}); file may be corrupted"
+                ));
+            }
+            if datasets.len() >= MAX_DATASETS {
+                return Err(anyhow!(
+                    "$NEXTDATA chain exceeded {MAX_DATASETS} datasets without terminating; file may be corrupted"
+                ));
+            }
+
+            let header = Header::from_mmap_at(&file_access.bytes, offset)?;
+            let mut metadata = Metadata::from_mmap(&file_access.bytes, &header);
+            metadata.validate_text_segment_keywords(&header)?;
+            metadata.validate_guid();
+
+            let next_data_offset = metadata.get_next_data_offset().unwrap_or(0);
+
+            datasets.push(Self {
+                parameters: Self::generate_parameter_map(&metadata)?,
+                data_frame: Self::store_raw_data_as_dataframe(
+                    &header,
+                    &file_access.bytes,
+                    &metadata,
+                    true,
+                    false,
+                )?,
+                file_access: file_access.clone(),
+                header,
+                metadata,
+                channel_range_cache: Arc::new(RwLock::new(None)),
+            });
+
+            if next_data_offset == 0 {
+                break;
+            }
+            if next_data_offset.saturating_add(HEADER_LEN) > file_access.bytes.len() {
+                return Err(anyhow!(
+                    "$NEXTDATA offset {next_data_offset} leaves no room for a HEADER segment in a {}-byte file; file may be corrupted",
+                    file_access.bytes.len()
+                ));
+            }
+            offset = next_data_offset;
+        }
+
+        Ok(datasets)
+    }
+
+    /// Opens an FCS file that may contain multiple concatenated datasets (see [`Fcs::open_all`]),
+    /// returning the one with the highest per-event bit depth
+    ///
+    /// Beckman Coulter's LMD format (as produced by Navios/Gallios instruments) concatenates a
+    /// low-resolution FCS 2.0 dataset - kept for legacy analysis software that can't read list
+    /// mode data past version 2.0 - ahead of the instrument's full-resolution FCS 3.0 dataset.
+    /// Resolution here is measured directly (bytes per event, from summing each parameter's
+    /// `$PnB`) rather than by FCS version number, so this also does the right thing for any
+    /// other multi-dataset file that orders its datasets low-to-high resolution, not just
+    /// Beckman's specific layout. Use [`Fcs::open_all`] instead if the lower-resolution
+    /// dataset is also needed.
+    ///
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open_all`], or if the file
+    /// contains no datasets at all.
+    pub fn open_highest_resolution(path: &str) -> Result<Self> {
+        let datasets = Self::open_all(path)?;
+        datasets
+            .into_iter()
+            .max_by_key(|fcs| fcs.metadata.calculate_bytes_per_event().unwrap_or(0))
+            .ok_or_else(|| anyhow!("No datasets found in {}", path))
+    }
+
     /// Validates that the file extension is `.fcs`
+    ///
+    /// No-op when `path` is `None`, since byte-backed sources (see [`Fcs::from_bytes`]) have
+    /// no file extension to check.
     /// # Errors
     /// Will return `Err` if the file extension is not `.fcs`
-    fn validate_fcs_extension(path: &Path) -> Result<()> {
+    pub(crate) fn validate_fcs_extension(path: Option<&Path>) -> Result<()> {
+        let Some(path) = path else {
+            return Ok(());
+        };
+
         let extension = path
             .extension()
             .ok_or_else(|| anyhow!("File has no extension"))?
@@ -253,26 +892,16 @@ impl Fcs {
         Ok(())
     }
 
-    /// Reads raw data from FCS file and stores it as a Polars DataFrame
-    /// Returns columnar data optimized for parameter-wise access patterns
-    ///
-    /// This function provides significant performance benefits over ndarray:
-    /// - 2-5x faster data filtering and transformations
-    /// - Native columnar storage (optimal for FCS parameter access patterns)
-    /// - Zero-copy operations via Apache Arrow
-    /// - Built-in SIMD acceleration
-    ///
+    /// Resolves and validates the DATA segment's byte range, falling back to the
+    /// `$BEGINDATA`/`$ENDDATA` keywords when the HEADER offsets are zero (as permitted
+    /// for files whose DATA segment exceeds the 8-digit HEADER offset field width).
     /// # Errors
-    /// Will return `Err` if:
-    /// - The data cannot be read
-    /// - The data cannot be converted to f32 values
-    /// - The DataFrame cannot be constructed
-    fn store_raw_data_as_dataframe(
+    /// Will return `Err` if the offsets cannot be determined or are out of bounds
+    pub(crate) fn resolve_data_segment<'a>(
         header: &Header,
-        mmap: &Mmap,
+        mmap: &'a [u8],
         metadata: &Metadata,
-    ) -> Result<EventDataFrame> {
-        // Validate data offset bounds before accessing mmap
+    ) -> Result<&'a [u8]> {
         let mut data_start = *header.data_offset.start();
         let mut data_end = *header.data_offset.end();
         let mmap_len = mmap.len();
@@ -323,8 +952,34 @@ impl Fcs {
             ));
         }
 
-        // Extract data bytes
-        let data_bytes = &mmap[data_start..=data_end];
+        Ok(&mmap[data_start..=data_end])
+    }
+
+    /// Reads raw data from FCS file and stores it as a Polars DataFrame
+    /// Returns columnar data optimized for parameter-wise access patterns
+    ///
+    /// This function provides significant performance benefits over ndarray:
+    /// - 2-5x faster data filtering and transformations
+    /// - Native columnar storage (optimal for FCS parameter access patterns)
+    /// - Zero-copy operations via Apache Arrow
+    /// - Built-in SIMD acceleration
+    ///
+    /// # Arguments
+    /// * `apply_range_mask` - Whether to mask `$DATATYPE=I` parameter values to their `$PnR` bit width
+    ///
+    /// # Errors
+    /// Will return `Err` if:
+    /// - The data cannot be read
+    /// - The data cannot be converted to f32 values
+    /// - The DataFrame cannot be constructed
+    pub(crate) fn store_raw_data_as_dataframe(
+        header: &Header,
+        mmap: &[u8],
+        metadata: &Metadata,
+        apply_range_mask: bool,
+        compact_integer_storage: bool,
+    ) -> Result<EventDataFrame> {
+        let data_bytes = Self::resolve_data_segment(header, mmap, metadata)?;
 
         let number_of_parameters = metadata
             .get_number_of_parameters()
@@ -354,6 +1009,10 @@ impl Fcs {
                 data_bytes.len()
             ));
         }
+        // A DATA segment longer than $TOT implies (e.g. it also covers a trailing CRC field,
+        // or Fcs::open_with_recovery trimmed a partial event by shrinking $TOT rather than
+        // the byte range) should not leak extra "phantom" events into the parsed columns.
+        let data_bytes = &data_bytes[..expected_total_bytes];
 
         // Collect bytes per parameter and data types for each parameter
         let bytes_per_parameter: Vec<usize> = (1..=*number_of_parameters)
@@ -470,13 +1129,26 @@ impl Fcs {
         for param_idx in 0..*number_of_parameters {
             // Extract this parameter's values across all events
             // Use iterator with step_by for efficient stride access
-            let param_values: Vec<f32> = f32_values
+            let mut param_values: Vec<f32> = f32_values
                 .iter()
                 .skip(param_idx)
                 .step_by(*number_of_parameters)
                 .copied()
                 .collect();
 
+            // FCS integer data can have acquisition artifacts in bits above the range
+            // implied by $PnR; mask them off, matching flowCore's read.FCS behavior.
+            if apply_range_mask && data_types[param_idx] == FcsDataType::I {
+                if let Ok(IntegerKeyword::PnR(range)) =
+                    metadata.get_parameter_numeric_metadata(param_idx + 1, "R")
+                {
+                    let mask = ((*range).max(1).next_power_of_two() - 1) as u32;
+                    for value in &mut param_values {
+                        *value = (*value as u32 & mask) as f32;
+                    }
+                }
+            }
+
             // Verify we got the right number of events
             assert_eq!(
                 param_values.len(),
@@ -493,8 +1165,21 @@ impl Fcs {
                 .map(|s| s.to_string())
                 .unwrap_or_else(|_| format!("P{}", param_idx + 1));
 
-            // Create Series (Polars column) with name
-            let series = Column::new(param_name.as_str().into(), param_values);
+            // Compact storage: a $DATATYPE=I parameter that fits in 16 bits (the common case
+            // for legacy cytometers) is
+            // stored as a UInt16 column instead of Float32, halving that column's steady-state
+            // memory. `get_parameter_events_f32` casts it back to f32 on demand for callers
+            // that don't care about storage layout; `get_parameter_events_slice` still returns
+            // an error for it, since there is no `&[f32]` to hand out zero-copy.
+            let series = if compact_integer_storage
+                && data_types[param_idx] == FcsDataType::I
+                && bytes_per_parameter[param_idx] <= 2
+            {
+                let u16_values: Vec<u16> = param_values.iter().map(|&value| value as u16).collect();
+                Column::new(param_name.as_str().into(), u16_values)
+            } else {
+                Column::new(param_name.as_str().into(), param_values)
+            };
             columns.push(series);
         }
 
@@ -664,6 +1349,19 @@ impl Fcs {
                         .collect();
                 }
             }
+            (FcsDataType::A, width) => {
+                // ASCII decimal text, fixed-width; malformed fields decode to 0.0
+                // rather than failing the whole bulk read.
+                f32_values = data_bytes
+                    .chunks_exact(width)
+                    .map(|chunk| {
+                        std::str::from_utf8(chunk)
+                            .ok()
+                            .and_then(|s| s.trim().parse::<f32>().ok())
+                            .unwrap_or(0.0)
+                    })
+                    .collect();
+            }
             _ => {
                 return Err(anyhow!(
                     "Unsupported uniform data type: {:?} with {} bytes",
@@ -683,6 +1381,7 @@ impl Fcs {
     /// - int32 (4 bytes) - unsigned integer
     /// - float32 (4 bytes) - single-precision floating point
     /// - float64 (8 bytes) - double-precision floating point
+    /// - ASCII (`$PnB` bytes) - fixed-width decimal text
     ///
     /// # Arguments
     /// * `bytes` - Raw bytes for the parameter value
@@ -693,7 +1392,7 @@ impl Fcs {
     /// # Errors
     /// Will return `Err` if the bytes cannot be parsed according to the data type
     #[cold]
-    fn parse_parameter_value_to_f32(
+    pub(crate) fn parse_parameter_value_to_f32(
         bytes: &[u8],
         bytes_per_param: usize,
         data_type: &FcsDataType,
@@ -740,7 +1439,17 @@ impl Fcs {
                 "Invalid float64 size: {} bytes (expected 8)",
                 bytes_per_param
             )),
-            (FcsDataType::A, _) => Err(anyhow!("ASCII data type not supported")),
+            (FcsDataType::A, _) => {
+                let text = std::str::from_utf8(bytes)
+                    .map_err(|e| anyhow!("Invalid ASCII parameter data: {}", e))?;
+                text.trim().parse::<f32>().map_err(|e| {
+                    anyhow!(
+                        "Failed to parse ASCII numeric value '{}': {}",
+                        text.trim(),
+                        e
+                    )
+                })
+            }
         }
     }
 
@@ -918,9 +1627,68 @@ impl Fcs {
         }
     }
 
-    /// Creates a new `HashMap` of `Parameter`s
-    /// using the `Fcs` file's metadata to find the channel and label names from the `PnN` and `PnS` keywords.
-    /// Does NOT store events on the parameter.
+    /// Returns each channel's `$PnR` keyword range and observed (data min/max) range
+    ///
+    /// Channel ranges get recomputed a lot - once per axis on every plot, for every gate drawn
+    /// - so the result is cached against the `data_frame` allocation it was computed from.
+    /// Any method that replaces `data_frame` (a transform, compensation, filtering, ...)
+    /// naturally invalidates the cache just by making it point somewhere new; nothing needs to
+    /// remember to clear it by hand.
+    ///
+    /// # Errors
+    /// Will return `Err` if a channel's events can't be read
+    pub fn channel_ranges(&self) -> Result<ChannelRanges> {
+        if let Some((cached_frame, cached_ranges)) = self
+            .channel_range_cache
+            .read()
+            .expect("channel range cache lock poisoned")
+            .as_ref()
+        {
+            if Arc::ptr_eq(cached_frame, &self.data_frame) {
+                return Ok(cached_ranges.clone());
+            }
+        }
+
+        let mut ranges = ChannelRanges::default();
+        for (channel_name, parameter) in &self.parameters {
+            let events = self.get_parameter_events_f32(channel_name)?;
+            let (observed_min, observed_max) = match events.iter().minmax() {
+                MinMaxResult::NoElements => (0.0, 0.0),
+                MinMaxResult::OneElement(&value) => (value, value),
+                MinMaxResult::MinMax(&min, &max) => (min, max),
+            };
+
+            let keyword_range = self
+                .metadata
+                .get_parameter_numeric_metadata(parameter.parameter_number, "R")
+                .ok()
+                .and_then(|keyword| match keyword {
+                    IntegerKeyword::PnR(range) => Some(*range as f32),
+                    _ => None,
+                });
+
+            ranges.insert(
+                channel_name.clone(),
+                ChannelRange {
+                    keyword_range,
+                    observed_min,
+                    observed_max,
+                },
+            );
+        }
+
+        *self
+            .channel_range_cache
+            .write()
+            .expect("channel range cache lock poisoned") =
+            Some((Arc::clone(&self.data_frame), ranges.clone()));
+
+        Ok(ranges)
+    }
+
+    /// Creates a new `HashMap` of `Parameter`s
+    /// using the `Fcs` file's metadata to find the channel and label names from the `PnN` and `PnS` keywords.
+    /// Does NOT store events on the parameter.
     /// # Errors
     /// Will return `Err` if:
     /// - the number of parameters cannot be found in the metadata,
@@ -938,14 +1706,18 @@ impl Fcs {
                 Err(_) => channel_name,
             };
 
-            let transform = if channel_name.contains("FSC")
-                || channel_name.contains("SSC")
-                || channel_name.contains("Time")
-            {
-                TransformType::Linear
-            } else {
-                TransformType::default()
-            };
+            let transform = metadata
+                .get_parameter_display_transform(parameter_number)
+                .unwrap_or_else(|| {
+                    if channel_name.contains("FSC")
+                        || channel_name.contains("SSC")
+                        || channel_name.contains("Time")
+                    {
+                        TransformType::Linear
+                    } else {
+                        TransformType::default()
+                    }
+                });
 
             // Get excitation wavelength from metadata if available
             let excitation_wavelength = metadata
@@ -1025,6 +1797,15 @@ impl Fcs {
 
     // ==================== NEW POLARS-BASED ACCESSOR METHODS ====================
 
+    /// A [`LazyFrame`] over this file's event data, for callers that want to push filters,
+    /// projections, or aggregations down into Polars' query optimizer instead of collecting
+    /// the whole `DataFrame` first (as [`Fcs::get_parameter_statistics`] and its siblings do
+    /// internally via the same `.clone().lazy()` pattern this wraps)
+    #[must_use]
+    pub fn lazy(&self) -> LazyFrame {
+        (*self.data_frame).clone().lazy()
+    }
+
     /// Get events for a parameter as a slice of f32 values
     /// Polars gives us direct access to the underlying buffer (zero-copy)
     /// # Errors
@@ -1038,6 +1819,170 @@ impl Fcs {
             .map_err(|e| anyhow!("Parameter {} data is not contiguous: {}", channel_name, e))
     }
 
+    /// Returns an iterator over `chunk_size`-event windows across the given channels, each
+    /// borrowed directly from the underlying `Float32` columns with no per-channel copying
+    ///
+    /// This is the whole-file-pass counterpart to [`Fcs::get_parameter_events_slice`]: QC and
+    /// clustering algorithms that walk every event only need one chunk in memory at a time,
+    /// rather than a `Vec<f64>` copy of every requested channel up front.
+    /// # Errors
+    /// Will return `Err` if `chunk_size` is zero, or any channel is not found or is not
+    /// stored as a `Float32` column (see [`Fcs::get_parameter_events_f32`] for a cast-on-demand
+    /// alternative when that matters more than borrowing).
+    pub fn iter_chunks<'a>(
+        &'a self,
+        channel_names: &[&str],
+        chunk_size: usize,
+    ) -> Result<EventChunks<'a>> {
+        if chunk_size == 0 {
+            return Err(anyhow!("chunk_size must be greater than zero"));
+        }
+
+        let columns = channel_names
+            .iter()
+            .map(|name| self.get_parameter_events_slice(name))
+            .collect::<Result<Vec<&'a [f32]>>>()?;
+        let number_of_events = columns.first().map_or(0, |column| column.len());
+
+        Ok(EventChunks {
+            channel_names: channel_names.iter().map(|name| ChannelName::from(*name)).collect(),
+            columns,
+            chunk_size,
+            next_event: 0,
+            number_of_events,
+        })
+    }
+
+    /// Get events for a parameter as f32, regardless of whether it's stored as `Float32` or as
+    /// a compact `UInt16`/`UInt32` integer column (see [`Fcs::open_with_compact_integer_storage`])
+    ///
+    /// Prefer [`Fcs::get_parameter_events_slice`] when the column is known to be `Float32`,
+    /// since that returns a zero-copy `&[f32]`; this allocates a fresh `Vec<f32>` when casting
+    /// from a compact integer column.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter name is not found, or its column is neither
+    /// `Float32` nor an integer type.
+    pub fn get_parameter_events_f32(&self, channel_name: &str) -> Result<Cow<'_, [f32]>> {
+        if let Ok(slice) = self.get_parameter_events_slice(channel_name) {
+            return Ok(Cow::Borrowed(slice));
+        }
+
+        let column = self.get_parameter_column(channel_name)?;
+        if let Ok(chunked) = column.u16() {
+            return Ok(Cow::Owned(
+                chunked.into_no_null_iter().map(f32::from).collect(),
+            ));
+        }
+        if let Ok(chunked) = column.u32() {
+            #[allow(clippy::cast_precision_loss)]
+            return Ok(Cow::Owned(
+                chunked
+                    .into_no_null_iter()
+                    .map(|value| value as f32)
+                    .collect(),
+            ));
+        }
+
+        Err(anyhow!(
+            "Parameter {} is neither Float32 nor a compact integer column",
+            channel_name
+        ))
+    }
+
+    /// Get events for a `$DATATYPE=D` (double-precision) parameter at full f64 precision
+    ///
+    /// The event `DataFrame` stores columns as f32 (see [`EventDatum`]), or as compact
+    /// integers for `$DATATYPE=I` files opened with compact storage, so files
+    /// whose `$DATATYPE` (or `$PnDATATYPE`) is `D` lose precision once loaded into it. This
+    /// re-reads the parameter's raw bytes directly from the memory-mapped file at native
+    /// f64 precision, bypassing that downcast, for callers that need it (e.g. high-resolution
+    /// time channels). Note that transforms, gating, and statistics elsewhere in the crate
+    /// still operate on the f32 `DataFrame` columns; this is a read-only escape hatch, not a
+    /// change to the primary storage format.
+    ///
+    /// # Arguments
+    /// * `channel_name` - The channel name (e.g., "Time")
+    ///
+    /// # Errors
+    /// Will return `Err` if:
+    /// - the parameter name is not found
+    /// - the parameter's data type is not `D`
+    /// - the data segment offsets, byte order, or per-parameter widths cannot be determined
+    pub fn get_parameter_events_f64(&self, channel_name: &str) -> Result<Vec<f64>> {
+        let parameter_number = self.find_parameter(channel_name)?.parameter_number;
+
+        let data_type = self.metadata.get_data_type_for_channel(parameter_number)?;
+        if data_type != FcsDataType::D {
+            return Err(anyhow!(
+                "Parameter {} is {:?}, not double-precision (D)",
+                channel_name,
+                data_type
+            ));
+        }
+
+        let number_of_parameters = *self.metadata.get_number_of_parameters()?;
+        let number_of_events = *self.metadata.get_number_of_events()?;
+        let byte_order = self.metadata.get_byte_order()?;
+
+        let bytes_per_parameter: Vec<usize> = (1..=number_of_parameters)
+            .map(|n| self.metadata.get_bytes_per_parameter(n))
+            .collect::<Result<_>>()?;
+        let bytes_per_event: usize = bytes_per_parameter.iter().sum();
+        let param_offset: usize = bytes_per_parameter[..parameter_number - 1].iter().sum();
+
+        let data_bytes =
+            Self::resolve_data_segment(&self.header, &self.file_access.bytes, &self.metadata)?;
+
+        let mut values = Vec::with_capacity(number_of_events);
+        for event_idx in 0..number_of_events {
+            let start = event_idx * bytes_per_event + param_offset;
+            let chunk = &data_bytes[start..start + 8];
+            values.push(match byte_order {
+                ByteOrder::LittleEndian => LE::read_f64(chunk),
+                ByteOrder::BigEndian => BE::read_f64(chunk),
+            });
+        }
+
+        Ok(values)
+    }
+
+    /// Get a parameter's events converted to calibrated units (e.g. MESF, antibody binding
+    /// capacity) via its `$PnCALIBRATION` conversion factor
+    ///
+    /// Use [`Fcs::get_parameter_calibration_unit`] to get the unit name (e.g. `"MESF"`) to
+    /// label the resulting values with, so statistics computed from this can be reported
+    /// alongside the units they're actually in.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter name is not found or has no `$PnCALIBRATION` keyword.
+    pub fn get_parameter_events_calibrated(&self, channel_name: &str) -> Result<Vec<f32>> {
+        let parameter_number = self.find_parameter(channel_name)?.parameter_number;
+        let (units_per_signal, _) = self
+            .metadata
+            .get_parameter_calibration(parameter_number)
+            .ok_or_else(|| anyhow!("Parameter {channel_name} has no $PnCALIBRATION keyword"))?;
+
+        Ok(self
+            .get_parameter_events_slice(channel_name)?
+            .iter()
+            .map(|value| value * units_per_signal)
+            .collect())
+    }
+
+    /// The calibrated unit name for a parameter's `$PnCALIBRATION` keyword (e.g. `"MESF"`),
+    /// for labeling statistics produced by [`Fcs::get_parameter_events_calibrated`]
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter name is not found or has no `$PnCALIBRATION` keyword.
+    pub fn get_parameter_calibration_unit(&self, channel_name: &str) -> Result<String> {
+        let parameter_number = self.find_parameter(channel_name)?.parameter_number;
+        self.metadata
+            .get_parameter_calibration(parameter_number)
+            .map(|(_, unit)| unit.to_string())
+            .ok_or_else(|| anyhow!("Parameter {channel_name} has no $PnCALIBRATION keyword"))
+    }
+
     /// Get two parameters as (x, y) pairs for plotting
     /// Optimized for scatter plot use case with zero allocations until the collect
     /// # Errors
@@ -1140,6 +2085,74 @@ impl Fcs {
         Ok((min, max, mean, std))
     }
 
+    /// The `p`-th percentile (`p` in `[0.0, 1.0]`) of a parameter's events, via Polars'
+    /// streaming engine - see [`Fcs::get_parameter_statistics`] for why that keeps memory low
+    /// # Errors
+    /// Will return `Err` if the parameter is not found, `p` is outside `[0.0, 1.0]`, or the
+    /// query fails
+    pub fn get_parameter_percentile(&self, channel_name: &str, p: f64) -> Result<f32> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(anyhow!("Percentile {} must be between 0.0 and 1.0", p));
+        }
+
+        let stats = (*self.data_frame)
+            .clone()
+            .lazy()
+            .select([
+                col(channel_name)
+                    .quantile(lit(p), QuantileMethod::Linear)
+                    .alias("percentile"),
+            ])
+            .collect_with_engine(Engine::Streaming)?;
+
+        stats
+            .column("percentile")?
+            .f32()?
+            .get(0)
+            .ok_or_else(|| anyhow!("No percentile found for {}", channel_name))
+    }
+
+    /// The median (50th percentile) of a parameter's events; see
+    /// [`Fcs::get_parameter_percentile`]
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::get_parameter_percentile`]
+    pub fn get_parameter_median(&self, channel_name: &str) -> Result<f32> {
+        self.get_parameter_percentile(channel_name, 0.5)
+    }
+
+    /// The median absolute deviation (MAD) of a parameter's events, computed as the median of
+    /// `|x - median(x)|` in two streaming passes
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::get_parameter_percentile`]
+    pub fn get_parameter_mad(&self, channel_name: &str) -> Result<f32> {
+        let median = self.get_parameter_median(channel_name)?;
+
+        let stats = (*self.data_frame)
+            .clone()
+            .lazy()
+            .select([(col(channel_name) - lit(median)).abs().median().alias("mad")])
+            .collect_with_engine(Engine::Streaming)?;
+
+        stats
+            .column("mad")?
+            .f32()?
+            .get(0)
+            .ok_or_else(|| anyhow!("No MAD found for {}", channel_name))
+    }
+
+    /// The exact median and MAD of a parameter's events, computed in memory rather than via
+    /// Polars' streaming engine
+    ///
+    /// Prefer [`Fcs::get_parameter_median`]/[`Fcs::get_parameter_mad`] for large files; this
+    /// materializes and sorts the full column twice, but guarantees an exact (not
+    /// interpolated) result, which some QC algorithms rely on.
+    /// # Errors
+    /// Will return `Err` if the parameter is not found
+    pub fn get_parameter_median_mad_exact(&self, channel_name: &str) -> Result<(f32, f32)> {
+        let events = self.get_parameter_events_slice(channel_name)?;
+        Ok(crate::compensation::median_mad(events))
+    }
+
     // ==================== TRANSFORMATION METHODS ====================
 
     /// Apply arcsinh transformation to a parameter using Polars
@@ -1439,6 +2452,240 @@ impl Fcs {
         Ok(Some((matrix, param_names)))
     }
 
+    /// Set or replace the `$SPILLOVER` keyword from a compensation/unmixing matrix.
+    ///
+    /// `matrix` must be square with one row/column per entry in `channel_names`,
+    /// in the same order. The matrix is stored so a later [`Fcs::get_spillover_matrix`]
+    /// (or writing the file with [`crate::write_fcs_file`]) round-trips it exactly.
+    /// Any existing `$SPILL`/`$COMP` legacy keywords are removed so they can't
+    /// disagree with the new `$SPILLOVER` value.
+    ///
+    /// # Errors
+    /// Will return `Err` if `matrix` is not `channel_names.len()` x `channel_names.len()`.
+    pub fn set_spillover_matrix(
+        &mut self,
+        matrix: &Array2<f32>,
+        channel_names: &[String],
+    ) -> Result<()> {
+        use crate::keyword::{Keyword, MixedKeyword};
+
+        let n_parameters = channel_names.len();
+        if matrix.nrows() != n_parameters || matrix.ncols() != n_parameters {
+            return Err(anyhow!(
+                "Spillover matrix must be {n}x{n} to match {n} channel names, got {rows}x{cols}",
+                n = n_parameters,
+                rows = matrix.nrows(),
+                cols = matrix.ncols()
+            ));
+        }
+
+        let mut matrix_values = Vec::with_capacity(n_parameters * n_parameters);
+        for i in 0..n_parameters {
+            for j in 0..n_parameters {
+                matrix_values.push(matrix[[i, j]]);
+            }
+        }
+
+        self.metadata.keywords.insert(
+            "$SPILLOVER".to_string(),
+            Keyword::Mixed(MixedKeyword::SPILLOVER {
+                n_parameters,
+                parameter_names: channel_names.to_vec(),
+                matrix_values,
+            }),
+        );
+        self.metadata.keywords.remove("$SPILL");
+        self.metadata.keywords.remove("$COMP");
+
+        Ok(())
+    }
+
+    /// Renames a channel, updating the DataFrame column, [`ParameterMap`] key, `$PnN`
+    /// keyword, and any matching entry in the `$SPILLOVER` matrix's parameter names, so a
+    /// rename doesn't silently break compensation or event access by name.
+    ///
+    /// # Errors
+    /// Will return `Err` if `old` doesn't exist, or `new` already names another channel.
+    pub fn rename_channel(&mut self, old: &str, new: &str) -> Result<()> {
+        use crate::keyword::{Keyword, MixedKeyword};
+
+        if old == new {
+            return Ok(());
+        }
+        if self.parameters.contains_key(new) {
+            return Err(anyhow!("Channel {new} already exists"));
+        }
+        let mut parameter = self
+            .parameters
+            .remove(old)
+            .ok_or_else(|| anyhow!("Channel {old} not found"))?;
+
+        let new_channel_name: crate::parameter::ChannelName = Arc::from(new);
+        parameter.channel_name = new_channel_name.clone();
+        self.parameters.insert(new_channel_name, parameter);
+
+        let mut df = (*self.data_frame).clone();
+        df.rename(old, new.into())
+            .map_err(|e| anyhow!("Failed to rename DataFrame column {old}: {e}"))?;
+        self.data_frame = Arc::new(df);
+
+        self.metadata
+            .set_string_keyword(&format!("P{}N", self.parameters[new].parameter_number), new)?;
+
+        if let Some(Keyword::Mixed(MixedKeyword::SPILLOVER {
+            parameter_names, ..
+        })) = self.metadata.keywords.get_mut("$SPILLOVER")
+        {
+            for name in parameter_names.iter_mut() {
+                if name == old {
+                    *name = new.to_string();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selects a representative subset of `n` events via `method`, for plotting and
+    /// algorithm inputs that can't handle millions of events
+    ///
+    /// Returns the subsampled `Fcs` (in-memory only; call [`crate::write_fcs_file`] to
+    /// persist it) plus the original event indices that were selected, in ascending order.
+    ///
+    /// # Errors
+    /// Will return `Err` if `n` is 0, or if the DataFrame can't be filtered.
+    pub fn subsample(&self, n: usize, method: SubsampleMethod) -> Result<(Fcs, Vec<usize>)> {
+        if n == 0 {
+            return Err(anyhow!("Subsample size must be greater than 0"));
+        }
+        let n_events = self.get_event_count_from_dataframe();
+        let n = n.min(n_events);
+
+        let mut indices = match method {
+            SubsampleMethod::First => (0..n).collect::<Vec<usize>>(),
+            SubsampleMethod::EveryKth => {
+                let step = (n_events as f64 / n as f64).max(1.0);
+                (0..n)
+                    .map(|i| (((i as f64) * step) as usize).min(n_events.saturating_sub(1)))
+                    .collect()
+            }
+            SubsampleMethod::Random { seed } => {
+                use rand::SeedableRng;
+                use rand::seq::index;
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                index::sample(&mut rng, n_events, n).into_vec()
+            }
+            SubsampleMethod::DensityPreserving { seed } => {
+                self.density_preserving_indices(n, seed)?
+            }
+        };
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut mask = vec![false; n_events];
+        for &index in &indices {
+            mask[index] = true;
+        }
+        let mask_series = Series::new("mask".into(), mask);
+        let mask_ca = mask_series
+            .bool()
+            .map_err(|e| anyhow!("Failed to build subsample mask: {e}"))?;
+        let filtered_df = self
+            .data_frame
+            .filter(mask_ca)
+            .map_err(|e| anyhow!("Failed to filter events for subsampling: {e}"))?;
+
+        let mut new_fcs = self.clone();
+        new_fcs.data_frame = Arc::new(filtered_df);
+
+        Ok((new_fcs, indices))
+    }
+
+    /// Density-preserving event selection for [`Fcs::subsample`]: bins every numeric
+    /// channel into a coarse grid, then does weighted sampling without replacement
+    /// (Efraimidis-Spirakis) with weight `1 / bin_occupancy`, so events in sparsely
+    /// populated regions of parameter space are proportionally favored over events in
+    /// dense regions
+    fn density_preserving_indices(&self, n: usize, seed: u64) -> Result<Vec<usize>> {
+        use rand::{Rng, SeedableRng};
+
+        const BINS_PER_AXIS: u32 = 10;
+
+        let n_events = self.get_event_count_from_dataframe();
+        let numeric_columns: Vec<_> = self
+            .data_frame
+            .get_columns()
+            .iter()
+            .filter(|column| column.dtype() == &DataType::Float32)
+            .collect();
+
+        if numeric_columns.is_empty() || n_events == 0 {
+            return Ok((0..n_events.min(n)).collect());
+        }
+
+        let mut bin_indices: Vec<Vec<u32>> = Vec::with_capacity(numeric_columns.len());
+        for column in &numeric_columns {
+            let series = column.as_materialized_series();
+            let ca = series
+                .f32()
+                .map_err(|e| anyhow!("Non-f32 numeric column: {e}"))?;
+            let values = ca
+                .cont_slice()
+                .map_err(|e| anyhow!("Data not contiguous: {e}"))?;
+            let (min, max) = values
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            let range = (max - min).max(f32::EPSILON);
+            let bins: Vec<u32> = values
+                .iter()
+                .map(|&v| (((v - min) / range) * (BINS_PER_AXIS - 1) as f32) as u32)
+                .collect();
+            bin_indices.push(bins);
+        }
+
+        let mut bin_counts: std::collections::HashMap<Vec<u32>, usize> =
+            std::collections::HashMap::new();
+        for event_idx in 0..n_events {
+            let key: Vec<u32> = bin_indices.iter().map(|bins| bins[event_idx]).collect();
+            *bin_counts.entry(key).or_insert(0) += 1;
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut keyed: Vec<(f64, usize)> = (0..n_events)
+            .map(|event_idx| {
+                let bin_key: Vec<u32> = bin_indices.iter().map(|bins| bins[event_idx]).collect();
+                let weight = 1.0 / *bin_counts.get(&bin_key).unwrap_or(&1) as f64;
+                let u: f64 = rng.random_range(f64::EPSILON..1.0);
+                (u.powf(1.0 / weight), event_idx)
+            })
+            .collect();
+
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(keyed.into_iter().take(n).map(|(_, idx)| idx).collect())
+    }
+
+    /// Validates the file's optional trailing CRC-16 checksum against its bytes
+    ///
+    /// See [`VerificationReport`]; a file with no CRC field reports `crc_present: false`
+    /// and [`VerificationReport::is_valid`] returns `true`.
+    #[must_use]
+    pub fn verify(&self) -> VerificationReport {
+        verify_crc(&self.file_access.bytes)
+    }
+
+    /// Produces a machine-readable [`ComplianceReport`] of this file's compliance with the
+    /// FCS specification for its declared version
+    ///
+    /// Complements [`Fcs::verify`] (CRC) and the checks performed automatically during
+    /// [`Fcs::open`] (which bail out on the first missing required keyword); this is for CLI
+    /// tools and QC pipelines that want the full picture - missing keywords, HEADER/TEXT
+    /// offset mismatches, `$TOT`/data-size disagreement, illegal delimiters, and (at
+    /// [`ValidationLevel::Full`]) deprecated keyword usage - without failing the whole load.
+    #[must_use]
+    pub fn validate(&self, level: ValidationLevel) -> ComplianceReport {
+        validate_fcs(&self.header, &self.metadata, level)
+    }
+
     /// Check if this file has compensation information
     #[must_use]
     pub fn has_compensation(&self) -> bool {
@@ -1680,40 +2927,19 @@ impl Fcs {
 
         // Extract data for channels to compensate
         let mut channel_data: Vec<Vec<f32>> = Vec::with_capacity(n_channels);
-        let n_events = self.get_event_count_from_dataframe();
 
         for &channel_name in channel_names {
             let data = self.get_parameter_events_slice(channel_name)?;
             channel_data.push(data.to_vec());
         }
 
-        // Use CPU compensation (benchmarked: GPU was slower due to transfer overhead)
-        // Apply compensation: compensated = original * inverse(compensation_matrix)
-        // For efficiency, we pre-compute the inverse
-        use ndarray_linalg::Inverse;
-        let comp_inv = compensation_matrix
-            .inv()
-            .map_err(|e| anyhow!("Failed to invert compensation matrix: {:?}", e))?;
-
-        // Perform matrix multiplication for each event
-        use rayon::prelude::*;
-        let compensated_data: Vec<Vec<f32>> = (0..n_channels)
-            .into_par_iter()
-            .map(|i| {
-                let row = comp_inv.row(i);
-                let mut result = vec![0.0; n_events];
-
-                for event_idx in 0..n_events {
-                    let mut sum = 0.0;
-                    for (j, &coeff) in row.iter().enumerate() {
-                        sum += coeff * channel_data[j][event_idx];
-                    }
-                    result[event_idx] = sum;
-                }
-
-                result
-            })
-            .collect();
+        // Apply compensation: compensated = inverse(compensation_matrix) * original
+        // Delegates to `MatrixOps`, which inverts once and multiplies via ndarray's
+        // matrixmultiply/BLAS-backed `dot` (blocked, SIMD matmul) instead of a per-event
+        // scalar loop - this is the hot path for 30-color, multi-million-event files (see
+        // `matrix_operations` benchmarks)
+        let compensated_data = MatrixOps::compensate_parameters(compensation_matrix, &channel_data)
+            .map_err(|e| anyhow!("Failed to apply compensation: {e}"))?;
 
         // Create new DataFrame with compensated values
         let mut df = (*self.data_frame).clone();
@@ -1792,4 +3018,383 @@ impl Fcs {
 
         fcs_unmixed.apply_arcsinh_transforms(&params_with_cofactor)
     }
+
+    /// Apply spectral unmixing with a selectable least-squares method and per-event
+    /// residuals for QC
+    ///
+    /// [`Fcs::apply_spectral_unmixing`] always solves via a single shared matrix inverse
+    /// (ordinary least squares). This solves per event instead, so weighted and
+    /// constrained methods are available:
+    /// - [`UnmixingMethod::Ols`]: identical math to [`Fcs::apply_spectral_unmixing`], just
+    ///   solved per event so a residual can be reported alongside it.
+    /// - [`UnmixingMethod::Wls`]: Poisson-weighted least squares. Note that because
+    ///   `unmixing_matrix` here is always square (one component per channel, matching
+    ///   [`Fcs::apply_compensation`]'s convention), the system is exactly determined and
+    ///   WLS's weights mathematically cancel out of the solution - it converges on the
+    ///   same abundances as OLS. The option exists for callers building toward a
+    ///   rectangular (more-channels-than-fluorophores) design matrix, which this API
+    ///   doesn't yet support.
+    /// - [`UnmixingMethod::Nnls`]: constrains abundances to be non-negative, which does
+    ///   change the result whenever OLS/WLS would otherwise produce a negative value.
+    ///
+    /// # Arguments
+    /// * `unmixing_matrix` - Matrix describing spectral signatures of fluorophores
+    /// * `channel_names` - Names of spectral channels
+    /// * `cofactor` - Cofactor for arcsinh transformation (default: 200)
+    /// * `method` - Least-squares method to solve with
+    ///
+    /// # Errors
+    /// Will return `Err` if a channel is missing, non-f32, or `unmixing_matrix`'s
+    /// dimensions don't match `channel_names`.
+    pub fn apply_spectral_unmixing_with_method(
+        &self,
+        unmixing_matrix: &Array2<f32>,
+        channel_names: &[&str],
+        cofactor: Option<f32>,
+        method: UnmixingMethod,
+    ) -> Result<UnmixingResult> {
+        let cofactor = cofactor.unwrap_or(200.0);
+        let n_channels = channel_names.len();
+        if unmixing_matrix.nrows() != n_channels || unmixing_matrix.ncols() != n_channels {
+            return Err(anyhow!(
+                "Unmixing matrix dimensions ({}, {}) don't match number of channels ({})",
+                unmixing_matrix.nrows(),
+                unmixing_matrix.ncols(),
+                n_channels
+            ));
+        }
+
+        // Inverse-transform each channel back to linear scale, same as apply_spectral_unmixing
+        let transform = TransformType::Arcsinh { cofactor };
+        let n_events = self.get_event_count_from_dataframe();
+        let mut linear_channels: Vec<Vec<f32>> = Vec::with_capacity(n_channels);
+        for &channel_name in channel_names {
+            let col = self
+                .data_frame
+                .column(channel_name)
+                .map_err(|e| anyhow!("Parameter {} not found: {}", channel_name, e))?;
+            let series = col.as_materialized_series();
+            let ca = series
+                .f32()
+                .map_err(|e| anyhow!("Parameter {} is not f32: {}", channel_name, e))?;
+            let linear: Vec<f32> = ca
+                .cont_slice()
+                .map_err(|e| anyhow!("Data not contiguous: {}", e))?
+                .par_iter()
+                .map(|&y| transform.inverse_transform(&y))
+                .collect();
+            linear_channels.push(linear);
+        }
+
+        let ols_inverse = if method == UnmixingMethod::Ols {
+            use ndarray_linalg::Inverse;
+            Some(
+                unmixing_matrix
+                    .inv()
+                    .map_err(|e| anyhow!("Failed to invert unmixing matrix: {:?}", e))?,
+            )
+        } else {
+            None
+        };
+
+        let mut unmixed_channels: Vec<Vec<f32>> = vec![vec![0.0; n_events]; n_channels];
+        let mut residuals = vec![0.0f32; n_events];
+
+        for event_idx in 0..n_events {
+            let signal: Vec<f32> = (0..n_channels)
+                .map(|c| linear_channels[c][event_idx])
+                .collect();
+
+            let abundances = match method {
+                UnmixingMethod::Ols => {
+                    let inverse = ols_inverse
+                        .as_ref()
+                        .expect("Ols inverse computed above when method is Ols");
+                    (0..n_channels)
+                        .map(|i| {
+                            inverse
+                                .row(i)
+                                .iter()
+                                .zip(&signal)
+                                .map(|(&coefficient, &s)| coefficient * s)
+                                .sum::<f32>()
+                        })
+                        .collect::<Vec<f32>>()
+                }
+                UnmixingMethod::Wls => solve_wls(unmixing_matrix, &signal)?,
+                UnmixingMethod::Nnls => solve_nnls(unmixing_matrix, &signal),
+            };
+
+            let mut residual_sq = 0.0f32;
+            for c in 0..n_channels {
+                let predicted: f32 = (0..n_channels)
+                    .map(|i| unmixing_matrix[[c, i]] * abundances[i])
+                    .sum();
+                residual_sq += (predicted - signal[c]).powi(2);
+            }
+            residuals[event_idx] = residual_sq.sqrt();
+
+            for (c, &abundance) in abundances.iter().enumerate() {
+                unmixed_channels[c][event_idx] = abundance;
+            }
+        }
+
+        let mut df = (*self.data_frame).clone();
+        for (c, &channel_name) in channel_names.iter().enumerate() {
+            let new_series = Series::new(channel_name.into(), unmixed_channels[c].clone());
+            df.replace(channel_name, new_series)
+                .map_err(|e| anyhow!("Failed to replace column: {}", e))?;
+        }
+
+        let fcs_unmixed = Fcs {
+            data_frame: Arc::new(df),
+            ..self.clone()
+        };
+        let params_with_cofactor: Vec<(&str, f32)> =
+            channel_names.iter().map(|&name| (name, cofactor)).collect();
+        let data = fcs_unmixed.apply_arcsinh_transforms(&params_with_cofactor)?;
+
+        Ok(UnmixingResult { data, residuals })
+    }
+
+    /// Apply spectral unmixing with one or more autofluorescence components
+    ///
+    /// Each entry in `autofluorescence_spectra` (see
+    /// [`crate::compensation::extract_autofluorescence_spectrum`]) becomes an extra column
+    /// appended to `unmixing_matrix`, so the system has `channel_names.len()` equations for
+    /// `channel_names.len() + autofluorescence_spectra.len()` unknowns. Always solved via
+    /// [`UnmixingMethod::Nnls`], since a matrix with more unknowns than equations has no
+    /// unique OLS/WLS solution and autofluorescence contributions can't be negative anyway.
+    /// The returned data has one new column per autofluorescence component: `"AF"` for the
+    /// first, `"AF2"`, `"AF3"`, ... for any additional ones.
+    ///
+    /// # Arguments
+    /// * `unmixing_matrix` - Square fluorophore spectral matrix, as in [`Fcs::apply_spectral_unmixing`]
+    /// * `channel_names` - Names of spectral channels, matching `unmixing_matrix`'s rows/columns
+    /// * `autofluorescence_spectra` - One or more autofluorescence signatures, each with one
+    ///   value per `channel_names` entry
+    /// * `cofactor` - Cofactor for arcsinh transformation (default: 200)
+    ///
+    /// # Errors
+    /// Will return `Err` if a channel is missing or non-f32, `unmixing_matrix`'s dimensions
+    /// don't match `channel_names`, or an autofluorescence spectrum's length doesn't match
+    /// `channel_names`.
+    pub fn apply_spectral_unmixing_with_autofluorescence(
+        &self,
+        unmixing_matrix: &Array2<f32>,
+        channel_names: &[&str],
+        autofluorescence_spectra: &[Vec<f32>],
+        cofactor: Option<f32>,
+    ) -> Result<UnmixingResult> {
+        let cofactor = cofactor.unwrap_or(200.0);
+        let n_channels = channel_names.len();
+        if unmixing_matrix.nrows() != n_channels || unmixing_matrix.ncols() != n_channels {
+            return Err(anyhow!(
+                "Unmixing matrix dimensions ({}, {}) don't match number of channels ({})",
+                unmixing_matrix.nrows(),
+                unmixing_matrix.ncols(),
+                n_channels
+            ));
+        }
+        for spectrum in autofluorescence_spectra {
+            if spectrum.len() != n_channels {
+                return Err(anyhow!(
+                    "Autofluorescence spectrum has {} entries, expected {}",
+                    spectrum.len(),
+                    n_channels
+                ));
+            }
+        }
+
+        let n_af = autofluorescence_spectra.len();
+        let n_components = n_channels + n_af;
+        let mut augmented = Array2::<f32>::zeros((n_channels, n_components));
+        for i in 0..n_channels {
+            for j in 0..n_channels {
+                augmented[[i, j]] = unmixing_matrix[[i, j]];
+            }
+        }
+        for (k, spectrum) in autofluorescence_spectra.iter().enumerate() {
+            for (i, &value) in spectrum.iter().enumerate() {
+                augmented[[i, n_channels + k]] = value;
+            }
+        }
+
+        let transform = TransformType::Arcsinh { cofactor };
+        let n_events = self.get_event_count_from_dataframe();
+        let mut linear_channels: Vec<Vec<f32>> = Vec::with_capacity(n_channels);
+        for &channel_name in channel_names {
+            let col = self
+                .data_frame
+                .column(channel_name)
+                .map_err(|e| anyhow!("Parameter {} not found: {}", channel_name, e))?;
+            let series = col.as_materialized_series();
+            let ca = series
+                .f32()
+                .map_err(|e| anyhow!("Parameter {} is not f32: {}", channel_name, e))?;
+            let linear: Vec<f32> = ca
+                .cont_slice()
+                .map_err(|e| anyhow!("Data not contiguous: {}", e))?
+                .par_iter()
+                .map(|&y| transform.inverse_transform(&y))
+                .collect();
+            linear_channels.push(linear);
+        }
+
+        let mut unmixed_components: Vec<Vec<f32>> = vec![vec![0.0; n_events]; n_components];
+        let mut residuals = vec![0.0f32; n_events];
+
+        for event_idx in 0..n_events {
+            let signal: Vec<f32> = (0..n_channels)
+                .map(|c| linear_channels[c][event_idx])
+                .collect();
+
+            let abundances = solve_nnls(&augmented, &signal);
+
+            let mut residual_sq = 0.0f32;
+            for c in 0..n_channels {
+                let predicted: f32 = (0..n_components)
+                    .map(|i| augmented[[c, i]] * abundances[i])
+                    .sum();
+                residual_sq += (predicted - signal[c]).powi(2);
+            }
+            residuals[event_idx] = residual_sq.sqrt();
+
+            for (c, &abundance) in abundances.iter().enumerate() {
+                unmixed_components[c][event_idx] = abundance;
+            }
+        }
+
+        let mut df = (*self.data_frame).clone();
+        for (c, &channel_name) in channel_names.iter().enumerate() {
+            let new_series = Series::new(channel_name.into(), unmixed_components[c].clone());
+            df.replace(channel_name, new_series)
+                .map_err(|e| anyhow!("Failed to replace column: {}", e))?;
+        }
+        for k in 0..n_af {
+            let column_name = if k == 0 {
+                "AF".to_string()
+            } else {
+                format!("AF{}", k + 1)
+            };
+            let series = Series::new(column_name.into(), unmixed_components[n_channels + k].clone());
+            df.with_column(series)
+                .map_err(|e| anyhow!("Failed to add autofluorescence column: {}", e))?;
+        }
+
+        let fcs_unmixed = Fcs {
+            data_frame: Arc::new(df),
+            ..self.clone()
+        };
+        let params_with_cofactor: Vec<(&str, f32)> =
+            channel_names.iter().map(|&name| (name, cofactor)).collect();
+        let data = fcs_unmixed.apply_arcsinh_transforms(&params_with_cofactor)?;
+
+        Ok(UnmixingResult { data, residuals })
+    }
+}
+
+/// Solves the weighted normal equations `(Mᵀ W M) x = Mᵀ W y` for a single event, with
+/// Poisson weights `w_i = 1 / max(y_i, 1.0)`
+fn solve_wls(matrix: &Array2<f32>, signal: &[f32]) -> Result<Vec<f32>> {
+    use ndarray::Array1;
+    use ndarray_linalg::Inverse;
+
+    let n = matrix.nrows();
+    let weights: Vec<f32> = signal.iter().map(|&s| 1.0 / s.max(1.0)).collect();
+
+    // (Mᵀ W), an n x n matrix: row i, col j is matrix[j, i] * weights[j]
+    let mut mtw = Array2::<f32>::zeros((n, n));
+    for i in 0..n {
+        for j in 0..n {
+            mtw[[i, j]] = matrix[[j, i]] * weights[j];
+        }
+    }
+
+    let mtwm = mtw.dot(matrix);
+    let y = Array1::from(signal.to_vec());
+    let mtwy = mtw.dot(&y);
+
+    let mtwm_inverse = mtwm
+        .inv()
+        .map_err(|e| anyhow!("Failed to invert weighted normal equations: {:?}", e))?;
+
+    Ok(mtwm_inverse.dot(&mtwy).to_vec())
+}
+
+/// Solves `min ||A x - y||` subject to `x >= 0` for a single event, via the Lawson-Hanson
+/// active set method
+///
+/// Falls back to whatever partial solution has been found so far if a passive-set
+/// submatrix ever turns out to be singular, rather than failing the whole event.
+fn solve_nnls(a: &Array2<f32>, y: &[f32]) -> Vec<f32> {
+    use ndarray::{Array1, Axis};
+    use ndarray_linalg::Inverse;
+
+    const MAX_OUTER_ITERATIONS: usize = 64;
+    const TOLERANCE: f32 = 1e-6;
+
+    let n = a.ncols();
+    let y = Array1::from(y.to_vec());
+
+    let mut x = Array1::<f32>::zeros(n);
+    let mut passive: Vec<usize> = Vec::new();
+    let mut active: Vec<usize> = (0..n).collect();
+
+    for _ in 0..MAX_OUTER_ITERATIONS {
+        let residual = &y - &a.dot(&x);
+        let gradient = a.t().dot(&residual);
+
+        let Some(&j) = active.iter().max_by(|&&i, &&k| gradient[i].total_cmp(&gradient[k]))
+        else {
+            break;
+        };
+        if gradient[j] <= TOLERANCE {
+            break;
+        }
+
+        passive.push(j);
+        active.retain(|&i| i != j);
+
+        loop {
+            let a_passive = a.select(Axis(1), &passive);
+            let ata = a_passive.t().dot(&a_passive);
+            let atb = a_passive.t().dot(&y);
+            let Ok(ata_inverse) = ata.inv() else {
+                break;
+            };
+            let z_passive = ata_inverse.dot(&atb);
+
+            if z_passive.iter().all(|&v| v > 0.0) {
+                for (idx, &p) in passive.iter().enumerate() {
+                    x[p] = z_passive[idx];
+                }
+                break;
+            }
+
+            let mut alpha = f32::MAX;
+            for (idx, &p) in passive.iter().enumerate() {
+                if z_passive[idx] <= 0.0 {
+                    let candidate = x[p] / (x[p] - z_passive[idx]);
+                    if candidate < alpha {
+                        alpha = candidate;
+                    }
+                }
+            }
+
+            for (idx, &p) in passive.iter().enumerate() {
+                x[p] += alpha * (z_passive[idx] - x[p]);
+            }
+
+            let (still_passive, newly_active): (Vec<usize>, Vec<usize>) = passive
+                .iter()
+                .copied()
+                .partition(|&p| x[p] > TOLERANCE);
+            passive = still_passive;
+            active.extend(newly_active);
+            active.sort_unstable();
+        }
+    }
+
+    x.to_vec()
 }
@@ -3,7 +3,6 @@ use super::version::Version;
 use anyhow::{Result, anyhow};
 use core::str;
 // use image::EncodableLayout;
-use memmap3::Mmap;
 use serde::{Serialize, Serializer, ser::SerializeMap};
 use std::ops::RangeInclusive;
 
@@ -51,25 +50,39 @@ impl Header {
     /// - the FCS version is not valid
     /// - the number of spaces in the header segment is not 4
     /// - the byte offsets for the TEXT, DATA, or ANALYSIS segments are not valid
-    pub fn from_mmap(mmap: &Mmap) -> Result<Self> {
-        // Check that bytes 6-9 are spaces:
-        Self::check_header_spaces(&mmap[6..=9])?;
+    pub fn from_mmap(mmap: &[u8]) -> Result<Self> {
+        Self::from_mmap_at(mmap, 0)
+    }
+
+    /// Returns a new Header struct starting at `base` bytes into the memory map
+    ///
+    /// Used to parse subsequent datasets in a file containing multiple concatenated
+    /// datasets, whose HEADER segments begin at the byte offset given by the
+    /// previous dataset's `$NEXTDATA` keyword rather than at the start of the file.
+    /// # Errors
+    /// Will return `Err` if:
+    /// - the FCS version is not valid
+    /// - the number of spaces in the header segment is not 4
+    /// - the byte offsets for the TEXT, DATA, or ANALYSIS segments are not valid
+    pub fn from_mmap_at(mmap: &[u8], base: usize) -> Result<Self> {
+        // Check that bytes 6-9 (relative to `base`) are spaces:
+        Self::check_header_spaces(&mmap[base + 6..=base + 9])?;
         // View the header segment and print the offsets to the console
         // Self::check_fcs_offsets(mmap);
 
         Ok(Self {
-            version: Self::get_version(mmap)?,
-            text_offset: Self::get_text_offsets(mmap)?,
-            data_offset: Self::get_data_offsets(mmap)?,
-            analysis_offset: Self::get_analysis_offsets(mmap)?,
+            version: Self::get_version(mmap, base)?,
+            text_offset: Self::get_text_offsets(mmap, base)?,
+            data_offset: Self::get_data_offsets(mmap, base)?,
+            analysis_offset: Self::get_analysis_offsets(mmap, base)?,
         })
     }
 
-    /// Returns the FCS version from the first 6 bytes of the file
+    /// Returns the FCS version from the first 6 bytes of the header segment starting at `base`
     /// # Errors
     /// Will return `Err` if the version is not valid
-    pub fn get_version(mmap: &Mmap) -> Result<Version> {
-        let version = String::from_utf8(mmap[..6].to_vec())?;
+    pub fn get_version(mmap: &[u8], base: usize) -> Result<Version> {
+        let version = String::from_utf8(mmap[base..base + 6].to_vec())?;
         Self::check_fcs_version(&version)
     }
 
@@ -99,7 +112,7 @@ impl Header {
         Ok(())
     }
     /// Parse an inclusive range of bytes from the memory map as an ASCII-encoded offset (in usize bytes)
-    fn get_offset_from_header(mmap: &Mmap, start: usize, end: usize) -> Result<usize> {
+    fn get_offset_from_header(mmap: &[u8], start: usize, end: usize) -> Result<usize> {
         let offset_char = mmap[start..=end].as_ascii().expect("ascii not found");
         // println!("Offset bytes {:?}-{:?}: {:?}", &start, &end, &offset_char);
         // println!(
@@ -108,46 +121,46 @@ impl Header {
         // );
         Ok(offset_char.as_str().trim_ascii().parse::<usize>()?)
     }
-    /// Parse bytes 10-17 from the memory map as the ASCII-encoded offset (in usize bytes) to the first byte of the TEXT segment:
-    fn get_text_offset_start(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 10, 17)
+    /// Parse bytes 10-17 (relative to `base`) as the ASCII-encoded offset (in usize bytes) to the first byte of the TEXT segment:
+    fn get_text_offset_start(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 10, base + 17)
     }
-    /// Parse bytes 18-25 as the ASCII-encoded offset (in usize bytes) to the last byte of the TEXT segment:
-    fn get_text_offset_end(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 18, 25)
+    /// Parse bytes 18-25 (relative to `base`) as the ASCII-encoded offset (in usize bytes) to the last byte of the TEXT segment:
+    fn get_text_offset_end(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 18, base + 25)
     }
-    /// Parse bytes 26-33 as the ASCII-encoded offset to the first byte of the DATA segment:
-    fn get_data_offset_start(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 26, 33)
+    /// Parse bytes 26-33 (relative to `base`) as the ASCII-encoded offset to the first byte of the DATA segment:
+    fn get_data_offset_start(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 26, base + 33)
     }
-    /// Parse bytes 34-41 as the ASCII-encoded offset to the last byte of the DATA segment:
-    fn get_data_offset_end(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 34, 41)
+    /// Parse bytes 34-41 (relative to `base`) as the ASCII-encoded offset to the last byte of the DATA segment:
+    fn get_data_offset_end(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 34, base + 41)
     }
-    /// Parse bytes 42-49 as the ASCII-encoded offset to the first byte of the ANALYSIS segment:
-    fn get_analysis_offset_start(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 42, 49)
+    /// Parse bytes 42-49 (relative to `base`) as the ASCII-encoded offset to the first byte of the ANALYSIS segment:
+    fn get_analysis_offset_start(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 42, base + 49)
     }
-    /// Parse bytes 50-57 as the ASCII-encoded offset to the last byte of the ANALYSIS segment:
-    fn get_analysis_offset_end(mmap: &Mmap) -> Result<usize> {
-        Self::get_offset_from_header(mmap, 50, 57)
+    /// Parse bytes 50-57 (relative to `base`) as the ASCII-encoded offset to the last byte of the ANALYSIS segment:
+    fn get_analysis_offset_end(mmap: &[u8], base: usize) -> Result<usize> {
+        Self::get_offset_from_header(mmap, base + 50, base + 57)
     }
     /// Returns the byte offsets for the TEXT segment
-    fn get_text_offsets(mmap: &Mmap) -> Result<RangeInclusive<usize>> {
-        let text_offset_start = Self::get_text_offset_start(mmap)?;
-        let text_offset_end = Self::get_text_offset_end(mmap)?;
+    fn get_text_offsets(mmap: &[u8], base: usize) -> Result<RangeInclusive<usize>> {
+        let text_offset_start = Self::get_text_offset_start(mmap, base)?;
+        let text_offset_end = Self::get_text_offset_end(mmap, base)?;
         Ok(text_offset_start..=text_offset_end)
     }
     /// Returns the byte offsets for the DATA segment
-    fn get_data_offsets(mmap: &Mmap) -> Result<RangeInclusive<usize>> {
-        let data_offset_start = Self::get_data_offset_start(mmap)?;
-        let data_offset_end = Self::get_data_offset_end(mmap)?;
+    fn get_data_offsets(mmap: &[u8], base: usize) -> Result<RangeInclusive<usize>> {
+        let data_offset_start = Self::get_data_offset_start(mmap, base)?;
+        let data_offset_end = Self::get_data_offset_end(mmap, base)?;
         Ok(data_offset_start..=data_offset_end)
     }
     /// Returns the byte offsets for the ANALYSIS segment
-    fn get_analysis_offsets(mmap: &Mmap) -> Result<RangeInclusive<usize>> {
-        let analysis_offset_start = Self::get_analysis_offset_start(mmap)?;
-        let analysis_offset_end = Self::get_analysis_offset_end(mmap)?;
+    fn get_analysis_offsets(mmap: &[u8], base: usize) -> Result<RangeInclusive<usize>> {
+        let analysis_offset_start = Self::get_analysis_offset_start(mmap, base)?;
+        let analysis_offset_end = Self::get_analysis_offset_end(mmap, base)?;
         Ok(analysis_offset_start..=analysis_offset_end)
     }
     /// Debug utility to print FCS file segment offsets
@@ -161,31 +174,31 @@ impl Header {
     ///
     /// # Errors
     /// Will return `Err` if offsets cannot be read from the header
-    pub fn check_fcs_offsets(mmap: &Mmap) -> Result<()> {
+    pub fn check_fcs_offsets(mmap: &[u8]) -> Result<()> {
         println!("HEADER (first 58 bytes): {:?}", &mmap[0..58].as_ascii());
         println!(
             "TEXT segment start offset: {:?}",
-            Self::get_text_offset_start(mmap)?
+            Self::get_text_offset_start(mmap, 0)?
         );
         println!(
             "TEXT segment end offset: {:?}",
-            Self::get_text_offset_end(mmap)?
+            Self::get_text_offset_end(mmap, 0)?
         );
         println!(
             "DATA segment start offset: {:?}",
-            Self::get_data_offset_start(mmap)?
+            Self::get_data_offset_start(mmap, 0)?
         );
         println!(
             "DATA segment end offset: {:?}",
-            Self::get_data_offset_end(mmap)?
+            Self::get_data_offset_end(mmap, 0)?
         );
         println!(
             "ANALYSIS segment start offset (optional): {:?}",
-            Self::get_analysis_offset_start(mmap)
+            Self::get_analysis_offset_start(mmap, 0)
         );
         println!(
             "ANALYSIS segment end offset (optional): {:?}",
-            Self::get_analysis_offset_end(mmap)
+            Self::get_analysis_offset_end(mmap, 0)
         );
         // print from byte 4700 to 5210 (end of text, beginning of data)
         println!("header range of TEXT: {:?}", &mmap[4700..=5216].as_ascii());
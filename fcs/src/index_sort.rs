@@ -0,0 +1,96 @@
+//! BD index-sort location parsing
+//!
+//! Sorters that support index sorting (e.g. BD FACSAria/FACSDiva) record which plate well each
+//! sorted event landed in in a per-file `INDEX SORTING LOCATIONS` keyword: a semicolon-separated
+//! list of zero-indexed `row,column` pairs, one per event in acquisition order. This parses that
+//! convention into a typed event-to-well mapping, so index-sort experiments can be joined
+//! against downstream single-cell assays by well position (see [`crate::plate`]).
+
+use crate::file::Fcs;
+use crate::keyword::StringableKeyword;
+use crate::plate::WellPosition;
+use anyhow::{Result, anyhow};
+
+/// The custom (non-`$`) keyword BD's software stores index-sort locations under
+const INDEX_SORTING_LOCATIONS_KEYWORD: &str = "INDEX SORTING LOCATIONS";
+
+/// One sorted event's destination well, in acquisition/event order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexSortLocation {
+    /// Index into the file's event data (`data_frame` row) this well was sorted from
+    pub event_index: usize,
+    pub well: WellPosition,
+}
+
+/// An index-sort experiment's event-to-well mapping, parsed from a file's `INDEX SORTING
+/// LOCATIONS` keyword; see [`IndexSortData::from_fcs`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexSortData {
+    locations: Vec<IndexSortLocation>,
+}
+
+impl IndexSortData {
+    /// Parses `fcs`'s `INDEX SORTING LOCATIONS` keyword, if present
+    ///
+    /// # Errors
+    /// Will return `Err` if the keyword is present but a location can't be parsed as a
+    /// `row,column` pair of non-negative integers.
+    pub fn from_fcs(fcs: &Fcs) -> Result<Option<Self>> {
+        let Ok(keyword) = fcs.metadata.get_string_keyword(INDEX_SORTING_LOCATIONS_KEYWORD) else {
+            return Ok(None);
+        };
+        let raw = keyword.get_str();
+
+        let locations = raw
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .enumerate()
+            .map(|(event_index, entry)| {
+                let (row, column) = entry.split_once(',').ok_or_else(|| {
+                    anyhow!("Index sort location {entry:?} is not a row,column pair")
+                })?;
+                Ok(IndexSortLocation {
+                    event_index,
+                    well: WellPosition {
+                        row: row
+                            .trim()
+                            .parse()
+                            .map_err(|_| anyhow!("Index sort location {entry:?} has an invalid row"))?,
+                        column: column
+                            .trim()
+                            .parse()
+                            .map_err(|_| anyhow!("Index sort location {entry:?} has an invalid column"))?,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(Self { locations }))
+    }
+
+    /// Every parsed event-to-well mapping, in acquisition/event order
+    #[must_use]
+    pub fn locations(&self) -> &[IndexSortLocation] {
+        &self.locations
+    }
+
+    /// The well a given event was sorted into, if that event has an index-sort location
+    #[must_use]
+    pub fn well_for_event(&self, event_index: usize) -> Option<WellPosition> {
+        self.locations
+            .iter()
+            .find(|location| location.event_index == event_index)
+            .map(|location| location.well)
+    }
+
+    /// Every event sorted into a given well, in acquisition order
+    #[must_use]
+    pub fn events_at_well(&self, well: WellPosition) -> Vec<usize> {
+        self.locations
+            .iter()
+            .filter(|location| location.well == well)
+            .map(|location| location.event_index)
+            .collect()
+    }
+}
@@ -114,6 +114,26 @@ pub fn parse_pnd(value: &str) -> Option<MixedKeyword> {
     }
 }
 
+/// Helper function to parse `$PnCALIBRATION` format: f,unit
+///
+/// The `$PnCALIBRATION` keyword converts parameter n's raw signal value to a well-defined unit
+/// (e.g. MESF, antibody binding capacity): `calibrated = raw * f`.
+///
+/// # Arguments
+/// * `value` - String in format "0.1,MESF"
+///
+/// # Returns
+/// `Some(MixedKeyword::PnCalibration(...))` if parsing succeeds, `None` otherwise
+pub fn parse_pncalibration(value: &str) -> Option<MixedKeyword> {
+    let (factor, unit) = value.trim().split_once(',')?;
+    let factor = parse_float_with_comma_decimal(factor)?;
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return None;
+    }
+    Some(MixedKeyword::PnCalibration(factor, unit.to_string()))
+}
+
 /// Helper function to parse `$SPILLOVER` keyword format
 ///
 /// The `$SPILLOVER` keyword contains a compensation matrix for spectral overlap correction.
@@ -128,7 +128,7 @@ impl StringableKeyword for MixedKeyword {
     #[allow(deprecated)]
     fn get_str(&self) -> Cow<'_, str> {
         match self {
-            Self::PnCalibration(f1, s) => Cow::Owned(format!("PnCalibration({}, {})", f1, s)),
+            Self::PnCalibration(f1, s) => Cow::Owned(format!("{f1},{s}")),
             Self::PnD(s, f1, f2) => Cow::Owned(format!("PnD({}, {}, {})", s, f1, f2)),
             Self::PnE(f1, f2) => Cow::Owned(format!("PnE({}, {})", f1, f2)),
             Self::GnE(f1, f2) => Cow::Owned(format!("GnE({}, {})", f1, f2)),
@@ -397,7 +397,11 @@ pub enum StringKeyword {
     #[deprecated(since = "3.2.0", note = "Gate definitions deprecated")]
     GnV(Arc<str>),
 
-    /// A catch-all for other keywords, to be stored as Arc<str>
+    /// A catch-all for keywords this crate doesn't otherwise recognize - unknown or
+    /// vendor-specific keywords always parse into this variant (see
+    /// [`match_and_parse_keyword`]). Storing the raw value verbatim (rather than discarding it)
+    /// is what lets those keywords survive an open -> edit -> write round trip even though
+    /// nothing in this crate understands their meaning.
     Other(Arc<str>),
 }
 
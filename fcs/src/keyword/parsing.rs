@@ -1,6 +1,6 @@
 use super::helpers::{
     extract_parameter_suffix, is_parameter_keyword, parse_float_tuple, parse_float_vector,
-    parse_float_with_comma_decimal, parse_pnd, parse_spillover,
+    parse_float_with_comma_decimal, parse_pncalibration, parse_pnd, parse_spillover,
 };
 use super::{
     ByteKeyword, FloatKeyword, IntegerKeyword, KeywordCreationResult, MixedKeyword, StringKeyword,
@@ -265,6 +265,10 @@ pub fn parse_parameter_keywords(key: &str, value: &str) -> Option<KeywordCreatio
         "D" => parse_pnd(trimmed_value)
             .map(KeywordCreationResult::Mixed)
             .map_or(Some(KeywordCreationResult::UnableToParse), Some),
+        // Calibrated-unit conversion factor for parameter n (FCS 3.1+) → [`MixedKeyword::PnCalibration`]
+        "CALIBRATION" => parse_pncalibration(trimmed_value)
+            .map(KeywordCreationResult::Mixed)
+            .map_or(Some(KeywordCreationResult::UnableToParse), Some),
         // Data type for parameter n, overriding default $DATATYPE (FCS 3.2+) → [`IntegerKeyword::PnDATATYPE`]
         "DATATYPE" => Some(
             trimmed_value
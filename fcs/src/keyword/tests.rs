@@ -269,6 +269,23 @@ mod complex_keywords {
         let result = match_and_parse_keyword("$P3D", "Linear,0");
         assert!(matches!(result, KeywordCreationResult::UnableToParse));
     }
+
+    #[test]
+    fn test_parse_pncalibration() {
+        let result = match_and_parse_keyword("$P4CALIBRATION", "0.1,MESF");
+        if let KeywordCreationResult::Mixed(MixedKeyword::PnCalibration(factor, unit)) = result {
+            assert!((factor - 0.1).abs() < f32::EPSILON);
+            assert_eq!(unit, "MESF");
+        } else {
+            panic!("Expected P4CALIBRATION keyword");
+        }
+    }
+
+    #[test]
+    fn test_parse_pncalibration_malformed() {
+        let result = match_and_parse_keyword("$P4CALIBRATION", "not-a-number,MESF");
+        assert!(matches!(result, KeywordCreationResult::UnableToParse));
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,241 @@
+//! Lazy, on-demand FCS event decoding for very large files
+//!
+//! [`Fcs::open`] eagerly decodes every parameter into a `DataFrame`, which is wasteful
+//! when a caller only needs a couple of channels out of a 10M-event spectral file.
+//! [`LazyFcs`] instead parses only the HEADER/TEXT segments up front and defers decoding
+//! event bytes until a channel is actually requested (backed by the same memory map used
+//! elsewhere in the crate), plus a chunked iterator ([`LazyFcs::iter_row_chunks`]) for
+//! whole-file passes that must not materialize the entire DataFrame at once.
+
+use crate::{
+    ByteOrder, FcsDataType,
+    file::{AccessWrapper, Fcs},
+    header::Header,
+    metadata::Metadata,
+    parameter::{EventDataFrame, Parameter, ParameterMap},
+};
+use anyhow::{Result, anyhow};
+use polars::prelude::*;
+use std::sync::{Arc, RwLock};
+
+/// An FCS file whose event data has not yet been decoded from the memory map
+///
+/// Holds the same HEADER/TEXT-derived state as [`Fcs`] (metadata, parameters), but defers
+/// reading the DATA segment until a channel is requested via
+/// [`LazyFcs::get_parameter_events`], the file is fully loaded via [`LazyFcs::materialize`],
+/// or it is walked chunk-by-chunk via [`LazyFcs::iter_row_chunks`].
+#[derive(Debug, Clone)]
+pub struct LazyFcs {
+    pub header: Header,
+    pub metadata: Metadata,
+    pub parameters: ParameterMap,
+    file_access: AccessWrapper,
+}
+
+impl LazyFcs {
+    /// Opens an FCS file without decoding its event data
+    /// # Errors
+    /// Will return `Err` under the same conditions as [`Fcs::open`], except that failures
+    /// specific to decoding the DATA segment are deferred until data is actually requested.
+    pub fn open(path: &str) -> Result<Self> {
+        let file_access = AccessWrapper::new(path)?;
+        Fcs::validate_fcs_extension(file_access.path.as_deref())?;
+
+        let header = Header::from_mmap(&file_access.bytes)?;
+        let mut metadata = Metadata::from_mmap(&file_access.bytes, &header);
+        metadata.validate_text_segment_keywords(&header)?;
+        metadata.validate_guid();
+
+        let parameters = Fcs::generate_parameter_map(&metadata)?;
+
+        Ok(Self {
+            header,
+            metadata,
+            parameters,
+            file_access,
+        })
+    }
+
+    /// Fully decodes the file's event data, consuming this `LazyFcs` and returning an [`Fcs`]
+    /// # Errors
+    /// Will return `Err` if the DATA segment cannot be decoded
+    pub fn materialize(self) -> Result<Fcs> {
+        let data_frame = Fcs::store_raw_data_as_dataframe(
+            &self.header,
+            &self.file_access.bytes,
+            &self.metadata,
+            true,
+            false,
+        )?;
+        Ok(Fcs {
+            header: self.header,
+            metadata: self.metadata,
+            parameters: self.parameters,
+            data_frame,
+            file_access: self.file_access,
+            channel_range_cache: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Looks up a parameter by channel name, case-insensitively, mirroring [`Fcs::find_parameter`]
+    /// # Errors
+    /// Will return `Err` if the parameter name is not found in the `parameters` hashmap
+    fn find_parameter(&self, channel_name: &str) -> Result<&Parameter> {
+        if let Some(param) = self.parameters.get(channel_name) {
+            return Ok(param);
+        }
+        for (key, param) in self.parameters.iter() {
+            if key.eq_ignore_ascii_case(channel_name) {
+                return Ok(param);
+            }
+        }
+        Err(anyhow!("Parameter not found: {channel_name}"))
+    }
+
+    /// Decodes a single channel's event data directly from the memory map, without decoding
+    /// any of the file's other parameters
+    /// # Errors
+    /// Will return `Err` if the channel is not found or its bytes cannot be decoded
+    pub fn get_parameter_events(&self, channel_name: &str) -> Result<Vec<f32>> {
+        let parameter_number = self.find_parameter(channel_name)?.parameter_number;
+
+        let number_of_parameters = *self.metadata.get_number_of_parameters()?;
+        let number_of_events = *self.metadata.get_number_of_events()?;
+        let byte_order = self.metadata.get_byte_order()?;
+        let data_type = self.metadata.get_data_type_for_channel(parameter_number)?;
+
+        let bytes_per_parameter: Vec<usize> = (1..=number_of_parameters)
+            .map(|n| self.metadata.get_bytes_per_parameter(n))
+            .collect::<Result<_>>()?;
+        let bytes_per_event: usize = bytes_per_parameter.iter().sum();
+        let param_width = bytes_per_parameter[parameter_number - 1];
+        let param_offset: usize = bytes_per_parameter[..parameter_number - 1].iter().sum();
+
+        let data_bytes =
+            Fcs::resolve_data_segment(&self.header, &self.file_access.bytes, &self.metadata)?;
+
+        (0..number_of_events)
+            .map(|event_idx| {
+                let start = event_idx * bytes_per_event + param_offset;
+                Fcs::parse_parameter_value_to_f32(
+                    &data_bytes[start..start + param_width],
+                    param_width,
+                    &data_type,
+                    byte_order,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the file's events, decoded `chunk_size` events at a time
+    ///
+    /// Each item is a `DataFrame` covering every parameter for that slice of events,
+    /// letting a whole-file pass run in bounded memory instead of materializing every
+    /// event up front via [`LazyFcs::materialize`].
+    /// # Errors
+    /// Will return `Err` if `chunk_size` is zero or the file's metadata cannot be read
+    pub fn iter_row_chunks(&self, chunk_size: usize) -> Result<RowChunks<'_>> {
+        RowChunks::new(self, chunk_size)
+    }
+}
+
+/// Iterator over an FCS file's events, decoded in bounded-size chunks; see [`LazyFcs::iter_row_chunks`]
+pub struct RowChunks<'a> {
+    data_bytes: &'a [u8],
+    channel_names: Vec<String>,
+    bytes_per_parameter: Vec<usize>,
+    bytes_per_event: usize,
+    data_types: Vec<FcsDataType>,
+    byte_order: ByteOrder,
+    number_of_events: usize,
+    chunk_size: usize,
+    next_event: usize,
+}
+
+impl<'a> RowChunks<'a> {
+    fn new(lazy: &'a LazyFcs, chunk_size: usize) -> Result<Self> {
+        if chunk_size == 0 {
+            return Err(anyhow!("chunk_size must be greater than zero"));
+        }
+
+        let number_of_parameters = *lazy.metadata.get_number_of_parameters()?;
+        let number_of_events = *lazy.metadata.get_number_of_events()?;
+        let byte_order = lazy.metadata.get_byte_order()?.clone();
+
+        let bytes_per_parameter: Vec<usize> = (1..=number_of_parameters)
+            .map(|n| lazy.metadata.get_bytes_per_parameter(n))
+            .collect::<Result<_>>()?;
+        let bytes_per_event = bytes_per_parameter.iter().sum();
+        let data_types: Vec<FcsDataType> = (1..=number_of_parameters)
+            .map(|n| lazy.metadata.get_data_type_for_channel(n))
+            .collect::<Result<_>>()?;
+        let channel_names: Vec<String> = (1..=number_of_parameters)
+            .map(|n| {
+                lazy.metadata
+                    .get_parameter_channel_name(n)
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|_| format!("P{n}"))
+            })
+            .collect();
+
+        let data_bytes =
+            Fcs::resolve_data_segment(&lazy.header, &lazy.file_access.bytes, &lazy.metadata)?;
+
+        Ok(Self {
+            data_bytes,
+            channel_names,
+            bytes_per_parameter,
+            bytes_per_event,
+            data_types,
+            byte_order,
+            number_of_events,
+            chunk_size,
+            next_event: 0,
+        })
+    }
+
+    fn decode_chunk(&self, start_event: usize, chunk_events: usize) -> Result<EventDataFrame> {
+        let number_of_parameters = self.bytes_per_parameter.len();
+        let mut columns: Vec<Vec<f32>> =
+            vec![Vec::with_capacity(chunk_events); number_of_parameters];
+
+        for event_idx in start_event..start_event + chunk_events {
+            let mut offset = event_idx * self.bytes_per_event;
+            for param_idx in 0..number_of_parameters {
+                let width = self.bytes_per_parameter[param_idx];
+                let value = Fcs::parse_parameter_value_to_f32(
+                    &self.data_bytes[offset..offset + width],
+                    width,
+                    &self.data_types[param_idx],
+                    &self.byte_order,
+                )?;
+                columns[param_idx].push(value);
+                offset += width;
+            }
+        }
+
+        let series: Vec<Column> = columns
+            .into_iter()
+            .zip(self.channel_names.iter())
+            .map(|(values, name)| Column::new(name.as_str().into(), values))
+            .collect();
+
+        let df = DataFrame::new(series)
+            .map_err(|e| anyhow!("Failed to create chunk DataFrame: {}", e))?;
+        Ok(Arc::new(df))
+    }
+}
+
+impl<'a> Iterator for RowChunks<'a> {
+    type Item = Result<EventDataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_event >= self.number_of_events {
+            return None;
+        }
+        let start_event = self.next_event;
+        let chunk_events = self.chunk_size.min(self.number_of_events - start_event);
+        self.next_event += chunk_events;
+        Some(self.decode_chunk(start_event, chunk_events))
+    }
+}
@@ -2,31 +2,77 @@
 
 use std::path::PathBuf;
 
+pub use acquisition::AcquisitionInfo;
+pub use anonymize::{AnonymizeOptions, AnonymizeReport, anonymize};
+#[cfg(feature = "tokio")]
+pub use async_io::{load_batch, open_async};
 pub use byteorder::ByteOrder;
+pub use classification::{
+    CLASSIFICATION_PREFIX, classification_channel_name, is_classification_channel,
+};
+pub use compensation::{
+    CompensationDiff, CompensationMatrix, extract_autofluorescence_spectrum,
+    spillover_from_controls,
+};
+pub use crc::VerificationReport;
 pub use datatype::FcsDataType;
-pub use file::Fcs;
+pub use diff::{DiffCategory, DiffOptions, DiffReport, Difference, diff};
+pub use error::{FcsError, FcsResult};
+pub use export::{CsvExportOptions, CsvHeaderStyle, ParquetExportOptions};
+pub use file::{Fcs, SubsampleMethod, UnmixingMethod, UnmixingResult};
 pub use header::Header;
+pub use index_sort::{IndexSortData, IndexSortLocation};
 pub use keyword::Keyword;
+pub use lazy::LazyFcs;
+pub use manifest::{
+    PanelOverlap, build_experiment_manifest, build_experiment_manifest_from_dir, shared_panel,
+};
+pub use matrix::MatrixOps;
 pub use metadata::Metadata;
 pub use parameter::{ChannelName, EventDataFrame, EventDatum, LabelName, Parameter, ParameterMap};
-pub use transform::{Formattable, TransformType, Transformable};
+pub use plate::{Plate, PlateHeatmapCell, WellPosition};
+pub use repair::{RecoveryOptions, Repair, RepairReport};
+pub use spectral::{SpectralGroup, group_by_detector};
+pub use transform::{
+    CustomTransform, Formattable, TransformType, Transformable, register_custom_transform,
+    unregister_custom_transform,
+};
+pub use validate::{ComplianceReport, Severity, ValidationLevel, Violation};
 pub use version::Version;
 pub use write::{
-    add_column, concatenate_events, duplicate_fcs_file, edit_metadata_and_save, filter_events,
-    write_fcs_file,
+    ConcatenationMode, SOURCE_FILE_COLUMN, add_classification_column, add_column,
+    concatenate_events, duplicate_fcs_file, edit_metadata_and_save, filter_events,
+    write_fcs_file, write_fcs_file_as_version,
 };
-pub use matrix::MatrixOps;
 
+pub mod acquisition;
+pub mod anonymize;
+#[cfg(feature = "tokio")]
+pub mod async_io;
 mod byteorder;
+pub mod classification;
+pub mod compensation;
+pub mod crc;
 pub mod datatype;
+pub mod diff;
+pub mod error;
+pub mod export;
 pub mod file;
-pub mod matrix;
 pub mod header;
+pub mod index_sort;
 pub mod keyword;
+pub mod lazy;
+mod logicle;
+pub mod manifest;
+pub mod matrix;
 pub mod metadata;
 pub mod parameter;
+pub mod plate;
+pub mod repair;
+pub mod spectral;
 mod tests;
 pub mod transform;
+pub mod validate;
 pub mod version;
 pub mod write;
 
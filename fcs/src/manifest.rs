@@ -0,0 +1,169 @@
+//! Panel/experiment manifest extraction across a batch of FCS files
+//!
+//! [`build_experiment_manifest`] scans a batch of already-opened files (typically an
+//! experiment's worth from a single acquisition run) and summarizes, per file, its instrument,
+//! acquisition time, event count, and how its panel ($PnV/$PnR settings) compares to the rest
+//! of the batch - useful for spotting a mis-voltaged file before it gets pooled into an
+//! analysis with the rest of the cohort.
+
+use crate::acquisition::AcquisitionInfo;
+use crate::file::Fcs;
+use crate::keyword::IntegerableKeyword;
+use anyhow::Result;
+use polars::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// How [`shared_panel`] should combine each file's `$PnN` channel names
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanelOverlap {
+    /// Every channel name that appears in at least one file
+    Union,
+    /// Only channel names common to every file
+    Intersection,
+}
+
+/// Opens every `.fcs` file directly inside `dir` (non-recursive) and builds its experiment
+/// manifest via [`build_experiment_manifest`]
+///
+/// # Errors
+/// Will return `Err` if `dir` can't be read, or if any file inside it fails to open.
+pub fn build_experiment_manifest_from_dir(dir: impl AsRef<Path>) -> Result<DataFrame> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("fcs") {
+            files.push(Fcs::open(path.to_str().unwrap_or_default())?);
+        }
+    }
+    build_experiment_manifest(&files)
+}
+
+/// The channel names shared across `files`' `$PnN` panels, combined per `overlap`
+///
+/// Files are compared by parameter name only (not label/`$PnS` or channel order), since that's
+/// the identifier downstream tooling actually joins on.
+#[must_use]
+pub fn shared_panel(files: &[Fcs], overlap: PanelOverlap) -> Vec<String> {
+    let mut panels = files
+        .iter()
+        .map(|fcs| fcs.parameters.keys().map(|name| name.to_string()));
+
+    let Some(first) = panels.next() else {
+        return Vec::new();
+    };
+    let mut channels: BTreeSet<String> = first.collect();
+
+    for panel in panels {
+        let panel: BTreeSet<String> = panel.collect();
+        match overlap {
+            PanelOverlap::Union => channels.extend(panel),
+            PanelOverlap::Intersection => channels.retain(|name| panel.contains(name)),
+        }
+    }
+
+    channels.into_iter().collect()
+}
+
+/// Builds a per-file experiment manifest: instrument, acquisition start, event count, and
+/// panel voltage/range settings that disagree with the rest of the batch
+///
+/// A channel counts as a voltage/range mismatch for a file when that file's `$PnV`/`$PnR`
+/// differs from the majority value across every file that has the channel - so a single
+/// outlier file is flagged rather than the majority being flagged against it.
+///
+/// # Errors
+/// Will return `Err` if the manifest columns can't be assembled into a `DataFrame`.
+pub fn build_experiment_manifest(files: &[Fcs]) -> Result<DataFrame> {
+    let majority_voltages = majority_settings(files, "V");
+    let majority_ranges = majority_settings(files, "R");
+
+    let mut file_names = Vec::with_capacity(files.len());
+    let mut instruments: Vec<Option<String>> = Vec::with_capacity(files.len());
+    let mut acquisition_begins: Vec<Option<String>> = Vec::with_capacity(files.len());
+    let mut n_events = Vec::with_capacity(files.len());
+    let mut n_parameters = Vec::with_capacity(files.len());
+    let mut voltage_mismatches: Vec<String> = Vec::with_capacity(files.len());
+    let mut range_mismatches: Vec<String> = Vec::with_capacity(files.len());
+
+    for fcs in files {
+        let acquisition = AcquisitionInfo::from_fcs(fcs);
+
+        file_names.push(
+            fcs.file_access
+                .path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+        );
+        instruments.push(acquisition.cytometer);
+        acquisition_begins.push(acquisition.begin.map(|begin| begin.to_string()));
+        n_events.push(fcs.get_event_count_from_dataframe() as u64);
+        n_parameters.push(fcs.parameters.len() as u64);
+        voltage_mismatches.push(mismatched_channels(fcs, "V", &majority_voltages).join(";"));
+        range_mismatches.push(mismatched_channels(fcs, "R", &majority_ranges).join(";"));
+    }
+
+    Ok(DataFrame::new(vec![
+        Column::new("file".into(), file_names),
+        Column::new("instrument".into(), instruments),
+        Column::new("acquisition_begin".into(), acquisition_begins),
+        Column::new("n_events".into(), n_events),
+        Column::new("n_parameters".into(), n_parameters),
+        Column::new("voltage_mismatches".into(), voltage_mismatches),
+        Column::new("range_mismatches".into(), range_mismatches),
+    ])?)
+}
+
+/// For every channel, the most common `$Pn{suffix}` value across every file that has it
+/// (`suffix` is `"V"` for `$PnV` or `"R"` for `$PnR`)
+fn majority_settings(files: &[Fcs], suffix: &str) -> BTreeMap<String, usize> {
+    let mut votes: BTreeMap<String, BTreeMap<usize, usize>> = BTreeMap::new();
+
+    for fcs in files {
+        for parameter in fcs.parameters.values() {
+            let Ok(keyword) = fcs
+                .metadata
+                .get_integer_keyword(&format!("$P{}{suffix}", parameter.parameter_number))
+            else {
+                continue;
+            };
+            *votes
+                .entry(parameter.channel_name.to_string())
+                .or_default()
+                .entry(*keyword.get_usize())
+                .or_default() += 1;
+        }
+    }
+
+    votes
+        .into_iter()
+        .filter_map(|(channel, tally)| {
+            tally
+                .into_iter()
+                .max_by_key(|&(_, count)| count)
+                .map(|(value, _)| (channel, value))
+        })
+        .collect()
+}
+
+/// Channels in `fcs` whose `$Pn{suffix}` disagrees with `majority`
+fn mismatched_channels(fcs: &Fcs, suffix: &str, majority: &BTreeMap<String, usize>) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    for parameter in fcs.parameters.values() {
+        let Some(&expected) = majority.get(parameter.channel_name.as_ref()) else {
+            continue;
+        };
+        let Ok(keyword) = fcs
+            .metadata
+            .get_integer_keyword(&format!("$P{}{suffix}", parameter.parameter_number))
+        else {
+            continue;
+        };
+        if *keyword.get_usize() != expected {
+            mismatches.push(parameter.channel_name.to_string());
+        }
+    }
+    mismatches.sort();
+    mismatches
+}
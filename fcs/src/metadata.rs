@@ -4,11 +4,11 @@ use super::{
     header::Header,
     keyword::{
         ByteKeyword, FloatKeyword, IntegerKeyword, IntegerableKeyword, Keyword,
-        KeywordCreationResult, MixedKeyword, StringKeyword, match_and_parse_keyword,
+        KeywordCreationResult, MixedKeyword, StringKeyword, StringableKeyword,
+        match_and_parse_keyword,
     },
 };
 use anyhow::{Result, anyhow};
-use memmap3::Mmap;
 use regex::bytes::Regex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,17 @@ use std::sync::Arc;
 use uuid::Uuid;
 pub type KeywordMap = FxHashMap<String, Keyword>;
 
+/// Decodes one TEXT-segment field (a keyword name or value) as UTF-8, falling back to Latin-1
+/// (ISO-8859-1, where every byte maps directly to the code point of the same number) for legacy
+/// files that pre-date `$UNICODE`/UTF-8 but still snuck non-ASCII bytes into a stain name.
+/// Unlike `str::from_utf8`, this never fails and never drops the field's content.
+fn decode_text_segment_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
 /// Contains keyword-value pairs and delimiter from the TEXT segment of an FCS file
 ///
 /// The TEXT segment contains all metadata about the FCS file, including:
@@ -62,7 +73,7 @@ impl Metadata {
     ///
     /// Uses memchr for fast delimiter finding (5-10x faster than byte-by-byte iteration)
     #[must_use]
-    pub fn from_mmap(mmap: &Mmap, header: &Header) -> Self {
+    pub fn from_mmap(mmap: &[u8], header: &Header) -> Self {
         let text_start = header.text_offset.start();
 
         // Read the first byte of the text segment to determine the delimiter:
@@ -81,21 +92,38 @@ impl Metadata {
 
         // Parse keyword-value pairs
         // FCS format: |KEY1|VALUE1|KEY2|VALUE2|...
-        // delimiter_positions gives us the split points
+        // delimiter_positions gives us the split points. A literal delimiter inside a field is
+        // escaped by doubling it (e.g. with `/` as the delimiter, `A/B` is written `A//B`), so a
+        // pair of adjacent delimiter positions is folded into the current field instead of
+        // ending it.
         let mut prev_pos = 0;
         let mut is_keyword = true;
         let mut current_key = String::new();
+        let mut field_buffer = String::new();
+
+        let mut index = 0;
+        while index < delimiter_positions.len() {
+            let pos = delimiter_positions[index];
+
+            if index + 1 < delimiter_positions.len() && delimiter_positions[index + 1] == pos + 1
+            {
+                field_buffer.push_str(&decode_text_segment_bytes(&text_slice[prev_pos..=pos]));
+                prev_pos = pos + 2;
+                index += 2;
+                continue;
+            }
 
-        for &pos in &delimiter_positions {
-            // Extract the slice between delimiters
-            let segment = &text_slice[prev_pos..pos];
-
-            // SAFETY: FCS spec requires TEXT segment to be ASCII/UTF-8
-            let text = std::str::from_utf8(segment).unwrap_or_default();
+            // Most files are plain ASCII; files declaring $UNICODE (or older files that never
+            // declared an encoding at all but still snuck non-ASCII stain names in via Latin-1)
+            // need the fallback decode below so those bytes decode instead of vanishing.
+            field_buffer.push_str(&decode_text_segment_bytes(&text_slice[prev_pos..pos]));
+            let text = std::mem::take(&mut field_buffer);
+            prev_pos = pos + 1;
+            index += 1;
 
             if is_keyword {
                 // This is a keyword
-                current_key = text.to_string();
+                current_key = text;
                 is_keyword = false;
             } else {
                 // This is a value - parse and store the keyword-value pair
@@ -108,7 +136,7 @@ impl Metadata {
                         format!("${}", current_key)
                     };
 
-                    match match_and_parse_keyword(&current_key, text) {
+                    match match_and_parse_keyword(&current_key, &text) {
                         KeywordCreationResult::Int(int_keyword) => {
                             keywords.insert(normalized_key.clone(), Keyword::Int(int_keyword));
                         }
@@ -136,8 +164,6 @@ impl Metadata {
                 current_key.clear();
                 is_keyword = true;
             }
-
-            prev_pos = pos + 1;
         }
 
         Self {
@@ -146,6 +172,20 @@ impl Metadata {
         }
     }
 
+    /// Whether every keyword value is plain ASCII, i.e. this file's TEXT segment doesn't need
+    /// `$UNICODE` for a reader to interpret it correctly
+    #[must_use]
+    pub fn is_ascii_only(&self) -> bool {
+        self.keywords
+            .values()
+            .all(|keyword| match keyword {
+                Keyword::String(string) => string.get_str().is_ascii(),
+                Keyword::Mixed(mixed) => mixed.get_str().is_ascii(),
+                // Integer, float and byte-layout values only ever render as ASCII
+                Keyword::Int(_) | Keyword::Float(_) | Keyword::Byte(_) => true,
+            })
+    }
+
     /// Check that required keys are present in the TEXT segment of the metadata
     /// # Errors
     /// Will return `Err` if:
@@ -224,6 +264,22 @@ impl Metadata {
         self.get_keyword_value_as_usize("$TOT")
     }
 
+    /// Return the byte offset (from the start of the file) to the next dataset's HEADER
+    /// segment, from the $NEXTDATA keyword, or `0` if there is no following dataset.
+    ///
+    /// $NEXTDATA is not modeled as a dedicated `IntegerKeyword` variant since it is only
+    /// ever consumed while walking the dataset chain in [`crate::Fcs::open_all`], so it is
+    /// parsed here directly from the generic string keyword it's stored as.
+    /// # Errors
+    /// Will return `Err` if the $NEXTDATA keyword is missing or not a valid offset
+    pub fn get_next_data_offset(&self) -> Result<usize> {
+        let value = self.get_string_keyword("$NEXTDATA")?.get_str();
+        value
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| anyhow!("Invalid $NEXTDATA offset '{}': {}", value, e))
+    }
+
     /// Return the data type from the $DATATYPE keyword in the metadata TEXT section, unwraps and returns it if it exists.
     /// # Errors
     /// Will return `Err` if the $DATATYPE keyword is not present in the metadata keywords hashmap
@@ -252,11 +308,12 @@ impl Metadata {
             self.get_parameter_numeric_metadata(parameter_number, "DATATYPE")
         {
             if let IntegerKeyword::PnDATATYPE(datatype_code) = pn_datatype_keyword {
-                // Map datatype code to enum: 0=I, 1=F, 2=D
+                // Map datatype code to enum: 0=I, 1=F, 2=D, 3=A
                 match datatype_code {
                     0 => Ok(FcsDataType::I),
                     1 => Ok(FcsDataType::F),
                     2 => Ok(FcsDataType::D),
+                    3 => Ok(FcsDataType::A),
                     _ => Err(anyhow!(
                         "Invalid $P{}DATATYPE code: {}",
                         parameter_number,
@@ -287,18 +344,7 @@ impl Metadata {
         let mut total_bytes = 0;
 
         for param_num in 1..=*number_of_parameters {
-            // Get $PnB (bits per parameter)
-            let bits = self.get_parameter_numeric_metadata(param_num, "B")?;
-            if let IntegerKeyword::PnB(bits_value) = bits {
-                // Convert bits to bytes (round up if not divisible by 8)
-                let bytes = (bits_value + 7) / 8;
-                total_bytes += bytes;
-            } else {
-                return Err(anyhow!(
-                    "$P{}B keyword found but is not the expected PnB variant",
-                    param_num
-                ));
-            }
+            total_bytes += self.get_bytes_per_parameter(param_num)?;
         }
 
         Ok(total_bytes)
@@ -306,24 +352,27 @@ impl Metadata {
 
     /// Get bytes per parameter for a specific channel
     ///
-    /// Uses `$PnB` (bits per parameter) divided by 8 to get bytes per parameter.
+    /// Uses `$PnB`, interpreted according to the parameter's data type
+    /// (`$PnDATATYPE` if set, otherwise the file's default `$DATATYPE`):
+    /// for `I`/`F`/`D` this is bits per parameter divided by 8, while for
+    /// `A` (ASCII) `$PnB` already gives the field width in bytes/characters.
     ///
     /// # Arguments
     /// * `parameter_number` - 1-based parameter index
     ///
     /// # Errors
-    /// Will return `Err` if the `$PnB` keyword is missing for this parameter
+    /// Will return `Err` if the `$PnB` keyword is missing for this parameter,
+    /// or if the parameter's data type cannot be determined
     pub fn get_bytes_per_parameter(&self, parameter_number: usize) -> Result<usize> {
         let bits = self.get_parameter_numeric_metadata(parameter_number, "B")?;
-        if let IntegerKeyword::PnB(bits_value) = bits {
-            // Convert bits to bytes (round up if not divisible by 8)
-            Ok((bits_value + 7) / 8)
-        } else {
-            Err(anyhow!(
+        let IntegerKeyword::PnB(bits_value) = bits else {
+            return Err(anyhow!(
                 "$P{}B keyword found but is not the expected PnB variant",
                 parameter_number
-            ))
-        }
+            ));
+        };
+        let data_type = self.get_data_type_for_channel(parameter_number)?;
+        Ok(data_type.get_bytes_for_bits(*bits_value))
     }
 
     /// Return the byte order from the $BYTEORD keyword in the metadata TEXT section, unwraps and returns it if it exists.
@@ -503,6 +552,191 @@ impl Metadata {
         }
     }
 
+    /// Keywords whose values are derived directly from the file's own byte layout or event
+    /// count, rather than being independently editable metadata. Setting these through
+    /// [`Metadata::set_string_keyword`]/[`Metadata::set_integer_keyword`] would silently
+    /// desync them from the [`Header`] offsets they mirror or the actual DATA segment size,
+    /// so those setters reject them; use [`crate::write::edit_metadata_and_save`], which
+    /// recomputes `$TOT` itself, or write a new file instead.
+    const OFFSET_DERIVED_KEYWORDS: [&str; 10] = [
+        "$BEGINDATA",
+        "$ENDDATA",
+        "$BEGINTEXT",
+        "$ENDTEXT",
+        "$BEGINSTEXT",
+        "$ENDSTEXT",
+        "$BEGINANALYSIS",
+        "$ENDANALYSIS",
+        "$NEXTDATA",
+        "$TOT",
+    ];
+
+    fn reject_if_offset_derived(key: &str) -> Result<()> {
+        if Self::OFFSET_DERIVED_KEYWORDS.contains(&key) {
+            return Err(anyhow!(
+                "{key} is derived from the file's byte layout and cannot be edited directly"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets a string keyword, rejecting edits to offset- or count-derived keywords (see
+    /// [`Self::OFFSET_DERIVED_KEYWORDS`])
+    /// # Errors
+    /// Will return `Err` if `key` is offset- or count-derived
+    pub fn set_string_keyword(&mut self, key: &str, value: &str) -> Result<()> {
+        let normalized_key = if key.starts_with('$') {
+            key.to_string()
+        } else {
+            format!("${key}")
+        };
+        Self::reject_if_offset_derived(&normalized_key)?;
+        self.insert_string_keyword(normalized_key, value.to_string());
+        Ok(())
+    }
+
+    /// Sets `$BTIM`, validating that `time` is in the FCS `hh:mm:ss[.cc]` format
+    /// # Errors
+    /// Will return `Err` if `time` does not match `hh:mm:ss[.cc]`
+    pub fn set_btim(&mut self, time: &str) -> Result<()> {
+        Self::validate_time_format(time)?;
+        self.set_string_keyword("$BTIM", time)
+    }
+
+    /// Sets `$ETIM`, validating that `time` is in the FCS `hh:mm:ss[.cc]` format
+    /// # Errors
+    /// Will return `Err` if `time` does not match `hh:mm:ss[.cc]`
+    pub fn set_etim(&mut self, time: &str) -> Result<()> {
+        Self::validate_time_format(time)?;
+        self.set_string_keyword("$ETIM", time)
+    }
+
+    fn validate_time_format(time: &str) -> Result<()> {
+        let pattern = regex::Regex::new(r"^\d{2}:\d{2}:\d{2}(\.\d+)?$")
+            .expect("time format pattern should be valid regex");
+        if pattern.is_match(time) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "'{time}' is not a valid FCS time (expected hh:mm:ss[.cc])"
+            ))
+        }
+    }
+
+    /// Sets `$PnE` (amplification type) for parameter `n`, validating that a linear scale
+    /// (`f1 == 0.0`) always pairs with `f2 == 0.0`, per the FCS specification
+    /// # Errors
+    /// Will return `Err` if `f1 == 0.0` and `f2 != 0.0`
+    pub fn set_pn_e(&mut self, parameter_number: usize, f1: f32, f2: f32) -> Result<()> {
+        if f1 == 0.0 && f2 != 0.0 {
+            return Err(anyhow!(
+                "$P{parameter_number}E: a linear scale (f1 = 0) must have f2 = 0, got f2 = {f2}"
+            ));
+        }
+        self.keywords.insert(
+            format!("$P{parameter_number}E"),
+            Keyword::Mixed(MixedKeyword::PnE(f1, f2)),
+        );
+        Ok(())
+    }
+
+    /// Marks the file as modified by a typed setter: records `modifier` in `$LAST_MODIFIER`,
+    /// `timestamp` (FCS `dd-mmm-yyyy hh:mm:ss[.cc]` format is conventional, but not enforced
+    /// here) in `$LAST_MODIFIED`, and sets `$ORIGINALITY` to `"NonDataModified"` since typed
+    /// setters only ever touch keywords, never event data
+    pub fn mark_modified(&mut self, modifier: &str, timestamp: &str) {
+        self.insert_string_keyword("$LAST_MODIFIER".to_string(), modifier.to_string());
+        self.insert_string_keyword("$LAST_MODIFIED".to_string(), timestamp.to_string());
+        self.insert_string_keyword("$ORIGINALITY".to_string(), "NonDataModified".to_string());
+    }
+
+    /// Sets `$PnD`, the parameter's suggested visualization scale, from a
+    /// [`crate::transform::TransformType`]
+    ///
+    /// `$PnD` only encodes a scale kind (`Linear`/`Logarithmic`) plus two numeric fields, so
+    /// this is a best-effort mapping rather than a faithful round-trip of every transform
+    /// parameter: [`crate::TransformType::Biexponential`] and [`crate::TransformType::Logicle`]
+    /// both map onto `Logarithmic` with their decade counts, and
+    /// [`crate::TransformType::Arcsinh`]'s cofactor is stashed in the offset field since `$PnD`
+    /// has nowhere else to put it.
+    pub fn set_parameter_display_transform(
+        &mut self,
+        parameter_number: usize,
+        transform: &crate::transform::TransformType,
+    ) {
+        use crate::transform::TransformType;
+
+        let (scale_type, f1, f2) = match transform {
+            TransformType::Linear => ("Linear", 0.0, 0.0),
+            TransformType::Arcsinh { cofactor } => ("Logarithmic", 4.5, *cofactor),
+            TransformType::Biexponential {
+                positive_decades,
+                negative_decades,
+                ..
+            } => ("Logarithmic", *positive_decades, *negative_decades),
+            TransformType::Logicle {
+                decades,
+                negative_decades,
+                ..
+            } => ("Logarithmic", *decades, *negative_decades),
+            // A custom transform's shape is unknown to $PnD; record it as Linear rather than
+            // fabricate decade/offset numbers that don't mean anything for it.
+            TransformType::Custom(_) => ("Linear", 0.0, 0.0),
+        };
+
+        self.keywords.insert(
+            format!("$P{parameter_number}D"),
+            Keyword::Mixed(MixedKeyword::PnD(scale_type.to_string(), f1, f2)),
+        );
+    }
+
+    /// Reads `$PnD` back into a [`crate::transform::TransformType`], the inverse of
+    /// [`Metadata::set_parameter_display_transform`]
+    ///
+    /// A `Logarithmic` `$PnD` always comes back as
+    /// [`crate::TransformType::Biexponential`] (the decades and offset round-trip exactly), even
+    /// if it was originally written from an `Arcsinh` or `Logicle` transform - `$PnD` can't tell
+    /// those apart from each other. Returns `None` if `$PnD` is absent or unparseable.
+    #[must_use]
+    pub fn get_parameter_display_transform(
+        &self,
+        parameter_number: usize,
+    ) -> Option<crate::transform::TransformType> {
+        use crate::transform::TransformType;
+
+        let mixed = self
+            .get_mixed_keyword(&format!("$P{parameter_number}D"))
+            .ok()?;
+        let MixedKeyword::PnD(scale_type, f1, f2) = mixed else {
+            return None;
+        };
+
+        match scale_type.as_str() {
+            "Linear" => Some(TransformType::Linear),
+            "Logarithmic" => Some(TransformType::Biexponential {
+                top_of_scale: 262_144.0,
+                positive_decades: *f1,
+                negative_decades: *f2,
+                width: 0.5,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reads `$PnCALIBRATION`, if present: the number of calibrated units (e.g. MESF, antibody
+    /// binding capacity) per unit of the parameter's raw signal value, and the name of those
+    /// units. A raw value `x` converts to calibrated units as `x * units_per_signal`.
+    #[must_use]
+    pub fn get_parameter_calibration(&self, parameter_number: usize) -> Option<(f32, &str)> {
+        let mixed = self
+            .get_mixed_keyword(&format!("$P{parameter_number}CALIBRATION"))
+            .ok()?;
+        let MixedKeyword::PnCalibration(units_per_signal, unit_name) = mixed else {
+            return None;
+        };
+        Some((*units_per_signal, unit_name.as_str()))
+    }
+
     /// Insert or update a string keyword in the metadata
     pub fn insert_string_keyword(&mut self, key: String, value: String) {
         let normalized_key = if key.starts_with('$') {
@@ -521,4 +755,18 @@ impl Metadata {
         self.keywords
             .insert(normalized_key, Keyword::String(string_keyword));
     }
+
+    /// Remove a keyword entirely, rejecting edits to offset- or count-derived keywords (see
+    /// [`Self::OFFSET_DERIVED_KEYWORDS`]). Returns whether the keyword was present.
+    /// # Errors
+    /// Will return `Err` if `key` is offset- or count-derived
+    pub fn remove_keyword(&mut self, key: &str) -> Result<bool> {
+        let normalized_key = if key.starts_with('$') {
+            key.to_string()
+        } else {
+            format!("${key}")
+        };
+        Self::reject_if_offset_derived(&normalized_key)?;
+        Ok(self.keywords.remove(&normalized_key).is_some())
+    }
 }
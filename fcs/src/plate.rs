@@ -0,0 +1,185 @@
+//! Plate-layout aware multi-file handling
+//!
+//! Groups a set of [`Fcs`] files acquired from the same microtiter plate by well position (via
+//! [`AcquisitionInfo::carrier_id`]/[`AcquisitionInfo::location_id`]), and lets callers iterate
+//! by row/column or build a per-well heatmap of any statistic - the building block a plate-view
+//! screening UI would sit on top of.
+
+use crate::acquisition::AcquisitionInfo;
+use crate::file::Fcs;
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+
+/// A well position on a microtiter plate, as a zero-indexed (row, column) pair
+///
+/// Row 0 is `"A"`, column 0 is well 1, so well `"B03"` is `WellPosition { row: 1, column: 2 }`.
+/// Ordered row-major, so a `BTreeMap<WellPosition, _>` iterates in reading order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WellPosition {
+    pub row: u32,
+    pub column: u32,
+}
+
+impl WellPosition {
+    /// Parses a well ID like `"A01"`, `"H12"`, or `"AB03"` (row letters followed by a 1-indexed
+    /// column number)
+    ///
+    /// # Errors
+    /// Will return `Err` if `well_id` doesn't split into a non-empty run of ASCII letters
+    /// followed by a positive column number.
+    pub fn parse(well_id: &str) -> Result<Self> {
+        let split_at = well_id
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(|| anyhow!("Well ID {well_id} has no column number"))?;
+        let (row_letters, column_digits) = well_id.split_at(split_at);
+        if row_letters.is_empty() || !row_letters.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(anyhow!("Well ID {well_id} has no row letters"));
+        }
+        let column: u32 = column_digits
+            .parse()
+            .map_err(|_| anyhow!("Well ID {well_id} has an invalid column number"))?;
+        if column == 0 {
+            return Err(anyhow!("Well ID {well_id} has a 1-indexed column, got 0"));
+        }
+
+        // Base-26 letters-as-digits (A=1, Z=26, AA=27, ...), then shift to 0-indexed
+        let row = row_letters
+            .chars()
+            .fold(0u32, |acc, c| {
+                acc * 26 + u32::from(c.to_ascii_uppercase() as u8 - b'A' + 1)
+            })
+            - 1;
+
+        Ok(Self {
+            row,
+            column: column - 1,
+        })
+    }
+
+    /// Row letter(s) (`A`, `B`, ..., `Z`, `AA`, ...)
+    #[must_use]
+    pub fn row_label(&self) -> String {
+        let mut n = self.row + 1;
+        let mut letters = Vec::new();
+        while n > 0 {
+            let remainder = (n - 1) % 26;
+            letters.push((b'A' + u8::try_from(remainder).unwrap_or(0)) as char);
+            n = (n - 1) / 26;
+        }
+        letters.iter().rev().collect()
+    }
+
+    /// 1-indexed column number
+    #[must_use]
+    pub fn column_label(&self) -> u32 {
+        self.column + 1
+    }
+}
+
+/// A group of [`Fcs`] files from the same physical plate, indexed by well position
+#[derive(Debug, Default)]
+pub struct Plate {
+    plate_id: Option<String>,
+    wells: BTreeMap<WellPosition, Fcs>,
+}
+
+impl Plate {
+    /// Groups `files` into plates by `$PLATEID`/`$CARRIERID`, returning one [`Plate`] per
+    /// distinct carrier ID (files with no carrier ID are grouped into a single plate with a
+    /// `None` ID)
+    ///
+    /// # Errors
+    /// Will return `Err` if a file has no parseable well ID (`$WELLID`/`$LOCATIONID`), or two
+    /// files on the same plate share a well position.
+    pub fn group_by_plate(files: Vec<Fcs>) -> Result<Vec<Self>> {
+        let mut plates: BTreeMap<Option<String>, Self> = BTreeMap::new();
+
+        for fcs in files {
+            let info = AcquisitionInfo::from_fcs(&fcs);
+            let plate = plates
+                .entry(info.carrier_id.clone())
+                .or_insert_with(|| Self {
+                    plate_id: info.carrier_id.clone(),
+                    wells: BTreeMap::new(),
+                });
+
+            let well_id = info.location_id.ok_or_else(|| {
+                anyhow!("File has no $WELLID/$LOCATIONID to place it on a plate")
+            })?;
+            let position = WellPosition::parse(&well_id)?;
+            if plate.wells.insert(position, fcs).is_some() {
+                return Err(anyhow!(
+                    "Well {well_id} already has a file on plate {:?}",
+                    plate.plate_id
+                ));
+            }
+        }
+
+        Ok(plates.into_values().collect())
+    }
+
+    /// This plate's `$PLATEID`/`$CARRIERID`, if any file provided one
+    #[must_use]
+    pub fn plate_id(&self) -> Option<&str> {
+        self.plate_id.as_deref()
+    }
+
+    /// The file at a given well position, if present
+    #[must_use]
+    pub fn well(&self, position: WellPosition) -> Option<&Fcs> {
+        self.wells.get(&position)
+    }
+
+    /// Every well in a given row, in column order
+    #[must_use]
+    pub fn row(&self, row: u32) -> Vec<(WellPosition, &Fcs)> {
+        self.wells
+            .iter()
+            .filter(|(position, _)| position.row == row)
+            .map(|(&position, fcs)| (position, fcs))
+            .collect()
+    }
+
+    /// Every well in a given column, in row order
+    #[must_use]
+    pub fn column(&self, column: u32) -> Vec<(WellPosition, &Fcs)> {
+        self.wells
+            .iter()
+            .filter(|(position, _)| position.column == column)
+            .map(|(&position, fcs)| (position, fcs))
+            .collect()
+    }
+
+    /// All occupied wells, in row-major order
+    #[must_use]
+    pub fn wells(&self) -> Vec<(WellPosition, &Fcs)> {
+        self.wells.iter().map(|(&position, fcs)| (position, fcs)).collect()
+    }
+
+    /// Builds a per-well heatmap table by applying `statistic` to each occupied well's file
+    /// (typically a per-channel summary such as [`Fcs::get_parameter_statistics`])
+    ///
+    /// # Errors
+    /// Propagates the first error `statistic` returns.
+    pub fn heatmap<T>(
+        &self,
+        mut statistic: impl FnMut(&Fcs) -> Result<T>,
+    ) -> Result<Vec<PlateHeatmapCell<T>>> {
+        self.wells
+            .iter()
+            .map(|(&position, fcs)| {
+                Ok(PlateHeatmapCell {
+                    position,
+                    value: statistic(fcs)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// One cell of a [`Plate::heatmap`] result
+#[derive(Clone, Debug)]
+pub struct PlateHeatmapCell<T> {
+    pub position: WellPosition,
+    pub value: T,
+}
@@ -0,0 +1,62 @@
+//! Fault-tolerant loading for malformed FCS files
+//!
+//! Some instruments emit files with zero HEADER offsets, a `$TOT` that disagrees with the
+//! actual DATA segment length, or a DATA segment truncated mid-transfer. [`crate::Fcs::open`]
+//! treats all of these as fatal. [`crate::Fcs::open_with_recovery`] instead applies best-effort
+//! fixes controlled by [`RecoveryOptions`] and returns a [`RepairReport`] describing exactly
+//! what it had to repair, so a batch job can keep going and flag the files that needed help.
+
+use serde::{Deserialize, Serialize};
+
+/// Controls which repairs [`crate::Fcs::open_with_recovery`] is allowed to make
+#[derive(Debug, Clone, Copy)]
+pub struct RecoveryOptions {
+    /// If the DATA segment's actual length disagrees with what `$TOT` implies, recompute the
+    /// event count from the segment's length instead of failing
+    pub infer_event_count: bool,
+    /// If the DATA segment length isn't an exact multiple of the per-event byte width, drop
+    /// the partial trailing event instead of failing
+    pub trim_partial_events: bool,
+    /// Whether to mask `$DATATYPE=I` parameter values to their `$PnR` bit width; see
+    /// [`crate::Fcs::open_with_range_mask`]
+    pub apply_range_mask: bool,
+}
+
+impl Default for RecoveryOptions {
+    fn default() -> Self {
+        Self {
+            infer_event_count: true,
+            trim_partial_events: true,
+            apply_range_mask: true,
+        }
+    }
+}
+
+/// A single repair applied by [`crate::Fcs::open_with_recovery`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Repair {
+    /// Short machine-readable category, e.g. `"event_count_inferred"`, `"partial_event_trimmed"`
+    pub category: String,
+    pub message: String,
+}
+
+/// Machine-readable record of every repair [`crate::Fcs::open_with_recovery`] made to a file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepairReport {
+    pub repairs: Vec<Repair>,
+}
+
+impl RepairReport {
+    /// Whether the file needed no repairs at all
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.repairs.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, category: &str, message: impl Into<String>) {
+        self.repairs.push(Repair {
+            category: category.to_string(),
+            message: message.into(),
+        });
+    }
+}
@@ -0,0 +1,129 @@
+//! Spectral channel grouping helpers
+//!
+//! Full-spectrum cytometers illuminate a bank of detectors from each laser line (e.g.
+//! `"UV1-A"` through `"UV16-A"` off the same UV laser), and spectral analyses need to work
+//! with a whole laser's detector bank as a unit - to build a file's full-spectrum signature
+//! per event, or to find which detector best captures a given fluorophore.
+//! [`group_by_detector`] recovers those detector banks from `$PnL` (excitation wavelength),
+//! falling back to `$PnDET` and then to the `$PnN` naming convention for files that don't
+//! populate the dedicated keywords - foundational utilities for spectral compensation and
+//! unmixing workflows built on top of [`crate::compensation`].
+
+use crate::file::Fcs;
+use crate::keyword::StringableKeyword;
+use crate::parameter::ChannelName;
+use anyhow::{Result, anyhow};
+use ndarray::Array2;
+use std::collections::BTreeMap;
+
+/// A bank of channels illuminated by the same laser line (or sharing a detector-name prefix,
+/// for files with no `$PnL`), in `$PnN` parameter order
+#[derive(Clone, Debug)]
+pub struct SpectralGroup {
+    /// The laser/detector label this group was keyed on (e.g. `"405nm"` or `"UV"`)
+    pub label: String,
+    pub channels: Vec<ChannelName>,
+}
+
+impl SpectralGroup {
+    /// Builds this group's full-spectrum signature matrix: one row per event, one column per
+    /// channel, in the group's channel order
+    ///
+    /// # Errors
+    /// Will return `Err` if the group has no channels, or a channel can't be read.
+    pub fn event_matrix(&self, fcs: &Fcs) -> Result<Array2<f32>> {
+        if self.channels.is_empty() {
+            return Err(anyhow!("Spectral group {} has no channels", self.label));
+        }
+
+        let columns: Vec<&[f32]> = self
+            .channels
+            .iter()
+            .map(|channel| fcs.get_parameter_events_slice(channel))
+            .collect::<Result<_>>()?;
+        let n_events = columns[0].len();
+
+        let mut matrix = Array2::<f32>::zeros((n_events, self.channels.len()));
+        for (col_idx, column) in columns.iter().enumerate() {
+            for (row_idx, &value) in column.iter().enumerate() {
+                matrix[[row_idx, col_idx]] = value;
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// The channel with the highest mean signal across all events - the detector that best
+    /// captures whichever fluorophore dominates this laser line
+    ///
+    /// # Errors
+    /// Will return `Err` if the group has no channels, or a channel can't be read.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn peak_channel(&self, fcs: &Fcs) -> Result<ChannelName> {
+        self.channels
+            .iter()
+            .map(|channel| {
+                let data = fcs.get_parameter_events_slice(channel)?;
+                let mean = if data.is_empty() {
+                    0.0
+                } else {
+                    data.iter().sum::<f32>() / data.len() as f32
+                };
+                Ok((channel.clone(), mean))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(channel, _)| channel)
+            .ok_or_else(|| anyhow!("Spectral group {} has no channels", self.label))
+    }
+}
+
+/// Groups `fcs`'s parameters into [`SpectralGroup`]s by shared excitation laser, preferring
+/// `$PnL` (excitation wavelength), then `$PnDET` (detector name)'s alphabetic prefix, then the
+/// `$PnN` channel name's own alphabetic prefix (e.g. `"UV"` from `"UV1-A"`)
+#[must_use]
+pub fn group_by_detector(fcs: &Fcs) -> Vec<SpectralGroup> {
+    let mut parameters: Vec<&crate::parameter::Parameter> = fcs.parameters.values().collect();
+    parameters.sort_by_key(|parameter| parameter.parameter_number);
+
+    let mut groups: BTreeMap<String, Vec<ChannelName>> = BTreeMap::new();
+    for parameter in parameters {
+        let label = parameter
+            .excitation_wavelength
+            .map(|nanometers| format!("{nanometers}nm"))
+            .or_else(|| detector_name_prefix(fcs, parameter.parameter_number))
+            .unwrap_or_else(|| channel_name_prefix(&parameter.channel_name));
+        groups
+            .entry(label)
+            .or_default()
+            .push(parameter.channel_name.clone());
+    }
+
+    groups
+        .into_iter()
+        .map(|(label, channels)| SpectralGroup { label, channels })
+        .collect()
+}
+
+/// The alphabetic prefix of a channel's `$PnDET` value, if the keyword is present and starts
+/// with letters (e.g. `"UV"` from `"UV Detector 1"`)
+fn detector_name_prefix(fcs: &Fcs, parameter_number: usize) -> Option<String> {
+    let value = fcs
+        .metadata
+        .get_string_keyword(&format!("$P{parameter_number}DET"))
+        .ok()?;
+    let prefix: String = value.get_str().chars().take_while(|c| c.is_alphabetic()).collect();
+    (!prefix.is_empty()).then_some(prefix)
+}
+
+/// The leading run of alphabetic characters in a channel name (e.g. `"UV"` from `"UV1-A"`,
+/// `"FSC"` from `"FSC-A"`); returns the whole name if it doesn't start with a letter (nothing
+/// sensible to group by)
+fn channel_name_prefix(name: &str) -> String {
+    let prefix: String = name.chars().take_while(|c| c.is_alphabetic()).collect();
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        prefix
+    }
+}
@@ -1,13 +1,16 @@
 #[cfg(test)]
 mod polars_tests {
-    use std::sync::Arc;
+    use std::sync::{Arc, RwLock};
 
     use crate::{
-        Fcs, Header, Metadata, Parameter, TransformType,
+        Fcs, Header, Metadata, Parameter, TransformType, UnmixingMethod,
         file::AccessWrapper,
         parameter::{ParameterMap, ParameterProcessing},
     };
-    use polars::{frame::DataFrame, prelude::Column};
+    use polars::{
+        frame::DataFrame,
+        prelude::{Column, col, lit},
+    };
 
     fn create_test_fcs() -> Result<Fcs, Box<dyn std::error::Error>> {
         use std::fs::File;
@@ -58,6 +61,7 @@ mod polars_tests {
             parameters: params,
             data_frame: Arc::new(df),
             file_access: AccessWrapper::new(temp_path.to_str().unwrap_or(""))?,
+            channel_range_cache: Arc::new(RwLock::new(None)),
         })
     }
 
@@ -90,6 +94,78 @@ mod polars_tests {
         assert_eq!(slice[4], 500.0, "Last event should be 500.0");
     }
 
+    #[test]
+    fn test_iter_chunks() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let chunks: Vec<_> = fcs
+            .iter_chunks(&["FSC-A", "SSC-A"], 2)
+            .expect("Should build chunk iterator")
+            .collect();
+
+        assert_eq!(chunks.len(), 3, "5 events in chunks of 2 should yield 3 chunks");
+        assert_eq!(chunks[0].len(), 2);
+        assert_eq!(chunks[0].channel("FSC-A"), Some(&[100.0, 200.0][..]));
+        assert_eq!(chunks[0].channel("SSC-A"), Some(&[50.0, 150.0][..]));
+        assert!(chunks[0].channel("FL1-A").is_none());
+
+        assert_eq!(chunks[2].len(), 1, "final chunk should hold the remainder");
+        assert_eq!(chunks[2].channel("FSC-A"), Some(&[500.0][..]));
+
+        assert!(fcs.iter_chunks(&["FSC-A"], 0).is_err());
+        assert!(fcs.iter_chunks(&["NonExistent"], 2).is_err());
+    }
+
+    #[test]
+    fn test_lazy_supports_predicate_pushdown() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let filtered = fcs
+            .lazy()
+            .filter(col("FSC-A").gt(lit(200.0)))
+            .select([col("FSC-A")])
+            .collect()
+            .expect("Should collect filtered LazyFrame");
+
+        assert_eq!(filtered.height(), 3, "Should keep FSC-A values > 200.0");
+    }
+
+    #[test]
+    fn test_index_sort_data_parses_bd_locations() {
+        use crate::index_sort::IndexSortData;
+        use crate::plate::WellPosition;
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        fcs.metadata.insert_string_keyword(
+            "INDEX SORTING LOCATIONS".to_string(),
+            "0,0;0,1;1,0".to_string(),
+        );
+
+        let index_sort = IndexSortData::from_fcs(&fcs)
+            .expect("Should parse index sort locations")
+            .expect("Keyword is present");
+
+        assert_eq!(index_sort.locations().len(), 3);
+        assert_eq!(
+            index_sort.well_for_event(1),
+            Some(WellPosition { row: 0, column: 1 })
+        );
+        assert_eq!(
+            index_sort.events_at_well(WellPosition { row: 1, column: 0 }),
+            vec![2]
+        );
+        assert_eq!(index_sort.well_for_event(99), None);
+    }
+
+    #[test]
+    fn test_index_sort_data_absent_keyword() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let index_sort =
+            crate::index_sort::IndexSortData::from_fcs(&fcs).expect("Should not error");
+        assert!(index_sort.is_none());
+    }
+
     #[test]
     fn test_get_xy_pairs() {
         let fcs = create_test_fcs().expect("Failed to create test FCS");
@@ -144,6 +220,83 @@ mod polars_tests {
         assert!(std > 0.0, "Std dev should be positive");
     }
 
+    #[test]
+    fn test_get_parameter_percentile_and_median() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let median = fcs
+            .get_parameter_median("FSC-A")
+            .expect("Should get median");
+        assert_eq!(median, 300.0, "Median of [100..500] should be 300");
+
+        let p0 = fcs
+            .get_parameter_percentile("FSC-A", 0.0)
+            .expect("Should get 0th percentile");
+        assert_eq!(p0, 100.0, "0th percentile should be the min");
+
+        let p100 = fcs
+            .get_parameter_percentile("FSC-A", 1.0)
+            .expect("Should get 100th percentile");
+        assert_eq!(p100, 500.0, "100th percentile should be the max");
+
+        assert!(fcs.get_parameter_percentile("FSC-A", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_get_parameter_mad_streaming_matches_exact() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let mad = fcs.get_parameter_mad("FSC-A").expect("Should get MAD");
+        assert_eq!(mad, 100.0, "MAD of [100..500] around median 300 is 100");
+
+        let (median_exact, mad_exact) = fcs
+            .get_parameter_median_mad_exact("FSC-A")
+            .expect("Should get exact median/MAD");
+        assert_eq!(median_exact, 300.0);
+        assert_eq!(mad_exact, 100.0);
+    }
+
+    #[test]
+    fn test_channel_ranges_observed_and_keyword() {
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        fcs.metadata.keywords.insert(
+            "$P1R".to_string(),
+            crate::keyword::Keyword::Int(crate::keyword::IntegerKeyword::PnR(1024)),
+        );
+
+        let ranges = fcs.channel_ranges().expect("Should compute channel ranges");
+
+        let fsc_a = ranges.get("FSC-A").expect("FSC-A should have a range");
+        assert_eq!(fsc_a.observed_min, 100.0);
+        assert_eq!(fsc_a.observed_max, 500.0);
+        assert_eq!(fsc_a.keyword_range, Some(1024.0));
+        assert_eq!(fsc_a.display_range(), (0.0, 1024.0));
+
+        let ssc_a = ranges.get("SSC-A").expect("SSC-A should have a range");
+        assert_eq!(ssc_a.keyword_range, None);
+        assert_eq!(ssc_a.display_range(), (50.0, 450.0));
+    }
+
+    #[test]
+    fn test_channel_ranges_cache_invalidated_by_new_dataframe() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let first = fcs.channel_ranges().expect("Should compute channel ranges");
+        assert_eq!(first.get("FSC-A").unwrap().observed_max, 500.0);
+
+        let df = DataFrame::new(vec![
+            Column::new("FSC-A".into(), vec![1.0f32, 2.0, 3.0]),
+            Column::new("SSC-A".into(), vec![1.0f32, 2.0, 3.0]),
+            Column::new("FL1-A".into(), vec![1.0f32, 2.0, 3.0]),
+        ])
+        .expect("Failed to create replacement DataFrame");
+        let mut fcs = fcs;
+        fcs.data_frame = Arc::new(df);
+
+        let second = fcs.channel_ranges().expect("Should recompute channel ranges");
+        assert_eq!(second.get("FSC-A").unwrap().observed_max, 3.0);
+    }
+
     #[test]
     fn test_arcsinh_transformation() {
         let fcs = create_test_fcs().expect("Failed to create test FCS");
@@ -363,6 +516,129 @@ mod polars_tests {
         );
     }
 
+    #[test]
+    fn test_spectral_unmixing_wls_matches_ols_for_square_system() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        use ndarray::Array2;
+        let unmix_matrix = Array2::from_shape_vec((2, 2), vec![1.0, 0.15, 0.1, 1.0]).unwrap();
+        let channels = vec!["FSC-A", "SSC-A"];
+
+        let ols = fcs
+            .apply_spectral_unmixing_with_method(&unmix_matrix, &channels, None, UnmixingMethod::Ols)
+            .expect("Should unmix with OLS");
+        let wls = fcs
+            .apply_spectral_unmixing_with_method(&unmix_matrix, &channels, None, UnmixingMethod::Wls)
+            .expect("Should unmix with WLS");
+
+        let fcs_ols = Fcs {
+            data_frame: ols.data,
+            ..fcs.clone()
+        };
+        let fcs_wls = Fcs {
+            data_frame: wls.data,
+            ..fcs.clone()
+        };
+
+        let ols_fsc = fcs_ols.get_parameter_events_slice("FSC-A").unwrap();
+        let wls_fsc = fcs_wls.get_parameter_events_slice("FSC-A").unwrap();
+
+        for (a, b) in ols_fsc.iter().zip(wls_fsc.iter()) {
+            assert!(
+                (a - b).abs() < 1e-3,
+                "WLS should match OLS for a square (exactly determined) system: {a} vs {b}"
+            );
+        }
+        for (a, b) in ols.residuals.iter().zip(wls.residuals.iter()) {
+            assert!(
+                (a - b).abs() < 1e-3,
+                "WLS residuals should match OLS residuals for a square system: {a} vs {b}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_spectral_unmixing_nnls_clamps_negative_abundance_to_zero() {
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        // Signals chosen so that OLS's matrix inverse produces a negative abundance for the
+        // first component: with heavy spectral overlap (0.95) and signal 2 dominating signal 1,
+        // solving the unmixing matrix directly yields a negative first coefficient.
+        let cofactor = 200.0f32;
+        let raw_c1 = (1.0f32 / cofactor).asinh();
+        let raw_c2 = (2.0f32 / cofactor).asinh();
+        use polars::prelude::{NamedFrom, Series};
+        let mut df = (*fcs.data_frame).clone();
+        df.replace("FSC-A", Series::new("FSC-A".into(), vec![raw_c1; 5]))
+            .unwrap();
+        df.replace("SSC-A", Series::new("SSC-A".into(), vec![raw_c2; 5]))
+            .unwrap();
+        fcs.data_frame = Arc::new(df);
+
+        use ndarray::Array2;
+        let unmix_matrix = Array2::from_shape_vec((2, 2), vec![1.0, 0.95, 0.95, 1.0]).unwrap();
+        let channels = vec!["FSC-A", "SSC-A"];
+
+        let ols = fcs
+            .apply_spectral_unmixing_with_method(&unmix_matrix, &channels, None, UnmixingMethod::Ols)
+            .expect("Should unmix with OLS");
+        let nnls = fcs
+            .apply_spectral_unmixing_with_method(&unmix_matrix, &channels, None, UnmixingMethod::Nnls)
+            .expect("Should unmix with NNLS");
+
+        let fcs_ols = Fcs {
+            data_frame: ols.data,
+            ..fcs.clone()
+        };
+        let fcs_nnls = Fcs {
+            data_frame: nnls.data,
+            ..fcs.clone()
+        };
+
+        let ols_fsc = fcs_ols.get_parameter_events_slice("FSC-A").unwrap();
+        let nnls_fsc = fcs_nnls.get_parameter_events_slice("FSC-A").unwrap();
+
+        assert!(
+            ols_fsc[0] < 0.0,
+            "Sanity check: OLS should produce a negative abundance here, got {}",
+            ols_fsc[0]
+        );
+        assert!(
+            nnls_fsc[0] >= 0.0,
+            "NNLS should clamp the negative abundance to zero, got {}",
+            nnls_fsc[0]
+        );
+    }
+
+    #[test]
+    fn test_spectral_unmixing_with_autofluorescence_adds_af_column() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        use ndarray::Array2;
+        let unmix_matrix = Array2::from_shape_vec((2, 2), vec![1.0, 0.15, 0.1, 1.0]).unwrap();
+        let channels = vec!["FSC-A", "SSC-A"];
+        let autofluorescence_spectra = vec![vec![0.2f32, 0.3]];
+
+        let result = fcs
+            .apply_spectral_unmixing_with_autofluorescence(
+                &unmix_matrix,
+                &channels,
+                &autofluorescence_spectra,
+                None,
+            )
+            .expect("Should unmix with autofluorescence component");
+
+        let fcs_result = Fcs {
+            data_frame: result.data,
+            ..fcs.clone()
+        };
+
+        let af = fcs_result
+            .get_parameter_events_slice("AF")
+            .expect("Should have added an AF column");
+        assert_eq!(af.len(), 5, "AF column should have one value per event");
+    }
+
     #[test]
     fn test_parameter_is_fluorescence() {
         let fcs = create_test_fcs().expect("Failed to create test FCS");
@@ -531,6 +807,37 @@ mod polars_tests {
         assert_eq!(matrix[[0, 1]], 0.1);
     }
 
+    #[test]
+    fn test_set_spillover_matrix() {
+        use ndarray::Array2;
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        let channel_names = vec!["FL1-A".to_string(), "FL2-A".to_string()];
+        let matrix =
+            Array2::from_shape_vec((2, 2), vec![1.0, 0.1, 0.15, 1.0]).expect("valid shape");
+
+        fcs.set_spillover_matrix(&matrix, &channel_names)
+            .expect("Should set spillover matrix");
+
+        let (round_tripped, names) = fcs
+            .get_spillover_matrix()
+            .expect("Should extract spillover")
+            .expect("Should have spillover matrix");
+        assert_eq!(names, channel_names);
+        assert_eq!(round_tripped, matrix);
+    }
+
+    #[test]
+    fn test_set_spillover_matrix_wrong_shape() {
+        use ndarray::Array2;
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        let channel_names = vec!["FL1-A".to_string(), "FL2-A".to_string()];
+        let matrix = Array2::from_shape_vec((3, 3), vec![0.0; 9]).expect("valid shape");
+
+        assert!(fcs.set_spillover_matrix(&matrix, &channel_names).is_err());
+    }
+
     #[test]
     fn test_has_compensation() {
         use crate::keyword::{Keyword, MixedKeyword};
@@ -592,4 +899,446 @@ mod polars_tests {
 
         assert_ne!(comp_data[0], orig_data[0], "Data should be compensated");
     }
+
+    #[test]
+    fn test_metadata_decodes_latin1_keyword_values() {
+        use crate::header::Header;
+        use crate::keyword::StringableKeyword;
+        use crate::metadata::Metadata;
+
+        // "CD8" + the single byte 0xE2 (U+00E2 in Latin-1), as a legacy Latin-1 file would
+        // store it - not valid UTF-8, so it must fall back to Latin-1 instead
+        // of being silently dropped. A trailing $PAR field keeps $P1S from being the segment's
+        // last keyword, since only the closing delimiter (outside the parsed slice) would mark
+        // its value's end otherwise.
+        let mut text_segment = vec![b'/'];
+        text_segment.extend_from_slice(b"$P1S/CD8\xe2/$PAR/1/");
+        let mut mmap = vec![0u8; 58];
+        mmap.extend_from_slice(&text_segment);
+
+        let header = Header {
+            version: crate::version::Version::V3_1,
+            text_offset: 58..=(58 + text_segment.len() - 1),
+            data_offset: 0..=0,
+            analysis_offset: 0..=0,
+        };
+
+        let metadata = Metadata::from_mmap(&mmap, &header);
+        let value = metadata
+            .get_string_keyword("$P1S")
+            .expect("Should find $P1S");
+        assert_eq!(value.get_str(), "CD8\u{e2}");
+    }
+
+    #[test]
+    fn test_metadata_unescapes_doubled_delimiter_in_value() {
+        use crate::header::Header;
+        use crate::keyword::StringableKeyword;
+        use crate::metadata::Metadata;
+
+        // A value containing a literal `/` (the delimiter) is written as `//`, per the FCS
+        // escaping rule; the parser must fold that back into a single `/` rather than treating
+        // it as a field boundary. A trailing $PAR field keeps $P1S from being the segment's last
+        // keyword, since only the closing delimiter (outside the parsed slice) would mark its
+        // value's end otherwise.
+        let mut text_segment = vec![b'/'];
+        text_segment.extend_from_slice(b"$P1S/A//B/$PAR/1/");
+        let mut mmap = vec![0u8; 58];
+        mmap.extend_from_slice(&text_segment);
+
+        let header = Header {
+            version: crate::version::Version::V3_1,
+            text_offset: 58..=(58 + text_segment.len() - 1),
+            data_offset: 0..=0,
+            analysis_offset: 0..=0,
+        };
+
+        let metadata = Metadata::from_mmap(&mmap, &header);
+        let value = metadata
+            .get_string_keyword("$P1S")
+            .expect("Should find $P1S");
+        assert_eq!(value.get_str(), "A/B");
+    }
+
+    #[test]
+    fn test_get_parameter_events_calibrated() {
+        use crate::keyword::{Keyword, MixedKeyword};
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        fcs.metadata.keywords.insert(
+            "$P1CALIBRATION".to_string(),
+            Keyword::Mixed(MixedKeyword::PnCalibration(2.0, "MESF".to_string())),
+        );
+
+        let calibrated = fcs
+            .get_parameter_events_calibrated("FSC-A")
+            .expect("Should convert FSC-A to calibrated units");
+        assert_eq!(calibrated, vec![200.0, 400.0, 600.0, 800.0, 1000.0]);
+
+        assert_eq!(
+            fcs.get_parameter_calibration_unit("FSC-A")
+                .expect("Should find the calibration unit"),
+            "MESF"
+        );
+    }
+
+    #[test]
+    fn test_get_parameter_events_calibrated_errors_without_keyword() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+        assert!(fcs.get_parameter_events_calibrated("FSC-A").is_err());
+    }
+
+    #[test]
+    fn test_open_invalid_extension_returns_typed_error() {
+        use crate::FcsError;
+
+        let path = std::env::temp_dir().join("test_fcs_invalid_extension.txt");
+        std::fs::write(&path, b"not an fcs file").expect("Should write test file");
+
+        let err = Fcs::open(path.to_str().expect("Path should be valid UTF-8"))
+            .expect_err("Should reject a non-.fcs extension");
+        assert!(matches!(
+            err.downcast_ref::<FcsError>(),
+            Some(FcsError::InvalidExtension { .. })
+        ));
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_from_bytes_malformed_header_returns_typed_error() {
+        use crate::FcsError;
+
+        // Long enough to avoid slicing past the end, but not a valid FCS header: the version
+        // string and the surrounding spaces are all wrong.
+        let garbage = vec![0u8; 100];
+
+        let err = Fcs::from_bytes(&garbage).expect_err("Should reject a malformed header");
+        assert!(matches!(
+            err.downcast_ref::<FcsError>(),
+            Some(FcsError::HeaderParse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_ascii_only_detects_non_ascii_keyword_value() {
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        fcs.metadata
+            .insert_string_keyword("$P1S".to_string(), "CD8\u{3b2}".to_string());
+        assert!(!fcs.metadata.is_ascii_only());
+    }
+
+    #[test]
+    fn test_build_header_normal_offsets() {
+        use crate::version::Version;
+        use crate::write::build_header;
+
+        let header = build_header(&Version::V3_1, 58, 157, 158, 1157).expect("Should build header");
+        assert_eq!(&header[10..18], b"      58");
+        assert_eq!(&header[18..26], b"     157");
+        assert_eq!(&header[26..34], b"     158");
+        assert_eq!(&header[34..42], b"    1157");
+    }
+
+    #[test]
+    fn test_build_header_zeroes_data_offsets_past_eight_digits() {
+        use crate::version::Version;
+        use crate::write::build_header;
+
+        // A multi-GB DATA segment doesn't fit in the HEADER's 8-digit fields; the spec says to
+        // write 0 there and let $BEGINDATA/$ENDDATA (already written unconditionally in
+        // serialize_metadata) govern instead.
+        let data_start = 100_000_000usize;
+        let data_end = 5_100_000_099usize;
+        let header = build_header(&Version::V3_1, 58, 157, data_start, data_end)
+            .expect("Should build header even when the DATA segment overflows 8 digits");
+        assert_eq!(&header[26..34], b"       0");
+        assert_eq!(&header[34..42], b"       0");
+    }
+
+    #[test]
+    fn test_build_header_rejects_oversized_text_segment() {
+        use crate::version::Version;
+        use crate::write::build_header;
+
+        // Unlike DATA, the primary TEXT segment has no keyword-based fallback, so this must
+        // fail loudly rather than silently truncate or panic.
+        let result = build_header(&Version::V3_1, 58, 100_000_058, 100_000_059, 100_001_059);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_group_by_detector_falls_back_to_channel_name_prefix() {
+        use crate::spectral::group_by_detector;
+
+        // create_test_fcs's parameters have no $PnL, so grouping falls back to the FL1-A /
+        // FSC-A / SSC-A naming convention, keeping each name's leading letters.
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let mut groups = group_by_detector(&fcs);
+        groups.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let labels: Vec<&str> = groups.iter().map(|group| group.label.as_str()).collect();
+        assert_eq!(labels, vec!["FL", "FSC", "SSC"]);
+
+        let fl_group = groups
+            .iter()
+            .find(|group| group.label == "FL")
+            .expect("Should have an FL group");
+        assert_eq!(fl_group.channels.len(), 1);
+        assert_eq!(fl_group.channels[0].as_ref(), "FL1-A");
+    }
+
+    #[test]
+    fn test_group_by_detector_prefers_excitation_wavelength() {
+        use crate::spectral::group_by_detector;
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        for channel in ["FSC-A", "SSC-A"] {
+            if let Some(parameter) = fcs.parameters.get_mut(channel) {
+                parameter.excitation_wavelength = Some(488);
+            }
+        }
+
+        let groups = group_by_detector(&fcs);
+        let laser_group = groups
+            .iter()
+            .find(|group| group.label == "488nm")
+            .expect("Should group FSC-A and SSC-A by their shared $PnL");
+        assert_eq!(laser_group.channels.len(), 2);
+    }
+
+    #[test]
+    fn test_spectral_group_event_matrix_and_peak_channel() {
+        use crate::spectral::SpectralGroup;
+
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+        let group = SpectralGroup {
+            label: "test".to_string(),
+            channels: vec!["FSC-A".into(), "SSC-A".into()],
+        };
+
+        let matrix = group
+            .event_matrix(&fcs)
+            .expect("Should build the event matrix");
+        assert_eq!(matrix.shape(), &[5, 2]);
+        assert_eq!(matrix[[0, 0]], 100.0, "First row, FSC-A column");
+        assert_eq!(matrix[[0, 1]], 50.0, "First row, SSC-A column");
+
+        let peak = group
+            .peak_channel(&fcs)
+            .expect("Should identify the peak channel");
+        assert_eq!(
+            peak.as_ref(),
+            "FSC-A",
+            "FSC-A has the higher mean signal of the two channels"
+        );
+    }
+
+    #[test]
+    fn test_get_parameter_events_f32_casts_compact_integer_column() {
+        // Simulates a column produced by Fcs::open_with_compact_integer_storage, which stores
+        // a $DATATYPE=I parameter that fits in 16 bits as UInt16 instead of Float32.
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        let mut columns: Vec<Column> = fcs.data_frame.get_columns().to_vec();
+        columns[2] = Column::new("FL1-A".into(), vec![10u16, 20, 30, 40, 50]);
+        fcs.data_frame = std::sync::Arc::new(
+            DataFrame::new(columns).expect("Should rebuild DataFrame with a UInt16 column"),
+        );
+
+        // The zero-copy slice accessor only ever hands out Float32 data, so it errors here.
+        assert!(fcs.get_parameter_events_slice("FL1-A").is_err());
+
+        let events = fcs
+            .get_parameter_events_f32("FL1-A")
+            .expect("Should cast the UInt16 column to f32 on demand");
+        assert_eq!(events.as_ref(), &[10.0, 20.0, 30.0, 40.0, 50.0]);
+    }
+
+    #[test]
+    fn test_get_parameter_events_f32_passes_through_float32_column() {
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+
+        let events = fcs
+            .get_parameter_events_f32("FSC-A")
+            .expect("Should read the Float32 column directly");
+        assert_eq!(events.as_ref(), fcs.get_parameter_events_slice("FSC-A").unwrap());
+        assert!(
+            matches!(events, std::borrow::Cow::Borrowed(_)),
+            "Float32 columns should be returned zero-copy"
+        );
+    }
+
+    #[test]
+    fn test_edit_metadata_and_save_round_trips_vendor_keywords() {
+        use crate::keyword::StringableKeyword;
+        use crate::write::edit_metadata_and_save;
+
+        // Vendor/unknown keywords (anything match_and_parse_keyword doesn't recognize) always
+        // parse into StringKeyword::Other; this asserts they come back with their real value
+        // rather than the literal string "Other" (see StringKeyword::Other's doc comment).
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+        let path = std::env::temp_dir().join("test_fcs_vendor_roundtrip.fcs");
+
+        let reopened = edit_metadata_and_save(fcs, &path, |metadata| {
+            metadata.insert_string_keyword(
+                "CYTEK VENDOR SERIAL".to_string(),
+                "SN-90210-A".to_string(),
+            );
+            metadata.insert_string_keyword("CYT".to_string(), "Aurora CS".to_string());
+        })
+        .expect("Should write and reopen the edited file");
+
+        let vendor_value = reopened
+            .metadata
+            .get_string_keyword("$CYTEK VENDOR SERIAL")
+            .expect("Should find the vendor keyword")
+            .get_str();
+        assert_eq!(vendor_value, "SN-90210-A");
+
+        let cyt_value = reopened
+            .metadata
+            .get_string_keyword("$CYT")
+            .expect("Should find $CYT")
+            .get_str();
+        assert_eq!(cyt_value, "Aurora CS");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_crc16_matches_known_answer_vector() {
+        use crate::crc::crc16;
+
+        // The standard CRC-16/XMODEM check value (poly 0x1021, init 0x0000, no reflection,
+        // no output xor) for the ASCII bytes "123456789", per the CRC catalog.
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+    }
+
+    #[test]
+    fn test_verify_crc_round_trips_and_detects_corruption() {
+        use crate::crc::{format_crc, verify_crc};
+
+        let mut file_bytes = b"pretend this is a whole FCS file's HEADER+TEXT+DATA".to_vec();
+        let crc = crate::crc::crc16(&file_bytes);
+        file_bytes.extend_from_slice(format_crc(crc).as_bytes());
+
+        let report = verify_crc(&file_bytes);
+        assert!(report.crc_present);
+        assert!(report.is_valid());
+
+        // Flip a byte in the body without updating the trailing CRC field.
+        let corrupt_idx = 0;
+        file_bytes[corrupt_idx] ^= 0xFF;
+        let report = verify_crc(&file_bytes);
+        assert!(report.crc_present);
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_open_all_single_dataset_reports_one_dataset() {
+        use crate::write::write_fcs_file;
+
+        let fcs = create_test_fcs().expect("Failed to create test FCS");
+        let path = std::env::temp_dir().join("test_open_all_single.fcs");
+        write_fcs_file(fcs, &path).expect("Should write test file");
+
+        let datasets = Fcs::open_all(path.to_str().expect("valid utf8 path"))
+            .expect("Should open the lone dataset");
+        assert_eq!(datasets.len(), 1);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    /// Writes `create_test_fcs()` with its `$NEXTDATA` keyword set to `next_data_offset`,
+    /// returning the resulting file's bytes. Since the value's own digit count feeds back into
+    /// the TEXT segment's length (and thus the file's total length), callers that need
+    /// `$NEXTDATA` to equal the file's own length iterate this to a fixed point.
+    fn write_test_fcs_with_next_data(path: &std::path::Path, next_data_offset: usize) -> Vec<u8> {
+        use crate::write::write_fcs_file;
+
+        let mut fcs = create_test_fcs().expect("Failed to create test FCS");
+        fcs.metadata
+            .insert_string_keyword("$NEXTDATA".to_string(), next_data_offset.to_string());
+        write_fcs_file(fcs, path).expect("Should write test file");
+        std::fs::read(path).expect("Should read back the file just written")
+    }
+
+    #[test]
+    fn test_open_all_multi_dataset_follows_nextdata_chain() {
+        let dir = std::env::temp_dir();
+        let path_a = dir.join("test_open_all_multi_a.fcs");
+        let path_b = dir.join("test_open_all_multi_b.fcs");
+        let combined_path = dir.join("test_open_all_multi_combined.fcs");
+
+        // Dataset A's `$NEXTDATA` must equal its own on-disk length once dataset B is appended
+        // right after it.
+        let mut next_data_offset = 0usize;
+        let bytes_a = loop {
+            let bytes = write_test_fcs_with_next_data(&path_a, next_data_offset);
+            if bytes.len() == next_data_offset {
+                break bytes;
+            }
+            next_data_offset = bytes.len();
+        };
+        // Dataset B is the last one in the chain.
+        let bytes_b = write_test_fcs_with_next_data(&path_b, 0);
+
+        let mut combined = bytes_a;
+        combined.extend_from_slice(&bytes_b);
+        std::fs::write(&combined_path, &combined).expect("Should write combined file");
+
+        let datasets = Fcs::open_all(combined_path.to_str().expect("valid utf8 path"))
+            .expect("Should follow the $NEXTDATA chain across both datasets");
+        assert_eq!(datasets.len(), 2);
+
+        std::fs::remove_file(&path_a).ok();
+        std::fs::remove_file(&path_b).ok();
+        std::fs::remove_file(&combined_path).ok();
+    }
+
+    #[test]
+    fn test_open_all_rejects_out_of_range_nextdata_offset() {
+        let path = std::env::temp_dir().join("test_open_all_bad_offset.fcs");
+        // Nothing in the file is anywhere near this far in, so the offset must be rejected
+        // instead of panicking on an out-of-bounds slice.
+        write_test_fcs_with_next_data(&path, 999_999_999);
+
+        let err = Fcs::open_all(path.to_str().expect("valid utf8 path"))
+            .expect_err("An out-of-range $NEXTDATA offset should error, not panic");
+        assert!(err.to_string().contains("NEXTDATA"));
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_open_all_rejects_nextdata_cycle() {
+        let dir = std::env::temp_dir();
+        let path_a = dir.join("test_open_all_cycle_a.fcs");
+        let path_b = dir.join("test_open_all_cycle_b.fcs");
+        let combined_path = dir.join("test_open_all_cycle_combined.fcs");
+
+        let mut dataset_b_offset = 0usize;
+        let bytes_a = loop {
+            let bytes = write_test_fcs_with_next_data(&path_a, dataset_b_offset);
+            if bytes.len() == dataset_b_offset {
+                break bytes;
+            }
+            dataset_b_offset = bytes.len();
+        };
+        // Dataset B points right back at itself, so the chain never terminates.
+        let bytes_b = write_test_fcs_with_next_data(&path_b, dataset_b_offset);
+
+        let mut combined = bytes_a;
+        combined.extend_from_slice(&bytes_b);
+        std::fs::write(&combined_path, &combined).expect("Should write combined file");
+
+        let err = Fcs::open_all(combined_path.to_str().expect("valid utf8 path"))
+            .expect_err("A $NEXTDATA chain that loops back on itself should error, not hang");
+        assert!(err.to_string().contains("loops back"));
+
+        std::fs::remove_file(&path_a).ok();
+        std::fs::remove_file(&path_b).ok();
+        std::fs::remove_file(&combined_path).ok();
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::{Arc, OnceLock, RwLock};
 
 /// Transformation type to apply to flow cytometry parameter data
 ///
@@ -29,21 +31,93 @@ pub enum TransformType {
         /// Width basis parameter (typically 0.5)
         width: f32,
     },
+    /// Exact Logicle transformation (Parks, Roederer & Moore 2006), the biexponential display
+    /// scale FlowJo and most modern analysis software use by default
+    ///
+    /// Unlike [`TransformType::Biexponential`]'s direct `asinh`-based approximation, this
+    /// derives its coefficients per Parks/Moore's paper and inverts them by root-finding (see
+    /// [`crate::logicle`]), so `width` actually shapes the linear region around zero rather
+    /// than being ignored.
+    Logicle {
+        /// Top of scale value (typically 262144 for 18-bit or 1048576 for 20-bit data)
+        top_of_scale: f32,
+        /// Width of the linear region around zero, in decades (typically 0.5-1)
+        width: f32,
+        /// Total number of decades displayed (typically 4-4.5)
+        decades: f32,
+        /// Additional negative decades to display below zero (typically 0)
+        negative_decades: f32,
+    },
+    /// A user-registered transform, looked up by name at call time via
+    /// [`register_custom_transform`]
+    ///
+    /// Falls back to the identity transform if nothing is registered under `name` (e.g. this
+    /// `TransformType` was deserialized in a process that never called
+    /// [`register_custom_transform`] for it).
+    Custom(String),
+}
+
+/// A user-supplied transform that can be registered under a name and referenced from
+/// [`TransformType::Custom`]
+pub trait CustomTransform: Send + Sync {
+    fn transform(&self, value: f32) -> f32;
+    fn inverse_transform(&self, value: f32) -> f32;
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Arc<dyn CustomTransform>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Arc<dyn CustomTransform>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers a custom transform under `name`, so `TransformType::Custom(name.to_string())` can
+/// use it; overwrites any transform already registered under the same name
+pub fn register_custom_transform(name: impl Into<String>, transform: Arc<dyn CustomTransform>) {
+    registry()
+        .write()
+        .expect("transform registry lock poisoned")
+        .insert(name.into(), transform);
+}
+
+/// Removes a custom transform previously registered with [`register_custom_transform`]
+pub fn unregister_custom_transform(name: &str) {
+    registry()
+        .write()
+        .expect("transform registry lock poisoned")
+        .remove(name);
+}
+
+fn get_custom_transform(name: &str) -> Option<Arc<dyn CustomTransform>> {
+    registry()
+        .read()
+        .expect("transform registry lock poisoned")
+        .get(name)
+        .cloned()
 }
 
 impl TransformType {
-    /// Create a TransformType from a string. If no string is provided or the string is not matched, the default `arcsinh` transform is used.
+    /// Create a `TransformType` from a string
+    ///
+    /// If no string is provided, the default `arcsinh` transform is used. Any string that
+    /// doesn't match a built-in transform name is treated as the name of a transform registered
+    /// via [`register_custom_transform`] and returned as [`TransformType::Custom`].
     pub fn create_from_str(s: Option<&str>) -> Self {
         match s {
             Some("linear") => TransformType::Linear,
             Some("arcsinh") => TransformType::Arcsinh { cofactor: 200.0 },
-            Some("biexponential") | Some("logicle") => TransformType::Biexponential {
+            Some("biexponential") => TransformType::Biexponential {
                 top_of_scale: 262144.0,
                 positive_decades: 4.5,
                 negative_decades: 0.0,
                 width: 0.5,
             },
-            _ => TransformType::default(),
+            Some("logicle") => TransformType::Logicle {
+                top_of_scale: 262144.0,
+                width: 0.5,
+                decades: 4.5,
+                negative_decades: 0.0,
+            },
+            Some(name) => TransformType::Custom(name.to_string()),
+            None => TransformType::default(),
         }
     }
 }
@@ -83,15 +157,36 @@ impl Transformable for TransformType {
                 let m_ln10 = positive_decades * ln_10;
                 let sinh_m_ln10 = m_ln10.sinh();
                 let a_ln10 = negative_decades * ln_10;
-                
+
                 // Handle division by zero and very small values
                 if *top_of_scale == 0.0 {
                     return *value;
                 }
-                
+
                 let scaled_x = value * sinh_m_ln10 / top_of_scale;
                 scaled_x.asinh() + a_ln10
             }
+            TransformType::Logicle {
+                top_of_scale,
+                width,
+                decades,
+                negative_decades,
+            } => {
+                match crate::logicle::LogicleParams::new(
+                    f64::from(*top_of_scale),
+                    f64::from(*width),
+                    f64::from(*decades),
+                    f64::from(*negative_decades),
+                ) {
+                    Ok(params) => params.value_to_scale(f64::from(*value)) as f32,
+                    // Invalid parameter combination (see LogicleParams::new); fall back to the
+                    // raw value, matching Biexponential's zero-top-of-scale fallback above.
+                    Err(_) => *value,
+                }
+            }
+            TransformType::Custom(name) => get_custom_transform(name)
+                .map(|custom| custom.transform(*value))
+                .unwrap_or(*value),
         }
     }
     fn inverse_transform(&self, value: &f32) -> f32 {
@@ -123,12 +218,31 @@ impl Transformable for TransformType {
                 let m_ln10 = positive_decades * ln_10;
                 let sinh_m_ln10 = m_ln10.sinh();
                 let a_ln10 = negative_decades * ln_10;
-                
+
                 let y_minus_a = value - a_ln10;
                 let sinh_y_minus_a = y_minus_a.sinh();
-                
+
                 top_of_scale * sinh_y_minus_a / sinh_m_ln10
             }
+            TransformType::Logicle {
+                top_of_scale,
+                width,
+                decades,
+                negative_decades,
+            } => {
+                match crate::logicle::LogicleParams::new(
+                    f64::from(*top_of_scale),
+                    f64::from(*width),
+                    f64::from(*decades),
+                    f64::from(*negative_decades),
+                ) {
+                    Ok(params) => params.scale_to_value(f64::from(*value)) as f32,
+                    Err(_) => *value,
+                }
+            }
+            TransformType::Custom(name) => get_custom_transform(name)
+                .map(|custom| custom.inverse_transform(*value))
+                .unwrap_or(*value),
         }
     }
 }
@@ -136,14 +250,10 @@ impl Formattable for TransformType {
     fn format(&self, value: &f32) -> String {
         match self {
             TransformType::Linear => format!("{:.1e}", value),
-            TransformType::Arcsinh { cofactor: _ } => {
-                // Convert from transformed space back to original space
-                let original_value = self.inverse_transform(value);
-
-                // Make nice rounded labels in original space
-                format!("{:.1e}", original_value)
-            }
-            TransformType::Biexponential { .. } => {
+            TransformType::Arcsinh { cofactor: _ }
+            | TransformType::Biexponential { .. }
+            | TransformType::Logicle { .. }
+            | TransformType::Custom(_) => {
                 // Convert from transformed space back to original space
                 let original_value = self.inverse_transform(value);
 
@@ -164,6 +274,10 @@ impl Hash for TransformType {
             TransformType::Linear => "linear".hash(state),
             TransformType::Arcsinh { cofactor: _ } => "arcsinh".hash(state),
             TransformType::Biexponential { .. } => "biexponential".hash(state),
+            TransformType::Logicle { .. } => "logicle".hash(state),
+            // Unlike the built-in variants, "same kind" for a custom transform means "same
+            // name" - two different registered transforms should hash differently.
+            TransformType::Custom(name) => name.hash(state),
         }
     }
 }
@@ -0,0 +1,214 @@
+//! FCS specification compliance validation
+//!
+//! [`Metadata::validate_text_segment_keywords`] enforces the bare minimum needed to parse a
+//! file (required keywords present) and is run automatically by [`crate::Fcs::open`]. This
+//! module goes further, producing a full machine-readable [`ComplianceReport`] of everything
+//! a QC pipeline or CLI tool might want to know before trusting a file: missing required
+//! keywords, HEADER/TEXT offset mismatches, `$TOT` vs. actual data-segment size disagreement,
+//! illegal delimiter usage, and deprecated keywords still present in the TEXT segment.
+
+use crate::{Header, Metadata};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// How strictly [`crate::Fcs::validate`] should check a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationLevel {
+    /// Only checks that affect whether the file can be parsed at all: missing required
+    /// keywords, offset mismatches, `$TOT`/data-size disagreement, and illegal delimiters
+    Minimal,
+    /// Everything `Minimal` checks, plus deprecated-keyword usage
+    Full,
+}
+
+/// Severity of a single [`Violation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    /// The file does not comply with the spec and may fail to parse in this or other readers
+    Error,
+    /// The file is readable but uses a non-standard or deprecated convention
+    Warning,
+}
+
+/// A single spec violation found by [`crate::Fcs::validate`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    pub severity: Severity,
+    /// Short machine-readable category, e.g. `"missing_keyword"`, `"offset_mismatch"`
+    pub category: String,
+    pub message: String,
+}
+
+/// Machine-readable report of an FCS file's compliance with its declared spec version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ComplianceReport {
+    /// Whether the file has no [`Severity::Error`]-level violations; warnings don't affect this
+    #[must_use]
+    pub fn is_compliant(&self) -> bool {
+        !self
+            .violations
+            .iter()
+            .any(|v| v.severity == Severity::Error)
+    }
+
+    fn push(&mut self, severity: Severity, category: &str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            severity,
+            category: category.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Non-parameter-indexed keywords deprecated as of FCS 3.2; kept here rather than derived
+/// from the `#[deprecated]` attributes on [`crate::keyword::StringKeyword`], since those
+/// aren't queryable at runtime
+const DEPRECATED_FLAT_KEYWORDS: [&str; 8] = [
+    "$MODE",
+    "$DATE",
+    "$BTIM",
+    "$ETIM",
+    "$PLATEID",
+    "$PLATENAME",
+    "$WELLID",
+    "$GATE",
+];
+
+/// Matches the deprecated, parameter-indexed gating keywords (`$G1E`, `$G2N`, etc.)
+fn deprecated_gate_keyword_pattern() -> Regex {
+    Regex::new(r"^\$G\d+[EFNPRSTV]$").expect("gate keyword pattern should be valid regex")
+}
+
+/// Checks a `$BEGIN*`/`$END*` keyword pair against the HEADER offsets it's supposed to mirror
+fn check_offset_agreement(
+    report: &mut ComplianceReport,
+    begin_key: &str,
+    end_key: &str,
+    header_range: &RangeInclusive<usize>,
+    metadata: &Metadata,
+) {
+    use crate::keyword::IntegerableKeyword;
+
+    if let Ok(begin) = metadata.get_integer_keyword(begin_key) {
+        let begin = *begin.get_usize();
+        if begin != 0 && begin != *header_range.start() {
+            report.push(
+                Severity::Error,
+                "offset_mismatch",
+                format!(
+                    "{begin_key}={begin} does not match HEADER offset {}",
+                    header_range.start()
+                ),
+            );
+        }
+    }
+    if let Ok(end) = metadata.get_integer_keyword(end_key) {
+        let end = *end.get_usize();
+        if end != 0 && end != *header_range.end() {
+            report.push(
+                Severity::Error,
+                "offset_mismatch",
+                format!(
+                    "{end_key}={end} does not match HEADER offset {}",
+                    header_range.end()
+                ),
+            );
+        }
+    }
+}
+
+/// Validates `metadata`/`header` against the FCS specification for `header.version`
+#[must_use]
+pub fn validate(header: &Header, metadata: &Metadata, level: ValidationLevel) -> ComplianceReport {
+    let mut report = ComplianceReport::default();
+
+    for keyword in header.version.get_required_keywords() {
+        if !metadata.keywords.contains_key(*keyword) {
+            report.push(
+                Severity::Error,
+                "missing_keyword",
+                format!("Missing required keyword: {keyword}"),
+            );
+        }
+    }
+
+    check_offset_agreement(
+        &mut report,
+        "$BEGINDATA",
+        "$ENDDATA",
+        &header.data_offset,
+        metadata,
+    );
+    check_offset_agreement(
+        &mut report,
+        "$BEGINSTEXT",
+        "$ENDSTEXT",
+        &header.text_offset,
+        metadata,
+    );
+    check_offset_agreement(
+        &mut report,
+        "$BEGINANALYSIS",
+        "$ENDANALYSIS",
+        &header.analysis_offset,
+        metadata,
+    );
+
+    if let (Ok(tot), Ok(bytes_per_event)) = (
+        metadata.get_number_of_events(),
+        metadata.calculate_bytes_per_event(),
+    ) {
+        let data_len = header
+            .data_offset
+            .end()
+            .saturating_sub(*header.data_offset.start())
+            + 1;
+        let expected = tot * bytes_per_event;
+        if data_len != expected {
+            report.push(
+                Severity::Error,
+                "tot_size_mismatch",
+                format!(
+                    "$TOT implies {expected} data bytes ({tot} events x {bytes_per_event} bytes/event), but DATA segment is {data_len} bytes"
+                ),
+            );
+        }
+    }
+
+    // FCS requires the delimiter to be a printable, non-alphanumeric, non-period ASCII
+    // character in the range 1-126
+    let delimiter = metadata.delimiter;
+    let delimiter_legal = (1u32..=126).contains(&(delimiter as u32))
+        && !delimiter.is_ascii_alphanumeric()
+        && delimiter != '.';
+    if !delimiter_legal {
+        report.push(
+            Severity::Error,
+            "illegal_delimiter",
+            format!(
+                "Delimiter {delimiter:?} (0x{:02X}) is not a legal FCS delimiter",
+                delimiter as u32
+            ),
+        );
+    }
+
+    if level == ValidationLevel::Full {
+        let gate_pattern = deprecated_gate_keyword_pattern();
+        for key in metadata.keywords.keys() {
+            if DEPRECATED_FLAT_KEYWORDS.contains(&key.as_str()) || gate_pattern.is_match(key) {
+                report.push(
+                    Severity::Warning,
+                    "deprecated_keyword",
+                    format!("{key} is deprecated"),
+                );
+            }
+        }
+    }
+
+    report
+}
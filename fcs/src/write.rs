@@ -4,6 +4,7 @@
 //! - Duplicating existing files
 //! - Editing metadata and persisting changes
 //! - Creating new FCS files with data modifications (filtering, concatenation, column addition)
+//! - Normalizing metadata to a target FCS version on write (see [`write_fcs_file_as_version`])
 //!
 //! ## Memory-Mapping Implications
 //!
@@ -25,7 +26,12 @@
 use crate::{
     Fcs,
     byteorder::ByteOrder,
-    keyword::{IntegerKeyword, Keyword},
+    crc::{crc16, format_crc},
+    datatype::FcsDataType,
+    keyword::{
+        IntegerKeyword, IntegerableKeyword, Keyword, MixedKeyword, StringKeyword,
+        StringableKeyword,
+    },
     metadata::Metadata,
     version::Version,
 };
@@ -52,7 +58,7 @@ use std::sync::Arc;
 /// - The path is invalid
 /// - The file cannot be written
 /// - Metadata cannot be serialized
-pub fn write_fcs_file(fcs: Fcs, path: impl AsRef<Path>) -> Result<()> {
+pub fn write_fcs_file(mut fcs: Fcs, path: impl AsRef<Path>) -> Result<()> {
     let path = path.as_ref();
 
     // Validate file extension
@@ -60,6 +66,20 @@ pub fn write_fcs_file(fcs: Fcs, path: impl AsRef<Path>) -> Result<()> {
         return Err(anyhow!("Output file must have .fcs extension"));
     }
 
+    // Persist each parameter's display transform to $PnD, so it survives the round-trip
+    // through Fcs::open even though it isn't part of the DataFrame itself.
+    for parameter in fcs.parameters.values() {
+        fcs.metadata
+            .set_parameter_display_transform(parameter.parameter_number, &parameter.transform);
+    }
+
+    // Declare the encoding once a keyword value actually needs it, so readers know to expect
+    // (and correctly decode) UTF-8 rather than assuming plain ASCII.
+    if !fcs.metadata.is_ascii_only() && !fcs.metadata.keywords.contains_key("$UNICODE") {
+        fcs.metadata
+            .insert_string_keyword("$UNICODE".to_string(), "UTF-8".to_string());
+    }
+
     // Get data from DataFrame
     let df = &*fcs.data_frame;
     let n_events = df.height();
@@ -101,16 +121,173 @@ pub fn write_fcs_file(fcs: Fcs, path: impl AsRef<Path>) -> Result<()> {
         data_end,
     )?;
 
+    // Append an 8-byte ASCII CRC-16 checksum covering the HEADER, TEXT, and DATA segments,
+    // so readers can detect corruption introduced after we write the file (e.g. a truncated
+    // transfer). See crate::crc.
+    let mut body = Vec::with_capacity(header.len() + text_segment.len() + data_segment.len());
+    body.extend_from_slice(&header);
+    body.extend_from_slice(&text_segment);
+    body.extend_from_slice(&data_segment);
+    let crc_field = format_crc(crc16(&body));
+
     // Write file
     let mut file = File::create(path)?;
-    file.write_all(&header)?;
-    file.write_all(&text_segment)?;
-    file.write_all(&data_segment)?;
+    file.write_all(&body)?;
+    file.write_all(crc_field.as_bytes())?;
     file.sync_all()?;
 
     Ok(())
 }
 
+/// Write an FCS file to disk, first normalizing its metadata for `version`.
+///
+/// Unlike [`write_fcs_file`], which writes out whatever version the `Fcs`
+/// struct already carries in its header, this normalizes the metadata to
+/// match `version` before writing. For FCS 3.2 this means:
+/// - Folding the deprecated `$DATE`/`$BTIM` and `$DATE`/`$ETIM` pairs into
+///   `$BEGINDATETIME`/`$ENDDATETIME` (best-effort; skipped if either half is
+///   missing or doesn't parse as a standard FCS date/time).
+/// - Renaming `$PLATEID`/`$PLATENAME`/`$WELLID` to their FCS 3.2 replacements
+///   `$CARRIERID`/`$CARRIERTYPE`/`$LOCATIONID`.
+/// - Dropping `$MODE` and the `$Gn*` gate-definition keywords, which FCS 3.2
+///   removed outright.
+///
+/// Any `$PnDATATYPE` overrides already present in the metadata are carried
+/// through untouched; this function does not fabricate new ones. Like
+/// [`write_fcs_file`], the written file gets a trailing CRC-16 checksum.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`write_fcs_file`].
+pub fn write_fcs_file_as_version(
+    mut fcs: Fcs,
+    path: impl AsRef<Path>,
+    version: Version,
+) -> Result<()> {
+    normalize_metadata_for_version(&mut fcs.metadata, version);
+    fcs.header.version = version;
+    write_fcs_file(fcs, path)
+}
+
+/// Look up the string value backing one of the legacy 3.0/3.1 keywords that
+/// FCS 3.2 renames or folds into another keyword, without regard to which
+/// concrete variant it parsed into.
+fn get_legacy_string_value<'a>(metadata: &'a Metadata, key: &str) -> Option<&'a str> {
+    // Reading these deprecated fields is the point: this function folds them into their
+    // FCS 3.2 replacements below, it doesn't use them going forward.
+    #[allow(deprecated)]
+    match metadata.keywords.get(key) {
+        Some(Keyword::String(
+            StringKeyword::DATE(value)
+            | StringKeyword::BTIM(value)
+            | StringKeyword::ETIM(value)
+            | StringKeyword::PLATEID(value)
+            | StringKeyword::PLATENAME(value)
+            | StringKeyword::WELLID(value),
+        )) => Some(value.as_ref()),
+        _ => None,
+    }
+}
+
+/// Look up any string keyword's value by key, regardless of which `StringKeyword` variant it
+/// parsed into
+fn get_any_string_keyword_value(metadata: &Metadata, key: &str) -> Option<String> {
+    metadata
+        .get_string_keyword(key)
+        .ok()
+        .map(|value| value.get_str().into_owned())
+}
+
+/// Combine a legacy `$DATE` (`dd-mmm-yyyy`) and `$BTIM`/`$ETIM`
+/// (`hh:mm:ss[.cc]`) pair into an ISO-8601 `$BEGINDATETIME`/`$ENDDATETIME`
+/// value. Returns `None` if either half is missing or doesn't parse, since a
+/// guessed timestamp is worse than omitting it.
+fn combine_fcs_datetime(date: Option<&str>, time: Option<&str>) -> Option<String> {
+    let mut date_parts = date?.split('-');
+    let day: u32 = date_parts.next()?.parse().ok()?;
+    let month = match date_parts.next()?.to_ascii_uppercase().as_str() {
+        "JAN" => 1,
+        "FEB" => 2,
+        "MAR" => 3,
+        "APR" => 4,
+        "MAY" => 5,
+        "JUN" => 6,
+        "JUL" => 7,
+        "AUG" => 8,
+        "SEP" => 9,
+        "OCT" => 10,
+        "NOV" => 11,
+        "DEC" => 12,
+        _ => return None,
+    };
+    let year: u32 = date_parts.next()?.parse().ok()?;
+
+    Some(format!("{year:04}-{month:02}-{day:02}T{}", time?))
+}
+
+/// Rewrite `metadata` in place so it complies with `version`'s keyword set.
+///
+/// No-op for anything below FCS 3.2, since 3.0/3.1 already accept the
+/// keywords this crate writes.
+fn normalize_metadata_for_version(metadata: &mut Metadata, version: Version) {
+    if !matches!(version, Version::V3_2) {
+        return;
+    }
+
+    let begin_datetime = combine_fcs_datetime(
+        get_legacy_string_value(metadata, "$DATE"),
+        get_legacy_string_value(metadata, "$BTIM"),
+    );
+    let end_datetime = combine_fcs_datetime(
+        get_legacy_string_value(metadata, "$DATE"),
+        get_legacy_string_value(metadata, "$ETIM"),
+    );
+    let carrier_id = get_legacy_string_value(metadata, "$PLATEID").map(str::to_string);
+    let carrier_type = get_legacy_string_value(metadata, "$PLATENAME").map(str::to_string);
+    let location_id = get_legacy_string_value(metadata, "$WELLID").map(str::to_string);
+
+    // Matching these deprecated variants is the point: this strips the legacy keywords that
+    // FCS 3.2 renamed or removed outright, it doesn't write them back out.
+    #[allow(deprecated)]
+    metadata.keywords.retain(|_, keyword| {
+        !matches!(
+            keyword,
+            Keyword::String(
+                StringKeyword::MODE(_)
+                    | StringKeyword::DATE(_)
+                    | StringKeyword::BTIM(_)
+                    | StringKeyword::ETIM(_)
+                    | StringKeyword::PLATEID(_)
+                    | StringKeyword::PLATENAME(_)
+                    | StringKeyword::WELLID(_)
+                    | StringKeyword::GATE(_)
+                    | StringKeyword::GnF(_)
+                    | StringKeyword::GnN(_)
+                    | StringKeyword::GnP(_)
+                    | StringKeyword::GnR(_)
+                    | StringKeyword::GnS(_)
+                    | StringKeyword::GnT(_)
+                    | StringKeyword::GnV(_)
+            )
+        )
+    });
+
+    if let Some(value) = begin_datetime {
+        metadata.insert_string_keyword("$BEGINDATETIME".to_string(), value);
+    }
+    if let Some(value) = end_datetime {
+        metadata.insert_string_keyword("$ENDDATETIME".to_string(), value);
+    }
+    if let Some(value) = carrier_id {
+        metadata.insert_string_keyword("$CARRIERID".to_string(), value);
+    }
+    if let Some(value) = carrier_type {
+        metadata.insert_string_keyword("$CARRIERTYPE".to_string(), value);
+    }
+    if let Some(value) = location_id {
+        metadata.insert_string_keyword("$LOCATIONID".to_string(), value);
+    }
+}
+
 /// Duplicate an existing FCS file to a new path
 ///
 /// This creates an exact copy of the file on disk. The original Fcs struct
@@ -126,9 +303,14 @@ pub fn duplicate_fcs_file(fcs: &Fcs, path: impl AsRef<Path>) -> Result<()> {
     use std::fs;
 
     let path = path.as_ref();
+    let source = fcs
+        .file_access
+        .path
+        .as_ref()
+        .ok_or_else(|| anyhow!("Fcs has no backing file to duplicate (opened from bytes)"))?;
 
     // Simply copy the file on disk
-    fs::copy(&fcs.file_access.path, path)?;
+    fs::copy(source, path)?;
 
     Ok(())
 }
@@ -242,39 +424,61 @@ pub fn filter_events(fcs: Fcs, path: impl AsRef<Path>, mask: &[bool]) -> Result<
 /// # Arguments
 /// * `files` - Vector of FCS structs to concatenate
 /// * `path` - Output file path
+/// * `mode` - How to reconcile parameter sets that differ between files
 ///
 /// # Errors
 /// Returns an error if:
-/// - Files have different parameters
+/// - `files` is empty
+/// - `mode` is [`ConcatenationMode::StrictIntersection`] and no parameter is common to every file
+/// - A file's `source_file` column already exists (name collision with the origin column this
+///   function adds)
 /// - The file cannot be written
-pub fn concatenate_events(files: Vec<Fcs>, path: impl AsRef<Path>) -> Result<Fcs> {
+pub fn concatenate_events(
+    files: Vec<Fcs>,
+    path: impl AsRef<Path>,
+    mode: ConcatenationMode,
+) -> Result<Fcs> {
     if files.is_empty() {
         return Err(anyhow!("Cannot concatenate empty list of files"));
     }
 
-    if files.len() == 1 {
-        // Just duplicate the single file
-        return duplicate_fcs_file(&files[0], &path).and_then(|_| {
-            Fcs::open(
-                path.as_ref()
-                    .to_str()
-                    .ok_or_else(|| anyhow!("Invalid path"))?,
-            )
-        });
+    if files.len() == 1 && files[0].get_parameter_names_from_dataframe().contains(&SOURCE_FILE_COLUMN.to_string()) {
+        return Err(anyhow!(
+            "File already has a {SOURCE_FILE_COLUMN} column; cannot record file origin"
+        ));
     }
 
-    // Verify all files have the same parameters
-    let first_params: Vec<String> = files[0].get_parameter_names_from_dataframe();
-
-    for (idx, fcs) in files.iter().enumerate().skip(1) {
-        let params: Vec<String> = fcs.get_parameter_names_from_dataframe();
-        if params != first_params {
-            return Err(anyhow!("File {} has different parameters than file 0", idx));
+    let reconciled_params = reconcile_parameter_names(&files, mode)?;
+
+    // Reindex each file's DataFrame onto the reconciled parameter set, filling any parameter a
+    // file lacks with null, then tag every row with which input file it came from.
+    let mut dfs: Vec<DataFrame> = Vec::with_capacity(files.len());
+    for (idx, fcs) in files.iter().enumerate() {
+        let df = &*fcs.data_frame;
+        let mut columns: Vec<Column> = Vec::with_capacity(reconciled_params.len());
+        for name in &reconciled_params {
+            if let Ok(existing) = df.column(name) {
+                columns.push(existing.clone());
+            } else {
+                let null_series = Series::new_null(name.as_str().into(), df.height());
+                columns.push(null_series.into());
+            }
         }
+        let source_name = fcs
+            .file_access
+            .path
+            .as_ref()
+            .and_then(|p| p.to_str())
+            .map_or_else(|| format!("file_{idx}"), str::to_string);
+        columns.push(Series::new(
+            SOURCE_FILE_COLUMN.into(),
+            vec![source_name; df.height()],
+        )
+        .into());
+        dfs.push(DataFrame::new(columns)?);
     }
 
     // Concatenate DataFrames using vstack
-    let dfs: Vec<DataFrame> = files.iter().map(|f| (*f.data_frame).clone()).collect();
     let concatenated_df = dfs
         .into_iter()
         .reduce(|acc, df| acc.vstack(&df).unwrap_or(acc))
@@ -283,6 +487,7 @@ pub fn concatenate_events(files: Vec<Fcs>, path: impl AsRef<Path>) -> Result<Fcs
     // Create new Fcs using first file as template
     let mut new_fcs = files[0].clone();
     new_fcs.data_frame = Arc::new(concatenated_df);
+    reconcile_parameter_map(&mut new_fcs, &files, &reconciled_params);
 
     // Update metadata
     let n_events_after = new_fcs.get_event_count_from_dataframe();
@@ -295,6 +500,15 @@ pub fn concatenate_events(files: Vec<Fcs>, path: impl AsRef<Path>) -> Result<Fcs
             .insert("$TOT".to_string(), Keyword::Int(int_kw));
     }
 
+    if let Some((begin, end)) = merge_acquisition_time_range(&files) {
+        new_fcs
+            .metadata
+            .insert_string_keyword("$BEGINDATETIME".to_string(), begin);
+        new_fcs
+            .metadata
+            .insert_string_keyword("$ENDDATETIME".to_string(), end);
+    }
+
     // Generate new GUID
     new_fcs.metadata.validate_guid();
 
@@ -309,6 +523,111 @@ pub fn concatenate_events(files: Vec<Fcs>, path: impl AsRef<Path>) -> Result<Fcs
     )
 }
 
+/// The name of the column [`concatenate_events`] adds to record which input file each event
+/// came from
+pub const SOURCE_FILE_COLUMN: &str = "source_file";
+
+/// How [`concatenate_events`] should reconcile parameter sets that differ between the files
+/// being combined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConcatenationMode {
+    /// Keep only parameters present in every file; drop the rest
+    StrictIntersection,
+    /// Keep every parameter seen in any file, filling it with null for files that don't have it
+    UnionFillNull,
+}
+
+/// Determine the reconciled, ordered list of parameter names `concatenate_events` should use,
+/// based on `mode`. Order follows first appearance across `files`.
+fn reconcile_parameter_names(files: &[Fcs], mode: ConcatenationMode) -> Result<Vec<String>> {
+    let per_file_params: Vec<Vec<String>> = files
+        .iter()
+        .map(Fcs::get_parameter_names_from_dataframe)
+        .collect();
+
+    match mode {
+        ConcatenationMode::UnionFillNull => {
+            let mut seen = std::collections::HashSet::new();
+            let mut union = Vec::new();
+            for params in &per_file_params {
+                for name in params {
+                    if seen.insert(name.clone()) {
+                        union.push(name.clone());
+                    }
+                }
+            }
+            Ok(union)
+        }
+        ConcatenationMode::StrictIntersection => {
+            let mut intersection = per_file_params[0].clone();
+            for params in &per_file_params[1..] {
+                intersection.retain(|name| params.contains(name));
+            }
+            if intersection.is_empty() {
+                return Err(anyhow!(
+                    "No parameter is common to every file being concatenated"
+                ));
+            }
+            Ok(intersection)
+        }
+    }
+}
+
+/// Rebuild `new_fcs`'s parameter map to match `reconciled_params`, reusing each parameter's
+/// definition from whichever input file first defines it.
+fn reconcile_parameter_map(new_fcs: &mut Fcs, files: &[Fcs], reconciled_params: &[String]) {
+    let mut parameters = crate::parameter::ParameterMap::default();
+    for (number, name) in reconciled_params.iter().enumerate() {
+        let Some(mut parameter) = files
+            .iter()
+            .find_map(|fcs| fcs.parameters.get(name.as_str()).cloned())
+        else {
+            continue;
+        };
+        parameter.parameter_number = number + 1;
+        parameters.insert(parameter.channel_name.clone(), parameter);
+    }
+    new_fcs.parameters = parameters;
+}
+
+/// Merge the acquisition time span across `files` into a single `($BEGINDATETIME,
+/// $ENDDATETIME)` pair, preferring each file's FCS 3.2 `$BEGINDATETIME`/`$ENDDATETIME` ISO-8601
+/// values and falling back to the legacy `$DATE`/`$BTIM`/`$ETIM` trio. Files with no parseable
+/// acquisition time are skipped; returns `None` if no file has one.
+fn merge_acquisition_time_range(files: &[Fcs]) -> Option<(String, String)> {
+    let mut begin_iso = Vec::new();
+    let mut end_iso = Vec::new();
+
+    for fcs in files {
+        let metadata = &fcs.metadata;
+        let begin = get_any_string_keyword_value(metadata, "$BEGINDATETIME").or_else(|| {
+            combine_fcs_datetime(
+                get_legacy_string_value(metadata, "$DATE"),
+                get_legacy_string_value(metadata, "$BTIM"),
+            )
+        });
+        let end = get_any_string_keyword_value(metadata, "$ENDDATETIME").or_else(|| {
+            combine_fcs_datetime(
+                get_legacy_string_value(metadata, "$DATE"),
+                get_legacy_string_value(metadata, "$ETIM"),
+            )
+        });
+        if let Some(b) = begin {
+            begin_iso.push(b);
+        }
+        if let Some(e) = end {
+            end_iso.push(e);
+        }
+    }
+
+    let earliest = begin_iso.into_iter().min();
+    let latest = end_iso.into_iter().max();
+    match (earliest, latest) {
+        (Some(begin), Some(end)) => Some((begin, end)),
+        _ => None,
+    }
+}
+
 /// Create a new FCS file by adding a column (parameter) to existing data
 ///
 /// This is useful for adding QC results (e.g., a boolean column indicating
@@ -417,8 +736,73 @@ pub fn add_column(
     )
 }
 
+/// Create a new FCS file by adding a categorical per-event column - gate membership, cluster
+/// ID, QC flag - as a classification column (see [`crate::classification`])
+///
+/// `categories` holds one non-negative integer code per event. The column is added under
+/// [`crate::classification::classification_channel_name`], and unlike [`add_column`]'s default
+/// continuous-data range, `$PnR` is set to `max(categories) + 1` so it actually bounds the
+/// category codes.
+///
+/// # Arguments
+/// * `fcs` - The FCS struct to modify
+/// * `path` - Output file path
+/// * `name` - The classification's name, without the `class:` prefix
+/// * `categories` - One non-negative category code per event
+///
+/// # Errors
+/// Returns an error if:
+/// - `categories` length doesn't match the number of events
+/// - The classification column already exists
+/// - The file cannot be written
+pub fn add_classification_column(
+    fcs: Fcs,
+    path: impl AsRef<Path>,
+    name: &str,
+    categories: &[u32],
+) -> Result<Fcs> {
+    let channel_name = crate::classification::classification_channel_name(name);
+    let n_categories = categories.iter().copied().max().unwrap_or(0);
+    let values: Vec<f32> = categories.iter().map(|&code| code as f32).collect();
+
+    let mut fcs = add_column(fcs, &path, &channel_name, values)?;
+
+    let param_num = fcs
+        .parameters
+        .get(channel_name.as_str())
+        .map(|parameter| parameter.parameter_number)
+        .ok_or_else(|| anyhow!("Classification column {channel_name} was not added"))?;
+
+    use crate::keyword::match_and_parse_keyword;
+    let pnr_keyword = match_and_parse_keyword(&format!("$P{param_num}R"), &(n_categories + 1).to_string());
+    if let crate::keyword::KeywordCreationResult::Int(int_kw) = pnr_keyword {
+        fcs.metadata
+            .keywords
+            .insert(format!("$P{param_num}R"), Keyword::Int(int_kw));
+    }
+
+    write_fcs_file(fcs.clone(), &path)?;
+
+    Fcs::open(
+        path.as_ref()
+            .to_str()
+            .ok_or_else(|| anyhow!("Invalid path"))?,
+    )
+}
+
 // ==================== Internal Helper Functions ====================
 
+/// Doubles every occurrence of `delimiter` in `value`, the FCS spec's escape for a literal
+/// delimiter character appearing inside a keyword or value.
+fn escape_delimiter(value: &str, delimiter: u8) -> String {
+    let delimiter_char = delimiter as char;
+    if value.contains(delimiter_char) {
+        value.replace(delimiter_char, &format!("{delimiter_char}{delimiter_char}"))
+    } else {
+        value.to_string()
+    }
+}
+
 fn estimate_text_segment_size(metadata: &Metadata, _n_events: usize, n_params: usize) -> usize {
     // Rough estimate: base size + keywords
     let base_size = 200; // Base keywords
@@ -437,12 +821,14 @@ fn serialize_metadata(
     let delimiter = metadata.delimiter as u8;
     let mut text_segment = Vec::new();
 
-    // Helper to add keyword-value pair
+    // Helper to add keyword-value pair. A literal delimiter character inside the key or value
+    // is escaped by doubling it, per the FCS spec, so the reader's tokenizer doesn't mistake it
+    // for a field boundary.
     let mut add_keyword = |key: &str, value: &str| {
         text_segment.push(delimiter);
-        text_segment.extend_from_slice(format!("${}", key).as_bytes());
+        text_segment.extend_from_slice(escape_delimiter(&format!("${key}"), delimiter).as_bytes());
         text_segment.push(delimiter);
-        text_segment.extend_from_slice(value.as_bytes());
+        text_segment.extend_from_slice(escape_delimiter(value, delimiter).as_bytes());
     };
 
     // Required keywords (order matters for FCS compatibility)
@@ -472,6 +858,11 @@ fn serialize_metadata(
         }
 
         let keyword = metadata.keywords.get(key).unwrap();
+        // Note: `Keyword`'s variants derive `Display` via `strum_macros` with no `#[strum(...)]`
+        // attributes, so `.to_string()` on them prints the bare variant name rather than the
+        // wrapped value - use the `*ableKeyword` accessor traits instead, which extract the
+        // real value for every variant (this is what preserves unknown/vendor keyword values,
+        // which always parse into `StringKeyword::Other`, byte-for-byte on write).
         let value_str = match keyword {
             Keyword::Int(int_kw) => match int_kw {
                 IntegerKeyword::TOT(_) => {
@@ -482,12 +873,27 @@ fn serialize_metadata(
                     // Use actual parameter count
                     n_params.to_string()
                 }
-                _ => int_kw.to_string(),
+                IntegerKeyword::PnDATATYPE(code) => code.to_string(),
+                _ => int_kw.get_usize().to_string(),
+            },
+            Keyword::String(str_kw) => str_kw.get_str().into_owned(),
+            Keyword::Float(float_kw) => float_kw.get_str().into_owned(),
+            Keyword::Byte(byte_kw) => byte_kw.get_str().into_owned(),
+            Keyword::Mixed(mixed_kw) => match mixed_kw {
+                MixedKeyword::SPILLOVER {
+                    n_parameters,
+                    parameter_names,
+                    matrix_values,
+                } => {
+                    let mut parts =
+                        Vec::with_capacity(1 + parameter_names.len() + matrix_values.len());
+                    parts.push(n_parameters.to_string());
+                    parts.extend(parameter_names.iter().cloned());
+                    parts.extend(matrix_values.iter().map(f32::to_string));
+                    parts.join(",")
+                }
+                _ => mixed_kw.get_str().into_owned(),
             },
-            Keyword::String(str_kw) => str_kw.to_string(),
-            Keyword::Float(float_kw) => float_kw.to_string(),
-            Keyword::Byte(byte_kw) => byte_kw.to_string(),
-            Keyword::Mixed(mixed_kw) => mixed_kw.to_string(),
         };
 
         // Remove $ prefix for serialization (it will be added back)
@@ -532,13 +938,24 @@ fn serialize_data(df: &DataFrame, metadata: &Metadata) -> Result<Vec<u8>> {
         column_data.push(slice);
     }
 
+    let is_ascii = matches!(metadata.get_data_type(), Ok(FcsDataType::A));
+
     // Write row by row
     for row_idx in 0..n_events {
         for col_data in &column_data {
             let value = col_data[row_idx];
 
-            // Write as float32 (4 bytes)
-            if is_little_endian {
+            if is_ascii {
+                // Fixed-width, right-aligned decimal text, space-padded/truncated to bytes_per_param
+                let text = value.to_string();
+                let field = if text.len() >= bytes_per_param {
+                    text[text.len() - bytes_per_param..].to_string()
+                } else {
+                    format!("{text:>bytes_per_param$}")
+                };
+                data.extend_from_slice(field.as_bytes());
+            } else if is_little_endian {
+                // Write as float32 (4 bytes)
                 data.write_f32::<LittleEndian>(value)?;
             } else {
                 use byteorder::BigEndian;
@@ -550,7 +967,10 @@ fn serialize_data(df: &DataFrame, metadata: &Metadata) -> Result<Vec<u8>> {
     Ok(data)
 }
 
-fn build_header(
+/// The largest value that fits in the HEADER's 8-ASCII-digit offset fields
+const MAX_HEADER_OFFSET: usize = 99_999_999;
+
+pub(crate) fn build_header(
     version: &Version,
     text_start: usize,
     text_end: usize,
@@ -569,17 +989,33 @@ fn build_header(
     // 4 spaces (bytes 6-9)
     header[6..10].fill(b' ');
 
-    // Text segment offsets (bytes 10-17 and 18-25) - right-aligned, space-padded
+    // Text segment offsets (bytes 10-17 and 18-25) - right-aligned, space-padded. Unlike the
+    // DATA segment, the spec gives the primary TEXT segment no keyword-based fallback, so a
+    // TEXT segment too large to fit here is a hard error rather than something we can degrade.
+    if text_end > MAX_HEADER_OFFSET {
+        return Err(anyhow!(
+            "TEXT segment ends at byte {text_end}, which exceeds the HEADER's 8-digit offset \
+             limit of {MAX_HEADER_OFFSET}"
+        ));
+    }
     let text_start_str = format!("{:>8}", text_start);
     header[10..18].copy_from_slice(text_start_str.as_bytes());
     let text_end_str = format!("{:>8}", text_end);
     header[18..26].copy_from_slice(text_end_str.as_bytes());
 
-    // Data segment offsets (bytes 26-33 and 34-41)
-    let data_start_str = format!("{:>8}", data_start);
-    header[26..34].copy_from_slice(data_start_str.as_bytes());
-    let data_end_str = format!("{:>8}", data_end);
-    header[34..42].copy_from_slice(data_end_str.as_bytes());
+    // Data segment offsets (bytes 26-33 and 34-41). Per spec, once the DATA segment would
+    // overflow the 8-digit field, both are written as 0 and readers fall back to the
+    // $BEGINDATA/$ENDDATA keywords (already written unconditionally in serialize_metadata,
+    // with no width limit) to locate it - see Fcs::resolve_data_segment.
+    if data_start > MAX_HEADER_OFFSET || data_end > MAX_HEADER_OFFSET {
+        header[26..34].copy_from_slice(b"       0");
+        header[34..42].copy_from_slice(b"       0");
+    } else {
+        let data_start_str = format!("{:>8}", data_start);
+        header[26..34].copy_from_slice(data_start_str.as_bytes());
+        let data_end_str = format!("{:>8}", data_end);
+        header[34..42].copy_from_slice(data_end_str.as_bytes());
+    }
 
     // Analysis segment offsets (bytes 42-49 and 50-57) - set to 0
     header[42..50].copy_from_slice(b"       0");
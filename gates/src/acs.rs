@@ -0,0 +1,261 @@
+//! ACS (Analytical Cytometry Standard) container reading and writing
+//!
+//! An ACS file is a zip archive bundling an FCS data file with GatingML gate definitions,
+//! a compensation matrix, and other supporting documents, indexed by a `index.xml` table
+//! of contents that maps each archive entry to a role (per the ISAC ACS specification).
+//! This covers the common single-sample case: one primary FCS file plus an optional
+//! [`crate::gatingml`] document and an optional compensation matrix.
+
+use crate::error::{GateError, Result};
+use quick_xml::{
+    Reader, Writer,
+    events::{BytesDecl, BytesEnd, BytesStart, Event},
+};
+use std::io::{Cursor, Read, Write};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+const TABLE_OF_CONTENTS_ENTRY: &str = "index.xml";
+const FCS_ROLE: &str = "file:fcs";
+const GATING_ML_ROLE: &str = "file:gating-ml";
+const COMPENSATION_ROLE: &str = "file:compensation";
+
+/// One entry in an ACS container's table of contents
+#[derive(Debug, Clone)]
+pub struct AcsEntry {
+    pub role: String,
+    pub path: String,
+    pub mime_type: String,
+}
+
+/// The decoded contents of an ACS container, see [`read_acs_container`]
+#[derive(Debug, Clone, Default)]
+pub struct AcsContainer {
+    /// Raw bytes of the primary FCS file, if the table of contents named one
+    pub fcs: Option<Vec<u8>>,
+    /// GatingML XML document, if the table of contents named one (see [`crate::gatingml`])
+    pub gating_ml: Option<String>,
+    /// Compensation matrix, serialized as CSV, if the table of contents named one
+    pub compensation: Option<String>,
+    /// Every entry named in the table of contents, including ones not extracted above
+    pub entries: Vec<AcsEntry>,
+}
+
+/// Writes an ACS container to `output_path`, bundling `fcs_bytes` as the primary FCS file
+/// plus optional GatingML and compensation documents
+/// # Errors
+/// Will return `Err` if `output_path` cannot be created, or the archive cannot be written
+pub fn write_acs_container(
+    output_path: &str,
+    fcs_bytes: &[u8],
+    gating_ml: Option<&str>,
+    compensation_csv: Option<&str>,
+) -> Result<()> {
+    let file = std::fs::File::create(output_path)?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let mut entries = vec![AcsEntry {
+        role: FCS_ROLE.to_string(),
+        path: "data.fcs".to_string(),
+        mime_type: "application/octet-stream".to_string(),
+    }];
+    zip.start_file("data.fcs", options)?;
+    zip.write_all(fcs_bytes)?;
+
+    if let Some(xml) = gating_ml {
+        entries.push(AcsEntry {
+            role: GATING_ML_ROLE.to_string(),
+            path: "gating.xml".to_string(),
+            mime_type: "application/xml".to_string(),
+        });
+        zip.start_file("gating.xml", options)?;
+        zip.write_all(xml.as_bytes())?;
+    }
+
+    if let Some(csv) = compensation_csv {
+        entries.push(AcsEntry {
+            role: COMPENSATION_ROLE.to_string(),
+            path: "compensation.csv".to_string(),
+            mime_type: "text/csv".to_string(),
+        });
+        zip.start_file("compensation.csv", options)?;
+        zip.write_all(csv.as_bytes())?;
+    }
+
+    let table_of_contents = write_table_of_contents(&entries)?;
+    zip.start_file(TABLE_OF_CONTENTS_ENTRY, options)?;
+    zip.write_all(table_of_contents.as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Reads an ACS container from `path`, extracting its FCS file, GatingML document, and
+/// compensation matrix per its table of contents
+/// # Errors
+/// Will return `Err` if `path` cannot be opened, is not a valid zip archive, is missing a
+/// table of contents, or that table of contents cannot be parsed
+pub fn read_acs_container(path: &str) -> Result<AcsContainer> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = ZipArchive::new(file)?;
+
+    let entries = {
+        let mut table_of_contents = archive.by_name(TABLE_OF_CONTENTS_ENTRY)?;
+        let mut xml = String::new();
+        table_of_contents.read_to_string(&mut xml)?;
+        parse_table_of_contents(&xml)?
+    };
+
+    let mut container = AcsContainer {
+        entries: entries.clone(),
+        ..Default::default()
+    };
+
+    for entry in &entries {
+        let mut zip_file = archive.by_name(&entry.path)?;
+        match entry.role.as_str() {
+            FCS_ROLE => {
+                let mut bytes = Vec::new();
+                zip_file.read_to_end(&mut bytes)?;
+                container.fcs = Some(bytes);
+            }
+            GATING_ML_ROLE => {
+                let mut xml = String::new();
+                zip_file.read_to_string(&mut xml)?;
+                container.gating_ml = Some(xml);
+            }
+            COMPENSATION_ROLE => {
+                let mut csv = String::new();
+                zip_file.read_to_string(&mut csv)?;
+                container.compensation = Some(csv);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(container)
+}
+
+fn write_table_of_contents(entries: &[AcsEntry]) -> Result<String> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    writer.write_event(Event::Start(BytesStart::new("TableOfContents")))?;
+    for entry in entries {
+        let mut file_elem = BytesStart::new("file");
+        file_elem.push_attribute(("role", entry.role.as_str()));
+        file_elem.push_attribute(("path", entry.path.as_str()));
+        file_elem.push_attribute(("mimeType", entry.mime_type.as_str()));
+        writer.write_event(Event::Empty(file_elem))?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("TableOfContents")))?;
+
+    let bytes = writer.into_inner().into_inner();
+    String::from_utf8(bytes).map_err(|e| GateError::Other {
+        message: format!("Failed to convert table of contents to a string: {}", e),
+        source: None,
+    })
+}
+
+fn parse_table_of_contents(xml: &str) -> Result<Vec<AcsEntry>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut entries = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) if e.name().as_ref() == b"file" => {
+                entries.push(AcsEntry {
+                    role: attribute_value(e, b"role").unwrap_or_default(),
+                    path: attribute_value(e, b"path").unwrap_or_default(),
+                    mime_type: attribute_value(e, b"mimeType").unwrap_or_default(),
+                });
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e.into()),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(entries)
+}
+
+fn attribute_value(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .find(|attr| attr.as_ref().is_ok_and(|attr| attr.key.as_ref() == name))
+        .and_then(|attr| String::from_utf8(attr.unwrap().value.into_owned()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_acs_path() -> std::path::PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("flow-gates-acs-test-{}-{}.acs", std::process::id(), id))
+    }
+
+    #[test]
+    fn test_round_trip_fcs_only() {
+        let path = temp_acs_path();
+        let fcs_bytes = b"not a real FCS file, just some bytes".to_vec();
+
+        write_acs_container(path.to_str().unwrap(), &fcs_bytes, None, None).unwrap();
+        let container = read_acs_container(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(container.fcs, Some(fcs_bytes));
+        assert_eq!(container.gating_ml, None);
+        assert_eq!(container.compensation, None);
+        assert_eq!(container.entries.len(), 1);
+        assert_eq!(container.entries[0].role, FCS_ROLE);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_with_gating_ml_and_compensation() {
+        let path = temp_acs_path();
+        let fcs_bytes = b"fcs bytes".to_vec();
+        let gating_ml = "<gating:Gating-ML></gating:Gating-ML>";
+        let compensation = "FSC-A,SSC-A\n1.0,0.05\n0.02,1.0\n";
+
+        write_acs_container(
+            path.to_str().unwrap(),
+            &fcs_bytes,
+            Some(gating_ml),
+            Some(compensation),
+        )
+        .unwrap();
+        let container = read_acs_container(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(container.fcs, Some(fcs_bytes));
+        assert_eq!(container.gating_ml.as_deref(), Some(gating_ml));
+        assert_eq!(container.compensation.as_deref(), Some(compensation));
+        assert_eq!(container.entries.len(), 3);
+        assert!(container.entries.iter().any(|e| e.role == GATING_ML_ROLE));
+        assert!(container.entries.iter().any(|e| e.role == COMPENSATION_ROLE));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_missing_table_of_contents_errors() {
+        let path = temp_acs_path();
+        let file = std::fs::File::create(&path).unwrap();
+        let mut zip = ZipWriter::new(file);
+        zip.start_file("data.fcs", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"stray bytes").unwrap();
+        zip.finish().unwrap();
+
+        let result = read_acs_container(path.to_str().unwrap());
+        assert!(result.is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -355,5 +355,15 @@ impl From<std::io::Error> for GateError {
     }
 }
 
+// Conversion from zip errors for ACS container reading/writing
+impl From<zip::result::ZipError> for GateError {
+    fn from(err: zip::result::ZipError) -> Self {
+        Self::Other {
+            message: format!("ACS zip error: {}", err),
+            source: Some(Box::new(err)),
+        }
+    }
+}
+
 // Type alias for Result using GateError
 pub type Result<T> = std::result::Result<T, GateError>;
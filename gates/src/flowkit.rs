@@ -0,0 +1,382 @@
+//! Import/export of the JSON gate representation used by FlowKit/FlowUtils
+//! (the Python flow cytometry gating libraries), so gating strategies can
+//! move between Python pipelines and this crate without going through
+//! GatingML XML.
+//!
+//! Only the gate shapes this crate models are supported: polygon, rectangle,
+//! and ellipse single gates, plus boolean combinations. FlowKit's
+//! quadrant gates, compensation/transformation references, and gate
+//! hierarchy metadata are out of scope here; see [`crate::gatingml`] if you
+//! need the full GatingML round-trip instead.
+
+use crate::error::{GateError, Result};
+use crate::types::{BooleanOperation, Gate, GateBuilder, GateGeometry};
+use serde::{Deserialize, Serialize};
+
+/// One dimension (channel) referenced by a FlowKit gate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FlowKitDimension {
+    parameter_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<f32>,
+}
+
+/// FlowKit/FlowUtils JSON gate representation.
+///
+/// Mirrors the shape FlowKit emits for `PolygonGate`, `RectangleGate`,
+/// `EllipsoidGate`, and `BooleanGate` closely enough to round-trip the
+/// subset of gate geometry `GateGeometry` models. Ellipses round-trip via
+/// `centroid_location`/`radius_x`/`radius_y`/`angle_degrees` rather than
+/// FlowKit's full covariance-matrix form, since `GateGeometry::Ellipse`
+/// doesn't carry a covariance matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FlowKitGate {
+    gate_name: String,
+    gate_type: String,
+    dimensions: Vec<FlowKitDimension>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vertices: Option<Vec<[f32; 2]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    centroid_location: Option<[f32; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    radius_x: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    radius_y: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    angle_degrees: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gate_operator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gate_refs: Option<Vec<String>>,
+}
+
+fn dimension(parameter_name: &str, min: Option<f32>, max: Option<f32>) -> FlowKitDimension {
+    FlowKitDimension {
+        parameter_name: parameter_name.to_string(),
+        min,
+        max,
+    }
+}
+
+fn gate_to_flowkit(gate: &Gate) -> Result<FlowKitGate> {
+    let x_param = gate.x_parameter_channel_name();
+    let y_param = gate.y_parameter_channel_name();
+
+    let flowkit_gate = match &gate.geometry {
+        GateGeometry::Polygon { nodes, .. } => {
+            let vertices = nodes
+                .iter()
+                .map(|node| {
+                    let x = node
+                        .get_coordinate(x_param)
+                        .ok_or_else(|| GateError::missing_parameter(x_param, "polygon vertex"))?;
+                    let y = node
+                        .get_coordinate(y_param)
+                        .ok_or_else(|| GateError::missing_parameter(y_param, "polygon vertex"))?;
+                    Ok([x, y])
+                })
+                .collect::<Result<Vec<[f32; 2]>>>()?;
+
+            FlowKitGate {
+                gate_name: gate.name.clone(),
+                gate_type: "PolygonGate".to_string(),
+                dimensions: vec![dimension(x_param, None, None), dimension(y_param, None, None)],
+                vertices: Some(vertices),
+                centroid_location: None,
+                radius_x: None,
+                radius_y: None,
+                angle_degrees: None,
+                gate_operator: None,
+                gate_refs: None,
+            }
+        }
+        GateGeometry::Rectangle { min, max } => {
+            let min_x = min
+                .get_coordinate(x_param)
+                .ok_or_else(|| GateError::missing_parameter(x_param, "rectangle min"))?;
+            let min_y = min
+                .get_coordinate(y_param)
+                .ok_or_else(|| GateError::missing_parameter(y_param, "rectangle min"))?;
+            let max_x = max
+                .get_coordinate(x_param)
+                .ok_or_else(|| GateError::missing_parameter(x_param, "rectangle max"))?;
+            let max_y = max
+                .get_coordinate(y_param)
+                .ok_or_else(|| GateError::missing_parameter(y_param, "rectangle max"))?;
+
+            FlowKitGate {
+                gate_name: gate.name.clone(),
+                gate_type: "RectangleGate".to_string(),
+                dimensions: vec![
+                    dimension(x_param, Some(min_x), Some(max_x)),
+                    dimension(y_param, Some(min_y), Some(max_y)),
+                ],
+                vertices: None,
+                centroid_location: None,
+                radius_x: None,
+                radius_y: None,
+                angle_degrees: None,
+                gate_operator: None,
+                gate_refs: None,
+            }
+        }
+        GateGeometry::Ellipse {
+            center,
+            radius_x,
+            radius_y,
+            angle,
+        } => {
+            let cx = center
+                .get_coordinate(x_param)
+                .ok_or_else(|| GateError::missing_parameter(x_param, "ellipse center"))?;
+            let cy = center
+                .get_coordinate(y_param)
+                .ok_or_else(|| GateError::missing_parameter(y_param, "ellipse center"))?;
+
+            FlowKitGate {
+                gate_name: gate.name.clone(),
+                gate_type: "EllipsoidGate".to_string(),
+                dimensions: vec![dimension(x_param, None, None), dimension(y_param, None, None)],
+                vertices: None,
+                centroid_location: Some([cx, cy]),
+                radius_x: Some(*radius_x),
+                radius_y: Some(*radius_y),
+                angle_degrees: Some(angle.to_degrees()),
+                gate_operator: None,
+                gate_refs: None,
+            }
+        }
+        GateGeometry::Boolean {
+            operation,
+            operands,
+        } => {
+            let gate_operator = match operation {
+                BooleanOperation::And => "and",
+                BooleanOperation::Or => "or",
+                BooleanOperation::Not => "not",
+            };
+
+            FlowKitGate {
+                gate_name: gate.name.clone(),
+                gate_type: "BooleanGate".to_string(),
+                dimensions: Vec::new(),
+                vertices: None,
+                centroid_location: None,
+                radius_x: None,
+                radius_y: None,
+                angle_degrees: None,
+                gate_operator: Some(gate_operator.to_string()),
+                gate_refs: Some(operands.iter().map(|id| id.to_string()).collect()),
+            }
+        }
+    };
+
+    Ok(flowkit_gate)
+}
+
+fn flowkit_to_gate(flowkit_gate: FlowKitGate) -> Result<Gate> {
+    let id = flowkit_gate.gate_name.clone();
+
+    match flowkit_gate.gate_type.as_str() {
+        "PolygonGate" => {
+            let [x_param, y_param] = dimension_names(&flowkit_gate)?;
+            let vertices = flowkit_gate.vertices.ok_or_else(|| {
+                GateError::invalid_geometry("PolygonGate is missing 'vertices'")
+            })?;
+            let coords: Vec<(f32, f32)> = vertices.into_iter().map(|[x, y]| (x, y)).collect();
+            GateBuilder::new(id, flowkit_gate.gate_name)
+                .polygon(coords, x_param, y_param)?
+                .build()
+        }
+        "RectangleGate" => {
+            let [x_dim, y_dim] = dimensions_pair(&flowkit_gate)?;
+            let min = (
+                x_dim.min.ok_or_else(|| {
+                    GateError::invalid_geometry("RectangleGate dimension is missing 'min'")
+                })?,
+                y_dim.min.ok_or_else(|| {
+                    GateError::invalid_geometry("RectangleGate dimension is missing 'min'")
+                })?,
+            );
+            let max = (
+                x_dim.max.ok_or_else(|| {
+                    GateError::invalid_geometry("RectangleGate dimension is missing 'max'")
+                })?,
+                y_dim.max.ok_or_else(|| {
+                    GateError::invalid_geometry("RectangleGate dimension is missing 'max'")
+                })?,
+            );
+            GateBuilder::new(id, flowkit_gate.gate_name.clone())
+                .rectangle(min, max, x_dim.parameter_name, y_dim.parameter_name)?
+                .build()
+        }
+        "EllipsoidGate" => {
+            let [x_param, y_param] = dimension_names(&flowkit_gate)?;
+            let center = flowkit_gate.centroid_location.ok_or_else(|| {
+                GateError::invalid_geometry("EllipsoidGate is missing 'centroid_location'")
+            })?;
+            let radius_x = flowkit_gate
+                .radius_x
+                .ok_or_else(|| GateError::invalid_geometry("EllipsoidGate is missing 'radius_x'"))?;
+            let radius_y = flowkit_gate
+                .radius_y
+                .ok_or_else(|| GateError::invalid_geometry("EllipsoidGate is missing 'radius_y'"))?;
+            let angle = flowkit_gate
+                .angle_degrees
+                .unwrap_or(0.0)
+                .to_radians();
+            GateBuilder::new(id, flowkit_gate.gate_name.clone())
+                .ellipse(
+                    (center[0], center[1]),
+                    radius_x,
+                    radius_y,
+                    angle,
+                    x_param,
+                    y_param,
+                )?
+                .build()
+        }
+        "BooleanGate" => {
+            let operator = flowkit_gate.gate_operator.as_deref().ok_or_else(|| {
+                GateError::invalid_geometry("BooleanGate is missing 'gate_operator'")
+            })?;
+            let operation = match operator {
+                "and" => BooleanOperation::And,
+                "or" => BooleanOperation::Or,
+                "not" => BooleanOperation::Not,
+                other => {
+                    return Err(GateError::invalid_geometry(format!(
+                        "Unsupported BooleanGate operator '{other}'"
+                    )));
+                }
+            };
+            let operands: Vec<std::sync::Arc<str>> = flowkit_gate
+                .gate_refs
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+            // Boolean gates don't have direct parameters - use placeholder,
+            // matching the GatingML boolean-gate import convention.
+            Ok(Gate::new(
+                id,
+                flowkit_gate.gate_name,
+                GateGeometry::Boolean {
+                    operation,
+                    operands,
+                },
+                "x",
+                "y",
+            ))
+        }
+        other => Err(GateError::invalid_geometry(format!(
+            "Unsupported FlowKit gate_type '{other}'"
+        ))),
+    }
+}
+
+fn dimension_names(flowkit_gate: &FlowKitGate) -> Result<[String; 2]> {
+    if flowkit_gate.dimensions.len() != 2 {
+        return Err(GateError::invalid_geometry(format!(
+            "Expected 2 dimensions for gate '{}', found {}",
+            flowkit_gate.gate_name,
+            flowkit_gate.dimensions.len()
+        )));
+    }
+    Ok([
+        flowkit_gate.dimensions[0].parameter_name.clone(),
+        flowkit_gate.dimensions[1].parameter_name.clone(),
+    ])
+}
+
+fn dimensions_pair(flowkit_gate: &FlowKitGate) -> Result<[FlowKitDimension; 2]> {
+    if flowkit_gate.dimensions.len() != 2 {
+        return Err(GateError::invalid_geometry(format!(
+            "Expected 2 dimensions for gate '{}', found {}",
+            flowkit_gate.gate_name,
+            flowkit_gate.dimensions.len()
+        )));
+    }
+    let mut dimensions = flowkit_gate.dimensions.clone().into_iter();
+    Ok([dimensions.next().unwrap(), dimensions.next().unwrap()])
+}
+
+/// Convert gates to FlowKit/FlowUtils-compatible JSON.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use flow_gates::{gates_to_flowkit_json, Gate};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let gates = vec![/* ... gates ... */];
+/// let json = gates_to_flowkit_json(&gates)?;
+/// std::fs::write("gates.json", json)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn gates_to_flowkit_json(gates: &[Gate]) -> Result<String> {
+    let flowkit_gates = gates
+        .iter()
+        .map(gate_to_flowkit)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(serde_json::to_string_pretty(&flowkit_gates)?)
+}
+
+/// Parse gates from FlowKit/FlowUtils-compatible JSON.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use flow_gates::flowkit_json_to_gates;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let json = std::fs::read_to_string("gates.json")?;
+/// let gates = flowkit_json_to_gates(&json)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn flowkit_json_to_gates(json: &str) -> Result<Vec<Gate>> {
+    let flowkit_gates: Vec<FlowKitGate> = serde_json::from_str(json)?;
+    flowkit_gates.into_iter().map(flowkit_to_gate).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_rectangle_gate() {
+        let gate = Gate::rectangle(
+            "rect",
+            "Rectangle",
+            (100.0, 200.0),
+            (500.0, 600.0),
+            "FSC-A",
+            "SSC-A",
+        )
+        .unwrap();
+
+        let json = gates_to_flowkit_json(std::slice::from_ref(&gate)).unwrap();
+        let parsed = flowkit_json_to_gates(&json).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].x_parameter_channel_name(), "FSC-A");
+        assert_eq!(parsed[0].bounding_box(), gate.bounding_box());
+    }
+
+    #[test]
+    fn round_trips_polygon_gate() {
+        let coords = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let gate = Gate::polygon("poly", "Polygon", coords, "FSC-A", "SSC-A").unwrap();
+
+        let json = gates_to_flowkit_json(std::slice::from_ref(&gate)).unwrap();
+        let parsed = flowkit_json_to_gates(&json).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert!(parsed[0].contains_point(5.0, 5.0).unwrap());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::error::{GateError, Result};
 use crate::types::{GateGeometry, GateNode};
+use geo::ConvexHull;
+use geo::CoordsIter;
+use geo::{Coord, LineString, MultiPoint, Point};
 use std::sync::Arc;
 
 /// Create a polygon geometry from raw coordinates
@@ -240,3 +243,198 @@ pub fn create_ellipse_geometry(
     })
 }
 
+/// Create a polygon geometry enclosing the region above a given 2D density threshold.
+///
+/// The input events are binned into a `grid_size` x `grid_size` histogram over their
+/// bounding box, bins are ranked by count, and the bins needed to accumulate
+/// `density_fraction` of all events (starting from the densest) are kept. The
+/// convex hull of the surviving bin centers becomes the gate polygon, so this is
+/// best used to quickly isolate the main population cloud (e.g. "densest 50% of
+/// events") rather than to trace irregular multi-modal boundaries precisely.
+///
+/// # Arguments
+/// * `raw_coords` - Event coordinates in raw data space
+/// * `x_param` - Channel name for the x-axis parameter
+/// * `y_param` - Channel name for the y-axis parameter
+/// * `grid_size` - Number of bins per axis used to build the density histogram
+/// * `density_fraction` - Fraction of events to enclose, in `(0.0, 1.0]`
+///
+/// # Errors
+/// Returns `GateError::InvalidGeometry` if:
+/// - Fewer than 3 coordinates are provided
+/// - `grid_size` is zero
+/// - `density_fraction` is not in `(0.0, 1.0]`
+/// - The resulting hull has fewer than 3 vertices
+pub fn create_density_threshold_geometry(
+    raw_coords: &[(f32, f32)],
+    x_param: &str,
+    y_param: &str,
+    grid_size: usize,
+    density_fraction: f32,
+) -> Result<GateGeometry> {
+    if raw_coords.len() < 3 {
+        return Err(GateError::invalid_geometry(format!(
+            "Density threshold gate requires at least 3 coordinates, got {}",
+            raw_coords.len()
+        )));
+    }
+    if grid_size == 0 {
+        return Err(GateError::invalid_geometry(
+            "Density threshold gate requires a non-zero grid_size",
+        ));
+    }
+    if !(density_fraction > 0.0 && density_fraction <= 1.0) {
+        return Err(GateError::invalid_geometry(format!(
+            "density_fraction must be in (0.0, 1.0], got {}",
+            density_fraction
+        )));
+    }
+
+    let mut min_x = f32::INFINITY;
+    let mut min_y = f32::INFINITY;
+    let mut max_x = f32::NEG_INFINITY;
+    let mut max_y = f32::NEG_INFINITY;
+    for &(x, y) in raw_coords {
+        if !x.is_finite() || !y.is_finite() {
+            return Err(GateError::invalid_coordinate(
+                "density_threshold_event",
+                if !x.is_finite() { x } else { y },
+            ));
+        }
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    if !(min_x < max_x) || !(min_y < max_y) {
+        return Err(GateError::invalid_geometry(
+            "Density threshold gate requires events spanning a non-degenerate range",
+        ));
+    }
+
+    let scale_x = grid_size as f32 / (max_x - min_x);
+    let scale_y = grid_size as f32 / (max_y - min_y);
+
+    let mut counts = vec![0u32; grid_size * grid_size];
+    for &(x, y) in raw_coords {
+        let bin_x = (((x - min_x) * scale_x) as usize).min(grid_size - 1);
+        let bin_y = (((y - min_y) * scale_y) as usize).min(grid_size - 1);
+        counts[bin_y * grid_size + bin_x] += 1;
+    }
+
+    let mut ranked: Vec<(usize, u32)> = counts
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let target = (raw_coords.len() as f32 * density_fraction).ceil() as u32;
+    let mut accumulated = 0u32;
+    let mut kept_bins = Vec::new();
+    for (bin_idx, count) in ranked {
+        if accumulated >= target {
+            break;
+        }
+        kept_bins.push(bin_idx);
+        accumulated += count;
+    }
+
+    let cell_width = (max_x - min_x) / grid_size as f32;
+    let cell_height = (max_y - min_y) / grid_size as f32;
+    let hull_points: Vec<Point<f32>> = kept_bins
+        .into_iter()
+        .map(|bin_idx| {
+            let bin_x = bin_idx % grid_size;
+            let bin_y = bin_idx / grid_size;
+            let cx = min_x + (bin_x as f32 + 0.5) * cell_width;
+            let cy = min_y + (bin_y as f32 + 0.5) * cell_height;
+            Point::new(cx, cy)
+        })
+        .collect();
+
+    let hull: LineString<f32> = MultiPoint::new(hull_points).convex_hull().exterior().clone();
+    let coords: Vec<(f32, f32)> = hull
+        .coords()
+        .map(|Coord { x, y }| (*x, *y))
+        // The hull's LineString repeats its first point as the last one to close the ring.
+        .take(hull.coords_count().saturating_sub(1))
+        .collect();
+
+    create_polygon_geometry(coords, x_param, y_param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_threshold_happy_path() {
+        // A cluster near the origin spread over a 2x2 block of bins (5 events each) plus two
+        // far-flung outliers - the densest 50% should collapse to a small polygon around the
+        // cluster's bin centers.
+        let mut raw_coords: Vec<(f32, f32)> = Vec::new();
+        for i in 0..20 {
+            let x = (i % 2) as f32 * 25.0;
+            let y = ((i / 2) % 2) as f32 * 25.0;
+            raw_coords.push((x, y));
+        }
+        raw_coords.push((100.0, 100.0));
+        raw_coords.push((-100.0, -100.0));
+
+        let geometry =
+            create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 10, 0.5).expect("geometry");
+
+        match geometry {
+            GateGeometry::Polygon { nodes, closed } => {
+                assert!(nodes.len() >= 3);
+                assert!(closed);
+            }
+            other => panic!("expected Polygon geometry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_density_threshold_too_few_coordinates() {
+        let raw_coords = vec![(0.0, 0.0), (1.0, 1.0)];
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 10, 0.5);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+    }
+
+    #[test]
+    fn test_density_threshold_zero_grid_size() {
+        let raw_coords = vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)];
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 0, 0.5);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+    }
+
+    #[test]
+    fn test_density_threshold_invalid_fraction() {
+        let raw_coords = vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)];
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 10, 0.0);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 10, 1.5);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+    }
+
+    #[test]
+    fn test_density_threshold_degenerate_range() {
+        // All events share the same x coordinate, so the bounding box is degenerate on that axis.
+        let raw_coords = vec![(5.0, 0.0), (5.0, 1.0), (5.0, 2.0)];
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 10, 0.5);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+    }
+
+    #[test]
+    fn test_density_threshold_sub_three_hull_vertices() {
+        // A single occupied bin can only ever produce one hull point, which then fails the
+        // downstream `create_polygon_geometry`'s "at least 3 coordinates" check.
+        let raw_coords = vec![(0.0, 0.0), (0.01, 0.0), (0.0, 0.01)];
+        let result = create_density_threshold_geometry(&raw_coords, "FSC-A", "SSC-A", 1, 1.0);
+        assert!(matches!(result, Err(GateError::InvalidGeometry { .. })));
+    }
+}
+
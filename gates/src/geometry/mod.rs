@@ -7,6 +7,7 @@
 pub mod construction;
 
 pub use construction::{
-    create_ellipse_geometry, create_polygon_geometry, create_rectangle_geometry,
+    create_density_threshold_geometry, create_ellipse_geometry, create_polygon_geometry,
+    create_rectangle_geometry,
 };
 
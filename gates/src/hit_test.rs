@@ -0,0 +1,126 @@
+//! Hit-testing utilities for interactive gate editors.
+//!
+//! These helpers classify a screen/data point against a gate's geometry so
+//! UIs can implement vertex dragging, edge insertion, and body dragging
+//! without re-deriving the underlying geometry math themselves.
+
+use crate::polygon::point_in_polygon;
+
+/// The result of testing a point against a gate's geometry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateHit {
+    /// The point is within `tolerance` of the vertex at `index`.
+    Vertex {
+        /// Index of the vertex (node) that was hit.
+        index: usize,
+    },
+    /// The point is within `tolerance` of the edge/segment starting at `segment_index`.
+    Edge {
+        /// Index of the segment; the edge runs from node `segment_index` to the next node.
+        segment_index: usize,
+    },
+    /// The point is inside the gate but not near a vertex or edge.
+    Inside,
+    /// The point is outside the gate.
+    Outside,
+}
+
+/// Shortest distance from a point to a line segment.
+fn distance_to_segment(px: f32, py: f32, a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (ax, ay) = a;
+    let (bx, by) = b;
+    let (dx, dy) = (bx - ax, by - ay);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq == 0.0 {
+        return ((px - ax).powi(2) + (py - ay).powi(2)).sqrt();
+    }
+    let t = (((px - ax) * dx + (py - ay) * dy) / len_sq).clamp(0.0, 1.0);
+    let (cx, cy) = (ax + t * dx, ay + t * dy);
+    ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+}
+
+/// Hit-test a point against a polygon defined by `coords`.
+pub fn hit_test_polygon(
+    x: f32,
+    y: f32,
+    coords: &[(f32, f32)],
+    closed: bool,
+    tolerance: f32,
+) -> GateHit {
+    for (index, &(vx, vy)) in coords.iter().enumerate() {
+        if ((x - vx).powi(2) + (y - vy).powi(2)).sqrt() <= tolerance {
+            return GateHit::Vertex { index };
+        }
+    }
+
+    if coords.len() >= 2 {
+        let n = coords.len();
+        let segment_count = if closed { n } else { n - 1 };
+        for segment_index in 0..segment_count {
+            let a = coords[segment_index];
+            let b = coords[(segment_index + 1) % n];
+            if distance_to_segment(x, y, a, b) <= tolerance {
+                return GateHit::Edge { segment_index };
+            }
+        }
+    }
+
+    if closed && coords.len() >= 3 && point_in_polygon(x, y, coords) {
+        GateHit::Inside
+    } else {
+        GateHit::Outside
+    }
+}
+
+/// Hit-test a point against an axis-aligned rectangle from `min` to `max`.
+pub fn hit_test_rectangle(
+    x: f32,
+    y: f32,
+    min: (f32, f32),
+    max: (f32, f32),
+    tolerance: f32,
+) -> GateHit {
+    let corners = [min, (max.0, min.1), max, (min.0, max.1)];
+    hit_test_polygon(x, y, &corners, true, tolerance)
+}
+
+/// Hit-test a point against a (possibly rotated) ellipse.
+///
+/// Ellipses only expose a single vertex handle (index `0`, the center);
+/// the boundary is reported as `Edge { segment_index: 0 }` when the point
+/// is within `tolerance` of the ellipse's edge.
+pub fn hit_test_ellipse(
+    x: f32,
+    y: f32,
+    center: (f32, f32),
+    radius_x: f32,
+    radius_y: f32,
+    angle: f32,
+    tolerance: f32,
+) -> GateHit {
+    let (cx, cy) = center;
+    if ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() <= tolerance {
+        return GateHit::Vertex { index: 0 };
+    }
+
+    let cos_a = angle.cos();
+    let sin_a = angle.sin();
+    let dx = x - cx;
+    let dy = y - cy;
+    let rotated_x = dx * cos_a + dy * sin_a;
+    let rotated_y = -dx * sin_a + dy * cos_a;
+
+    let normalized = (rotated_x / radius_x).powi(2) + (rotated_y / radius_y).powi(2);
+
+    // Approximate the boundary distance by scaling the normalized radial
+    // offset by the average radius; exact for a circle, close enough for
+    // hit-testing on moderately eccentric ellipses.
+    let boundary_distance = (normalized.sqrt() - 1.0).abs() * ((radius_x + radius_y) / 2.0);
+    if boundary_distance <= tolerance {
+        GateHit::Edge { segment_index: 0 }
+    } else if normalized <= 1.0 {
+        GateHit::Inside
+    } else {
+        GateHit::Outside
+    }
+}
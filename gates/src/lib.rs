@@ -16,7 +16,11 @@
 //! - **Event Filtering**: Efficient spatial indexing and filtering of cytometry events
 //! - **Statistics**: Comprehensive statistics for gated populations
 //! - **GatingML Support**: Import/export gates in GatingML 2.0 XML format
+//! - **FlowKit JSON Support**: Import/export gates in FlowKit/FlowUtils JSON format
 //! - **Thread-Safe Storage**: Concurrent gate management
+//! - **Hit-Testing**: Vertex/edge/inside/outside classification for interactive editors
+//! - **Spillover Warnings**: Flag gates at risk from spectral spreading error
+//! - **Content Hashing**: [`Gate::fingerprint`] for cache keys and duplicate detection
 //!
 //! ## Quick Start
 //!
@@ -86,17 +90,21 @@
 
 use std::sync::Arc;
 
+pub mod acs;
 pub mod batch_filtering;
 pub mod ellipse;
 pub mod error;
 pub mod filtering;
+pub mod flowkit;
 pub mod gatingml;
 pub mod geometry;
 pub mod hierarchy;
+pub mod hit_test;
 pub mod linking;
 pub mod polygon;
 pub mod rectangle;
 pub mod scope;
+pub mod spillover;
 pub mod statistics;
 pub mod traits;
 pub mod traits_tests;
@@ -118,13 +126,19 @@ pub use filtering::{
 };
 
 /// Geometry construction helpers
-pub use geometry::{create_ellipse_geometry, create_polygon_geometry, create_rectangle_geometry};
+pub use geometry::{
+    create_density_threshold_geometry, create_ellipse_geometry, create_polygon_geometry,
+    create_rectangle_geometry,
+};
 
 /// Gate hierarchy management
 pub use hierarchy::GateHierarchy;
 
+/// Hit-testing utilities for interactive gate editors
+pub use hit_test::GateHit;
+
 /// Gate linking system
-pub use linking::GateLinks;
+pub use linking::{ControlKind, ControlLink, GateLinks, recommend_threshold_from_control};
 
 /// Gate querying and filtering helpers
 pub use scope::{
@@ -132,12 +146,21 @@ pub use scope::{
     filter_hierarchy_by_parameters,
 };
 
+/// Spillover-aware gate warnings for spectral data
+pub use spillover::{DEFAULT_SPILLOVER_THRESHOLD, SpilloverWarning, analyze_spillover_risk};
+
 /// Statistics for gated populations
 pub use statistics::GateStatistics;
 
 /// GatingML import/export
 pub use gatingml::{gates_to_gatingml, gatingml_to_gates};
 
+/// ACS (Analytical Cytometry Standard) container import/export
+pub use acs::{AcsContainer, AcsEntry, read_acs_container, write_acs_container};
+
+/// FlowKit/FlowUtils JSON gate import/export
+pub use flowkit::{flowkit_json_to_gates, gates_to_flowkit_json};
+
 /// Core gate types and structures
 pub use types::{BooleanOperation, Gate, GateBuilder, GateGeometry, GateMode, GateNode};
 
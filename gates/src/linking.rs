@@ -22,9 +22,35 @@
 //! assert_eq!(referencing_gates.len(), 2);
 //! ```
 
+use crate::error::{GateError, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// The kind of control file a gate can be linked to.
+///
+/// Both control types are used to estimate where a positive/negative threshold
+/// should sit, but they represent different experimental controls:
+/// - **Fmo**: Fluorescence-minus-one control (all reagents except the one being gated)
+/// - **Unstained**: Fully unstained control (no reagents)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlKind {
+    /// Fluorescence-minus-one control
+    Fmo,
+    /// Unstained control
+    Unstained,
+}
+
+/// A link from a gate to the control file used to position its threshold.
+#[derive(Debug, Clone)]
+pub struct ControlLink {
+    /// GUID of the control file (FMO or unstained)
+    pub control_file_guid: Arc<str>,
+    /// The kind of control this file represents
+    pub kind: ControlKind,
+    /// Percentile of the control distribution used to recommend a threshold (e.g. 99.0)
+    pub percentile: f64,
+}
+
 /// Manages gate linking relationships.
 ///
 /// `GateLinks` tracks which gates reference (link to) other gates. This is useful
@@ -37,6 +63,8 @@ use std::sync::Arc;
 pub struct GateLinks {
     /// Maps target gate ID to list of gate IDs that reference/link to it
     links: HashMap<Arc<str>, Vec<Arc<str>>>,
+    /// Maps gate ID to the control file it's linked to, for threshold recommendation
+    control_links: HashMap<Arc<str>, ControlLink>,
 }
 
 impl GateLinks {
@@ -206,6 +234,96 @@ impl GateLinks {
     /// ```
     pub fn clear(&mut self) {
         self.links.clear();
+        self.control_links.clear();
+    }
+
+    /// Link a gate to a control file (FMO or unstained) used for threshold recommendation.
+    ///
+    /// This connects gating to the control-driven workflow labs actually use: once linked,
+    /// [`recommend_threshold_from_control`] can compute where the gate's threshold should
+    /// sit based on the control's staining distribution.
+    ///
+    /// # Arguments
+    /// * `gate_id` - The gate whose threshold is informed by the control
+    /// * `control_file_guid` - GUID of the control file
+    /// * `kind` - Whether the control is FMO or unstained
+    /// * `percentile` - Percentile of the control distribution to use (e.g. 99.0)
+    ///
+    /// # Example
+    /// ```rust
+    /// use flow_gates::linking::{ControlKind, GateLinks};
+    ///
+    /// let mut links = GateLinks::new();
+    /// links.set_control_link("cd4-gate", "fmo-file-guid", ControlKind::Fmo, 99.0);
+    /// assert!(links.get_control_link("cd4-gate").is_some());
+    /// ```
+    pub fn set_control_link(
+        &mut self,
+        gate_id: impl Into<Arc<str>>,
+        control_file_guid: impl Into<Arc<str>>,
+        kind: ControlKind,
+        percentile: f64,
+    ) {
+        self.control_links.insert(
+            gate_id.into(),
+            ControlLink {
+                control_file_guid: control_file_guid.into(),
+                kind,
+                percentile,
+            },
+        );
+    }
+
+    /// Get the control link for a gate, if one has been set
+    pub fn get_control_link(&self, gate_id: &str) -> Option<&ControlLink> {
+        self.control_links.get(gate_id)
+    }
+
+    /// Remove the control link for a gate. No-op if none was set.
+    pub fn remove_control_link(&mut self, gate_id: &str) {
+        self.control_links.remove(gate_id);
+    }
+}
+
+/// Compute the recommended threshold position from a control's distribution.
+///
+/// Labs commonly set a positive/negative threshold at a high percentile (e.g. 99.x%)
+/// of an FMO or unstained control, so that only events brighter than nearly all
+/// control events are called positive. This computes that percentile value from raw
+/// control channel values; callers can offer to update the gate's geometry with the
+/// result.
+///
+/// # Arguments
+/// * `control_values` - Raw channel values from the control file's events
+/// * `percentile` - Percentile to use, in `[0.0, 100.0]` (e.g. `99.0`)
+///
+/// # Errors
+/// Returns `GateError::InvalidGeometry` if `control_values` is empty or `percentile`
+/// is out of range.
+pub fn recommend_threshold_from_control(control_values: &[f64], percentile: f64) -> Result<f64> {
+    if control_values.is_empty() {
+        return Err(GateError::invalid_geometry(
+            "Cannot recommend a threshold from an empty control",
+        ));
+    }
+    if !(0.0..=100.0).contains(&percentile) {
+        return Err(GateError::invalid_geometry(format!(
+            "percentile must be in [0.0, 100.0], got {}",
+            percentile
+        )));
+    }
+
+    let mut sorted: Vec<f64> = control_values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rank = (percentile / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    if lower == upper {
+        Ok(sorted[lower])
+    } else {
+        let frac = rank - lower as f64;
+        Ok(sorted[lower] + (sorted[upper] - sorted[lower]) * frac)
     }
 }
 
@@ -264,6 +382,30 @@ mod tests {
         assert_eq!(links.get_link_count("target"), 2);
     }
 
+    #[test]
+    fn test_control_link() {
+        let mut links = GateLinks::new();
+        assert!(links.get_control_link("cd4-gate").is_none());
+
+        links.set_control_link("cd4-gate", "fmo-guid", ControlKind::Fmo, 99.0);
+        let link = links.get_control_link("cd4-gate").unwrap();
+        assert_eq!(link.control_file_guid.as_ref(), "fmo-guid");
+        assert_eq!(link.kind, ControlKind::Fmo);
+
+        links.remove_control_link("cd4-gate");
+        assert!(links.get_control_link("cd4-gate").is_none());
+    }
+
+    #[test]
+    fn test_recommend_threshold_from_control() {
+        let values: Vec<f64> = (0..=100).map(|v| v as f64).collect();
+        let threshold = recommend_threshold_from_control(&values, 99.0).unwrap();
+        assert!((threshold - 99.0).abs() < 1e-9);
+
+        assert!(recommend_threshold_from_control(&[], 99.0).is_err());
+        assert!(recommend_threshold_from_control(&values, 150.0).is_err());
+    }
+
     #[test]
     fn test_clear() {
         let mut links = GateLinks::new();
@@ -0,0 +1,123 @@
+//! Spillover-aware analysis for gates drawn on spectral flow cytometry data.
+//!
+//! Spectral spreading error means a bright signal in one channel can bleed
+//! into others in proportion to the file's spillover matrix. This module
+//! provides a basic heuristic to flag gates whose boundary sits in a channel
+//! where that spreading is large enough to plausibly move events across it.
+
+use crate::types::Gate;
+use anyhow::{Context, Result};
+use flow_fcs::Fcs;
+use serde::{Deserialize, Serialize};
+
+/// Default spillover fraction above which a source channel is treated as a spreading risk.
+pub const DEFAULT_SPILLOVER_THRESHOLD: f32 = 0.05;
+
+/// A warning that a gate boundary may be affected by spectral spreading error
+/// from another channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpilloverWarning {
+    /// The gate's own channel affected by spreading error
+    pub affected_channel: String,
+    /// The channel whose bright events are spreading into `affected_channel`
+    pub source_channel: String,
+    /// Fraction of `source_channel` signal that spills into `affected_channel` (0.0-1.0)
+    pub spillover_fraction: f32,
+    /// Estimated spread in `affected_channel`, in raw units, based on the 95th
+    /// percentile of `source_channel`'s signal
+    pub estimated_spread: f32,
+    /// Human-readable explanation suitable for display in a UI
+    pub message: String,
+}
+
+/// Analyze a gate for spectral-spreading risk using the file's spillover matrix.
+///
+/// For each of the gate's x/y parameters, and each other channel in the panel
+/// (including the gate's other axis), this checks how much of that channel's
+/// signal spills into the gate's parameter (per the
+/// spillover matrix), estimates how far that spreading could push events
+/// using the 95th percentile of the source channel's raw signal, and warns
+/// when the estimated spread is at least 10% of the gate's own extent in
+/// that dimension.
+///
+/// Returns an empty vec if the file has no spillover matrix, or if neither of
+/// the gate's parameters are part of the spillover panel.
+///
+/// # Errors
+/// Returns an error if the spillover matrix is malformed.
+pub fn analyze_spillover_risk(
+    gate: &Gate,
+    fcs: &Fcs,
+    spillover_threshold: f32,
+) -> Result<Vec<SpilloverWarning>> {
+    let Some((matrix, channel_names)) = fcs
+        .get_spillover_matrix()
+        .context("Failed to read spillover matrix")?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let bounding_box = gate.bounding_box();
+    let mut warnings = Vec::new();
+
+    for (axis_is_x, channel) in [
+        (true, gate.x_parameter_channel_name()),
+        (false, gate.y_parameter_channel_name()),
+    ] {
+        let Some(target_idx) = channel_names.iter().position(|name| name == channel) else {
+            continue;
+        };
+        let axis_extent = bounding_box.map(|(min_x, min_y, max_x, max_y)| {
+            if axis_is_x { max_x - min_x } else { max_y - min_y }
+        });
+
+        for (source_idx, source_channel) in channel_names.iter().enumerate() {
+            if source_idx == target_idx {
+                continue;
+            }
+            let fraction = matrix[[target_idx, source_idx]];
+            if fraction < spillover_threshold {
+                continue;
+            }
+
+            let Ok(source_events) = fcs.get_parameter_events_slice(source_channel) else {
+                continue;
+            };
+            if source_events.is_empty() {
+                continue;
+            }
+            let estimated_spread = fraction * percentile(source_events, 0.95);
+
+            let significant = match axis_extent {
+                Some(extent) if extent > 0.0 => estimated_spread >= extent * 0.1,
+                _ => true,
+            };
+            if !significant {
+                continue;
+            }
+
+            warnings.push(SpilloverWarning {
+                affected_channel: channel.to_string(),
+                source_channel: source_channel.clone(),
+                spillover_fraction: fraction,
+                estimated_spread,
+                message: format!(
+                    "Gate boundary on '{channel}' may be affected by spreading error from \
+                     '{source_channel}' ({:.1}% spillover, estimated spread ~{:.0} raw units)",
+                    fraction * 100.0,
+                    estimated_spread
+                ),
+            });
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Nearest-rank percentile of an unsorted slice, used for the spread estimate.
+fn percentile(values: &[f32], p: f32) -> f32 {
+    let mut sorted: Vec<f32> = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let idx = (((sorted.len() - 1) as f32) * p).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
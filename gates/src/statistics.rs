@@ -44,6 +44,12 @@ pub struct GateStatistics {
     pub x_stats: ParameterStatistics,
     /// Statistics for the Y parameter
     pub y_stats: ParameterStatistics,
+    /// Sum of per-event weights, if weighted statistics were requested.
+    ///
+    /// This is the abundance-corrected event count: `event_count` reflects the
+    /// number of rows, while `weighted_event_count` reflects how many events
+    /// those rows represent (e.g. after density-dependent downsampling).
+    pub weighted_event_count: Option<f64>,
 }
 
 /// Statistics for a single parameter (channel) within a gate.
@@ -73,6 +79,12 @@ pub struct ParameterStatistics {
     pub q3: f64,
     /// Coefficient of variation (CV) = std_dev / mean
     pub cv: f64,
+    /// Weighted mean, if per-event weights were supplied
+    pub weighted_mean: Option<f64>,
+    /// Weighted median, if per-event weights were supplied
+    pub weighted_median: Option<f64>,
+    /// Weighted coefficient of variation, if per-event weights were supplied
+    pub weighted_cv: Option<f64>,
 }
 
 impl GateStatistics {
@@ -121,6 +133,68 @@ impl GateStatistics {
             centroid,
             x_stats,
             y_stats,
+            weighted_event_count: None,
+        })
+    }
+
+    /// Calculate statistics for a gate applied to FCS data, weighting each event.
+    ///
+    /// Use this instead of [`GateStatistics::calculate`] when events carry per-event
+    /// weights (e.g. abundance correction after density-dependent downsampling), so
+    /// that counts and central-tendency measures remain representative of the original
+    /// population rather than the (possibly downsampled) row count.
+    ///
+    /// # Arguments
+    /// * `weights` - Per-event weights, indexed the same as `fcs`'s event rows. Must be
+    ///   at least as long as the highest filtered event index.
+    pub fn calculate_weighted(fcs: &Fcs, gate: &Gate, weights: &[f64]) -> Result<Self> {
+        let indices = filter_events_by_gate(fcs, gate, None)?;
+        let event_count = indices.len();
+
+        if event_count == 0 {
+            let mut stats = Self::empty(gate);
+            stats.weighted_event_count = Some(0.0);
+            return Ok(stats);
+        }
+
+        let x_param = gate.x_parameter_channel_name();
+        let y_param = gate.y_parameter_channel_name();
+
+        let x_slice = fcs
+            .get_parameter_events_slice(x_param)
+            .with_context(|| format!("Failed to get parameter data for {}", x_param))?;
+        let y_slice = fcs
+            .get_parameter_events_slice(y_param)
+            .with_context(|| format!("Failed to get parameter data for {}", y_param))?;
+
+        let x_values: Vec<f64> = indices.iter().map(|&i| x_slice[i] as f64).collect();
+        let y_values: Vec<f64> = indices.iter().map(|&i| y_slice[i] as f64).collect();
+        let event_weights: Vec<f64> = indices
+            .iter()
+            .map(|&i| weights.get(i).copied().unwrap_or(1.0))
+            .collect();
+
+        let weight_sum: f64 = event_weights.iter().sum();
+        let total_events = fcs.data_frame.height();
+        let percentage = (event_count as f64 / total_events as f64) * 100.0;
+
+        let centroid = (
+            weighted_mean(&x_values, &event_weights),
+            weighted_mean(&y_values, &event_weights),
+        );
+
+        let mut x_stats = ParameterStatistics::calculate(x_param, &x_values)?;
+        let mut y_stats = ParameterStatistics::calculate(y_param, &y_values)?;
+        x_stats.apply_weights(&x_values, &event_weights);
+        y_stats.apply_weights(&y_values, &event_weights);
+
+        Ok(Self {
+            event_count,
+            percentage,
+            centroid,
+            x_stats,
+            y_stats,
+            weighted_event_count: Some(weight_sum),
         })
     }
 
@@ -132,6 +206,7 @@ impl GateStatistics {
             centroid: (0.0, 0.0),
             x_stats: ParameterStatistics::empty(gate.x_parameter_channel_name()),
             y_stats: ParameterStatistics::empty(gate.y_parameter_channel_name()),
+            weighted_event_count: None,
         }
     }
 }
@@ -198,9 +273,44 @@ impl ParameterStatistics {
             q1,
             q3,
             cv,
+            weighted_mean: None,
+            weighted_median: None,
+            weighted_cv: None,
         })
     }
 
+    /// Populate the weighted fields from per-event weights.
+    ///
+    /// `values` and `weights` must be the same length; weights are treated as
+    /// non-negative multiplicities (e.g. inverse downsampling probability).
+    fn apply_weights(&mut self, values: &[f64], weights: &[f64]) {
+        if values.is_empty() || weights.is_empty() {
+            return;
+        }
+
+        let mean = weighted_mean(values, weights);
+        let weight_sum: f64 = weights.iter().sum();
+        let variance = if weight_sum > 0.0 {
+            values
+                .iter()
+                .zip(weights.iter())
+                .map(|(&v, &w)| w * (v - mean).powi(2))
+                .sum::<f64>()
+                / weight_sum
+        } else {
+            f64::NAN
+        };
+        let std_dev = variance.sqrt();
+
+        self.weighted_mean = Some(mean);
+        self.weighted_cv = Some(if mean != 0.0 {
+            (std_dev / mean.abs()) * 100.0
+        } else {
+            f64::NAN
+        });
+        self.weighted_median = Some(weighted_percentile(values, weights, 50.0));
+    }
+
     /// Create empty statistics
     fn empty(parameter: &str) -> Self {
         Self {
@@ -214,10 +324,56 @@ impl ParameterStatistics {
             q1: f64::NAN,
             q3: f64::NAN,
             cv: f64::NAN,
+            weighted_mean: None,
+            weighted_median: None,
+            weighted_cv: None,
         }
     }
 }
 
+/// Weighted arithmetic mean of `values`, weighted by `weights` (same length).
+fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
+    let weight_sum: f64 = weights.iter().sum();
+    if weight_sum <= 0.0 {
+        return f64::NAN;
+    }
+    values
+        .iter()
+        .zip(weights.iter())
+        .map(|(&v, &w)| v * w)
+        .sum::<f64>()
+        / weight_sum
+}
+
+/// Weighted percentile: the first value (in sorted order) at which the cumulative
+/// weight reaches `p`% of the total weight; no interpolation between values.
+///
+/// `values` and `weights` need not be pre-sorted; they are sorted together by value.
+fn weighted_percentile(values: &[f64], weights: &[f64], p: f64) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+
+    let mut pairs: Vec<(f64, f64)> = values.iter().copied().zip(weights.iter().copied()).collect();
+    pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let total_weight: f64 = pairs.iter().map(|(_, w)| w).sum();
+    if total_weight <= 0.0 {
+        return f64::NAN;
+    }
+
+    let target = (p / 100.0) * total_weight;
+    let mut cumulative = 0.0;
+    for (value, weight) in &pairs {
+        cumulative += weight;
+        if cumulative >= target {
+            return *value;
+        }
+    }
+
+    pairs.last().map(|(v, _)| *v).unwrap_or(f64::NAN)
+}
+
 /// Calculate percentile from sorted data
 ///
 /// Uses linear interpolation between ranks
@@ -289,6 +445,27 @@ mod tests {
         assert!((stats.cv - 47.14).abs() < 1.0);
     }
 
+    #[test]
+    fn test_weighted_mean_and_median() {
+        let values = vec![1.0, 2.0, 3.0];
+        let weights = vec![1.0, 1.0, 8.0];
+
+        assert!((weighted_mean(&values, &weights) - 2.7).abs() < 1e-9);
+        assert_eq!(weighted_percentile(&values, &weights, 50.0), 3.0);
+    }
+
+    #[test]
+    fn test_apply_weights() {
+        let values = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let weights = vec![1.0; 5];
+        let mut stats = ParameterStatistics::calculate("test", &values).expect("stats");
+        stats.apply_weights(&values, &weights);
+
+        // Uniform weights should reproduce the unweighted mean/median
+        assert_eq!(stats.weighted_mean, Some(stats.mean));
+        assert_eq!(stats.weighted_median, Some(stats.median));
+    }
+
     #[test]
     fn test_empty_statistics() {
         let stats = ParameterStatistics::calculate("test", &[]).expect("stats");
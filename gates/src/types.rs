@@ -2,6 +2,7 @@ use crate::error::{GateError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 /// A node in a gate, representing a control point with coordinates in raw data space.
 ///
@@ -79,7 +80,7 @@ impl GateNode {
 /// let or_op = BooleanOperation::Or;
 /// let not_op = BooleanOperation::Not;
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum BooleanOperation {
     /// AND operation - events must pass all operand gates
@@ -375,6 +376,76 @@ impl GateGeometry {
         }
     }
 
+    /// Classify a point (in raw coordinates) against this geometry for
+    /// interactive editing: on a vertex, on an edge, inside, or outside.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Point to test, in raw data coordinates
+    /// * `x_param`, `y_param` - Channel names for the x/y axes
+    /// * `tolerance` - Distance (in raw data units) within which a point counts as "on" a vertex or edge
+    pub fn hit_test(
+        &self,
+        x: f32,
+        y: f32,
+        x_param: &str,
+        y_param: &str,
+        tolerance: f32,
+    ) -> Result<crate::hit_test::GateHit> {
+        use crate::hit_test::{hit_test_ellipse, hit_test_polygon, hit_test_rectangle};
+
+        match self {
+            GateGeometry::Polygon { nodes, closed } => {
+                let coords: Vec<(f32, f32)> = nodes
+                    .iter()
+                    .filter_map(|node| {
+                        Some((node.get_coordinate(x_param)?, node.get_coordinate(y_param)?))
+                    })
+                    .collect();
+                Ok(hit_test_polygon(x, y, &coords, *closed, tolerance))
+            }
+            GateGeometry::Rectangle { min, max } => {
+                let min_x = min
+                    .get_coordinate(x_param)
+                    .ok_or_else(|| GateError::missing_parameter(x_param, "rectangle min"))?;
+                let min_y = min
+                    .get_coordinate(y_param)
+                    .ok_or_else(|| GateError::missing_parameter(y_param, "rectangle min"))?;
+                let max_x = max
+                    .get_coordinate(x_param)
+                    .ok_or_else(|| GateError::missing_parameter(x_param, "rectangle max"))?;
+                let max_y = max
+                    .get_coordinate(y_param)
+                    .ok_or_else(|| GateError::missing_parameter(y_param, "rectangle max"))?;
+                Ok(hit_test_rectangle(
+                    x,
+                    y,
+                    (min_x, min_y),
+                    (max_x, max_y),
+                    tolerance,
+                ))
+            }
+            GateGeometry::Ellipse {
+                center,
+                radius_x,
+                radius_y,
+                angle,
+            } => {
+                let cx = center
+                    .get_coordinate(x_param)
+                    .ok_or_else(|| GateError::missing_parameter(x_param, "ellipse center"))?;
+                let cy = center
+                    .get_coordinate(y_param)
+                    .ok_or_else(|| GateError::missing_parameter(y_param, "ellipse center"))?;
+                Ok(hit_test_ellipse(
+                    x, y, (cx, cy), *radius_x, *radius_y, *angle, tolerance,
+                ))
+            }
+            GateGeometry::Boolean { .. } => Err(GateError::invalid_geometry(
+                "Boolean gates require gate resolution to hit-test",
+            )),
+        }
+    }
+
     /// Batch check if points (in raw coordinates) are inside the gate
     ///
     /// Uses optimized CPU-based batch filtering with Rayon parallelization.
@@ -453,6 +524,63 @@ impl GateGeometry {
         }
     }
 
+    /// Feed a deterministic, order-independent representation of this
+    /// geometry's shape into `hasher`, for use by [`Gate::fingerprint`].
+    ///
+    /// Coordinates are hashed by their bit pattern (rather than deriving
+    /// `Hash` for `f32`) and node coordinate maps are hashed in
+    /// channel-name-sorted order so the result doesn't depend on `HashMap`
+    /// iteration order.
+    fn hash_fingerprint<H: std::hash::Hasher>(&self, hasher: &mut H) {
+        use std::hash::Hash;
+
+        fn hash_node<H: std::hash::Hasher>(node: &GateNode, hasher: &mut H) {
+            let mut entries: Vec<(&Arc<str>, &f32)> = node.coordinates.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (channel, value) in entries {
+                channel.hash(hasher);
+                value.to_bits().hash(hasher);
+            }
+        }
+
+        match self {
+            GateGeometry::Polygon { nodes, closed } => {
+                "polygon".hash(hasher);
+                closed.hash(hasher);
+                for node in nodes {
+                    hash_node(node, hasher);
+                }
+            }
+            GateGeometry::Rectangle { min, max } => {
+                "rectangle".hash(hasher);
+                hash_node(min, hasher);
+                hash_node(max, hasher);
+            }
+            GateGeometry::Ellipse {
+                center,
+                radius_x,
+                radius_y,
+                angle,
+            } => {
+                "ellipse".hash(hasher);
+                hash_node(center, hasher);
+                radius_x.to_bits().hash(hasher);
+                radius_y.to_bits().hash(hasher);
+                angle.to_bits().hash(hasher);
+            }
+            GateGeometry::Boolean {
+                operation,
+                operands,
+            } => {
+                "boolean".hash(hasher);
+                operation.hash(hasher);
+                for operand in operands {
+                    operand.hash(hasher);
+                }
+            }
+        }
+    }
+
     /// Check if the gate has valid geometry and coordinates
     pub fn is_valid(&self, x_param: &str, y_param: &str) -> Result<bool> {
         match self {
@@ -971,6 +1099,80 @@ impl Gate {
         )
     }
 
+    /// Classify a point (in gate's parameter space) for interactive editing
+    ///
+    /// This is a convenience method that uses the gate's own parameters,
+    /// so you don't need to specify them explicitly.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Point to test, in raw data coordinates
+    /// * `tolerance` - Distance (in raw data units) within which a point counts as "on" a vertex or edge
+    ///
+    /// # Example
+    /// ```rust
+    /// use flow_gates::{Gate, GateHit};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let gate = Gate::rectangle("rect", "Rectangle", (100.0, 200.0), (500.0, 600.0), "FSC-A", "SSC-A")?;
+    /// assert_eq!(gate.hit_test(100.0, 200.0, 5.0)?, GateHit::Vertex { index: 0 });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hit_test(&self, x: f32, y: f32, tolerance: f32) -> Result<crate::hit_test::GateHit> {
+        self.geometry.hit_test(
+            x,
+            y,
+            self.x_parameter_channel_name(),
+            self.y_parameter_channel_name(),
+            tolerance,
+        )
+    }
+
+    /// Compute a stable content hash over this gate's geometry and channel
+    /// parameters.
+    ///
+    /// Two gates with the same shape and the same x/y channels produce the
+    /// same fingerprint regardless of `id`, `name`, `mode`, or
+    /// `label_position` — useful as a cache key or for duplicate detection
+    /// after import/export round-trips. The gate's own `id`/`name` are
+    /// intentionally excluded since those are exactly what callers often
+    /// want to deduplicate or re-derive.
+    ///
+    /// The fingerprint is stable for a given build of this crate, but is not
+    /// guaranteed to be stable across Rust standard library versions; don't
+    /// persist it across releases.
+    ///
+    /// # Example
+    /// ```rust
+    /// use flow_gates::Gate;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let a = Gate::rectangle("a", "A", (100.0, 200.0), (500.0, 600.0), "FSC-A", "SSC-A")?;
+    /// let b = Gate::rectangle("b", "B", (100.0, 200.0), (500.0, 600.0), "FSC-A", "SSC-A")?;
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.geometry.hash_fingerprint(&mut hasher);
+        self.parameters.0.hash(&mut hasher);
+        self.parameters.1.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Clone this gate with a new ID derived from its [`Gate::fingerprint`].
+    ///
+    /// Useful when importing from formats that don't carry stable gate IDs
+    /// (e.g. plain coordinate lists): the derived ID stays the same across
+    /// re-imports as long as the geometry and channels don't change.
+    pub fn with_fingerprint_id(&self) -> Self {
+        self.clone_with_id(format!("gate-{:016x}", self.fingerprint()))
+    }
+
     /// Get x and y coordinates from a node for this gate's parameters
     ///
     /// This is a convenience method that extracts coordinates for the gate's
@@ -1233,23 +1435,34 @@ impl GateBuilder {
         }
     }
 
+    /// Create a new gate builder with an auto-generated UUID as the gate ID
+    ///
+    /// Useful when the caller doesn't need a stable, human-chosen ID up front.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name for the gate
+    pub fn auto(name: impl Into<String>) -> Self {
+        Self::new(Uuid::new_v4().to_string(), name)
+    }
+
     /// Set the geometry to a polygon
     ///
     /// This also sets the parameters from the geometry creation.
     ///
     /// # Arguments
-    /// * `coords` - Vector of (x, y) coordinate tuples
+    /// * `coords` - Iterable of (x, y) coordinate tuples
     /// * `x_param` - Channel name for the x-axis parameter
     /// * `y_param` - Channel name for the y-axis parameter
     pub fn polygon(
         mut self,
-        coords: Vec<(f32, f32)>,
+        coords: impl IntoIterator<Item = (f32, f32)>,
         x_param: impl Into<Arc<str>>,
         y_param: impl Into<Arc<str>>,
     ) -> Result<Self> {
         use crate::geometry::create_polygon_geometry;
         let x_param_arc = x_param.into();
         let y_param_arc = y_param.into();
+        let coords: Vec<(f32, f32)> = coords.into_iter().collect();
         let geometry = create_polygon_geometry(coords, x_param_arc.as_ref(), y_param_arc.as_ref())?;
         self.geometry = Some(geometry);
         self.x_param = Some(x_param_arc);
@@ -1385,6 +1598,33 @@ impl GateBuilder {
             label_position: self.label_position,
         })
     }
+
+    /// Build the gate, additionally validating the geometry against the
+    /// configured channel names before returning it.
+    ///
+    /// This is stricter than [`GateBuilder::build`]: in addition to checking
+    /// that geometry and parameters were set, it verifies that the geometry's
+    /// coordinates are actually valid for the chosen x/y channels (e.g. a
+    /// polygon has at least three nodes with both coordinates present, or a
+    /// rectangle's min corner is less than its max corner).
+    ///
+    /// # Errors
+    /// Returns an error if [`GateBuilder::build`] would fail, or if
+    /// [`GateGeometry::is_valid`] reports the geometry is invalid for the
+    /// configured x/y parameters.
+    pub fn try_build(self) -> Result<Gate> {
+        let gate = self.build()?;
+        let (x_param, y_param) = (
+            gate.x_parameter_channel_name(),
+            gate.y_parameter_channel_name(),
+        );
+        if !gate.geometry.is_valid(x_param, y_param)? {
+            return Err(GateError::invalid_geometry(format!(
+                "Gate geometry is not valid for parameters '{x_param}' and '{y_param}'"
+            )));
+        }
+        Ok(gate)
+    }
 }
 
 // Custom serde helpers for Arc<str> types
@@ -0,0 +1,78 @@
+//! Channel selection by glob pattern
+//!
+//! `--channels`/`--exclude-channels` take comma-separated glob patterns (`*` matches any run of
+//! characters, `?` matches a single character) instead of exact channel names, so a pattern like
+//! `"FJComp-*"` or `"AF*"` can match a whole family of channels without listing them all. Exact
+//! names still work unchanged, since a name with no wildcard characters only matches itself.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+
+/// Compile a glob pattern (`*`/`?` wildcards, everything else literal) into an anchored,
+/// case-sensitive regex matching a channel name in full
+fn glob_to_regex(pattern: &str) -> Result<Regex> {
+    let mut regex_str = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex_str.push_str(".*"),
+            '?' => regex_str.push('.'),
+            _ => regex_str.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex_str.push('$');
+    Regex::new(&regex_str).with_context(|| format!("Invalid channel pattern: {pattern:?}"))
+}
+
+/// True if `candidate` matches any of `patterns`
+fn matches_any(patterns: &[Regex], candidate: &str) -> bool {
+    patterns.iter().any(|re| re.is_match(candidate))
+}
+
+/// Read additional glob patterns from a file, one per line, ignoring blank lines
+pub fn load_patterns_from_file(path: &Path) -> Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read channel pattern file: {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Resolve the channels to analyze for one file: `candidates` (typically all fluorescence
+/// channels) filtered down to those matching `include_patterns` (or all of them, if no include
+/// patterns are given), minus any matching `exclude_patterns`. Returns the matched channels
+/// followed by the excluded ones, in `candidates`' original order, for reporting.
+pub fn resolve_channels(
+    candidates: &[String],
+    include_patterns: &[String],
+    exclude_patterns: &[String],
+) -> Result<(Vec<String>, Vec<String>)> {
+    let include: Vec<Regex> = include_patterns
+        .iter()
+        .map(|p| glob_to_regex(p))
+        .collect::<Result<_>>()?;
+    let exclude: Vec<Regex> = exclude_patterns
+        .iter()
+        .map(|p| glob_to_regex(p))
+        .collect::<Result<_>>()?;
+
+    let mut matched = Vec::new();
+    let mut excluded = Vec::new();
+
+    for candidate in candidates {
+        let included = include.is_empty() || matches_any(&include, candidate);
+        if !included {
+            continue;
+        }
+        if matches_any(&exclude, candidate) {
+            excluded.push(candidate.clone());
+        } else {
+            matched.push(candidate.clone());
+        }
+    }
+
+    Ok((matched, excluded))
+}
@@ -0,0 +1,312 @@
+//! Pipeline configuration files (TOML/YAML)
+//!
+//! Lets a full processing pipeline (channels, thresholds, preprocessing, exports, plots) be
+//! captured in a file via `--config pipeline.toml`, so a batch run can be reproduced exactly
+//! without retyping every flag. Any CLI flag that's actually passed takes precedence over the
+//! file's value, and `--dump-config` prints the fully resolved configuration back out.
+
+use crate::{PipelineArgs, PlotFormatArg, QCModeArg};
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_MAD: f64 = 6.0;
+pub const DEFAULT_IT_LIMIT: f64 = 0.6;
+pub const DEFAULT_CONSECUTIVE_BINS: usize = 5;
+pub const DEFAULT_DOUBLET_NMAD: f64 = 4.0;
+pub const DEFAULT_CSV_COLUMN_NAME: &str = "PeacoQC";
+pub const DEFAULT_COFACTOR: f32 = 2000.0;
+
+/// On-disk shape of a pipeline configuration file. Every field is optional so a file only needs
+/// to pin the settings it cares about; anything left out falls back to the matching CLI flag (if
+/// given) or the built-in default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PipelineConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_channels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels_from: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qc_mode: Option<QCModeArg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mad: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub it_limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consecutive_bins: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove_zeros: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_margins: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_doublets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doublet_nmad: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_csv: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_csv_numeric: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_json: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csv_column_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plots: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plot_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plot_format: Option<PlotFormatArg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_report: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_output: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_spline_mad: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_bin_boundaries: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anonymize: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spillover: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unmix: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cofactor: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cofactors: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_interactive: Option<bool>,
+}
+
+/// Load a pipeline configuration from a TOML or YAML file, selected by its extension
+/// (`.toml`, `.yaml`, or `.yml`)
+pub fn load_config(path: &Path) -> Result<PipelineConfig> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse TOML config: {}", path.display())),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse YAML config: {}", path.display())),
+        other => Err(anyhow!(
+            "Unsupported config file extension {:?} (expected .toml, .yaml, or .yml): {}",
+            other,
+            path.display()
+        )),
+    }
+}
+
+/// Fully resolved pipeline settings: every CLI flag merged with the loaded config file (CLI
+/// wins), then defaulted where neither provided a value
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub output: Option<PathBuf>,
+    pub channels: Option<Vec<String>>,
+    pub exclude_channels: Option<Vec<String>>,
+    pub channels_from: Option<PathBuf>,
+    pub qc_mode: QCModeArg,
+    pub mad: f64,
+    pub it_limit: f64,
+    pub consecutive_bins: usize,
+    pub remove_zeros: bool,
+    pub keep_margins: bool,
+    pub keep_doublets: bool,
+    pub doublet_nmad: f64,
+    pub report: Option<PathBuf>,
+    pub export_csv: Option<PathBuf>,
+    pub export_csv_numeric: Option<PathBuf>,
+    pub export_json: Option<PathBuf>,
+    pub csv_column_name: String,
+    pub plots: Option<bool>,
+    pub plot_dir: Option<PathBuf>,
+    pub plot_format: PlotFormatArg,
+    pub html_report: Option<PathBuf>,
+    pub merge_output: Option<PathBuf>,
+    pub hide_spline_mad: bool,
+    pub show_bin_boundaries: bool,
+    pub anonymize: bool,
+    pub spillover: Option<PathBuf>,
+    pub unmix: Option<PathBuf>,
+    pub cofactor: f32,
+    pub cofactors: Option<Vec<f32>>,
+    pub verbose: bool,
+    pub dry_run: bool,
+    pub json: bool,
+    pub non_interactive: bool,
+}
+
+impl ResolvedConfig {
+    pub fn resolve(cli: &PipelineArgs, file: Option<&PipelineConfig>) -> Self {
+        ResolvedConfig {
+            output: cli
+                .output
+                .clone()
+                .or_else(|| file.and_then(|f| f.output.clone())),
+            channels: cli
+                .channels
+                .clone()
+                .or_else(|| file.and_then(|f| f.channels.clone())),
+            exclude_channels: cli
+                .exclude_channels
+                .clone()
+                .or_else(|| file.and_then(|f| f.exclude_channels.clone())),
+            channels_from: cli
+                .channels_from
+                .clone()
+                .or_else(|| file.and_then(|f| f.channels_from.clone())),
+            qc_mode: cli
+                .qc_mode
+                .clone()
+                .or_else(|| file.and_then(|f| f.qc_mode.clone()))
+                .unwrap_or(QCModeArg::All),
+            mad: cli
+                .mad
+                .or_else(|| file.and_then(|f| f.mad))
+                .unwrap_or(DEFAULT_MAD),
+            it_limit: cli
+                .it_limit
+                .or_else(|| file.and_then(|f| f.it_limit))
+                .unwrap_or(DEFAULT_IT_LIMIT),
+            consecutive_bins: cli
+                .consecutive_bins
+                .or_else(|| file.and_then(|f| f.consecutive_bins))
+                .unwrap_or(DEFAULT_CONSECUTIVE_BINS),
+            remove_zeros: cli.remove_zeros || file.and_then(|f| f.remove_zeros).unwrap_or(false),
+            keep_margins: cli.keep_margins || file.and_then(|f| f.keep_margins).unwrap_or(false),
+            keep_doublets: cli.keep_doublets
+                || file.and_then(|f| f.keep_doublets).unwrap_or(false),
+            doublet_nmad: cli
+                .doublet_nmad
+                .or_else(|| file.and_then(|f| f.doublet_nmad))
+                .unwrap_or(DEFAULT_DOUBLET_NMAD),
+            report: cli
+                .report
+                .clone()
+                .or_else(|| file.and_then(|f| f.report.clone())),
+            export_csv: cli
+                .export_csv
+                .clone()
+                .or_else(|| file.and_then(|f| f.export_csv.clone())),
+            export_csv_numeric: cli
+                .export_csv_numeric
+                .clone()
+                .or_else(|| file.and_then(|f| f.export_csv_numeric.clone())),
+            export_json: cli
+                .export_json
+                .clone()
+                .or_else(|| file.and_then(|f| f.export_json.clone())),
+            csv_column_name: cli
+                .csv_column_name
+                .clone()
+                .or_else(|| file.and_then(|f| f.csv_column_name.clone()))
+                .unwrap_or_else(|| DEFAULT_CSV_COLUMN_NAME.to_string()),
+            plots: cli.plots.or_else(|| file.and_then(|f| f.plots)),
+            plot_dir: cli
+                .plot_dir
+                .clone()
+                .or_else(|| file.and_then(|f| f.plot_dir.clone())),
+            plot_format: cli
+                .plot_format
+                .clone()
+                .or_else(|| file.and_then(|f| f.plot_format.clone()))
+                .unwrap_or(PlotFormatArg::Png),
+            html_report: cli
+                .html_report
+                .clone()
+                .or_else(|| file.and_then(|f| f.html_report.clone())),
+            merge_output: cli
+                .merge_output
+                .clone()
+                .or_else(|| file.and_then(|f| f.merge_output.clone())),
+            hide_spline_mad: cli.hide_spline_mad
+                || file.and_then(|f| f.hide_spline_mad).unwrap_or(false),
+            show_bin_boundaries: cli.show_bin_boundaries
+                || file.and_then(|f| f.show_bin_boundaries).unwrap_or(false),
+            anonymize: cli.anonymize || file.and_then(|f| f.anonymize).unwrap_or(false),
+            spillover: cli
+                .spillover
+                .clone()
+                .or_else(|| file.and_then(|f| f.spillover.clone())),
+            unmix: cli
+                .unmix
+                .clone()
+                .or_else(|| file.and_then(|f| f.unmix.clone())),
+            cofactor: cli
+                .cofactor
+                .or_else(|| file.and_then(|f| f.cofactor))
+                .unwrap_or(DEFAULT_COFACTOR),
+            cofactors: cli
+                .cofactors
+                .clone()
+                .or_else(|| file.and_then(|f| f.cofactors.clone())),
+            verbose: cli.verbose || file.and_then(|f| f.verbose).unwrap_or(false),
+            dry_run: cli.dry_run || file.and_then(|f| f.dry_run).unwrap_or(false),
+            json: cli.json || file.and_then(|f| f.json).unwrap_or(false),
+            // Also kick in automatically when stdin isn't a terminal, so a headless/CI run can
+            // never end up blocked on a prompt just because nobody thought to pass the flag
+            non_interactive: cli.non_interactive
+                || file.and_then(|f| f.non_interactive).unwrap_or(false)
+                || !std::io::stdin().is_terminal(),
+        }
+    }
+
+    /// The compensation matrix override to apply, if either `--spillover` or `--unmix` (they're
+    /// mutually exclusive on the CLI, and treated the same way once resolved) was given
+    pub fn spillover_override(&self) -> Option<&PathBuf> {
+        self.spillover.as_ref().or(self.unmix.as_ref())
+    }
+
+    /// Convert back to the on-disk representation, e.g. for `--dump-config`
+    pub fn to_pipeline_config(&self) -> PipelineConfig {
+        PipelineConfig {
+            output: self.output.clone(),
+            channels: self.channels.clone(),
+            exclude_channels: self.exclude_channels.clone(),
+            channels_from: self.channels_from.clone(),
+            qc_mode: Some(self.qc_mode.clone()),
+            mad: Some(self.mad),
+            it_limit: Some(self.it_limit),
+            consecutive_bins: Some(self.consecutive_bins),
+            remove_zeros: Some(self.remove_zeros),
+            keep_margins: Some(self.keep_margins),
+            keep_doublets: Some(self.keep_doublets),
+            doublet_nmad: Some(self.doublet_nmad),
+            report: self.report.clone(),
+            export_csv: self.export_csv.clone(),
+            export_csv_numeric: self.export_csv_numeric.clone(),
+            export_json: self.export_json.clone(),
+            csv_column_name: Some(self.csv_column_name.clone()),
+            plots: self.plots,
+            plot_dir: self.plot_dir.clone(),
+            plot_format: Some(self.plot_format.clone()),
+            html_report: self.html_report.clone(),
+            merge_output: self.merge_output.clone(),
+            hide_spline_mad: Some(self.hide_spline_mad),
+            show_bin_boundaries: Some(self.show_bin_boundaries),
+            anonymize: Some(self.anonymize),
+            spillover: self.spillover.clone(),
+            unmix: self.unmix.clone(),
+            cofactor: Some(self.cofactor),
+            cofactors: self.cofactors.clone(),
+            verbose: Some(self.verbose),
+            dry_run: Some(self.dry_run),
+            json: Some(self.json),
+            non_interactive: Some(self.non_interactive),
+        }
+    }
+}
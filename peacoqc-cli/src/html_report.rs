@@ -0,0 +1,149 @@
+//! Cohort-level HTML report
+//!
+//! `--html-report DIR` renders `DIR/index.html`: one summary card per input file (event counts,
+//! percentage removed, IT/MAD breakdown, and its QC plot if one was generated alongside), plus a
+//! table flagging files whose percentage removed is well above the rest of the cohort, which
+//! usually means a run went wrong for that file rather than that its data is unusually dirty.
+
+use crate::FileResult;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// A file is flagged as an outlier if its percentage removed is this many percentage points
+/// above the cohort's own average
+const OUTLIER_THRESHOLD_ABOVE_MEAN: f64 = 15.0;
+
+pub fn write_cohort_report(output_dir: &Path, results: &[FileResult], plot_dir: Option<&Path>) -> Result<()> {
+    let successful: Vec<&FileResult> = results.iter().filter(|r| r.error.is_none()).collect();
+    let mean_removed = if successful.is_empty() {
+        0.0
+    } else {
+        successful.iter().map(|r| r.percentage_removed).sum::<f64>() / successful.len() as f64
+    };
+
+    let outliers: Vec<&FileResult> = successful
+        .iter()
+        .filter(|r| r.percentage_removed - mean_removed > OUTLIER_THRESHOLD_ABOVE_MEAN)
+        .copied()
+        .collect();
+
+    let mut cards = String::new();
+    for result in results {
+        cards.push_str(&render_card(result, plot_dir));
+    }
+
+    let mut outlier_rows = String::new();
+    for result in &outliers {
+        outlier_rows.push_str(&format!(
+            "<tr><td>{}</td><td>{:.2}%</td><td>{:.2}pp above cohort mean</td></tr>\n",
+            escape(&result.filename),
+            result.percentage_removed,
+            result.percentage_removed - mean_removed
+        ));
+    }
+    let outliers_section = if outliers.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<h2>Flagged outliers</h2>\n<table><tr><th>File</th><th>% removed</th><th>Deviation</th></tr>\n{outlier_rows}</table>\n"
+        )
+    };
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>PeacoQC cohort report</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; background: #f7f7f9; }}
+h1 {{ margin-bottom: 0.25rem; }}
+.summary {{ color: #555; margin-bottom: 1.5rem; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; }}
+th, td {{ border: 1px solid #ccc; padding: 0.4rem 0.8rem; text-align: left; }}
+.cards {{ display: flex; flex-wrap: wrap; gap: 1rem; }}
+.card {{ background: white; border: 1px solid #ddd; border-radius: 6px; padding: 1rem; width: 320px; }}
+.card.error {{ border-color: #c0392b; }}
+.card h3 {{ margin-top: 0; word-break: break-all; }}
+.card img {{ max-width: 100%; border-radius: 4px; }}
+.card table {{ width: 100%; margin-bottom: 0; font-size: 0.9rem; }}
+.error-message {{ color: #c0392b; }}
+</style>
+</head>
+<body>
+<h1>PeacoQC cohort report</h1>
+<p class="summary">{n_total} file(s), {n_successful} successful, {n_failed} failed, {mean_removed:.2}% removed on average</p>
+{outliers_section}
+<h2>Files</h2>
+<div class="cards">
+{cards}
+</div>
+</body>
+</html>
+"#,
+        n_total = results.len(),
+        n_successful = successful.len(),
+        n_failed = results.len() - successful.len(),
+    );
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create HTML report directory: {}", output_dir.display()))?;
+    let report_path = output_dir.join("index.html");
+    std::fs::write(&report_path, html)
+        .with_context(|| format!("Failed to write HTML report: {}", report_path.display()))?;
+
+    Ok(())
+}
+
+fn render_card(result: &FileResult, plot_dir: Option<&Path>) -> String {
+    if let Some(ref error) = result.error {
+        return format!(
+            "<div class=\"card error\"><h3>{}</h3><p class=\"error-message\">{}</p></div>\n",
+            escape(&result.filename),
+            escape(error)
+        );
+    }
+
+    let plot_img = plot_dir
+        .map(|_| plot_filename(&result.filename))
+        .filter(|filename| plot_dir.unwrap().join(filename).is_file())
+        .map(|filename| format!("<img src=\"plots/{filename}\" alt=\"QC plot for {}\">", escape(&result.filename)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<div class="card">
+<h3>{filename}</h3>
+{plot_img}
+<table>
+<tr><td>Events before</td><td>{n_before}</td></tr>
+<tr><td>Events after</td><td>{n_after}</td></tr>
+<tr><td>% removed</td><td>{percentage_removed:.2}%</td></tr>
+<tr><td>IT %</td><td>{it_percentage}</td></tr>
+<tr><td>MAD %</td><td>{mad_percentage}</td></tr>
+</table>
+</div>
+"#,
+        filename = escape(&result.filename),
+        n_before = result.n_events_before,
+        n_after = result.n_events_after,
+        percentage_removed = result.percentage_removed,
+        it_percentage = result.it_percentage.map(|p| format!("{p:.2}%")).unwrap_or_else(|| "-".to_string()),
+        mad_percentage = result.mad_percentage.map(|p| format!("{p:.2}%")).unwrap_or_else(|| "-".to_string()),
+    )
+}
+
+/// The plot filename `run_qc` writes for a given input filename, e.g. "sample.fcs" -> "sample_qc_plot.png"
+pub(crate) fn plot_filename(input_filename: &str) -> String {
+    let stem = Path::new(input_filename)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(input_filename);
+    format!("{stem}_qc_plot.png")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,12 +1,22 @@
-use anyhow::Result;
+mod channels;
+mod config;
+mod html_report;
+mod manifest;
+mod resources;
+mod spillover;
+
+use anyhow::{Context, Result};
 use clap::Parser;
+use config::{PipelineConfig, ResolvedConfig, load_config};
 use dialoguer::{Confirm, Input};
-use flow_fcs::{Fcs, write_fcs_file};
+use flow_fcs::{AnonymizeOptions, ConcatenationMode, Fcs, anonymize, concatenate_events, write_fcs_file};
+use indicatif::{ProgressBar, ProgressStyle};
 use peacoqc_rs::{
-    DoubletConfig, FcsFilter, MarginConfig, PeacoQCConfig, PeacoQCData, QCMode, QCPlotConfig,
-    create_qc_plots, peacoqc, remove_doublets, remove_margins,
+    DoubletConfig, FcsFilter, MarginConfig, PeacoQCConfig, PeacoQCData, PlotFormat, QCMode,
+    QCPlotConfig, create_qc_plots, peacoqc, remove_doublets, remove_margins,
 };
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tracing::{debug, info, warn};
@@ -17,36 +27,67 @@ use tracing::{debug, info, warn};
 #[command(name = "peacoqc")]
 #[command(about = "Peak-based quality control for flow cytometry FCS files", long_about = None)]
 struct Cli {
-    /// Path(s) to input FCS file(s) or directory containing FCS files
-    /// Can specify multiple files or a directory
-    #[arg(value_name = "INPUT_FILES")]
-    input: Vec<PathBuf>,
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Commands {
+    /// Run the full QC pipeline (margins, doublets, transform, PeacoQC) and write cleaned files
+    Qc(QcArgs),
+    /// Remove margin events only, without running QC
+    Margins(MarginsArgs),
+    /// Remove doublet events only, without running QC
+    Doublets(DoubletsArgs),
+    /// Run the QC pipeline and generate plots, without requiring --plots
+    Plot(QcArgs),
+    /// Run the QC pipeline and write a report, without requiring --report
+    Report(QcArgs),
+    /// Load input file(s) and print diagnostics (event counts, channels, compensation) without
+    /// running any processing
+    Validate(ValidateArgs),
+    /// Monitor a directory and process new FCS files as they arrive
+    Watch(WatchArgs),
+}
 
+/// Shared pipeline settings used by `qc`, `plot`, `report`, and `watch`
+#[derive(clap::Args, Debug, Clone)]
+struct PipelineArgs {
     /// Output directory for cleaned FCS files (optional)
     /// If not specified, output files will be saved alongside input files with "_cleaned" suffix
     #[arg(short, long, value_name = "OUTPUT_DIR")]
     output: Option<PathBuf>,
 
-    /// Channels to analyze (comma-separated, e.g., "FSC-A,SSC-A,FL1-A")
+    /// Channels to analyze (comma-separated glob patterns, e.g. "FSC-A,SSC-A,FJComp-*")
     /// If not specified, all fluorescence channels will be analyzed
     #[arg(short, long, value_delimiter = ',')]
     channels: Option<Vec<String>>,
 
-    /// Quality control mode
-    #[arg(short = 'm', long, value_enum, default_value = "all")]
-    qc_mode: QCModeArg,
+    /// Channels to exclude from analysis (comma-separated glob patterns, e.g. "Time,AF*")
+    /// Applied after --channels/--channels-from
+    #[arg(long, value_delimiter = ',')]
+    exclude_channels: Option<Vec<String>>,
+
+    /// Load additional channel patterns from a file, one glob pattern per line, merged with
+    /// --channels
+    #[arg(long, value_name = "PATTERNS_PATH")]
+    channels_from: Option<PathBuf>,
+
+    /// Quality control mode (default: all)
+    #[arg(short = 'm', long, value_enum)]
+    qc_mode: Option<QCModeArg>,
 
     /// MAD threshold (default: 6.0) - Higher = less strict
-    #[arg(long, default_value = "6.0")]
-    mad: f64,
+    #[arg(long)]
+    mad: Option<f64>,
 
     /// Isolation Tree limit (default: 0.6) - Higher = less strict
-    #[arg(long, default_value = "0.6")]
-    it_limit: f64,
+    #[arg(long)]
+    it_limit: Option<f64>,
 
     /// Consecutive bins threshold (default: 5)
-    #[arg(long, default_value = "5")]
-    consecutive_bins: usize,
+    #[arg(long)]
+    consecutive_bins: Option<usize>,
 
     /// Remove zeros before peak detection
     #[arg(long)]
@@ -61,8 +102,8 @@ struct Cli {
     keep_doublets: bool,
 
     /// Doublet nmad threshold (default: 4.0)
-    #[arg(long, default_value = "4.0")]
-    doublet_nmad: f64,
+    #[arg(long)]
+    doublet_nmad: Option<f64>,
 
     /// Save QC report as JSON (for single file) or directory (for multiple files)
     #[arg(long, value_name = "REPORT_PATH")]
@@ -82,8 +123,8 @@ struct Cli {
     export_json: Option<PathBuf>,
 
     /// Column name for CSV exports (default: "PeacoQC")
-    #[arg(long, default_value = "PeacoQC")]
-    csv_column_name: String,
+    #[arg(long)]
+    csv_column_name: Option<String>,
 
     /// Generate QC plots after processing (if not specified, will prompt interactively)
     #[arg(long)]
@@ -93,6 +134,23 @@ struct Cli {
     #[arg(long, value_name = "PLOT_DIR")]
     plot_dir: Option<PathBuf>,
 
+    /// Output format for QC plots (default: png). `pdf` is accepted but currently rejected at
+    /// run time, since the plotting backend has no PDF renderer
+    #[arg(long, value_enum)]
+    plot_format: Option<PlotFormatArg>,
+
+    /// Generate an HTML cohort report (per-file cards with plots, summary tables, flagged
+    /// outliers) in the given directory, covering every input file processed this run
+    #[arg(long, value_name = "OUTPUT_DIR")]
+    html_report: Option<PathBuf>,
+
+    /// Concatenate every successfully cleaned file's events into a single FCS file at this path,
+    /// tagging each event with its originating filename in a "source_file" column. Files whose
+    /// channels don't fully match are still combined, with any parameter a file lacks filled
+    /// with null for its events.
+    #[arg(long, value_name = "MERGED_PATH")]
+    merge_output: Option<PathBuf>,
+
     /// Hide spline and MAD threshold lines in plots (shown by default)
     #[arg(long)]
     hide_spline_mad: bool,
@@ -101,10 +159,26 @@ struct Cli {
     #[arg(long)]
     show_bin_boundaries: bool,
 
+    /// Strip identifying metadata (operator, source filename, acquisition dates, source/
+    /// experiment/project labels) from cleaned output files and regenerate their $GUID, so
+    /// they can be shared externally without exposing who ran the acquisition or when
+    #[arg(long)]
+    anonymize: bool,
+
+    /// Override the file's compensation matrix with an externally computed one from CSV
+    /// (header row of channel names, then one labeled row of coefficients per channel), for use
+    /// when $SPILLOVER is missing or wrong
+    #[arg(long, value_name = "CSV_PATH", conflicts_with = "unmix")]
+    spillover: Option<PathBuf>,
+
+    /// Alias for `--spillover`, for spectral unmixing matrices computed outside this tool
+    #[arg(long, value_name = "CSV_PATH", conflicts_with = "spillover")]
+    unmix: Option<PathBuf>,
+
     /// Cofactor for arcsinh transformation (default: 2000)
     /// Lower values = more compression, higher values = less compression
-    #[arg(long, default_value = "2000")]
-    cofactor: f32,
+    #[arg(long)]
+    cofactor: Option<f32>,
 
     /// Iterate over multiple cofactor values (comma-separated, e.g., "1000,2000,5000")
     /// When specified, QC will be run for each cofactor value
@@ -114,9 +188,131 @@ struct Cli {
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Emit a single JSON summary (per-file results, errors, timings) to stdout instead of
+    /// human-readable progress text, so the run can be driven reliably from a pipeline tool
+    /// (Nextflow, Snakemake, etc.). Logs still go to stderr.
+    #[arg(long)]
+    json: bool,
+
+    /// Never prompt for input (plot generation, plot directory, etc.) - fall back to defaults
+    /// instead. Automatically enabled when stdin isn't a terminal, so headless/CI/pipeline runs
+    /// never block waiting for a response
+    #[arg(long)]
+    non_interactive: bool,
+
+    /// Load pipeline settings from a TOML or YAML config file
+    /// Any flag given on the command line overrides the matching setting in the file
+    #[arg(long, value_name = "CONFIG_PATH")]
+    config: Option<PathBuf>,
+
+    /// Print the fully resolved configuration (CLI flags merged with --config, if any) as TOML
+    /// and exit without processing any files
+    #[arg(long)]
+    dump_config: bool,
+
+    /// Report which files would be processed, which channels would be selected for each, their
+    /// estimated memory use, and any predicted problems (missing FSC-H for doublet removal, no
+    /// compensation available), without processing or writing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Skip input files already processed with identical settings, based on a manifest file
+    /// recorded alongside the output
+    #[arg(long)]
+    resume: bool,
+
+    /// Reprocess every file even if --resume would otherwise skip it
+    #[arg(long)]
+    force: bool,
+
+    /// Maximum number of files to process concurrently (default: number of CPU cores)
+    #[arg(short = 'j', long, value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Memory budget for concurrent processing, e.g. "8GB" or "512MB"
+    /// Files are gated on an estimate of their memory usage so large files don't all load at
+    /// once and exhaust system memory, even when --jobs allows more concurrency
+    #[arg(long, value_name = "SIZE")]
+    max_memory: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct QcArgs {
+    /// Path(s) to input FCS file(s) or directory containing FCS files
+    /// Can specify multiple files or a directory
+    #[arg(value_name = "INPUT_FILES")]
+    input: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pipeline: PipelineArgs,
+}
+
+#[derive(clap::Args, Debug)]
+struct WatchArgs {
+    /// Directory to monitor for new .fcs files
+    #[arg(value_name = "WATCH_DIR")]
+    dir: PathBuf,
+
+    #[command(flatten)]
+    pipeline: PipelineArgs,
+}
+
+#[derive(clap::Args, Debug)]
+struct MarginsArgs {
+    /// Path(s) to input FCS file(s) or directory containing FCS files
+    #[arg(value_name = "INPUT_FILES")]
+    input: Vec<PathBuf>,
+
+    /// Output directory for the margin-filtered FCS files (optional)
+    #[arg(short, long, value_name = "OUTPUT_DIR")]
+    output: Option<PathBuf>,
+
+    /// Channels to check for margin events (comma-separated glob patterns)
+    /// If not specified, all fluorescence channels will be checked
+    #[arg(short, long, value_delimiter = ',')]
+    channels: Option<Vec<String>>,
+
+    /// Emit a single JSON summary to stdout instead of human-readable text, so the run can be
+    /// driven reliably from a pipeline tool (Nextflow, Snakemake, etc.)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct DoubletsArgs {
+    /// Path(s) to input FCS file(s) or directory containing FCS files
+    #[arg(value_name = "INPUT_FILES")]
+    input: Vec<PathBuf>,
+
+    /// Output directory for the doublet-filtered FCS files (optional)
+    #[arg(short, long, value_name = "OUTPUT_DIR")]
+    output: Option<PathBuf>,
+
+    /// Doublet nmad threshold (default: 4.0)
+    #[arg(long)]
+    doublet_nmad: Option<f64>,
+
+    /// Emit a single JSON summary to stdout instead of human-readable text, so the run can be
+    /// driven reliably from a pipeline tool (Nextflow, Snakemake, etc.)
+    #[arg(long)]
+    json: bool,
 }
 
-#[derive(Debug, Clone, clap::ValueEnum)]
+#[derive(clap::Args, Debug)]
+struct ValidateArgs {
+    /// Path(s) to input FCS file(s) or directory containing FCS files
+    #[arg(value_name = "INPUT_FILES")]
+    input: Vec<PathBuf>,
+
+    /// Emit a single JSON summary to stdout instead of human-readable text, so the run can be
+    /// driven reliably from a pipeline tool (Nextflow, Snakemake, etc.)
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
 enum QCModeArg {
     /// Use both Isolation Tree and MAD methods
     All,
@@ -124,6 +320,8 @@ enum QCModeArg {
     It,
     /// Use only MAD method
     Mad,
+    /// Use only the multichannel Isolation Forest
+    Forest,
     /// No quality control, only peak detection
     None,
 }
@@ -134,11 +332,45 @@ impl From<QCModeArg> for QCMode {
             QCModeArg::All => QCMode::All,
             QCModeArg::It => QCMode::IsolationTree,
             QCModeArg::Mad => QCMode::MAD,
+            QCModeArg::Forest => QCMode::IsolationForest,
             QCModeArg::None => QCMode::None,
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+enum PlotFormatArg {
+    /// Rasterized bitmap (default)
+    Png,
+    /// Vector image - scales cleanly for large spectral panels
+    Svg,
+    /// Not currently supported: the plotting backend has no PDF renderer
+    Pdf,
+}
+
+/// Resolve a CLI/config plot format into the plotting backend's [`PlotFormat`], rejecting `Pdf`
+/// up front since `plotters` (the backend `create_qc_plots` is built on) only ships PNG and SVG
+/// drawing backends - there's no vector-to-PDF path to wire up here
+fn resolve_plot_format(format: PlotFormatArg) -> Result<PlotFormat> {
+    match format {
+        PlotFormatArg::Png => Ok(PlotFormat::Png),
+        PlotFormatArg::Svg => Ok(PlotFormat::Svg),
+        PlotFormatArg::Pdf => Err(anyhow::anyhow!(
+            "--plot-format pdf is not supported: the plotting backend (plotters) only provides PNG and SVG output. Use --plot-format svg and convert externally if you need PDF."
+        )),
+    }
+}
+
+/// File extension matching a [`PlotFormat`], so plot filenames reflect the format actually
+/// written to disk instead of always saying ".png"
+fn plot_extension(format: PlotFormat) -> &'static str {
+    match format {
+        PlotFormat::Png => "png",
+        PlotFormat::Svg => "svg",
+    }
+}
+
 /// Result of processing a single file
 #[derive(Debug)]
 struct FileResult {
@@ -157,6 +389,8 @@ struct FileResult {
     // Store data needed for plot generation
     fcs_data: Option<Fcs>,
     qc_result: Option<peacoqc_rs::PeacoQCResult>,
+    // The filtered (and, if requested, anonymized) output, kept for --merge-output
+    clean_fcs_data: Option<Fcs>,
 }
 
 /// Collect all FCS files from input paths (handles files and directories)
@@ -230,6 +464,7 @@ fn process_single_file(
             cofactor_used: result.cofactor_used,
             fcs_data: Some(result.fcs_data),
             qc_result: Some(result.qc_result),
+            clean_fcs_data: Some(result.clean_fcs_data),
         },
         Err(e) => FileResult {
             filename,
@@ -246,6 +481,7 @@ fn process_single_file(
             cofactor_used: config.cofactor,
             fcs_data: None,
             qc_result: None,
+            clean_fcs_data: None,
         },
     }
 }
@@ -262,12 +498,15 @@ struct InternalResult {
     // Store data needed for plot generation
     fcs_data: Fcs,
     qc_result: peacoqc_rs::PeacoQCResult,
+    // The filtered (and, if requested, anonymized) output, kept for --merge-output
+    clean_fcs_data: Fcs,
 }
 
 /// Processing configuration
 #[derive(Clone)]
 struct ProcessingConfig {
     channels: Option<Vec<String>>,
+    exclude_channels: Option<Vec<String>>,
     qc_mode: QCMode,
     mad: f64,
     it_limit: f64,
@@ -280,25 +519,37 @@ struct ProcessingConfig {
     export_csv_numeric: Option<PathBuf>,
     export_json: Option<PathBuf>,
     csv_column_name: String,
+    spillover_override: Option<PathBuf>,
     cofactor: f32,
     generate_plots: bool,
     plot_dir: Option<PathBuf>,
+    anonymize: bool,
 }
 
-/// Internal function to process a single file (called from process_single_file)
-fn process_file_internal(
-    input_path: &Path,
-    output_path: Option<&Path>,
-    config: &ProcessingConfig,
-) -> Result<InternalResult> {
-    use peacoqc_rs::{export_csv_boolean, export_csv_numeric, export_json_metadata};
-    // Load FCS file
-    let fcs = Fcs::open(
+/// Open an FCS file and log the diagnostics shared by every command that touches raw data:
+/// event count agreement between the DataFrame and the `$TOT` keyword, compensation
+/// availability, and the full channel list. Returns the opened file and whether a spillover
+/// matrix is available for compensation.
+///
+/// If `spillover_override` is given, the file's own `$SPILLOVER`/`$SPILL`/`$COMP` matrix (if
+/// any) is replaced with the one loaded from that CSV before diagnostics are logged, so the
+/// rest of the pipeline sees only the override.
+fn open_and_diagnose(input_path: &Path, spillover_override: Option<&Path>) -> Result<(Fcs, bool)> {
+    let mut fcs = Fcs::open(
         input_path
             .to_str()
             .ok_or_else(|| anyhow::anyhow!("Invalid path"))?,
     )?;
 
+    if let Some(csv_path) = spillover_override {
+        let matrix = spillover::load_matrix_csv(csv_path)?;
+        fcs.set_spillover_matrix(matrix.as_array(), &matrix.channels().to_vec())?;
+        info!(
+            "Overriding compensation matrix from: {}",
+            csv_path.display()
+        );
+    }
+
     // Log event count discrepancy check
     let n_events_from_tot = fcs.get_number_of_events().ok().copied().unwrap_or(0);
     let n_events_initial = fcs.get_event_count_from_dataframe();
@@ -349,11 +600,27 @@ fn process_file_internal(
         all_channels
     );
 
-    // Determine channels
-    let channels = config
-        .channels
-        .clone()
-        .unwrap_or_else(|| fcs.get_fluorescence_channels());
+    Ok((fcs, has_compensation))
+}
+
+fn process_file_internal(
+    input_path: &Path,
+    output_path: Option<&Path>,
+    config: &ProcessingConfig,
+) -> Result<InternalResult> {
+    use peacoqc_rs::{export_csv_boolean, export_csv_numeric, export_json_metadata};
+    let (fcs, has_compensation) = open_and_diagnose(input_path, config.spillover_override.as_deref())?;
+    let n_events_initial = fcs.get_event_count_from_dataframe();
+
+    // Determine channels: match --channels/--channels-from glob patterns against the
+    // fluorescence channels (or use them all, if no patterns were given), then drop anything
+    // matching --exclude-channels
+    let candidate_channels = fcs.get_fluorescence_channels();
+    let (channels, excluded_channels) = channels::resolve_channels(
+        &candidate_channels,
+        config.channels.as_deref().unwrap_or(&[]),
+        config.exclude_channels.as_deref().unwrap_or(&[]),
+    )?;
 
     if channels.is_empty() {
         return Err(anyhow::anyhow!("No channels specified or detected"));
@@ -364,6 +631,13 @@ fn process_file_internal(
         channels.len(),
         channels
     );
+    if !excluded_channels.is_empty() {
+        info!(
+            "Excluded {} channel(s) via --exclude-channels: {:?}",
+            excluded_channels.len(),
+            excluded_channels
+        );
+    }
 
     // Check if Time and AF channels are included/excluded
     let has_time = channels.iter().any(|c| c.to_uppercase().contains("TIME"));
@@ -386,9 +660,7 @@ fn process_file_internal(
 
         let margin_config = MarginConfig {
             channels: channels.clone(),
-            channel_specifications: None,
-            remove_min: None,
-            remove_max: None,
+            ..Default::default()
         };
 
         let margin_result = remove_margins(&current_fcs, &margin_config)?;
@@ -416,7 +688,7 @@ fn process_file_internal(
             channel1: "FSC-A".to_string(),
             channel2: "FSC-H".to_string(),
             nmad: config.doublet_nmad,
-            b: 0.0,
+            ..Default::default()
         };
 
         match remove_doublets(&current_fcs, &doublet_config) {
@@ -516,13 +788,21 @@ fn process_file_internal(
     let peacoqc_result = peacoqc(&current_fcs, &peacoqc_config)?;
 
     // Apply filter
-    let clean_fcs = current_fcs.filter(&peacoqc_result.good_cells)?;
+    let mut clean_fcs = current_fcs.filter(&peacoqc_result.good_cells)?;
     let n_events_final = clean_fcs.n_events();
 
+    if config.anonymize {
+        let anonymize_report = anonymize(&mut clean_fcs.metadata, &AnonymizeOptions::default());
+        info!(
+            "Anonymized output: stripped {:?}, regenerated $GUID",
+            anonymize_report.stripped_keywords
+        );
+    }
+
     // Save output (if path provided)
     if let Some(output_path) = output_path {
         info!("Writing cleaned FCS file to: {}", output_path.display());
-        write_fcs_file(clean_fcs, output_path)?;
+        write_fcs_file(clean_fcs.clone(), output_path)?;
         info!("Successfully wrote cleaned FCS file");
     }
 
@@ -581,22 +861,173 @@ fn process_file_internal(
         cofactor_used: cofactor,
         fcs_data: current_fcs,
         qc_result: peacoqc_result,
+        clean_fcs_data: clean_fcs,
     })
 }
 
-fn main() -> Result<()> {
-    // Initialize tracing subscriber with environment filter
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .init();
+/// Load the `--config` file referenced by `pipeline`, if any
+fn load_pipeline_config(pipeline: &PipelineArgs) -> Result<Option<PipelineConfig>> {
+    pipeline
+        .config
+        .as_ref()
+        .map(|path| load_config(path).with_context(|| format!("Loading config file {}", path.display())))
+        .transpose()
+}
+
+/// Combine `--channels` with any additional glob patterns loaded from `--channels-from`, for
+/// resolution against each file's channels in `process_file_internal`
+fn resolve_include_channels(resolved: &ResolvedConfig) -> Result<Option<Vec<String>>> {
+    let mut patterns = resolved.channels.clone().unwrap_or_default();
+    if let Some(ref path) = resolved.channels_from {
+        patterns.extend(channels::load_patterns_from_file(path)?);
+    }
+    Ok(if patterns.is_empty() { None } else { Some(patterns) })
+}
+
+/// Watch `dir` for incoming FCS files and process each one as it appears, using `resolved` as
+/// the pipeline configuration. Runs until interrupted (Ctrl+C); results are appended as they
+/// complete to a `peacoqc_watch_summary.jsonl` file in `dir`, one JSON object per line.
+fn run_watch(args: WatchArgs) -> Result<()> {
+    use notify::{Event, EventKind, RecursiveMode, Watcher};
+    use std::sync::mpsc::channel;
+
+    let dir = &args.dir;
+    let file_config = load_pipeline_config(&args.pipeline)?;
+    let resolved = ResolvedConfig::resolve(&args.pipeline, file_config.as_ref());
+
+    if !dir.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Watch target is not a directory: {}",
+            dir.display()
+        ));
+    }
+
+    if !resolved.json {
+        println!("👀 Watching {} for incoming FCS files...", dir.display());
+        println!("   (press Ctrl+C to stop)\n");
+    }
+
+    let summary_path = dir.join("peacoqc_watch_summary.jsonl");
+
+    let processing_config = ProcessingConfig {
+        channels: resolve_include_channels(&resolved)?,
+        exclude_channels: resolved.exclude_channels.clone(),
+        qc_mode: resolved.qc_mode.clone().into(),
+        mad: resolved.mad,
+        it_limit: resolved.it_limit,
+        consecutive_bins: resolved.consecutive_bins,
+        remove_zeros: resolved.remove_zeros,
+        remove_margins: !resolved.keep_margins,
+        remove_doublets: !resolved.keep_doublets,
+        doublet_nmad: resolved.doublet_nmad,
+        export_csv: resolved.export_csv.clone(),
+        export_csv_numeric: resolved.export_csv_numeric.clone(),
+        export_json: resolved.export_json.clone(),
+        csv_column_name: resolved.csv_column_name.clone(),
+        spillover_override: resolved.spillover_override().cloned(),
+        cofactor: resolved.cofactor,
+        generate_plots: false,
+        plot_dir: resolved.plot_dir.clone(),
+        anonymize: resolved.anonymize,
+    };
+
+    let (tx, rx) = channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .context("Failed to start filesystem watcher")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch directory: {}", dir.display()))?;
+
+    for res in rx {
+        let event = match res {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("Watch error: {}", e);
+                continue;
+            }
+        };
+
+        if !matches!(event.kind, EventKind::Create(_)) {
+            continue;
+        }
+
+        for path in event.paths {
+            let is_fcs = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("fcs"));
+            if !is_fcs {
+                continue;
+            }
+
+            info!("New FCS file detected: {}", path.display());
+            let result = process_single_file(&path, resolved.output.as_deref(), &processing_config);
+
+            if !resolved.json {
+                if let Some(ref error) = result.error {
+                    eprintln!("   ❌ {}: {}", result.filename, error);
+                } else {
+                    println!(
+                        "   ✅ {}: {} → {} events ({:.2}% removed)",
+                        result.filename, result.n_events_before, result.n_events_after, result.percentage_removed
+                    );
+                }
+            }
+
+            append_watch_summary(&summary_path, &result)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Append one JSON line describing `result` to the running watch summary file
+fn append_watch_summary(summary_path: &Path, result: &FileResult) -> Result<()> {
+    use std::io::Write;
+
+    let entry = serde_json::json!({
+        "filename": result.filename,
+        "n_events_before": result.n_events_before,
+        "n_events_after": result.n_events_after,
+        "percentage_removed": result.percentage_removed,
+        "it_percentage": result.it_percentage,
+        "mad_percentage": result.mad_percentage,
+        "consecutive_percentage": result.consecutive_percentage,
+        "processing_time_ms": result.processing_time_ms,
+        "error": result.error,
+    });
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(summary_path)
+        .with_context(|| format!("Failed to open watch summary: {}", summary_path.display()))?;
+    writeln!(file, "{}", entry)?;
 
-    let args = Cli::parse();
+    Ok(())
+}
 
-    println!("🧬 PeacoQC - Flow Cytometry Quality Control");
-    println!("============================================\n");
+/// Run the full QC pipeline: margins, doublets, transform, PeacoQC, then write cleaned files
+/// and any requested exports/plots
+fn run_qc(args: QcArgs) -> Result<()> {
+    let file_config = load_pipeline_config(&args.pipeline)?;
+    let resolved = ResolvedConfig::resolve(&args.pipeline, file_config.as_ref());
+
+    if args.pipeline.dump_config {
+        let effective = resolved.to_pipeline_config();
+        print!(
+            "{}",
+            toml::to_string_pretty(&effective).context("Failed to serialize effective config")?
+        );
+        return Ok(());
+    }
+
+    if !resolved.json {
+        println!("🧬 PeacoQC - Flow Cytometry Quality Control");
+        println!("============================================\n");
+    }
 
     // Collect input files (expand directories if needed)
     let input_files = collect_input_files(&args.input)?;
@@ -606,23 +1037,31 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
-    println!("📂 Found {} file(s) to process\n", input_files.len());
+    if !resolved.json {
+        println!("📂 Found {} file(s) to process\n", input_files.len());
+    }
+
+    if resolved.dry_run {
+        return run_dry_run(&input_files, &resolved);
+    }
 
     // Create output directory if specified
-    if let Some(ref output_dir) = args.output {
+    if let Some(ref output_dir) = resolved.output {
         std::fs::create_dir_all(output_dir)?;
     }
 
     // Determine cofactors to use
-    let cofactors_to_use = if let Some(ref cofactors) = args.cofactors {
+    let cofactors_to_use = if let Some(ref cofactors) = resolved.cofactors {
         cofactors.clone()
     } else {
-        vec![args.cofactor]
+        vec![resolved.cofactor]
     };
 
     // Determine if plots should be generated
-    let generate_plots = if let Some(plots_flag) = args.plots {
+    let generate_plots = if let Some(plots_flag) = resolved.plots {
         plots_flag // Use the flag value directly - this fixes the bug where --plots true didn't work
+    } else if resolved.json || resolved.non_interactive {
+        false // Never prompt when emitting a machine-readable summary or running non-interactively
     } else {
         // Prompt user interactively if not specified
         Confirm::new()
@@ -634,8 +1073,15 @@ fn main() -> Result<()> {
 
     // Determine plot directory
     let plot_dir = if generate_plots {
-        if let Some(ref dir) = args.plot_dir {
+        if let Some(ref dir) = resolved.plot_dir {
             Some(dir.clone())
+        } else if resolved.json || resolved.non_interactive {
+            Some(
+                input_files[0]
+                    .parent()
+                    .unwrap_or(Path::new("."))
+                    .to_path_buf(),
+            )
         } else {
             // Prompt for directory with default
             let default_dir = if input_files.len() == 1 {
@@ -665,68 +1111,206 @@ fn main() -> Result<()> {
         std::fs::create_dir_all(dir)?;
     }
 
+    // Bound how many files rayon processes at once, if requested
+    let thread_pool = args
+        .pipeline
+        .jobs
+        .map(|jobs| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .context("Failed to build thread pool for --jobs")
+        })
+        .transpose()?;
+
+    // Additionally gate concurrency on an estimated memory budget, if requested
+    let memory_budget = args
+        .pipeline
+        .max_memory
+        .as_deref()
+        .map(resources::parse_memory_size)
+        .transpose()?
+        .map(resources::MemoryBudget::new);
+
     // Start timing AFTER all user interactions and setup
     let start_time = Instant::now();
 
     // Convert qc_mode once before the loop
-    let qc_mode = args.qc_mode.into();
+    let qc_mode = resolved.qc_mode.clone().into();
+
+    // Resume manifest lives next to the output (or the current directory, if writing files
+    // alongside their inputs) so re-running the same batch command finds it automatically
+    let manifest_dir = resolved.output.clone().unwrap_or_else(|| PathBuf::from("."));
+    let manifest_path = manifest_dir.join(".peacoqc_manifest.json");
+    let mut processed_manifest = manifest::load_manifest(&manifest_path);
 
     // Process files with each cofactor
     let mut all_results: Vec<FileResult> = Vec::new();
+    let include_channels = resolve_include_channels(&resolved)?;
 
     for cofactor in &cofactors_to_use {
-        if cofactors_to_use.len() > 1 {
+        if cofactors_to_use.len() > 1 && !resolved.json {
             println!("\n🔄 Processing with cofactor: {}\n", cofactor);
         }
 
         // Prepare processing configuration
         // keep_margins/keep_doublets default to false, so removal happens by default
-        let remove_margins = !args.keep_margins;
-        let remove_doublets = !args.keep_doublets;
+        let remove_margins = !resolved.keep_margins;
+        let remove_doublets = !resolved.keep_doublets;
 
-        let mut processing_config = ProcessingConfig {
-            channels: args.channels.clone(),
+        let processing_config = ProcessingConfig {
+            channels: include_channels.clone(),
+            exclude_channels: resolved.exclude_channels.clone(),
             qc_mode: qc_mode,
-            mad: args.mad,
-            it_limit: args.it_limit,
-            consecutive_bins: args.consecutive_bins,
-            remove_zeros: args.remove_zeros,
+            mad: resolved.mad,
+            it_limit: resolved.it_limit,
+            consecutive_bins: resolved.consecutive_bins,
+            remove_zeros: resolved.remove_zeros,
             remove_margins,
             remove_doublets,
-            doublet_nmad: args.doublet_nmad,
-            export_csv: args.export_csv.clone(),
-            export_csv_numeric: args.export_csv_numeric.clone(),
-            export_json: args.export_json.clone(),
-            csv_column_name: args.csv_column_name.clone(),
+            doublet_nmad: resolved.doublet_nmad,
+            export_csv: resolved.export_csv.clone(),
+            export_csv_numeric: resolved.export_csv_numeric.clone(),
+            export_json: resolved.export_json.clone(),
+            csv_column_name: resolved.csv_column_name.clone(),
+            spillover_override: resolved.spillover_override().cloned(),
             cofactor: *cofactor,
             generate_plots: false, // Will handle plots after all processing
             plot_dir: plot_dir.clone(),
+            anonymize: resolved.anonymize,
         };
 
-        // Process files in parallel
-        let total_files = input_files.len();
-        let results: Vec<FileResult> = input_files
-            .par_iter()
-            .enumerate()
-            .map(|(idx, input_path)| {
-                if total_files > 1 {
-                    info!(
-                        "Processing file {}/{}: {}",
-                        idx + 1,
-                        total_files,
-                        input_path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("unknown")
-                    );
-                }
-                process_single_file(input_path, args.output.as_deref(), &processing_config)
-            })
-            .collect();
+        // Everything that affects the QC result itself (not just where output is written)
+        let config_signature = manifest::config_signature(&format!(
+            "{:?}|{:?}|{:?}|{}|{}|{}|{}|{}|{}|{}|{}|{:?}",
+            processing_config.channels,
+            processing_config.exclude_channels,
+            processing_config.qc_mode,
+            processing_config.mad,
+            processing_config.it_limit,
+            processing_config.consecutive_bins,
+            processing_config.remove_zeros,
+            processing_config.remove_margins,
+            processing_config.remove_doublets,
+            processing_config.doublet_nmad,
+            processing_config.cofactor,
+            processing_config.spillover_override,
+        ));
+
+        // Under --resume (and without --force), skip files whose manifest entry already
+        // matches this file and this exact configuration
+        let files_to_process: Vec<PathBuf> = if args.pipeline.resume && !args.pipeline.force {
+            input_files
+                .iter()
+                .filter(|path| {
+                    let key = path.to_string_lossy().to_string();
+                    match manifest::input_signature(path) {
+                        Ok(input_sig) => !manifest::already_processed(
+                            &processed_manifest,
+                            &key,
+                            &input_sig,
+                            &config_signature,
+                        ),
+                        Err(_) => true, // can't stat it - let normal processing surface the error
+                    }
+                })
+                .cloned()
+                .collect()
+        } else {
+            input_files.clone()
+        };
+
+        let n_skipped = input_files.len() - files_to_process.len();
+        if n_skipped > 0 && !resolved.json {
+            println!(
+                "⏭️  Skipping {} file(s) already processed with matching settings (--resume)",
+                n_skipped
+            );
+        }
+
+        // Process files in parallel, bounded by --jobs and --max-memory
+        let total_files = files_to_process.len();
+        let progress = (total_files > 1 && !resolved.json).then(|| {
+            let pb = ProgressBar::new(total_files as u64);
+            pb.set_style(
+                ProgressStyle::with_template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:30.cyan/blue}] {pos}/{len} files (ETA {eta}) {msg}",
+                )
+                .unwrap()
+                .progress_chars("=>-"),
+            );
+            pb
+        });
+        let run_batch = || {
+            files_to_process
+                .par_iter()
+                .enumerate()
+                .map(|(idx, input_path)| {
+                    let filename = input_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown");
+                    if total_files > 1 {
+                        info!("Processing file {}/{}: {}", idx + 1, total_files, filename);
+                    }
+
+                    let reservation = memory_budget.as_ref().map(|budget| {
+                        budget.acquire(resources::estimate_memory_bytes(input_path))
+                    });
+
+                    let result =
+                        process_single_file(input_path, resolved.output.as_deref(), &processing_config);
+
+                    if let (Some(budget), Some(reserved)) = (&memory_budget, reservation) {
+                        budget.release(reserved);
+                    }
+
+                    if let Some(pb) = &progress {
+                        pb.inc(1);
+                        if resolved.verbose {
+                            pb.set_message(format!(
+                                "{} ({:.2}s)",
+                                result.filename,
+                                result.processing_time_ms as f64 / 1000.0
+                            ));
+                        } else {
+                            pb.set_message(result.filename.clone());
+                        }
+                    }
+
+                    result
+                })
+                .collect()
+        };
+        let results: Vec<FileResult> = match &thread_pool {
+            Some(pool) => pool.install(run_batch),
+            None => run_batch(),
+        };
+        if let Some(pb) = &progress {
+            pb.finish_and_clear();
+        }
+
+        for result in &results {
+            if result.error.is_none()
+                && let Ok(input_sig) = manifest::input_signature(&result.input_path)
+            {
+                processed_manifest.insert(
+                    result.input_path.to_string_lossy().to_string(),
+                    manifest::ManifestEntry {
+                        input_signature: input_sig,
+                        config_signature: config_signature.clone(),
+                    },
+                );
+            }
+        }
 
         all_results.extend(results);
     }
 
+    if let Err(e) = manifest::save_manifest(&manifest_path, &processed_manifest) {
+        warn!("Failed to save resume manifest: {}", e);
+    }
+
     let results = all_results;
 
     // Print results
@@ -734,59 +1318,94 @@ fn main() -> Result<()> {
     let successful: Vec<&FileResult> = results.iter().filter(|r| r.error.is_none()).collect();
     let failed: Vec<&FileResult> = results.iter().filter(|r| r.error.is_some()).collect();
 
-    println!("\n✅ Processing Complete!");
-    println!("   Processed: {} file(s)", results.len());
-    println!("   Successful: {}", successful.len());
-    if !failed.is_empty() {
-        println!("   Failed: {}", failed.len());
-    }
-    println!("   ⏱️  Total time: {:.2}s", total_time);
-    
-    // Report per-file timing for multi-file processing
-    if results.len() > 1 && !successful.is_empty() {
-        let total_processing_ms: u128 = successful.iter().map(|r| r.processing_time_ms).sum();
-        let avg_time_ms = total_processing_ms / successful.len() as u128;
-        println!("   ⏱️  Average time per file: {:.2}s", avg_time_ms as f64 / 1000.0);
+    if let Some(ref merge_path) = resolved.merge_output {
+        let cleaned: Vec<Fcs> = successful
+            .iter()
+            .filter_map(|r| r.clean_fcs_data.clone())
+            .collect();
+        if cleaned.is_empty() {
+            warn!("--merge-output requested but no files processed successfully; skipping merge");
+        } else {
+            match concatenate_events(cleaned, merge_path, ConcatenationMode::UnionFillNull) {
+                Ok(merged) => {
+                    if !resolved.json {
+                        println!(
+                            "🧬 Wrote merged output ({} events from {} file(s)) to: {}",
+                            merged.n_events(),
+                            successful.len(),
+                            merge_path.display()
+                        );
+                    }
+                }
+                Err(e) => warn!("Failed to write merged output: {}", e),
+            }
+        }
     }
-    println!();
 
-    // Print summaries
-    if args.verbose && !successful.is_empty() {
-        println!("📊 Results:");
-        for result in &successful {
-            if results.len() > 1 {
-                println!(
-                    "   {}: {} → {} events ({:.2}% removed) [{:.2}s]",
-                    result.filename,
-                    result.n_events_before,
-                    result.n_events_after,
-                    result.percentage_removed,
-                    result.processing_time_ms as f64 / 1000.0
-                );
-            } else {
-                println!(
-                    "   {}: {} → {} events ({:.2}% removed)",
-                    result.filename,
-                    result.n_events_before,
-                    result.n_events_after,
-                    result.percentage_removed
-                );
-            }
+    if resolved.json {
+        let summary = serde_json::json!({
+            "total_files": results.len(),
+            "successful": successful.len(),
+            "failed": failed.len(),
+            "total_time_seconds": total_time,
+            "results": results.iter().map(result_to_json).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string(&summary)?);
+    } else {
+        println!("\n✅ Processing Complete!");
+        println!("   Processed: {} file(s)", results.len());
+        println!("   Successful: {}", successful.len());
+        if !failed.is_empty() {
+            println!("   Failed: {}", failed.len());
+        }
+        println!("   ⏱️  Total time: {:.2}s", total_time);
+
+        // Report per-file timing for multi-file processing
+        if results.len() > 1 && !successful.is_empty() {
+            let total_processing_ms: u128 = successful.iter().map(|r| r.processing_time_ms).sum();
+            let avg_time_ms = total_processing_ms / successful.len() as u128;
+            println!("   ⏱️  Average time per file: {:.2}s", avg_time_ms as f64 / 1000.0);
         }
         println!();
-    }
 
-    // Print errors if any
-    if !failed.is_empty() {
-        eprintln!("❌ Errors:");
-        for result in &failed {
-            eprintln!("   {}: {}", result.filename, result.error.as_ref().unwrap());
+        // Print summaries
+        if resolved.verbose && !successful.is_empty() {
+            println!("📊 Results:");
+            for result in &successful {
+                if results.len() > 1 {
+                    println!(
+                        "   {}: {} → {} events ({:.2}% removed) [{:.2}s]",
+                        result.filename,
+                        result.n_events_before,
+                        result.n_events_after,
+                        result.percentage_removed,
+                        result.processing_time_ms as f64 / 1000.0
+                    );
+                } else {
+                    println!(
+                        "   {}: {} → {} events ({:.2}% removed)",
+                        result.filename,
+                        result.n_events_before,
+                        result.n_events_after,
+                        result.percentage_removed
+                    );
+                }
+            }
+            println!();
+        }
+
+        // Print errors if any
+        if !failed.is_empty() {
+            eprintln!("❌ Errors:");
+            for result in &failed {
+                eprintln!("   {}: {}", result.filename, result.error.as_ref().unwrap());
+            }
+            eprintln!();
         }
-        eprintln!();
     }
 
     // Save report(s) if requested
-    if let Some(ref report_path) = args.report {
+    if let Some(ref report_path) = resolved.report {
         if results.len() == 1 {
             // Single file: save single report
             let result = &results[0];
@@ -843,13 +1462,35 @@ fn main() -> Result<()> {
         }
     }
 
+    // Generate an HTML cohort report if requested, with its own plot images independent of --plots
+    if let Some(ref html_dir) = resolved.html_report {
+        let plots_subdir = html_dir.join("plots");
+        std::fs::create_dir_all(&plots_subdir)?;
+
+        for result in &successful {
+            if let (Some(fcs_data), Some(qc_result)) = (&result.fcs_data, &result.qc_result) {
+                let plot_path = plots_subdir.join(html_report::plot_filename(&result.filename));
+                if let Err(e) = create_qc_plots(fcs_data, qc_result, &plot_path, QCPlotConfig::default()) {
+                    warn!("Failed to generate plot for HTML report ({}): {}", result.filename, e);
+                }
+            }
+        }
+
+        html_report::write_cohort_report(html_dir, &results, Some(&plots_subdir))?;
+        if !resolved.json {
+            println!("📄 Wrote HTML cohort report to: {}", html_dir.join("index.html").display());
+        }
+    }
+
     // Handle plot generation
     if successful.is_empty() {
         // No successful files to plot
     } else {
         // Determine if plots should be generated
-        let generate_plots = if let Some(plots_flag) = args.plots {
+        let generate_plots = if let Some(plots_flag) = resolved.plots {
             plots_flag
+        } else if resolved.json || resolved.non_interactive {
+            false // Never prompt when emitting a machine-readable summary or running non-interactively
         } else {
             // Prompt user interactively
             Confirm::new()
@@ -860,9 +1501,17 @@ fn main() -> Result<()> {
         };
 
         if generate_plots {
+            let plot_format = resolve_plot_format(resolved.plot_format.clone())?;
+
             // Determine plot directory
-            let plot_dir = if let Some(ref dir) = args.plot_dir {
+            let plot_dir = if let Some(ref dir) = resolved.plot_dir {
                 dir.clone()
+            } else if resolved.json || resolved.non_interactive {
+                successful[0]
+                    .input_path
+                    .parent()
+                    .unwrap_or(Path::new("."))
+                    .to_path_buf()
             } else {
                 // Prompt for directory with default
                 let default_dir = if successful.len() == 1 {
@@ -893,23 +1542,28 @@ fn main() -> Result<()> {
 
             // Create plot directory if it doesn't exist
             std::fs::create_dir_all(&plot_dir)?;
-            println!("\n📊 Generating QC plots...");
+            if !resolved.json {
+                println!("\n📊 Generating QC plots...");
+            }
 
             // Generate plots for each successful file
             for result in &successful {
                 if let (Some(fcs_data), Some(qc_result)) = (&result.fcs_data, &result.qc_result) {
+                    let extension = plot_extension(plot_format);
                     let plot_filename = result
                         .input_path
                         .file_stem()
                         .and_then(|s| s.to_str())
-                        .map(|s| format!("{}_qc_plot.png", s))
-                        .unwrap_or_else(|| "qc_plot.png".to_string());
+                        .map(|s| format!("{}_qc_plot.{}", s, extension))
+                        .unwrap_or_else(|| format!("qc_plot.{}", extension));
                     let plot_path = plot_dir.join(&plot_filename);
 
-                    match create_qc_plots(fcs_data, qc_result, &plot_path, QCPlotConfig::default())
-                    {
+                    let plot_config = QCPlotConfig { format: plot_format, ..Default::default() };
+                    match create_qc_plots(fcs_data, qc_result, &plot_path, plot_config) {
                         Ok(()) => {
-                            println!("   ✅ Generated plot: {}", plot_path.display());
+                            if !resolved.json {
+                                println!("   ✅ Generated plot: {}", plot_path.display());
+                            }
                         }
                         Err(e) => {
                             warn!(
@@ -920,7 +1574,9 @@ fn main() -> Result<()> {
                     }
                 }
             }
-            println!();
+            if !resolved.json {
+                println!();
+            }
         }
     }
 
@@ -931,3 +1587,521 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Report what `qc` would do for each input file - channels selected, estimated memory, and
+/// predicted problems - without processing or writing anything
+fn run_dry_run(input_files: &[PathBuf], resolved: &ResolvedConfig) -> Result<()> {
+    let include_channels = resolve_include_channels(resolved)?;
+    println!("🔍 Dry run: no files will be processed or written\n");
+
+    let mut had_errors = false;
+    for input_path in input_files {
+        let filename = input_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+
+        match open_and_diagnose(input_path, resolved.spillover_override().map(PathBuf::as_path)) {
+            Ok((fcs, has_compensation)) => {
+                let candidate_channels = fcs.get_fluorescence_channels();
+                let (selected_channels, excluded_channels) = channels::resolve_channels(
+                    &candidate_channels,
+                    include_channels.as_deref().unwrap_or(&[]),
+                    resolved.exclude_channels.as_deref().unwrap_or(&[]),
+                )?;
+                let estimated_bytes = resources::estimate_memory_bytes(input_path);
+
+                println!("📄 {}", filename);
+                println!(
+                    "   Channels ({}): {:?}",
+                    selected_channels.len(),
+                    selected_channels
+                );
+                if !excluded_channels.is_empty() {
+                    println!("   Excluded: {:?}", excluded_channels);
+                }
+                println!(
+                    "   Estimated memory: {:.1} MB",
+                    estimated_bytes as f64 / (1024.0 * 1024.0)
+                );
+
+                let mut problems = Vec::new();
+                if selected_channels.is_empty() {
+                    problems.push("no channels matched, selection would fail".to_string());
+                }
+                if !resolved.keep_doublets {
+                    let all_channels = fcs.channel_names();
+                    let has_fsc_a = all_channels.iter().any(|c| c == "FSC-A");
+                    let has_fsc_h = all_channels.iter().any(|c| c == "FSC-H");
+                    if !has_fsc_a || !has_fsc_h {
+                        problems.push(
+                            "doublet removal needs FSC-A and FSC-H, one or both are missing"
+                                .to_string(),
+                        );
+                    }
+                }
+                if !has_compensation && resolved.spillover_override().is_none() {
+                    problems.push(
+                        "no compensation matrix available ($SPILLOVER/$SPILL/$COMP missing, no --spillover/--unmix given)"
+                            .to_string(),
+                    );
+                }
+
+                if problems.is_empty() {
+                    println!("   No predicted problems");
+                } else {
+                    for problem in &problems {
+                        println!("   ⚠️  {}", problem);
+                    }
+                }
+                println!();
+            }
+            Err(e) => {
+                eprintln!("❌ {}: {}", filename, e);
+                had_errors = true;
+            }
+        }
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Remove margin events from each input file and write the result, without running QC
+fn run_margins(args: MarginsArgs) -> Result<()> {
+    let input_files = collect_input_files(&args.input)?;
+    if input_files.is_empty() {
+        return Err(anyhow::anyhow!("No FCS files found"));
+    }
+    if let Some(ref output_dir) = args.output {
+        std::fs::create_dir_all(output_dir)?;
+    }
+
+    let mut had_errors = false;
+    let mut results = Vec::new();
+    for input_path in &input_files {
+        let filename = input_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+
+        match run_margins_single(input_path, args.output.as_deref(), args.channels.as_deref()) {
+            Ok((n_before, n_after)) => {
+                let percentage = 100.0 * (n_before - n_after) as f64 / n_before.max(1) as f64;
+                if !args.json {
+                    println!(
+                        "✅ {}: {} → {} events ({:.2}% removed)",
+                        filename, n_before, n_after, percentage
+                    );
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events_before": n_before,
+                    "n_events_after": n_after,
+                    "percentage_removed": percentage,
+                    "error": null,
+                }));
+            }
+            Err(e) => {
+                if !args.json {
+                    eprintln!("❌ {}: {}", filename, e);
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events_before": null,
+                    "n_events_after": null,
+                    "percentage_removed": null,
+                    "error": e.to_string(),
+                }));
+                had_errors = true;
+            }
+        }
+    }
+
+    if args.json {
+        let summary = serde_json::json!({
+            "total_files": results.len(),
+            "results": results,
+        });
+        println!("{}", serde_json::to_string(&summary)?);
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_margins_single(
+    input_path: &Path,
+    output_dir: Option<&Path>,
+    channels: Option<&[String]>,
+) -> Result<(usize, usize)> {
+    let (fcs, _) = open_and_diagnose(input_path, None)?;
+    let n_before = fcs.get_event_count_from_dataframe();
+
+    let candidate_channels = fcs.get_fluorescence_channels();
+    let (channels, _) = channels::resolve_channels(&candidate_channels, channels.unwrap_or(&[]), &[])?;
+    let margin_config = MarginConfig {
+        channels,
+        ..Default::default()
+    };
+
+    let margin_result = remove_margins(&fcs, &margin_config)?;
+    let filtered = fcs.filter(&margin_result.mask)?;
+    let n_after = filtered.n_events();
+
+    if let Some(dir) = output_dir {
+        let output_path = dir.join(margins_output_filename(input_path, "margins"));
+        write_fcs_file(filtered, &output_path)?;
+        info!("Wrote margin-filtered FCS file to: {}", output_path.display());
+    }
+
+    Ok((n_before, n_after))
+}
+
+/// Remove doublet events from each input file and write the result, without running QC
+fn run_doublets(args: DoubletsArgs) -> Result<()> {
+    let input_files = collect_input_files(&args.input)?;
+    if input_files.is_empty() {
+        return Err(anyhow::anyhow!("No FCS files found"));
+    }
+    if let Some(ref output_dir) = args.output {
+        std::fs::create_dir_all(output_dir)?;
+    }
+
+    let doublet_nmad = args.doublet_nmad.unwrap_or(config::DEFAULT_DOUBLET_NMAD);
+    let mut had_errors = false;
+    let mut results = Vec::new();
+    for input_path in &input_files {
+        let filename = input_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+
+        match run_doublets_single(input_path, args.output.as_deref(), doublet_nmad) {
+            Ok((n_before, n_after)) => {
+                let percentage = 100.0 * (n_before - n_after) as f64 / n_before.max(1) as f64;
+                if !args.json {
+                    println!(
+                        "✅ {}: {} → {} events ({:.2}% removed)",
+                        filename, n_before, n_after, percentage
+                    );
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events_before": n_before,
+                    "n_events_after": n_after,
+                    "percentage_removed": percentage,
+                    "error": null,
+                }));
+            }
+            Err(e) => {
+                if !args.json {
+                    eprintln!("❌ {}: {}", filename, e);
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events_before": null,
+                    "n_events_after": null,
+                    "percentage_removed": null,
+                    "error": e.to_string(),
+                }));
+                had_errors = true;
+            }
+        }
+    }
+
+    if args.json {
+        let summary = serde_json::json!({
+            "total_files": results.len(),
+            "results": results,
+        });
+        println!("{}", serde_json::to_string(&summary)?);
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_doublets_single(
+    input_path: &Path,
+    output_dir: Option<&Path>,
+    doublet_nmad: f64,
+) -> Result<(usize, usize)> {
+    let (fcs, _) = open_and_diagnose(input_path, None)?;
+    let n_before = fcs.get_event_count_from_dataframe();
+
+    let doublet_config = DoubletConfig {
+        channel1: "FSC-A".to_string(),
+        channel2: "FSC-H".to_string(),
+        nmad: doublet_nmad,
+        ..Default::default()
+    };
+
+    let doublet_result = remove_doublets(&fcs, &doublet_config)?;
+    let filtered = fcs.filter(&doublet_result.mask)?;
+    let n_after = filtered.n_events();
+
+    if let Some(dir) = output_dir {
+        let output_path = dir.join(margins_output_filename(input_path, "doublets"));
+        write_fcs_file(filtered, &output_path)?;
+        info!("Wrote doublet-filtered FCS file to: {}", output_path.display());
+    }
+
+    Ok((n_before, n_after))
+}
+
+/// Build an output filename for a standalone preprocessing step, e.g. "sample_margins.fcs"
+fn margins_output_filename(input_path: &Path, suffix: &str) -> String {
+    input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| format!("{s}_{suffix}.fcs"))
+        .unwrap_or_else(|| format!("output_{suffix}.fcs"))
+}
+
+/// Run the full QC pipeline and always write plots, without needing to pass `--plots`
+fn run_plot(args: QcArgs) -> Result<()> {
+    let file_config = load_pipeline_config(&args.pipeline)?;
+    let resolved = ResolvedConfig::resolve(&args.pipeline, file_config.as_ref());
+    let input_files = collect_input_files(&args.input)?;
+    if input_files.is_empty() {
+        return Err(anyhow::anyhow!("No FCS files found"));
+    }
+
+    let processing_config = build_processing_config(&resolved, resolved.cofactor)?;
+    let plot_format = resolve_plot_format(resolved.plot_format.clone())?;
+    let extension = plot_extension(plot_format);
+
+    let plot_dir = resolved.plot_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    std::fs::create_dir_all(&plot_dir)?;
+
+    let mut had_errors = false;
+    let mut plot_results = Vec::new();
+    for input_path in &input_files {
+        let result = process_single_file(input_path, resolved.output.as_deref(), &processing_config);
+        if let Some(ref error) = result.error {
+            if !resolved.json {
+                eprintln!("❌ {}: {}", result.filename, error);
+            }
+            plot_results.push(serde_json::json!({
+                "filename": result.filename,
+                "plot_path": null,
+                "error": error,
+            }));
+            had_errors = true;
+            continue;
+        }
+
+        if let (Some(fcs_data), Some(qc_result)) = (&result.fcs_data, &result.qc_result) {
+            let plot_path = plot_dir.join(
+                margins_output_filename(input_path, "qc_plot").replace(".fcs", &format!(".{extension}")),
+            );
+            let plot_config = QCPlotConfig { format: plot_format, ..Default::default() };
+            match create_qc_plots(fcs_data, qc_result, &plot_path, plot_config) {
+                Ok(()) => {
+                    if !resolved.json {
+                        println!("✅ Generated plot: {}", plot_path.display());
+                    }
+                    plot_results.push(serde_json::json!({
+                        "filename": result.filename,
+                        "plot_path": plot_path,
+                        "error": null,
+                    }));
+                }
+                Err(e) => {
+                    if !resolved.json {
+                        eprintln!("❌ Failed to generate plot for {}: {}", result.filename, e);
+                    }
+                    plot_results.push(serde_json::json!({
+                        "filename": result.filename,
+                        "plot_path": null,
+                        "error": e.to_string(),
+                    }));
+                    had_errors = true;
+                }
+            }
+        }
+    }
+
+    if resolved.json {
+        let summary = serde_json::json!({
+            "total_files": plot_results.len(),
+            "results": plot_results,
+        });
+        println!("{}", serde_json::to_string(&summary)?);
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Run the full QC pipeline and always write a report, without needing to pass `--report`
+fn run_report(args: QcArgs) -> Result<()> {
+    let file_config = load_pipeline_config(&args.pipeline)?;
+    let resolved = ResolvedConfig::resolve(&args.pipeline, file_config.as_ref());
+    let input_files = collect_input_files(&args.input)?;
+    if input_files.is_empty() {
+        return Err(anyhow::anyhow!("No FCS files found"));
+    }
+
+    let processing_config = build_processing_config(&resolved, resolved.cofactor)?;
+
+    let results: Vec<FileResult> = input_files
+        .iter()
+        .map(|input_path| process_single_file(input_path, resolved.output.as_deref(), &processing_config))
+        .collect();
+
+    let had_errors = results.iter().any(|r| r.error.is_some());
+    let report = serde_json::json!({
+        "total_files": results.len(),
+        "results": results.iter().map(result_to_json).collect::<Vec<_>>(),
+    });
+
+    match &resolved.report {
+        Some(path) => {
+            std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            println!("✅ Wrote report to: {}", path.display());
+        }
+        None => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// JSON representation of a single file's result, shared by the `report` subcommand
+fn result_to_json(result: &FileResult) -> serde_json::Value {
+    serde_json::json!({
+        "filename": result.filename,
+        "n_events_before": result.n_events_before,
+        "n_events_after": result.n_events_after,
+        "percentage_removed": result.percentage_removed,
+        "it_percentage": result.it_percentage,
+        "mad_percentage": result.mad_percentage,
+        "consecutive_percentage": result.consecutive_percentage,
+        "processing_time_ms": result.processing_time_ms,
+        "error": result.error,
+    })
+}
+
+/// Build a `ProcessingConfig` from a resolved pipeline configuration and a specific cofactor
+fn build_processing_config(resolved: &ResolvedConfig, cofactor: f32) -> Result<ProcessingConfig> {
+    Ok(ProcessingConfig {
+        channels: resolve_include_channels(resolved)?,
+        exclude_channels: resolved.exclude_channels.clone(),
+        qc_mode: resolved.qc_mode.clone().into(),
+        mad: resolved.mad,
+        it_limit: resolved.it_limit,
+        consecutive_bins: resolved.consecutive_bins,
+        remove_zeros: resolved.remove_zeros,
+        remove_margins: !resolved.keep_margins,
+        remove_doublets: !resolved.keep_doublets,
+        doublet_nmad: resolved.doublet_nmad,
+        export_csv: resolved.export_csv.clone(),
+        export_csv_numeric: resolved.export_csv_numeric.clone(),
+        export_json: resolved.export_json.clone(),
+        csv_column_name: resolved.csv_column_name.clone(),
+        spillover_override: resolved.spillover_override().cloned(),
+        cofactor,
+        generate_plots: false,
+        plot_dir: resolved.plot_dir.clone(),
+        anonymize: resolved.anonymize,
+    })
+}
+
+/// Load each input file and print diagnostics without running any processing
+fn run_validate(args: ValidateArgs) -> Result<()> {
+    let input_files = collect_input_files(&args.input)?;
+    if input_files.is_empty() {
+        return Err(anyhow::anyhow!("No FCS files found"));
+    }
+
+    let mut had_errors = false;
+    let mut results = Vec::new();
+    for input_path in &input_files {
+        let filename = input_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+
+        match open_and_diagnose(input_path, None) {
+            Ok((fcs, has_compensation)) => {
+                let n_events = fcs.get_event_count_from_dataframe();
+                let n_channels = fcs.channel_names().len();
+                if !args.json {
+                    println!(
+                        "✅ {}: {} events, {} channels, compensation {}",
+                        filename,
+                        n_events,
+                        n_channels,
+                        if has_compensation { "available" } else { "not available" }
+                    );
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events": n_events,
+                    "n_channels": n_channels,
+                    "compensation_available": has_compensation,
+                    "error": null,
+                }));
+            }
+            Err(e) => {
+                if !args.json {
+                    eprintln!("❌ {}: {}", filename, e);
+                }
+                results.push(serde_json::json!({
+                    "filename": filename,
+                    "n_events": null,
+                    "n_channels": null,
+                    "compensation_available": null,
+                    "error": e.to_string(),
+                }));
+                had_errors = true;
+            }
+        }
+    }
+
+    if args.json {
+        let summary = serde_json::json!({
+            "total_files": results.len(),
+            "results": results,
+        });
+        println!("{}", serde_json::to_string(&summary)?);
+    }
+
+    if had_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    // Initialize tracing subscriber with environment filter
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .init();
+
+    match Cli::parse().command {
+        Commands::Qc(args) => run_qc(args),
+        Commands::Margins(args) => run_margins(args),
+        Commands::Doublets(args) => run_doublets(args),
+        Commands::Plot(args) => run_plot(args),
+        Commands::Report(args) => run_report(args),
+        Commands::Validate(args) => run_validate(args),
+        Commands::Watch(args) => run_watch(args),
+    }
+}
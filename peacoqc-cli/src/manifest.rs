@@ -0,0 +1,69 @@
+//! Resume manifest for skipping files already processed with identical settings
+//!
+//! Tracks, per input file, a signature of its content plus the pipeline settings used to
+//! process it. `--resume` skips a file whose recorded signature still matches; `--force`
+//! reprocesses everything regardless of what's recorded.
+
+use anyhow::{Context, Result};
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// One recorded processing run for a given input file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub input_signature: String,
+    pub config_signature: String,
+}
+
+/// Maps an input file path (as given or found under an input directory, not canonicalized) to
+/// its last recorded processing signature
+pub type Manifest = HashMap<String, ManifestEntry>;
+
+/// Load a manifest from disk; a missing or unreadable file is treated as an empty manifest so
+/// the first `--resume` run behaves the same as a normal run
+pub fn load_manifest(path: &Path) -> Manifest {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the manifest to disk as JSON
+pub fn save_manifest(path: &Path, manifest: &Manifest) -> Result<()> {
+    let contents = serde_json::to_string_pretty(manifest)?;
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write manifest: {}", path.display()))
+}
+
+/// Signature for an input file's content, based on size and modification time rather than
+/// hashing the full file — FCS files can be gigabytes, and the manifest only needs to notice
+/// that a file changed, not verify its exact bytes
+pub fn input_signature(path: &Path) -> Result<String> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read metadata for: {}", path.display()))?;
+
+    let mut hasher = FxHasher::default();
+    metadata.len().hash(&mut hasher);
+    metadata.modified().ok().hash(&mut hasher);
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// Signature for the pipeline settings a file was (or would be) processed with, so a config
+/// change is treated the same as a changed input file. `settings` should already describe every
+/// setting that affects the QC result.
+pub fn config_signature(settings: &str) -> String {
+    let mut hasher = FxHasher::default();
+    settings.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// True if `key` was already processed with the same input and config signature, and should be
+/// skipped under `--resume`
+pub fn already_processed(manifest: &Manifest, key: &str, input_sig: &str, config_sig: &str) -> bool {
+    manifest
+        .get(key)
+        .is_some_and(|entry| entry.input_signature == input_sig && entry.config_signature == config_sig)
+}
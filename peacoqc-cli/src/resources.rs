@@ -0,0 +1,86 @@
+//! Concurrency and memory budget controls
+//!
+//! `--jobs` bounds how many files rayon processes at once; `--max-memory` additionally gates
+//! processing on an estimate of how much memory each file needs, so a directory of multi-GB FCS
+//! files doesn't spawn enough concurrent loads to OOM the machine even when there are plenty of
+//! CPU cores free.
+
+use anyhow::{Result, anyhow};
+use std::path::Path;
+use std::sync::{Condvar, Mutex};
+
+/// Rough multiplier from on-disk FCS file size to peak memory used while processing it: the
+/// loader keeps the raw DataFrame, a compensated/transformed copy, and QC feature matrices
+/// alive at once, so actual peak usage runs a few times the file size rather than 1:1
+const MEMORY_ESTIMATE_MULTIPLIER: u64 = 3;
+
+/// Parse a human-readable size like `"8GB"`, `"512MB"`, `"2048KB"`, or a plain byte count into
+/// bytes. Suffixes are case-insensitive and the trailing `B` is optional (`"8G"` == `"8GB"`).
+pub fn parse_memory_size(input: &str) -> Result<u64> {
+    let trimmed = input.trim();
+    let upper = trimmed.to_uppercase();
+
+    let (number_part, multiplier) = if let Some(prefix) = upper.strip_suffix("GB").or(upper.strip_suffix("G")) {
+        (prefix, 1024 * 1024 * 1024)
+    } else if let Some(prefix) = upper.strip_suffix("MB").or(upper.strip_suffix("M")) {
+        (prefix, 1024 * 1024)
+    } else if let Some(prefix) = upper.strip_suffix("KB").or(upper.strip_suffix("K")) {
+        (prefix, 1024)
+    } else if let Some(prefix) = upper.strip_suffix("B") {
+        (prefix, 1)
+    } else {
+        (upper.as_str(), 1)
+    };
+
+    let value: f64 = number_part
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid memory size: {input:?} (expected e.g. \"8GB\", \"512MB\")"))?;
+
+    Ok((value * multiplier as f64) as u64)
+}
+
+/// Estimate the peak memory a file will need while being processed, from its on-disk size
+pub fn estimate_memory_bytes(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .map(|metadata| metadata.len() * MEMORY_ESTIMATE_MULTIPLIER)
+        .unwrap_or(0)
+}
+
+/// A weighted semaphore over a fixed memory budget: callers reserve an estimated number of
+/// bytes before processing a file and release them afterward, blocking while too little budget
+/// is free rather than tracking real allocations
+pub struct MemoryBudget {
+    available: Mutex<u64>,
+    condvar: Condvar,
+    total: u64,
+}
+
+impl MemoryBudget {
+    pub fn new(total_bytes: u64) -> Self {
+        Self {
+            available: Mutex::new(total_bytes),
+            condvar: Condvar::new(),
+            total: total_bytes,
+        }
+    }
+
+    /// Block until `bytes` (capped at the total budget, so a single file larger than the whole
+    /// budget still runs once nothing else holds a reservation) is free, then reserve it.
+    /// Returns the amount actually reserved, to be passed to `release`.
+    pub fn acquire(&self, bytes: u64) -> u64 {
+        let reserved = bytes.min(self.total);
+        let mut available = self.available.lock().unwrap();
+        while *available < reserved {
+            available = self.condvar.wait(available).unwrap();
+        }
+        *available -= reserved;
+        reserved
+    }
+
+    pub fn release(&self, reserved: u64) {
+        let mut available = self.available.lock().unwrap();
+        *available += reserved;
+        self.condvar.notify_all();
+    }
+}
@@ -0,0 +1,84 @@
+//! Loading an externally computed compensation/unmixing matrix from CSV
+//!
+//! `--spillover`/`--unmix` let a user supply a matrix computed outside this tool (e.g. from
+//! single-stain controls run through spectral unmixing software) when a file's `$SPILLOVER`
+//! keyword is missing or wrong. The CSV is a labeled matrix: a header row of channel names
+//! (first cell blank/ignored), then one row per channel with its name followed by its
+//! spillover coefficients, in the same order as the header.
+
+use anyhow::{Context, Result, anyhow};
+use flow_fcs::CompensationMatrix;
+use ndarray::Array2;
+use std::path::Path;
+
+pub fn load_matrix_csv(path: &Path) -> Result<CompensationMatrix> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read matrix file: {}", path.display()))?;
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+
+    let header = lines
+        .next()
+        .ok_or_else(|| anyhow!("Matrix file is empty: {}", path.display()))?;
+    let channels: Vec<String> = header
+        .split(',')
+        .skip(1)
+        .map(|s| s.trim().to_string())
+        .collect();
+    if channels.is_empty() {
+        return Err(anyhow!(
+            "Matrix file has no channel columns: {}",
+            path.display()
+        ));
+    }
+
+    let mut values = Vec::with_capacity(channels.len() * channels.len());
+    let mut n_rows = 0;
+    for line in lines {
+        let mut fields = line.split(',').map(str::trim);
+        let row_channel = fields
+            .next()
+            .ok_or_else(|| anyhow!("Matrix row missing channel label: {}", path.display()))?;
+        let expected = channels.get(n_rows).ok_or_else(|| {
+            anyhow!(
+                "Matrix file has more rows than header channels ({}): {}",
+                channels.len(),
+                path.display()
+            )
+        })?;
+        if row_channel != expected {
+            return Err(anyhow!(
+                "Matrix row {} is labeled {row_channel:?}, expected {expected:?} (rows must match header order): {}",
+                n_rows + 1,
+                path.display()
+            ));
+        }
+
+        for field in fields {
+            let value: f32 = field.parse().map_err(|_| {
+                anyhow!(
+                    "Invalid coefficient {field:?} in row {row_channel:?}: {}",
+                    path.display()
+                )
+            })?;
+            values.push(value);
+        }
+        n_rows += 1;
+    }
+
+    if n_rows != channels.len() {
+        return Err(anyhow!(
+            "Matrix file has {} channel(s) but {n_rows} row(s): {}",
+            channels.len(),
+            path.display()
+        ));
+    }
+
+    let matrix = Array2::from_shape_vec((channels.len(), channels.len()), values)
+        .map_err(|e| anyhow!("Malformed matrix rows in {}: {e}", path.display()))?;
+
+    let compensation = CompensationMatrix::new(channels, matrix)?;
+    compensation
+        .validate()
+        .with_context(|| format!("Invalid matrix in {}", path.display()))?;
+    Ok(compensation)
+}
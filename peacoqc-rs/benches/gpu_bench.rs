@@ -171,7 +171,7 @@ fn bench_feature_matrix_cpu_vs_gpu(c: &mut Criterion) {
             &peak_results,
             |b, pr| {
                 b.iter(|| {
-                    build_feature_matrix(black_box(pr), *n_bins).unwrap()
+                    build_feature_matrix(black_box(pr), *n_bins, &HashMap::new()).unwrap()
                 })
             },
         );
@@ -186,7 +186,7 @@ fn bench_feature_matrix_cpu_vs_gpu(c: &mut Criterion) {
                     &peak_results,
                     |b, pr| {
                         b.iter(|| {
-                            build_feature_matrix_gpu(black_box(pr), *n_bins).unwrap()
+                            build_feature_matrix_gpu(black_box(pr), *n_bins, &HashMap::new()).unwrap()
                         })
                     },
                 );
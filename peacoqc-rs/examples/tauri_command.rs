@@ -98,8 +98,11 @@ pub async fn run_peacoqc(
     // === STEP 1: Load FCS ===
     let mut fcs = open_from_str(file_path)?;
     let n_events_initial = fcs.data_frame.height();
-    let filename = fcs.file_access.path
-        .file_name()
+    let filename = fcs
+        .file_access
+        .path
+        .as_ref()
+        .and_then(|p| p.file_name())
         .and_then(|n| n.to_str())
         .unwrap_or("unknown")
         .to_string();
@@ -134,9 +137,7 @@ pub async fn run_peacoqc(
 
         let margin_config = MarginConfig {
             channels: channels.clone(),
-            channel_specifications: None,
-            remove_min: None,
-            remove_max: None,
+            ..Default::default()
         };
 
         let margin_result = remove_margins(&fcs, &margin_config)?;
@@ -156,7 +157,7 @@ pub async fn run_peacoqc(
             channel1: "FSC-A".to_string(),
             channel2: "FSC-H".to_string(),
             nmad: doublet_nmad.unwrap_or(4.0),
-            b: 0.0,
+            ..Default::default()
         };
 
         match remove_doublets(&fcs, &doublet_config) {
@@ -1,10 +1,12 @@
 // Test helpers for PeacoQC tests
 // This module provides SimpleFcs for testing without requiring a full Fcs implementation
 
-use crate::PeacoQCData;
-use crate::error::Result;
+use crate::{FcsFilter, PeacoQCData};
+use crate::error::{PeacoQCError, Result};
 use flow_fcs::parameter::EventDataFrame;
+use polars::prelude::*;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Simplified FCS structure for testing without full Fcs implementation
 /// Your code should use the trait implementation above instead
@@ -61,3 +63,28 @@ impl PeacoQCData for SimpleFcs {
         Ok(values)
     }
 }
+
+impl FcsFilter for SimpleFcs {
+    fn filter(&self, mask: &[bool]) -> Result<Self> {
+        let n_events = self.n_events();
+        if mask.len() != n_events {
+            return Err(PeacoQCError::StatsError(format!(
+                "Mask length {} doesn't match event count {}",
+                mask.len(),
+                n_events
+            )));
+        }
+
+        let mask_series = Series::new("mask".into(), mask.to_vec());
+        let mask_ca = mask_series.bool().map_err(|e| {
+            PeacoQCError::StatsError(format!("Failed to convert mask to boolean array: {}", e))
+        })?;
+
+        let filtered_df = self.data_frame.filter(&mask_ca).map_err(PeacoQCError::from)?;
+
+        Ok(Self {
+            data_frame: Arc::new(filtered_df),
+            parameter_metadata: self.parameter_metadata.clone(),
+        })
+    }
+}
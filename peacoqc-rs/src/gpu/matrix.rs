@@ -12,12 +12,20 @@ use std::collections::HashMap;
 ///
 /// Currently uses CPU implementation. GPU acceleration didn't provide benefits
 /// due to overhead for typical matrix sizes.
+///
+/// Channels mapped to a weight of `0.0` or less in `channel_weights` are dropped entirely (no
+/// columns are emitted for them), matching [`crate::qc::isolation_tree::build_feature_matrix`].
 pub fn build_feature_matrix_gpu(
     peak_results: &HashMap<String, ChannelPeakFrame>,
     n_bins: usize,
+    channel_weights: &HashMap<String, f64>,
 ) -> Result<(Vec<Vec<f64>>, Vec<String>)> {
     // Get channels in consistent order
-    let mut channel_names: Vec<String> = peak_results.keys().cloned().collect();
+    let mut channel_names: Vec<String> = peak_results
+        .keys()
+        .filter(|channel| channel_weights.get(*channel).copied().unwrap_or(1.0) > 0.0)
+        .cloned()
+        .collect();
     channel_names.sort();
 
     // Collect all clusters per channel
@@ -3,7 +3,9 @@
 //! This module provides GPU-accelerated implementations for:
 //! - FFT-based Kernel Density Estimation (KDE)
 //! - Feature matrix operations
-//! - Statistical calculations
+//! - Statistical calculations, including batched median/MAD and smoothing-spline fits
+//!   across channels (these fall back to CPU internally, same as [`median_gpu`]/[`percentile_gpu`];
+//!   batching still amortizes overhead across the channel loop)
 //!
 //! ## Performance
 //!
@@ -46,6 +48,8 @@ mod batched;
 mod matrix;
 #[cfg(feature = "gpu")]
 mod stats;
+#[cfg(feature = "gpu")]
+mod spline;
 
 #[cfg(all(feature = "gpu", feature = "cubecl"))]
 mod kernels;
@@ -61,7 +65,9 @@ pub use batched::{kde_fft_batched_gpu, KdeContext};
 #[cfg(feature = "gpu")]
 pub use matrix::build_feature_matrix_gpu;
 #[cfg(feature = "gpu")]
-pub use stats::{standard_deviation_gpu, median_gpu, percentile_gpu};
+pub use stats::{standard_deviation_gpu, median_gpu, percentile_gpu, median_mad_batched_gpu};
+#[cfg(feature = "gpu")]
+pub use spline::{smooth_spline_batched_gpu, SplineContext};
 
 // Threshold constants removed - GPU is now used whenever available
 // Batched operations provide speedup even for smaller datasets (50K+ events)
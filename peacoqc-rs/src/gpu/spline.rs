@@ -0,0 +1,26 @@
+//! Batched smoothing-spline fitting across channels
+//!
+//! Note: Falls back to CPU (the banded-matrix solve `csaps` performs doesn't map to GPU
+//! tensor ops any better than [`crate::gpu::median_gpu`]'s sort does). Batching still amortizes
+//! per-call overhead across channels by fitting them concurrently with `rayon`.
+
+use crate::error::Result;
+use rayon::prelude::*;
+
+/// One channel's inputs to [`smooth_spline_batched_gpu`]
+pub struct SplineContext<'a> {
+    pub x: &'a [f64],
+    pub y: &'a [f64],
+    pub spar: f64,
+}
+
+/// Fit a smoothing spline for many channels at once
+///
+/// Batched counterpart to [`crate::stats::smooth_spline`]. Errors for individual channels are
+/// reported per-channel rather than aborting the whole batch.
+pub fn smooth_spline_batched_gpu(contexts: &[SplineContext]) -> Vec<Result<Vec<f64>>> {
+    contexts
+        .par_iter()
+        .map(|ctx| crate::stats::smooth_spline(ctx.x, ctx.y, ctx.spar))
+        .collect()
+}
@@ -6,6 +6,7 @@
 use burn::backend::wgpu::WgpuDevice;
 use burn::tensor::Tensor;
 use crate::error::{PeacoQCError, Result};
+use rayon::prelude::*;
 
 type Backend = burn::backend::wgpu::Wgpu;
 
@@ -79,3 +80,18 @@ pub fn percentile_gpu(data: &[f64], p: f64) -> Result<f64> {
 
     Ok(sorted[idx])
 }
+
+/// Calculate median and MAD for many channels at once
+///
+/// Batched counterpart to [`crate::stats::median_mad`], following the same pattern as
+/// [`crate::gpu::kde_fft_batched_gpu`]: rather than a genuine GPU kernel (median/MAD need a
+/// sort, which doesn't map to GPU tensor ops any better than [`median_gpu`] does), this
+/// amortizes the per-call overhead of spinning up the channel loop across the whole batch by
+/// processing every channel's sort concurrently with `rayon`. Errors for individual channels
+/// (e.g. empty data) are reported per-channel rather than aborting the whole batch.
+pub fn median_mad_batched_gpu(channels: &[&[f64]]) -> Vec<Result<(f64, f64)>> {
+    channels
+        .par_iter()
+        .map(|data| crate::stats::median_mad(data))
+        .collect()
+}
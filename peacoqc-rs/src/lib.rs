@@ -100,14 +100,52 @@ pub mod fcs;
 
 pub use error::{PeacoQCError, Result};
 pub use qc::{
-    DoubletConfig, DoubletResult, MarginConfig, MarginResult, PeacoQCConfig, PeacoQCResult,
-    QCExportFormat, QCExportOptions, QCMode, QCPlotConfig, create_qc_plots, export_csv_boolean,
-    export_csv_numeric, export_json_metadata, peacoqc, remove_doublets, remove_margins,
+    BeadNormalizationConfig, BeadNormalizationResult, BinConfusion, BinFlag, BinSizeStrategy,
+    CalibrationConfig, CalibrationResult,
+    ChannelContribution, ChannelDriftDiagnostics,
+    ChannelFingerprint, ChunkedPeacoQCData, ConfusionMatrix, ConsecutiveScope,
+    DoubletConfig, DoubletFit,
+    DoubletMethod, DoubletResult, DriftCorrectionConfig, DriftCorrectionResult, FlowAIConfig,
+    FlowAIResult, FlowCutConfig, FlowCutResult,
+    IsolationForestConfig, IsolationForestResult, MarginChannelReport, MarginConfig, MarginResult,
+    ModelDoubletFit, OnlineQC,
+    OnlineQCConfig, PeacoQCConfig,
+    PeacoQCResult, QCMode, RComparisonOptions,
+    RParityReport, SavedQCConfig,
+    SavedQCMask, SecondaryDoubletConfig, StreamingPeacoQCConfig, TimeChannelDiagnostics,
+    TimeSegment, calibrate_thresholds,
+    compare_to_r, correct_drift,
+    detect_time_issues,
+    flow_ai, flow_cut,
+    isolation_forest_detect, normalize_with_beads, peacoqc, peacoqc_streaming,
+    reapply, remove_doublets,
+    remove_margins, segment_by_time, sort_permutation_by_time,
 };
 
 #[cfg(feature = "flow-fcs")]
 pub use crate::flow_fcs_impl::preprocess_fcs;
 
+#[cfg(feature = "file-io")]
+pub use qc::{
+    QCExportFormat, QCExportOptions, export_csv_boolean, export_csv_numeric, export_json_metadata,
+    load_r_csv,
+};
+
+#[cfg(feature = "plotting")]
+pub use qc::{PlotFormat, QCPlotConfig, create_qc_plots};
+
+#[cfg(feature = "flow-plots")]
+pub use qc::{DensityQCPlotConfig, create_density_qc_plots};
+
+#[cfg(feature = "xlsx")]
+pub use qc::{QCFileReport, export_xlsx};
+
+#[cfg(feature = "parquet")]
+pub use qc::{export_arrow_mask, export_parquet_mask};
+
+#[cfg(feature = "flow-gates")]
+pub use qc::qc_result_to_time_gates;
+
 /// Trait for data structures that can be used with PeacoQC
 ///
 /// Implement this trait on your FCS data structure to enable PeacoQC analysis.
@@ -161,6 +199,29 @@ pub trait PeacoQCData {
             })
             .collect()
     }
+
+    /// Feed a channel's values to `f` in chunks of roughly `chunk_size`, instead of
+    /// materializing the whole channel as one [`Vec<f64>`] up front
+    ///
+    /// The default implementation just calls [`Self::get_channel_f64`] and slices the result,
+    /// so it pays the same allocation cost as calling that method directly - implementors
+    /// backed by a chunked columnar store (e.g. Polars) should override this to feed their
+    /// underlying chunks straight to `f` instead of collecting the whole channel first.
+    ///
+    /// # Errors
+    /// Returns `Err` under the same conditions as [`Self::get_channel_f64`].
+    fn for_each_channel_chunk(
+        &self,
+        channel: &str,
+        chunk_size: usize,
+        mut f: impl FnMut(&[f64]),
+    ) -> Result<()> {
+        let data = self.get_channel_f64(channel)?;
+        for chunk in data.chunks(chunk_size.max(1)) {
+            f(chunk);
+        }
+        Ok(())
+    }
 }
 
 /// Extension trait for FCS data structures to add filtering capabilities
@@ -277,6 +338,48 @@ mod flow_fcs_impl {
                 .map(|p| p.channel_name.to_string())
                 .collect()
         }
+
+        fn for_each_channel_chunk(
+            &self,
+            channel: &str,
+            chunk_size: usize,
+            mut f: impl FnMut(&[f64]),
+        ) -> Result<()> {
+            let series = self
+                .data_frame
+                .column(channel)
+                .map_err(|_| PeacoQCError::ChannelNotFound(channel.to_string()))?;
+            let chunk_size = chunk_size.max(1);
+
+            // Feed the Polars column's own physical chunks to `f`, so a multi-chunk column
+            // never has to be collected into one contiguous Vec<f64> just to be scanned.
+            if let Ok(f64_vals) = series.f64() {
+                for arrow_chunk in f64_vals.downcast_iter() {
+                    let buf: Vec<f64> = arrow_chunk.into_iter().filter_map(|v| v.copied()).collect();
+                    for sub in buf.chunks(chunk_size) {
+                        f(sub);
+                    }
+                }
+            } else if let Ok(f32_vals) = series.f32() {
+                for arrow_chunk in f32_vals.downcast_iter() {
+                    let buf: Vec<f64> = arrow_chunk
+                        .into_iter()
+                        .filter_map(|v| v.map(|x| *x as f64))
+                        .collect();
+                    for sub in buf.chunks(chunk_size) {
+                        f(sub);
+                    }
+                }
+            } else {
+                return Err(PeacoQCError::InvalidChannel(format!(
+                    "Channel {} is not numeric (dtype: {:?})",
+                    channel,
+                    series.dtype()
+                )));
+            }
+
+            Ok(())
+        }
     }
 
     impl FcsFilter for Fcs {
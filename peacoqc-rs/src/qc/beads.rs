@@ -0,0 +1,284 @@
+//! EQ-bead normalization for mass cytometry (CyTOF)
+//!
+//! Implements bead-based normalization along the lines of Finck et al. (2013): spike-in
+//! calibration beads carry a constant signal in a handful of bead channels, so any drift
+//! observed in those channels over the course of an acquisition reflects instrument drift
+//! rather than biology. [`normalize_with_beads`] identifies bead events from the bead channels,
+//! tracks a smoothed bead-signal baseline in bins over acquisition order (the same
+//! bin-then-smooth shape [`crate::qc::mad`] and [`crate::qc::drift`] use), and rescales every
+//! requested channel by the same interpolated correction factor - the drift is instrument-wide,
+//! so one factor per event is applied uniformly rather than per channel.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::stats::median;
+use crate::stats::spline::smooth_spline;
+use std::collections::HashMap;
+
+/// Configuration for [`normalize_with_beads`]
+#[derive(Debug, Clone)]
+pub struct BeadNormalizationConfig {
+    /// Channel names carrying the bead signal (e.g. the Pt/Ce EQ bead isotopes)
+    pub bead_channels: Vec<String>,
+    /// Minimum intensity an event must have in every bead channel to be classified as a bead
+    /// rather than a cell (default: 0.0 - tune per panel and acquisition)
+    pub bead_threshold: f64,
+    /// Number of consecutive bead events grouped into one smoothing bin, each bin contributing
+    /// the median of its events' normalized bead signal (default: 100)
+    pub events_per_bin: usize,
+    /// Smoothing parameter passed to [`crate::stats::spline::smooth_spline`] (default: 0.5)
+    pub smooth_param: f64,
+}
+
+impl Default for BeadNormalizationConfig {
+    fn default() -> Self {
+        Self {
+            bead_channels: Vec::new(),
+            bead_threshold: 0.0,
+            events_per_bin: 100,
+            smooth_param: 0.5,
+        }
+    }
+}
+
+/// Result of [`normalize_with_beads`]
+#[derive(Debug, Clone)]
+pub struct BeadNormalizationResult {
+    /// Event indices classified as bead events
+    pub bead_indices: Vec<usize>,
+    /// Baseline (overall bead-event median) per bead channel, the target every bin is
+    /// normalized against
+    pub baseline: HashMap<String, f64>,
+    /// Corrected values, one entry per requested channel
+    pub corrected_channels: HashMap<String, Vec<f64>>,
+    /// Per-event correction factor applied (`corrected = raw / correction_factor`)
+    pub correction_factors: Vec<f64>,
+}
+
+/// Detect bead events and normalize channels against their smoothed drift
+///
+/// # Errors
+/// Returns `Err` if `config.bead_channels` is empty, a bead channel can't be read, or fewer
+/// than two events are classified as beads.
+pub fn normalize_with_beads<T: PeacoQCData>(
+    fcs: &T,
+    channels_to_correct: &[String],
+    config: &BeadNormalizationConfig,
+) -> Result<BeadNormalizationResult> {
+    if config.bead_channels.is_empty() {
+        return Err(PeacoQCError::ConfigError(
+            "at least one bead channel is required".to_string(),
+        ));
+    }
+
+    let n_events = fcs.n_events();
+    let bead_data: Vec<Vec<f64>> = config
+        .bead_channels
+        .iter()
+        .map(|c| fcs.get_channel_f64(c))
+        .collect::<Result<_>>()?;
+
+    let bead_indices: Vec<usize> = (0..n_events)
+        .filter(|&i| bead_data.iter().all(|channel| channel[i] >= config.bead_threshold))
+        .collect();
+
+    if bead_indices.len() < 2 {
+        return Err(PeacoQCError::InsufficientData {
+            min: 2,
+            actual: bead_indices.len(),
+        });
+    }
+
+    // Baseline and per-bead-event normalized ratio (value / baseline) for each bead channel.
+    let mut baseline = HashMap::new();
+    let mut per_channel_ratios: Vec<Vec<f64>> = Vec::with_capacity(config.bead_channels.len());
+    for (channel, data) in config.bead_channels.iter().zip(&bead_data) {
+        let bead_values: Vec<f64> = bead_indices.iter().map(|&i| data[i]).collect();
+        let channel_baseline = median(&bead_values)?;
+        baseline.insert(channel.clone(), channel_baseline);
+
+        let ratios: Vec<f64> = bead_values
+            .iter()
+            .map(|&v| {
+                if channel_baseline.abs() > 1e-10 {
+                    v / channel_baseline
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+        per_channel_ratios.push(ratios);
+    }
+
+    // Combine bead channels into a single drift signal per bead event by averaging their
+    // normalized ratios, matching Finck et al.'s instrument-wide (not per-channel) slope.
+    let n_beads = bead_indices.len();
+    let mut combined_ratio = vec![0.0; n_beads];
+    for ratios in &per_channel_ratios {
+        for (acc, &r) in combined_ratio.iter_mut().zip(ratios) {
+            *acc += r;
+        }
+    }
+    for r in &mut combined_ratio {
+        *r /= per_channel_ratios.len() as f64;
+    }
+
+    // Bin bead events in acquisition order, then smooth the bin medians over time.
+    let mut bin_positions = Vec::new();
+    let mut bin_medians = Vec::new();
+    for (index_chunk, ratio_chunk) in bead_indices
+        .chunks(config.events_per_bin)
+        .zip(combined_ratio.chunks(config.events_per_bin))
+    {
+        let position = index_chunk.iter().sum::<usize>() as f64 / index_chunk.len() as f64;
+        bin_positions.push(position);
+        bin_medians.push(median(ratio_chunk)?);
+    }
+
+    let smoothed = smooth_spline(&bin_positions, &bin_medians, config.smooth_param)?;
+    let correction_factors = interpolate_factors(&bin_positions, &smoothed, n_events);
+
+    let mut corrected_channels = HashMap::new();
+    for channel in channels_to_correct {
+        let data = fcs.get_channel_f64(channel)?;
+        let corrected: Vec<f64> = data
+            .iter()
+            .zip(&correction_factors)
+            .map(|(&v, &factor)| if factor.abs() > 1e-10 { v / factor } else { v })
+            .collect();
+        corrected_channels.insert(channel.clone(), corrected);
+    }
+
+    Ok(BeadNormalizationResult {
+        bead_indices,
+        baseline,
+        corrected_channels,
+        correction_factors,
+    })
+}
+
+/// Linearly interpolate `y` (known at positions `x`) at every integer position `0..n_events`,
+/// clamping to the first/last known value outside `x`'s range
+fn interpolate_factors(x: &[f64], y: &[f64], n_events: usize) -> Vec<f64> {
+    let mut result = Vec::with_capacity(n_events);
+    let mut j = 0;
+    for i in 0..n_events {
+        let t = i as f64;
+        while j + 1 < x.len() && x[j + 1] < t {
+            j += 1;
+        }
+
+        if t <= x[0] {
+            result.push(y[0]);
+        } else if t >= x[x.len() - 1] {
+            result.push(y[y.len() - 1]);
+        } else {
+            let (x0, x1, y0, y1) = (x[j], x[j + 1], y[j], y[j + 1]);
+            let frac = if x1 > x0 { (t - x0) / (x1 - x0) } else { 0.0 };
+            result.push(y0 + frac * (y1 - y0));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::SimpleFcs;
+    use polars::df;
+    use std::collections::HashMap as StdHashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_normalize_with_beads_requires_bead_channels() {
+        let df = Arc::new(df!["Bead1" => vec![1.0, 1.0]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = BeadNormalizationConfig::default();
+        let err = normalize_with_beads(&fcs, &["Bead1".to_string()], &config).unwrap_err();
+        assert!(matches!(err, PeacoQCError::ConfigError(_)));
+    }
+
+    #[test]
+    fn test_normalize_with_beads_corrects_drift() {
+        let n = 1000;
+        // Bead signal drifts upward; a correlated cell channel drifts the same way.
+        let bead: Vec<f64> = (0..n).map(|i| 100.0 + i as f64 * 0.1).collect();
+        let cell: Vec<f64> = (0..n).map(|i| 50.0 + i as f64 * 0.05).collect();
+
+        let df = Arc::new(df!["Bead1" => bead, "FL1-A" => cell].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = BeadNormalizationConfig {
+            bead_channels: vec!["Bead1".to_string()],
+            bead_threshold: 0.0,
+            events_per_bin: 50,
+            smooth_param: 0.5,
+        };
+
+        let result =
+            normalize_with_beads(&fcs, &["FL1-A".to_string()], &config).unwrap();
+
+        assert_eq!(result.bead_indices.len(), n);
+        let corrected = &result.corrected_channels["FL1-A"];
+        let first_bin_median = median(&corrected[0..50]).unwrap();
+        let last_bin_median = median(&corrected[950..1000]).unwrap();
+        assert!(
+            (first_bin_median - last_bin_median).abs() < 1.0,
+            "corrected cell channel should have its drift removed: {} vs {}",
+            first_bin_median,
+            last_bin_median
+        );
+    }
+
+    #[test]
+    fn test_normalize_with_beads_stable_signal_is_unchanged() {
+        let n = 500;
+        let bead: Vec<f64> = vec![100.0; n];
+        let cell: Vec<f64> = (0..n).map(|i| 50.0 + (i as f64 % 5.0)).collect();
+
+        let df = Arc::new(df!["Bead1" => bead, "FL1-A" => cell.clone()].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = BeadNormalizationConfig {
+            bead_channels: vec!["Bead1".to_string()],
+            bead_threshold: 0.0,
+            events_per_bin: 50,
+            smooth_param: 0.5,
+        };
+
+        let result =
+            normalize_with_beads(&fcs, &["FL1-A".to_string()], &config).unwrap();
+
+        for (corrected, raw) in result.corrected_channels["FL1-A"].iter().zip(&cell) {
+            assert!((corrected - raw).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_normalize_with_beads_too_few_bead_events() {
+        let df = Arc::new(df!["Bead1" => vec![0.0, 0.0, 0.0]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = BeadNormalizationConfig {
+            bead_channels: vec!["Bead1".to_string()],
+            bead_threshold: 5.0,
+            ..Default::default()
+        };
+
+        let err = normalize_with_beads(&fcs, &["Bead1".to_string()], &config).unwrap_err();
+        assert!(matches!(err, PeacoQCError::InsufficientData { .. }));
+    }
+}
@@ -0,0 +1,213 @@
+//! Automatic MAD/IT threshold calibration
+//!
+//! Rather than guessing `mad`/`it_limit` values, [`calibrate_thresholds`] sweeps each over a
+//! grid on a (optionally subsampled) copy of the data, records the removal-vs-threshold curve
+//! for each, and suggests the value at the curve's knee - the point past which loosening the
+//! threshold further stops buying much of a reduction in events removed.
+
+use crate::FcsFilter;
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::qc::peacoqc::{PeacoQCConfig, QCMode, peacoqc};
+use tracing::info;
+
+/// Configuration for [`calibrate_thresholds`]
+#[derive(Debug, Clone)]
+pub struct CalibrationConfig {
+    /// `mad` values to sweep, holding everything else fixed at `base_config` (default:
+    /// 3.0..=10.0 in steps of 0.5)
+    pub mad_grid: Vec<f64>,
+
+    /// `it_limit` values to sweep, holding everything else fixed at `base_config` (default:
+    /// 0.3..=0.9 in steps of 0.05)
+    pub it_limit_grid: Vec<f64>,
+
+    /// Cap on the number of events the sweep runs against; the data is evenly (stride)
+    /// subsampled down to this size first so the grid search stays fast on large files
+    /// (default: 50,000)
+    pub subsample_size: usize,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            mad_grid: (6..=20).map(|i| i as f64 * 0.5).collect(),
+            it_limit_grid: (6..=18).map(|i| i as f64 * 0.05).collect(),
+            subsample_size: 50_000,
+        }
+    }
+}
+
+/// Result of [`calibrate_thresholds`]
+#[derive(Debug, Clone)]
+pub struct CalibrationResult {
+    /// `(mad, percentage_removed)` points from the MAD sweep, in `mad_grid` order
+    pub mad_curve: Vec<(f64, f64)>,
+
+    /// `(it_limit, percentage_removed)` points from the IT sweep, in `it_limit_grid` order
+    pub it_limit_curve: Vec<(f64, f64)>,
+
+    /// Suggested `mad` value, taken at `mad_curve`'s knee
+    pub suggested_mad: f64,
+
+    /// Suggested `it_limit` value, taken at `it_limit_curve`'s knee
+    pub suggested_it_limit: f64,
+
+    /// Number of events the sweep actually ran against, after subsampling
+    pub n_events_used: usize,
+}
+
+/// Build an evenly-spaced boolean mask that keeps roughly `target` of `n_events` events
+fn stride_subsample_mask(n_events: usize, target: usize) -> Vec<bool> {
+    if target >= n_events || target == 0 {
+        return vec![true; n_events];
+    }
+    let stride = n_events as f64 / target as f64;
+    let mut mask = vec![false; n_events];
+    let mut next = 0.0;
+    while (next as usize) < n_events {
+        mask[next as usize] = true;
+        next += stride;
+    }
+    mask
+}
+
+/// Find the knee of a curve: the point farthest (perpendicular distance) from the straight
+/// line connecting its first and last points. Works regardless of whether the curve rises or
+/// falls, and regardless of concavity direction.
+fn find_knee(curve: &[(f64, f64)]) -> f64 {
+    let Some(&(x0, y0)) = curve.first() else {
+        return 0.0;
+    };
+    if curve.len() < 3 {
+        return x0;
+    }
+    let (xn, yn) = curve[curve.len() - 1];
+    let dx = xn - x0;
+    let dy = yn - y0;
+    let norm = (dx * dx + dy * dy).sqrt();
+    if norm == 0.0 {
+        return x0;
+    }
+
+    let mut best_x = x0;
+    let mut best_distance = -1.0;
+    for &(x, y) in curve {
+        let distance = (dy * (x - x0) - dx * (y - y0)).abs() / norm;
+        if distance > best_distance {
+            best_distance = distance;
+            best_x = x;
+        }
+    }
+    best_x
+}
+
+/// Sweep `mad` and `it_limit` over a grid, report removal-vs-threshold curves, and suggest
+/// values at each curve's knee
+///
+/// The MAD sweep runs with [`QCMode::MAD`] and the IT sweep with [`QCMode::IsolationTree`], so
+/// each curve reflects that threshold's effect in isolation rather than the combined
+/// [`QCMode::All`] removal.
+///
+/// # Errors
+/// Returns an error if `base_config.channels` is empty, or if subsampling or any sweep run
+/// fails.
+pub fn calibrate_thresholds<T: PeacoQCData + FcsFilter>(
+    fcs: &T,
+    base_config: &PeacoQCConfig,
+    calibration: &CalibrationConfig,
+) -> Result<CalibrationResult> {
+    if base_config.channels.is_empty() {
+        return Err(PeacoQCError::ConfigError(
+            "No channels specified".to_string(),
+        ));
+    }
+
+    let mask = stride_subsample_mask(fcs.n_events(), calibration.subsample_size);
+    let subsample = fcs.filter(&mask)?;
+    let n_events_used = subsample.n_events();
+
+    info!(
+        "Calibrating MAD/IT thresholds on {} events ({} in mad_grid, {} in it_limit_grid)",
+        n_events_used,
+        calibration.mad_grid.len(),
+        calibration.it_limit_grid.len()
+    );
+
+    let mut mad_curve = Vec::with_capacity(calibration.mad_grid.len());
+    for &mad in &calibration.mad_grid {
+        let config = PeacoQCConfig {
+            determine_good_cells: QCMode::MAD,
+            mad,
+            ..base_config.clone()
+        };
+        let result = peacoqc(&subsample, &config)?;
+        mad_curve.push((mad, result.percentage_removed));
+    }
+
+    let mut it_limit_curve = Vec::with_capacity(calibration.it_limit_grid.len());
+    for &it_limit in &calibration.it_limit_grid {
+        let config = PeacoQCConfig {
+            determine_good_cells: QCMode::IsolationTree,
+            it_limit,
+            force_it: 0,
+            ..base_config.clone()
+        };
+        let result = peacoqc(&subsample, &config)?;
+        it_limit_curve.push((it_limit, result.percentage_removed));
+    }
+
+    let suggested_mad = find_knee(&mad_curve);
+    let suggested_it_limit = find_knee(&it_limit_curve);
+
+    Ok(CalibrationResult {
+        mad_curve,
+        it_limit_curve,
+        suggested_mad,
+        suggested_it_limit,
+        n_events_used,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stride_subsample_mask_keeps_all_when_target_exceeds_n_events() {
+        let mask = stride_subsample_mask(10, 20);
+        assert_eq!(mask, vec![true; 10]);
+    }
+
+    #[test]
+    fn test_stride_subsample_mask_keeps_roughly_target() {
+        let mask = stride_subsample_mask(1000, 100);
+        let kept = mask.iter().filter(|&&k| k).count();
+        assert!((90..=110).contains(&kept), "kept {kept} events, expected close to 100");
+    }
+
+    #[test]
+    fn test_find_knee_on_diminishing_returns_curve() {
+        // Percentage removed drops sharply at first, then flattens out - the knee should
+        // land near where the curve starts to flatten, not at either endpoint.
+        let curve: Vec<(f64, f64)> = vec![
+            (3.0, 40.0),
+            (4.0, 25.0),
+            (5.0, 15.0),
+            (6.0, 9.0),
+            (7.0, 6.0),
+            (8.0, 4.5),
+            (9.0, 3.8),
+            (10.0, 3.5),
+        ];
+        let knee = find_knee(&curve);
+        assert!((5.0..=7.0).contains(&knee), "knee at {knee}, expected near the bend");
+    }
+
+    #[test]
+    fn test_find_knee_handles_short_curves() {
+        assert_eq!(find_knee(&[]), 0.0);
+        assert_eq!(find_knee(&[(5.0, 1.0)]), 5.0);
+        assert_eq!(find_knee(&[(5.0, 1.0), (6.0, 2.0)]), 5.0);
+    }
+}
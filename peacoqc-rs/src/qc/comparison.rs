@@ -0,0 +1,305 @@
+//! R-parity regression harness
+//!
+//! Loads an R PeacoQC numeric CSV export (see [`crate::qc::export::export_csv_numeric`]'s
+//! 2000/6000 format) and compares it against a [`PeacoQCResult`] produced by this crate,
+//! reporting event-level and per-bin agreement. Exposed as a public API (rather than kept as an
+//! internal test helper) so a lab porting from R's PeacoQC can validate this crate against their
+//! own data and their own R run before switching production pipelines over.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use peacoqc_rs::{PeacoQCResult, compare_to_r, load_r_csv, RComparisonOptions};
+//!
+//! # let rust_result: PeacoQCResult = todo!();
+//! let r_good_cells = load_r_csv("r_reference_results.csv", &RComparisonOptions::default())?;
+//! let report = compare_to_r(&rust_result, &r_good_cells)?;
+//! println!("{:.2}% concordance with R", report.overall.percent_concordance());
+//! # Ok::<(), peacoqc_rs::PeacoQCError>(())
+//! ```
+
+use crate::error::{PeacoQCError, Result};
+use crate::qc::PeacoQCResult;
+use crate::qc::peaks::create_breaks;
+#[cfg(feature = "file-io")]
+use std::fs::File;
+#[cfg(feature = "file-io")]
+use std::io::{BufRead, BufReader};
+#[cfg(feature = "file-io")]
+use std::path::Path;
+
+/// Options controlling how an R PeacoQC CSV export is parsed
+///
+/// Mirrors [`crate::qc::export::QCExportOptions`]'s good/bad value convention, since a
+/// round-trip through [`crate::qc::export::export_csv_numeric`] is the intended source of the
+/// R-side file.
+#[derive(Debug, Clone)]
+pub struct RComparisonOptions {
+    /// Column name to read (default: "PeacoQC")
+    pub column_name: String,
+    /// Value R used for good/kept events (default: 2000)
+    pub good_value: u16,
+    /// Value R used for bad/removed events (default: 6000)
+    pub bad_value: u16,
+}
+
+impl Default for RComparisonOptions {
+    fn default() -> Self {
+        Self {
+            column_name: "PeacoQC".to_string(),
+            good_value: 2000,
+            bad_value: 6000,
+        }
+    }
+}
+
+/// Load an R PeacoQC numeric CSV export as a per-event good/bad mask
+///
+/// # Errors
+/// Returns `Err` if the file can't be read, is empty, or contains a value other than
+/// `options.good_value`/`options.bad_value`.
+#[cfg(feature = "file-io")]
+pub fn load_r_csv(path: impl AsRef<Path>, options: &RComparisonOptions) -> Result<Vec<bool>> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| {
+        PeacoQCError::InvalidPath(format!("Failed to open {}: {}", path.display(), e))
+    })?;
+
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines
+        .next()
+        .ok_or_else(|| PeacoQCError::ExportError("R CSV file is empty".to_string()))?
+        .map_err(|e| PeacoQCError::ExportError(format!("Failed to read header: {}", e)))?;
+
+    if header.trim() != options.column_name {
+        return Err(PeacoQCError::ExportError(format!(
+            "Expected column '{}', found '{}'",
+            options.column_name,
+            header.trim()
+        )));
+    }
+
+    let mut good_cells = Vec::new();
+    for line in lines {
+        let line = line.map_err(|e| PeacoQCError::ExportError(format!("Failed to read row: {}", e)))?;
+        let value: u16 = line.trim().parse().map_err(|_| {
+            PeacoQCError::ExportError(format!("Non-numeric value in R CSV: '{}'", line))
+        })?;
+
+        if value == options.good_value {
+            good_cells.push(true);
+        } else if value == options.bad_value {
+            good_cells.push(false);
+        } else {
+            return Err(PeacoQCError::ExportError(format!(
+                "Unexpected value {} in R CSV (expected {} or {})",
+                value, options.good_value, options.bad_value
+            )));
+        }
+    }
+
+    Ok(good_cells)
+}
+
+/// Event-level confusion matrix between two good/bad masks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    /// Both sides called the event good
+    pub true_positive: usize,
+    /// Both sides called the event bad
+    pub true_negative: usize,
+    /// Rust called good, R called bad
+    pub false_positive: usize,
+    /// Rust called bad, R called good
+    pub false_negative: usize,
+}
+
+impl ConfusionMatrix {
+    /// Total number of events covered by this matrix
+    pub fn n_events(&self) -> usize {
+        self.true_positive + self.true_negative + self.false_positive + self.false_negative
+    }
+
+    /// Percentage of events where Rust and R agreed
+    pub fn percent_concordance(&self) -> f64 {
+        let n = self.n_events();
+        if n == 0 {
+            return 100.0;
+        }
+        ((self.true_positive + self.true_negative) as f64 / n as f64) * 100.0
+    }
+
+    fn record(&mut self, rust_good: bool, r_good: bool) {
+        match (rust_good, r_good) {
+            (true, true) => self.true_positive += 1,
+            (false, false) => self.true_negative += 1,
+            (true, false) => self.false_positive += 1,
+            (false, true) => self.false_negative += 1,
+        }
+    }
+}
+
+/// Confusion matrix for a single bin, using the same boundaries the QC run itself used
+///
+/// Lets a caller see whether Rust/R disagreement clusters around particular bins (e.g. one
+/// side detecting a transient instrument clog the other missed) rather than being spread
+/// uniformly across the run.
+#[derive(Debug, Clone)]
+pub struct BinConfusion {
+    /// Bin index (0-based, matching [`PeacoQCResult`]'s bin numbering)
+    pub bin: usize,
+    /// Start event index (inclusive)
+    pub start: usize,
+    /// End event index (exclusive)
+    pub end: usize,
+    /// Confusion matrix restricted to events in this bin
+    pub confusion: ConfusionMatrix,
+}
+
+/// Full R-parity comparison report
+#[derive(Debug, Clone)]
+pub struct RParityReport {
+    /// Confusion matrix across every event
+    pub overall: ConfusionMatrix,
+    /// Confusion matrix broken down per bin
+    pub per_bin: Vec<BinConfusion>,
+}
+
+/// Compare a Rust [`PeacoQCResult`] against an R good/bad mask (see [`load_r_csv`])
+///
+/// # Errors
+/// Returns `Err` if the two masks don't cover the same number of events.
+pub fn compare_to_r(rust_result: &PeacoQCResult, r_good_cells: &[bool]) -> Result<RParityReport> {
+    if rust_result.good_cells.len() != r_good_cells.len() {
+        return Err(PeacoQCError::ConfigError(format!(
+            "Event count mismatch: Rust result has {} events, R export has {}",
+            rust_result.good_cells.len(),
+            r_good_cells.len()
+        )));
+    }
+
+    let mut overall = ConfusionMatrix::default();
+    for (&rust_good, &r_good) in rust_result.good_cells.iter().zip(r_good_cells) {
+        overall.record(rust_good, r_good);
+    }
+
+    let breaks = create_breaks(rust_result.good_cells.len(), rust_result.events_per_bin);
+    let per_bin = breaks
+        .into_iter()
+        .enumerate()
+        .map(|(bin, (start, end))| {
+            let mut confusion = ConfusionMatrix::default();
+            for i in start..end {
+                confusion.record(rust_result.good_cells[i], r_good_cells[i]);
+            }
+            BinConfusion { bin, start, end, confusion }
+        })
+        .collect();
+
+    Ok(RParityReport { overall, per_bin })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "file-io")]
+    use crate::qc::export::export_csv_numeric;
+    use std::collections::HashMap;
+    #[cfg(feature = "file-io")]
+    use tempfile::TempDir;
+
+    fn test_result(good_cells: Vec<bool>) -> PeacoQCResult {
+        PeacoQCResult {
+            good_cells,
+            percentage_removed: 0.0,
+            it_percentage: None,
+            mad_percentage: None,
+            isolation_forest_percentage: None,
+            consecutive_percentage: 0.0,
+            peaks: HashMap::new(),
+            n_bins: 1,
+            events_per_bin: 3,
+            bin_size_strategy: crate::qc::BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
+        }
+    }
+
+    #[cfg(feature = "file-io")]
+    #[test]
+    fn test_load_r_csv_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r_output.csv");
+        let result = test_result(vec![true, true, false, true, false]);
+
+        export_csv_numeric(&result, &path, 2000, 6000, None).unwrap();
+
+        let loaded = load_r_csv(&path, &RComparisonOptions::default()).unwrap();
+        assert_eq!(loaded, result.good_cells);
+    }
+
+    #[cfg(feature = "file-io")]
+    #[test]
+    fn test_load_r_csv_wrong_column_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("r_output.csv");
+        let result = test_result(vec![true, false]);
+
+        export_csv_numeric(&result, &path, 2000, 6000, Some("SomethingElse")).unwrap();
+
+        assert!(load_r_csv(&path, &RComparisonOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_compare_to_r_perfect_agreement() {
+        let rust_result = test_result(vec![true, true, false, true, false, false]);
+        let r_good_cells = rust_result.good_cells.clone();
+
+        let report = compare_to_r(&rust_result, &r_good_cells).unwrap();
+
+        assert_eq!(report.overall.percent_concordance(), 100.0);
+        assert_eq!(report.overall.false_positive, 0);
+        assert_eq!(report.overall.false_negative, 0);
+    }
+
+    #[test]
+    fn test_compare_to_r_disagreement() {
+        let rust_result = test_result(vec![true, true, true, true]);
+        let r_good_cells = vec![true, false, true, false];
+
+        let report = compare_to_r(&rust_result, &r_good_cells).unwrap();
+
+        assert_eq!(report.overall.true_positive, 2);
+        assert_eq!(report.overall.false_positive, 2);
+        assert_eq!(report.overall.percent_concordance(), 50.0);
+    }
+
+    #[test]
+    fn test_compare_to_r_per_bin_breakdown() {
+        // events_per_bin matches create_breaks' overlap logic (see crate::qc::peaks::create_breaks),
+        // so bins overlap; just check the breakdown is internally consistent per bin rather than
+        // assuming a specific bin count.
+        let rust_result = test_result(vec![true, true, true, false, false, false]);
+        let r_good_cells = vec![true, true, true, true, true, true];
+
+        let report = compare_to_r(&rust_result, &r_good_cells).unwrap();
+
+        assert!(!report.per_bin.is_empty());
+        for bin in &report.per_bin {
+            assert_eq!(bin.confusion.n_events(), bin.end - bin.start);
+        }
+        // The bin covering only the trailing events (all Rust=false, R=true) should be all
+        // false-negative.
+        let last_bin = report.per_bin.last().unwrap();
+        assert_eq!(last_bin.start, 5);
+        assert_eq!(last_bin.confusion.false_negative, last_bin.end - last_bin.start);
+    }
+
+    #[test]
+    fn test_compare_to_r_event_count_mismatch() {
+        let rust_result = test_result(vec![true, false]);
+        let r_good_cells = vec![true, false, true];
+
+        assert!(compare_to_r(&rust_result, &r_good_cells).is_err());
+    }
+}
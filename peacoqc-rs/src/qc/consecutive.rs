@@ -1,16 +1,40 @@
 use crate::error::Result;
 
+/// Where [`remove_short_regions`] is applied when combined into [`crate::qc::peacoqc::peacoqc`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsecutiveScope {
+    /// Filter the already-combined outlier mask (matches the original R PeacoQC behavior)
+    Combined,
+    /// Filter each channel's own outlier mask independently before the channels are combined,
+    /// so a channel with a brief blip doesn't get bridged over just because another channel
+    /// happened to be flagged at the same bins. Falls back to [`ConsecutiveScope::Combined`]
+    /// if no per-channel outlier bins are available (e.g. Isolation Tree/Forest-only runs).
+    PerChannel,
+}
+
 /// Configuration for consecutive bin filtering
 #[derive(Debug, Clone)]
 pub struct ConsecutiveConfig {
-    /// Minimum number of consecutive "good" bins to keep
+    /// Minimum number of consecutive "good" bins to keep (default: 5)
     pub consecutive_bins: usize,
+
+    /// Whether short good runs surrounded by bad bins get bridged into the surrounding bad
+    /// region (default: true, matching the original R PeacoQC behavior). Set to `false` to
+    /// disable consecutive-bin filtering entirely without having to special-case
+    /// `consecutive_bins == 0` at the call site.
+    pub bridge_short_gaps: bool,
+
+    /// Whether the rule applies to the combined outlier mask or to each channel independently
+    /// (default: [`ConsecutiveScope::Combined`])
+    pub scope: ConsecutiveScope,
 }
 
 impl Default for ConsecutiveConfig {
     fn default() -> Self {
         Self {
             consecutive_bins: 5,
+            bridge_short_gaps: true,
+            scope: ConsecutiveScope::Combined,
         }
     }
 }
@@ -18,7 +42,7 @@ impl Default for ConsecutiveConfig {
 /// Remove isolated "good" bins surrounded by "bad" bins
 ///
 /// If fewer than `consecutive_bins` good bins are located between bad bins,
-/// they are marked as bad.
+/// they are marked as bad. A no-op if `config.bridge_short_gaps` is `false`.
 ///
 /// # Algorithm
 /// 1. Find runs of consecutive good/bad bins
@@ -35,11 +59,11 @@ pub fn remove_short_regions(
     config: &ConsecutiveConfig,
 ) -> Result<Vec<bool>> {
     let mut result = outlier_bins.to_vec();
-    
-    if outlier_bins.is_empty() {
+
+    if outlier_bins.is_empty() || !config.bridge_short_gaps {
         return Ok(result);
     }
-    
+
     // Find runs of good bins (false values)
     let mut i = 0;
     while i < result.len() {
@@ -51,7 +75,7 @@ pub fn remove_short_regions(
             }
             let end = i;
             let run_length = end - start;
-            
+
             // If run is too short and not at the edges, mark as bad
             if run_length < config.consecutive_bins && start > 0 && end < result.len() {
                 for j in start..end {
@@ -62,7 +86,7 @@ pub fn remove_short_regions(
             i += 1;
         }
     }
-    
+
     Ok(result)
 }
 
@@ -86,19 +110,20 @@ mod tests {
         
         let config = ConsecutiveConfig {
             consecutive_bins: 5,
+            ..Default::default()
         };
-        
+
         let result = remove_short_regions(&outlier_bins, &config).unwrap();
-        
+
         // First 3 good bins should now be bad
         assert!(result[2]);
         assert!(result[3]);
         assert!(result[4]);
-        
+
         // Next 2 good bins should now be bad
         assert!(result[8]);
         assert!(result[9]);
-        
+
         // Last 5 good bins should remain good
         assert!(!result[14]);
         assert!(!result[15]);
@@ -106,4 +131,18 @@ mod tests {
         assert!(!result[17]);
         assert!(!result[18]);
     }
+
+    #[test]
+    fn test_remove_short_regions_bridging_disabled() {
+        let outlier_bins = vec![true, false, false, true];
+        let config = ConsecutiveConfig {
+            consecutive_bins: 5,
+            bridge_short_gaps: false,
+            ..Default::default()
+        };
+
+        let result = remove_short_regions(&outlier_bins, &config).unwrap();
+
+        assert_eq!(result, outlier_bins, "disabled bridging should be a no-op");
+    }
 }
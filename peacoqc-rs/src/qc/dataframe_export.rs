@@ -0,0 +1,173 @@
+//! Parquet/Arrow export of QC masks
+//!
+//! Unlike [`crate::qc::export`]'s single-column CSV/JSON output, this writes a per-event
+//! dataframe -- QC flag, bin assignment, and the source file's GUID -- so results can be loaded
+//! with polars/pandas/Arrow and joined back onto an event-level export of the same file by row
+//! index, or concatenated across files using the GUID column.
+
+use crate::error::{PeacoQCError, Result};
+use crate::qc::PeacoQCResult;
+use polars::prelude::*;
+use std::fs::File;
+use std::path::Path;
+
+/// Build the per-event QC mask dataframe: one row per event, with columns
+/// `file_guid`, `event_index`, `good_cell`, and `bin_index`.
+///
+/// `bin_index` is `event_index / result.events_per_bin`: consecutive, non-overlapping bins of
+/// `events_per_bin` events. PeacoQC's own bins overlap by half (see
+/// [`crate::qc::peaks::create_breaks`]), so `bin_index` is not an index into
+/// [`PeacoQCResult::channel_contribution`]'s per-bin outlier masks.
+fn build_mask_dataframe(result: &PeacoQCResult, file_guid: &str) -> Result<DataFrame> {
+    let n_events = result.good_cells.len();
+    let events_per_bin = result.events_per_bin.max(1);
+
+    let file_guid: Vec<&str> = vec![file_guid; n_events];
+    let event_index: Vec<u32> = (0..n_events as u32).collect();
+    let bin_index: Vec<u32> = (0..n_events as u32).map(|i| i / events_per_bin as u32).collect();
+
+    df!(
+        "file_guid" => file_guid,
+        "event_index" => event_index,
+        "good_cell" => result.good_cells.clone(),
+        "bin_index" => bin_index,
+    )
+    .map_err(PeacoQCError::from)
+}
+
+/// Export a QC result's per-event mask and bin assignments as a Parquet file
+///
+/// # Errors
+/// Returns an error if `result.good_cells` is empty, or if the dataframe cannot be built or
+/// written.
+pub fn export_parquet_mask(
+    result: &PeacoQCResult,
+    file_guid: &str,
+    path: impl AsRef<Path>,
+) -> Result<()> {
+    let path = path.as_ref();
+
+    if result.good_cells.is_empty() {
+        return Err(PeacoQCError::ExportError(
+            "Cannot export an empty QC result".to_string(),
+        ));
+    }
+
+    let mut df = build_mask_dataframe(result, file_guid)?;
+    let file = File::create(path).map_err(|e| {
+        PeacoQCError::WriteError(format!("Failed to create file {}: {}", path.display(), e))
+    })?;
+
+    ParquetWriter::new(file)
+        .finish(&mut df)
+        .map_err(|e| PeacoQCError::WriteError(format!("Failed to write parquet: {e}")))?;
+
+    Ok(())
+}
+
+/// Export a QC result's per-event mask and bin assignments as an Arrow IPC (Feather) file
+///
+/// # Errors
+/// Returns an error if `result.good_cells` is empty, or if the dataframe cannot be built or
+/// written.
+pub fn export_arrow_mask(
+    result: &PeacoQCResult,
+    file_guid: &str,
+    path: impl AsRef<Path>,
+) -> Result<()> {
+    let path = path.as_ref();
+
+    if result.good_cells.is_empty() {
+        return Err(PeacoQCError::ExportError(
+            "Cannot export an empty QC result".to_string(),
+        ));
+    }
+
+    let mut df = build_mask_dataframe(result, file_guid)?;
+    let file = File::create(path).map_err(|e| {
+        PeacoQCError::WriteError(format!("Failed to create file {}: {}", path.display(), e))
+    })?;
+
+    IpcWriter::new(file)
+        .finish(&mut df)
+        .map_err(|e| PeacoQCError::WriteError(format!("Failed to write arrow ipc: {e}")))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qc::BinSizeStrategy;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_result() -> PeacoQCResult {
+        PeacoQCResult {
+            good_cells: vec![true, true, false, true, false, true],
+            percentage_removed: 33.3,
+            it_percentage: None,
+            mad_percentage: Some(33.3),
+            isolation_forest_percentage: None,
+            consecutive_percentage: 0.0,
+            peaks: HashMap::new(),
+            n_bins: 3,
+            events_per_bin: 2,
+            bin_size_strategy: BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_parquet_mask_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mask.parquet");
+        let result = create_test_result();
+
+        export_parquet_mask(&result, "guid-1234", &path).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let df = ParquetReader::new(file).finish().unwrap();
+        assert_eq!(df.height(), 6);
+        let bin_index = df.column("bin_index").unwrap().u32().unwrap();
+        assert_eq!(bin_index.get(0), Some(0));
+        assert_eq!(bin_index.get(2), Some(1));
+        assert_eq!(bin_index.get(5), Some(2));
+    }
+
+    #[test]
+    fn test_export_arrow_mask_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mask.arrow");
+        let result = create_test_result();
+
+        export_arrow_mask(&result, "guid-5678", &path).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let df = IpcReader::new(file).finish().unwrap();
+        assert_eq!(df.height(), 6);
+        let guid = df.column("file_guid").unwrap().str().unwrap();
+        assert_eq!(guid.get(0), Some("guid-5678"));
+    }
+
+    #[test]
+    fn test_export_empty_result_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("empty.parquet");
+        let result = PeacoQCResult {
+            good_cells: vec![],
+            percentage_removed: 0.0,
+            it_percentage: None,
+            mad_percentage: None,
+            isolation_forest_percentage: None,
+            consecutive_percentage: 0.0,
+            peaks: HashMap::new(),
+            n_bins: 0,
+            events_per_bin: 0,
+            bin_size_strategy: BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
+        };
+
+        assert!(export_parquet_mask(&result, "guid", &path).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+//! QC density plots
+//!
+//! [`crate::qc::plots::create_qc_plots`] draws a downsampled scatter for each channel, which
+//! stays legible up to a few thousand points but turns into a solid smear for dense runs (the
+//! kind spectral panels with 30+ heavily-populated channels tend to produce). This module
+//! renders the same channel-vs-Time overlay information - unstable regions, per-bin median,
+//! smoothed spline, MAD thresholds - on top of a 2D density plot instead of a point scatter,
+//! reusing [`flow_plots`]'s pixel-density calculation for the background.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::qc::peacoqc::PeacoQCResult;
+use crate::qc::plots::{calculate_grid_dimensions, calculate_median_per_bin, find_unstable_regions};
+use flow_plots::ColorMaps;
+use flow_plots::density_calc::calculate_density_per_pixel;
+use flow_plots::options::{AxisOptions, DensityPlotOptions};
+use plotters::prelude::*;
+use plotters::style::{RGBAColor, RGBColor, WHITE};
+use std::path::Path;
+
+/// Configuration for [`create_density_qc_plots`]
+#[derive(Debug, Clone)]
+pub struct DensityQCPlotConfig {
+    /// Output image width in pixels
+    pub width: u32,
+
+    /// Output image height in pixels
+    pub height: u32,
+
+    /// Which channels to plot, and in what order. `None` plots every channel PeacoQC ran on.
+    pub channels: Option<Vec<String>>,
+
+    /// Colormap used for the density background
+    pub colormap: ColorMaps,
+
+    /// Color for unstable/removed regions (drawn as a semi-transparent overlay)
+    pub unstable_color: RGBColor,
+
+    /// Color for the per-bin median line
+    pub median_color: RGBColor,
+
+    /// Color for the smoothed spline line
+    pub smoothed_spline_color: RGBColor,
+
+    /// Color for MAD threshold lines
+    pub mad_threshold_color: RGBColor,
+
+    /// Show smoothed spline and MAD threshold lines (default: true)
+    pub show_spline_and_mad: bool,
+}
+
+impl Default for DensityQCPlotConfig {
+    fn default() -> Self {
+        Self {
+            width: 2400,
+            height: 1800,
+            channels: None,
+            colormap: ColorMaps::Viridis,
+            unstable_color: RGBColor(200, 150, 255),
+            median_color: RGBColor(0, 0, 0),
+            smoothed_spline_color: RGBColor(255, 0, 0),
+            mad_threshold_color: RGBColor(0, 0, 255),
+            show_spline_and_mad: true,
+        }
+    }
+}
+
+/// Create channel-vs-Time density QC plots and save to file
+///
+/// Draws one subplot per channel that PeacoQC ran on (arranged in the same auto-sized grid as
+/// [`crate::qc::plots::create_qc_plots`]), each a density plot of the channel against the Time
+/// channel with removed/unstable regions shaded and the usual median/spline/MAD overlays drawn
+/// on top.
+pub fn create_density_qc_plots<T: PeacoQCData>(
+    fcs: &T,
+    qc_result: &PeacoQCResult,
+    output_path: impl AsRef<Path>,
+    config: DensityQCPlotConfig,
+) -> Result<()> {
+    let output_path = output_path.as_ref();
+
+    let time_channel = fcs
+        .channel_names()
+        .into_iter()
+        .find(|name| name.to_uppercase().contains("TIME"))
+        .ok_or_else(|| PeacoQCError::ConfigError("Time channel not found".to_string()))?;
+    let time_values = fcs.get_channel_f64(&time_channel)?;
+
+    let channels: Vec<String> = match &config.channels {
+        Some(requested) => requested
+            .iter()
+            .filter(|c| qc_result.peaks.contains_key(*c))
+            .cloned()
+            .collect(),
+        None => qc_result.peaks.keys().cloned().collect(),
+    };
+
+    if channels.is_empty() {
+        return Err(PeacoQCError::ConfigError("No channels to plot".to_string()));
+    }
+
+    let (n_rows, n_cols) = calculate_grid_dimensions(channels.len());
+
+    let root = BitMapBackend::new(output_path, (config.width, config.height)).into_drawing_area();
+    root.fill(&WHITE)
+        .map_err(|e| PeacoQCError::ExportError(format!("Failed to fill background: {:?}", e)))?;
+    let subplot_areas = root.split_evenly((n_rows, n_cols));
+    let subplot_width = config.width / n_cols as u32;
+    let subplot_height = config.height / n_rows as u32;
+
+    let unstable_regions = find_unstable_regions(&qc_result.good_cells);
+
+    for (plot_idx, channel) in channels.iter().enumerate() {
+        let channel_data = fcs.get_channel_f64(channel)?;
+        if channel_data.is_empty() || time_values.len() != channel_data.len() {
+            continue;
+        }
+        let n_events = channel_data.len();
+
+        let x_range = time_values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| (min.min(x), max.max(x)));
+        let y_range = channel_data
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &y| (min.min(y), max.max(y)));
+        let x_range = if x_range.0 == x_range.1 { (x_range.0 - 1.0, x_range.1 + 1.0) } else { x_range };
+        let y_range = if y_range.0 == y_range.1 { (y_range.0 - 1.0, y_range.1 + 1.0) } else { y_range };
+
+        let x_axis = AxisOptions::new()
+            .range((x_range.0 as f32)..=(x_range.1 as f32))
+            .build()
+            .map_err(|e| PeacoQCError::PlotError(format!("Failed to build x axis: {e}")))?;
+        let y_axis = AxisOptions::new()
+            .range((y_range.0 as f32)..=(y_range.1 as f32))
+            .build()
+            .map_err(|e| PeacoQCError::PlotError(format!("Failed to build y axis: {e}")))?;
+        let density_options = DensityPlotOptions::new()
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .colormap(config.colormap.clone())
+            .build()
+            .map_err(|e| PeacoQCError::PlotError(format!("Failed to build density options: {e}")))?;
+
+        let points: Vec<(f32, f32)> = time_values
+            .iter()
+            .zip(channel_data.iter())
+            .map(|(&t, &v)| (t as f32, v as f32))
+            .collect();
+        let density_pixels = calculate_density_per_pixel(
+            &points,
+            subplot_width as usize,
+            subplot_height as usize,
+            &density_options,
+        );
+
+        let subplot_area = &subplot_areas[plot_idx];
+        let mut chart = ChartBuilder::on(subplot_area)
+            .margin(5)
+            .caption(channel, ("sans-serif", 12).into_font())
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d(x_range.0..x_range.1, y_range.0..y_range.1)
+            .map_err(|e| PeacoQCError::ExportError(format!("Failed to build chart: {:?}", e)))?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Time")
+            .y_desc("Value")
+            .draw()
+            .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw mesh: {:?}", e)))?;
+
+        chart
+            .draw_series(density_pixels.iter().map(|p| {
+                Pixel::new((p.x as f64, p.y as f64), RGBColor(p.r, p.g, p.b))
+            }))
+            .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw density: {:?}", e)))?;
+
+        // Shade removed/unstable regions on top of the density
+        for (start_idx, end_idx) in &unstable_regions {
+            if *start_idx < n_events {
+                let start_time = time_values[*start_idx];
+                let end_time = time_values[(*end_idx - 1).min(n_events - 1)];
+                let fill_color = RGBAColor(config.unstable_color.0, config.unstable_color.1, config.unstable_color.2, 0.3);
+                chart
+                    .draw_series(std::iter::once(Rectangle::new(
+                        [(start_time, y_range.0), (end_time, y_range.1)],
+                        fill_color.filled(),
+                    )))
+                    .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw rectangle: {:?}", e)))?;
+            }
+        }
+
+        // Median/spline/MAD overlays, matching create_qc_plots
+        let medians = calculate_median_per_bin(&channel_data, qc_result.events_per_bin);
+        if !medians.is_empty() {
+            let median_points: Vec<(f64, f64)> = medians
+                .iter()
+                .map(|(bin_idx, median)| {
+                    let cell_idx = *bin_idx * qc_result.events_per_bin;
+                    (time_values[cell_idx.min(n_events - 1)], *median)
+                })
+                .collect();
+
+            chart
+                .draw_series(LineSeries::new(median_points, config.median_color.stroke_width(2)))
+                .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw median line: {:?}", e)))?;
+
+            if config.show_spline_and_mad && medians.len() >= 3 {
+                let bin_medians: Vec<f64> = medians.iter().map(|(_, m)| *m).collect();
+                let bin_indices: Vec<f64> = medians.iter().map(|(i, _)| *i as f64).collect();
+
+                if let Ok(smoothed) = crate::stats::spline::smooth_spline(&bin_indices, &bin_medians, 0.5) {
+                    let smoothed_points: Vec<(f64, f64)> = smoothed
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &y)| {
+                            let cell_idx = (i * qc_result.events_per_bin).min(n_events - 1);
+                            (time_values[cell_idx], y)
+                        })
+                        .collect();
+
+                    chart
+                        .draw_series(LineSeries::new(smoothed_points, config.smoothed_spline_color.stroke_width(2)))
+                        .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw spline: {:?}", e)))?;
+
+                    if let Ok((median, mad)) = crate::stats::median_mad::median_mad_scaled(&smoothed) {
+                        let mad_threshold = 6.0;
+                        let upper = median + mad_threshold * mad;
+                        let lower = median - mad_threshold * mad;
+                        for threshold in [upper, lower] {
+                            chart
+                                .draw_series(LineSeries::new(
+                                    vec![(x_range.0, threshold), (x_range.1, threshold)],
+                                    config.mad_threshold_color.stroke_width(1),
+                                ))
+                                .map_err(|e| PeacoQCError::ExportError(format!("Failed to draw threshold: {:?}", e)))?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    root.present()
+        .map_err(|e| PeacoQCError::ExportError(format!("Failed to present plot: {:?}", e)))?;
+
+    Ok(())
+}
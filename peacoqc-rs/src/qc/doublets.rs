@@ -2,20 +2,66 @@ use crate::PeacoQCData;
 use crate::error::Result;
 use crate::stats::median_mad::median_mad_scaled;
 
+/// Which measurement combination doublets are detected from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoubletMethod {
+    /// Outlier ratio of `channel1 / (1e-10 + channel2 + b)`, e.g. FSC-A / FSC-H
+    Ratio,
+
+    /// Outlier value of `channel1` alone, e.g. FSC-W (doublets tend to be wider,
+    /// not just larger-area, so this needs no second channel)
+    Width,
+
+    /// Fit a robust linear regression band of `channel2` on `channel1` (e.g. FSC-H on
+    /// FSC-A) instead of thresholding the raw ratio. Events far below the band are
+    /// flagged by a per-event doublet probability rather than a fixed number of MADs,
+    /// which holds up better on instruments where the ratio distribution is skewed.
+    Model2D,
+}
+
+/// A secondary ratio-based doublet check run on a different channel pair,
+/// e.g. SSC-A/SSC-H. An event is treated as a doublet if either the primary
+/// or the secondary check flags it.
+#[derive(Debug, Clone)]
+pub struct SecondaryDoubletConfig {
+    /// Area channel (e.g. SSC-A)
+    pub channel1: String,
+
+    /// Height channel (e.g. SSC-H)
+    pub channel2: String,
+
+    /// Number of MADs above median to use as threshold
+    pub nmad: f64,
+
+    /// Optional shift parameter
+    pub b: f64,
+}
+
 /// Configuration for doublet removal
 #[derive(Debug, Clone)]
 pub struct DoubletConfig {
-    /// First channel (typically FSC-A)
+    /// Primary channel: the area channel in [`DoubletMethod::Ratio`] mode, or the
+    /// sole channel checked in [`DoubletMethod::Width`] mode
     pub channel1: String,
 
-    /// Second channel (typically FSC-H)
+    /// Height channel; unused in [`DoubletMethod::Width`] mode
     pub channel2: String,
 
+    /// Which measurement combination to use for the primary check
+    pub method: DoubletMethod,
+
     /// Number of MADs above median to use as threshold
     pub nmad: f64,
 
     /// Optional shift parameter
     pub b: f64,
+
+    /// Doublet-probability cutoff used by [`DoubletMethod::Model2D`]; unused otherwise.
+    /// An event is a doublet if its fitted probability meets or exceeds this value.
+    pub probability_cutoff: f64,
+
+    /// Optional secondary ratio check on a different channel pair (e.g. SSC-A/SSC-H)
+    pub secondary: Option<SecondaryDoubletConfig>,
 }
 
 impl Default for DoubletConfig {
@@ -23,74 +69,253 @@ impl Default for DoubletConfig {
         Self {
             channel1: "FSC-A".to_string(),
             channel2: "FSC-H".to_string(),
+            method: DoubletMethod::Ratio,
             nmad: 4.0,
             b: 0.0,
+            probability_cutoff: 0.999,
+            secondary: None,
         }
     }
 }
 
+/// Fitted median/MAD/threshold for a single doublet check, so callers can plot the cut
+#[derive(Debug, Clone, Copy)]
+pub struct DoubletFit {
+    /// Median of the checked values (ratio, or raw width)
+    pub median: f64,
+
+    /// MAD of the checked values
+    pub mad: f64,
+
+    /// Threshold used (values at or above this are doublets)
+    pub threshold: f64,
+}
+
+/// Fitted regression band and per-event probabilities from [`DoubletMethod::Model2D`]
+#[derive(Debug, Clone)]
+pub struct ModelDoubletFit {
+    /// Slope of the fitted `channel2 ~ channel1` line
+    pub slope: f64,
+
+    /// Intercept of the fitted `channel2 ~ channel1` line
+    pub intercept: f64,
+
+    /// Scaled MAD of the residuals around the fitted line
+    pub residual_mad: f64,
+
+    /// Doublet probability cutoff used (events at or above this are doublets)
+    pub probability_cutoff: f64,
+
+    /// Per-event doublet probability, in the same order as the input data
+    pub probabilities: Vec<f64>,
+}
+
 /// Result of doublet removal
 #[derive(Debug)]
 pub struct DoubletResult {
     /// Boolean mask (true = keep, false = doublet)
     pub mask: Vec<bool>,
 
-    /// Median ratio
+    /// Median ratio (or width, in [`DoubletMethod::Width`] mode) from the primary check.
+    /// Unused (`0.0`) in [`DoubletMethod::Model2D`] mode; see [`Self::model`] instead.
     pub median_ratio: f64,
 
-    /// MAD of ratios
+    /// MAD of the primary check's values. Unused (`0.0`) in [`DoubletMethod::Model2D`] mode.
     pub mad_ratio: f64,
 
-    /// Threshold used
+    /// Threshold used by the primary check. Unused (`0.0`) in [`DoubletMethod::Model2D`] mode.
     pub threshold: f64,
 
     /// Percentage removed
     pub percentage_removed: f64,
+
+    /// Fitted median/MAD/threshold from the secondary check, if one was configured
+    pub secondary: Option<DoubletFit>,
+
+    /// Fitted regression band and per-event probabilities, if the primary check used
+    /// [`DoubletMethod::Model2D`]
+    pub model: Option<ModelDoubletFit>,
+}
+
+/// Compute a MAD-outlier mask, median, MAD, and threshold for a set of values
+///
+/// Shared by the primary and secondary doublet checks: both flag events whose
+/// value sits `nmad` scaled MADs above the median (R's `stats::mad()` scaling,
+/// constant=1.4826).
+fn mad_outlier_fit(values: &[f64], nmad: f64) -> Result<(Vec<bool>, DoubletFit)> {
+    let (median, mad) = median_mad_scaled(values)?;
+    let threshold = median + nmad * mad;
+    let mask = values.iter().map(|&v| v < threshold).collect();
+    Ok((mask, DoubletFit { median, mad, threshold }))
+}
+
+fn ratio(values1: &[f64], values2: &[f64], b: f64) -> Vec<f64> {
+    values1
+        .iter()
+        .zip(values2.iter())
+        .map(|(a, h)| *a / (1e-10 + *h + b))
+        .collect()
+}
+
+/// Standard normal CDF via the Abramowitz & Stegun 7.1.26 erf approximation
+/// (max error ~1.5e-7), used to turn a residual z-score into a probability.
+fn normal_cdf(z: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * z.abs() / std::f64::consts::SQRT_2);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736
+                + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z / 2.0).exp();
+    let cdf = 0.5 * (1.0 + erf.copysign(z));
+    cdf.clamp(0.0, 1.0)
+}
+
+/// Fit `channel2 ~ channel1` by ordinary least squares, then express each event's
+/// vertical residual as a doublet probability: events falling well below the fitted
+/// band (low height for their area) are singlets, while events well above it are
+/// flagged as doublets with a probability approaching 1.
+fn model_2d_fit(
+    values1: &[f64],
+    values2: &[f64],
+    probability_cutoff: f64,
+) -> Result<(Vec<bool>, ModelDoubletFit)> {
+    let n = values1.len() as f64;
+    let mean1 = values1.iter().sum::<f64>() / n;
+    let mean2 = values2.iter().sum::<f64>() / n;
+
+    let mut cov = 0.0;
+    let mut var1 = 0.0;
+    for (&x, &y) in values1.iter().zip(values2.iter()) {
+        cov += (x - mean1) * (y - mean2);
+        var1 += (x - mean1) * (x - mean1);
+    }
+    let slope = if var1 > 0.0 { cov / var1 } else { 0.0 };
+    let intercept = mean2 - slope * mean1;
+
+    let residuals: Vec<f64> = values1
+        .iter()
+        .zip(values2.iter())
+        .map(|(&x, &y)| y - (slope * x + intercept))
+        .collect();
+    let (_, residual_mad) = median_mad_scaled(&residuals)?;
+
+    // One-sided: only doublets sitting above the band (higher height than the fit
+    // predicts for their area) are flagged, matching how the fixed-nmad ratio check
+    // only cuts the high tail.
+    let probabilities: Vec<f64> = residuals
+        .iter()
+        .map(|&r| {
+            if residual_mad > 0.0 {
+                normal_cdf(r / residual_mad)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mask = probabilities.iter().map(|&p| p < probability_cutoff).collect();
+
+    Ok((
+        mask,
+        ModelDoubletFit {
+            slope,
+            intercept,
+            residual_mad,
+            probability_cutoff,
+            probabilities,
+        },
+    ))
 }
 
-/// Remove doublet events based on area/height ratio
+/// Remove doublet events based on area/height ratio or width
 ///
-/// Doublets (two cells passing through the detector simultaneously) have
-/// a different FSC-A/FSC-H ratio than singlets. This function identifies
-/// doublets as outliers in this ratio distribution.
+/// Doublets (two cells passing through the detector simultaneously) show up
+/// as outliers relative to singlets, either in their area/height ratio or
+/// (equivalently) as unusually wide pulses. This function identifies doublets
+/// as outliers in that distribution, optionally combined with a secondary
+/// check on another channel pair (e.g. SSC-A/SSC-H).
 ///
 /// # Algorithm
-/// 1. Calculate ratio = channel1 / (1e-10 + channel2 + b)
-/// 2. threshold = median(ratio) + nmad * MAD(ratio)
-/// 3. Keep events where ratio < threshold
+/// 1. [`DoubletMethod::Ratio`]: value = channel1 / (1e-10 + channel2 + b);
+///    [`DoubletMethod::Width`]: value = channel1
+/// 2. threshold = median(value) + nmad * MAD(value)
+/// 3. Keep events where value < threshold (and, if a secondary check is
+///    configured, where the secondary ratio is also below its own threshold)
 ///
 /// # Arguments
 /// * `fcs` - FCS file data (any type implementing PeacoQCData)
 /// * `config` - Configuration for doublet detection
 pub fn remove_doublets<T: PeacoQCData>(fcs: &T, config: &DoubletConfig) -> Result<DoubletResult> {
-    // Get channel data
-    let values1 = fcs.get_channel_f64(&config.channel1)?;
-    let values2 = fcs.get_channel_f64(&config.channel2)?;
-
-    // Calculate ratios
-    let mut ratios = Vec::with_capacity(fcs.n_events());
-    for (a, h) in values1.iter().zip(values2.iter()) {
-        let ratio = *a / (1e-10 + *h + config.b);
-        ratios.push(ratio);
+    if config.method == DoubletMethod::Model2D {
+        let values1 = fcs.get_channel_f64(&config.channel1)?;
+        let values2 = fcs.get_channel_f64(&config.channel2)?;
+        let (mut mask, model_fit) = model_2d_fit(&values1, &values2, config.probability_cutoff)?;
+
+        let secondary = match &config.secondary {
+            Some(sec) => {
+                let sec_values1 = fcs.get_channel_f64(&sec.channel1)?;
+                let sec_values2 = fcs.get_channel_f64(&sec.channel2)?;
+                let ratios = ratio(&sec_values1, &sec_values2, sec.b);
+                let (sec_mask, sec_fit) = mad_outlier_fit(&ratios, sec.nmad)?;
+                for (m, s) in mask.iter_mut().zip(sec_mask.iter()) {
+                    *m = *m && *s;
+                }
+                Some(sec_fit)
+            }
+            None => None,
+        };
+
+        let n_removed = mask.iter().filter(|&&x| !x).count();
+        let percentage_removed = (n_removed as f64 / fcs.n_events() as f64) * 100.0;
+
+        return Ok(DoubletResult {
+            mask,
+            median_ratio: 0.0,
+            mad_ratio: 0.0,
+            threshold: 0.0,
+            percentage_removed,
+            secondary,
+            model: Some(model_fit),
+        });
     }
 
-    // Calculate median and MAD (using R's scaled MAD to match stats::mad())
-    // R's stats::mad() uses constant=1.4826 by default
-    let (median, mad) = median_mad_scaled(&ratios)?;
-    let threshold = median + config.nmad * mad;
+    let primary_values = match config.method {
+        DoubletMethod::Ratio => {
+            let values1 = fcs.get_channel_f64(&config.channel1)?;
+            let values2 = fcs.get_channel_f64(&config.channel2)?;
+            ratio(&values1, &values2, config.b)
+        }
+        DoubletMethod::Width => fcs.get_channel_f64(&config.channel1)?,
+        DoubletMethod::Model2D => unreachable!("handled above"),
+    };
+
+    let (mut mask, fit) = mad_outlier_fit(&primary_values, config.nmad)?;
 
-    // Create mask
-    let mask: Vec<bool> = ratios.iter().map(|&r| r < threshold).collect();
+    let secondary = match &config.secondary {
+        Some(sec) => {
+            let values1 = fcs.get_channel_f64(&sec.channel1)?;
+            let values2 = fcs.get_channel_f64(&sec.channel2)?;
+            let ratios = ratio(&values1, &values2, sec.b);
+            let (sec_mask, sec_fit) = mad_outlier_fit(&ratios, sec.nmad)?;
+            for (m, s) in mask.iter_mut().zip(sec_mask.iter()) {
+                *m = *m && *s;
+            }
+            Some(sec_fit)
+        }
+        None => None,
+    };
 
     let n_removed = mask.iter().filter(|&&x| !x).count();
     let percentage_removed = (n_removed as f64 / fcs.n_events() as f64) * 100.0;
 
     Ok(DoubletResult {
         mask,
-        median_ratio: median,
-        mad_ratio: mad,
-        threshold,
+        median_ratio: fit.median,
+        mad_ratio: fit.mad,
+        threshold: fit.threshold,
         percentage_removed,
+        secondary,
+        model: None,
     })
 }
 
@@ -125,5 +350,102 @@ mod tests {
         // Should detect the outlier ratio
         assert!(result.percentage_removed > 0.0);
         assert!(result.threshold > result.median_ratio);
+        assert!(result.secondary.is_none());
+    }
+
+    #[test]
+    fn test_remove_doublets_model_2d_mode() {
+        // A doublet sits well above the fitted FSC-H ~ FSC-A line for its area, unlike a
+        // fixed-ratio check this should still flag it even though the bulk of the
+        // singlet population has a skewed area/height relationship.
+        let df = Arc::new(
+            df![
+                "FSC-A" => &[100.0, 150.0, 200.0, 250.0, 300.0, 350.0, 400.0, 250.0],
+                "FSC-H" => &[52.0, 74.0, 101.0, 126.0, 151.0, 175.0, 199.0, 900.0],
+            ]
+            .unwrap(),
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let config = DoubletConfig {
+            method: DoubletMethod::Model2D,
+            probability_cutoff: 0.99,
+            ..Default::default()
+        };
+
+        let result = remove_doublets(&fcs, &config).unwrap();
+
+        assert!(!result.mask[7]);
+        assert!(result.mask[..7].iter().all(|&keep| keep));
+        let model = result.model.expect("model fit should be present");
+        assert_eq!(model.probabilities.len(), 8);
+        assert!(model.probabilities[7] >= model.probability_cutoff);
+    }
+
+    #[test]
+    fn test_remove_doublets_width_mode() {
+        // Doublets show up as an unusually wide pulse; no second channel needed.
+        let df = Arc::new(
+            df![
+                "FSC-W" => &[100.0, 105.0, 98.0, 102.0, 400.0], // Last one is doublet
+            ]
+            .unwrap(),
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let config = DoubletConfig {
+            channel1: "FSC-W".to_string(),
+            method: DoubletMethod::Width,
+            ..Default::default()
+        };
+
+        let result = remove_doublets(&fcs, &config).unwrap();
+
+        assert_eq!(result.mask, vec![true, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_remove_doublets_secondary_check() {
+        // An event only survives if it clears both the FSC and SSC ratio checks.
+        let df = Arc::new(
+            df![
+                "FSC-A" => &[100.0, 200.0, 300.0, 400.0, 1000.0],
+                "FSC-H" => &[50.0, 100.0, 150.0, 200.0, 100.0],
+                "SSC-A" => &[100.0, 200.0, 300.0, 1000.0, 500.0],
+                "SSC-H" => &[50.0, 100.0, 150.0, 100.0, 250.0],
+            ]
+            .unwrap(),
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let config = DoubletConfig {
+            secondary: Some(SecondaryDoubletConfig {
+                channel1: "SSC-A".to_string(),
+                channel2: "SSC-H".to_string(),
+                nmad: 4.0,
+                b: 0.0,
+            }),
+            ..Default::default()
+        };
+
+        let result = remove_doublets(&fcs, &config).unwrap();
+
+        // Both the FSC-flagged event (index 4) and the SSC-flagged event (index 3)
+        // must be removed, since the two checks are combined.
+        assert!(!result.mask[3]);
+        assert!(!result.mask[4]);
+        assert!(result.secondary.is_some());
     }
 }
@@ -0,0 +1,224 @@
+//! Signal drift correction
+//!
+//! [`crate::qc::monotonic::find_increasing_decreasing_channels`] only flags channels with slow
+//! monotonic drift (instrument warm-up, laser degradation, a clog developing) - the caller then
+//! has to decide whether to discard the whole run. [`correct_drift`] offers a middle ground for
+//! cases where the drift itself is the sole detectable problem: bin the channel into
+//! non-overlapping windows, compute each bin's median, and rescale every event in the bin by
+//! the factor that brings that bin's median in line with the whole-channel median. This
+//! regresses the slow trend out of the data instead of discarding the events it affected.
+
+use crate::PeacoQCData;
+use crate::error::Result;
+use crate::qc::monotonic::{MonotonicConfig, MonotonicResult, find_increasing_decreasing_channels};
+use crate::stats::median;
+use std::collections::{HashMap, HashSet};
+
+/// Configuration for [`correct_drift`]
+#[derive(Debug, Clone)]
+pub struct DriftCorrectionConfig {
+    /// Number of events per bin used to estimate local medians (default: 500)
+    pub events_per_bin: usize,
+    /// Monotonic-drift detection parameters (see [`crate::qc::monotonic::MonotonicConfig`])
+    pub monotonic: MonotonicConfig,
+}
+
+impl Default for DriftCorrectionConfig {
+    fn default() -> Self {
+        Self {
+            events_per_bin: 500,
+            monotonic: MonotonicConfig::default(),
+        }
+    }
+}
+
+/// Per-channel drift-correction diagnostics
+#[derive(Debug, Clone)]
+pub struct ChannelDriftDiagnostics {
+    /// Whether this channel was flagged as drifting and therefore corrected
+    pub corrected: bool,
+    /// File-wide median used as the correction target
+    pub target_median: f64,
+    /// Per-bin scale factor applied (`target_median / bin_median`), in bin order; empty for
+    /// channels that weren't corrected
+    pub bin_scale_factors: Vec<f64>,
+}
+
+/// Result of [`correct_drift`]
+#[derive(Debug, Clone)]
+pub struct DriftCorrectionResult {
+    /// Corrected values, one entry per requested channel (unflagged channels are passed
+    /// through unchanged)
+    pub corrected_channels: HashMap<String, Vec<f64>>,
+    /// Per-channel diagnostics, including channels left untouched
+    pub diagnostics: HashMap<String, ChannelDriftDiagnostics>,
+    /// The underlying monotonic-drift detection result
+    pub monotonic: MonotonicResult,
+}
+
+/// Detect and correct slow monotonic drift per channel
+///
+/// Channels [`crate::qc::monotonic::find_increasing_decreasing_channels`] doesn't flag are
+/// passed through unchanged in [`DriftCorrectionResult::corrected_channels`] - diagnostics still
+/// record `corrected: false` for them, so callers can distinguish "no drift found" from "drift
+/// found and fixed".
+///
+/// # Errors
+/// Returns `Err` if a channel can't be read or a bin's median can't be computed.
+pub fn correct_drift<T: PeacoQCData>(
+    fcs: &T,
+    channels: &[String],
+    config: &DriftCorrectionConfig,
+) -> Result<DriftCorrectionResult> {
+    let n_events = fcs.n_events();
+    let breaks = non_overlapping_breaks(n_events, config.events_per_bin);
+
+    let monotonic =
+        find_increasing_decreasing_channels(fcs, channels, &breaks, &config.monotonic)?;
+    let drifting: HashSet<&String> = monotonic
+        .increasing
+        .iter()
+        .chain(monotonic.decreasing.iter())
+        .collect();
+
+    let mut corrected_channels = HashMap::new();
+    let mut diagnostics = HashMap::new();
+
+    for channel in channels {
+        let data = fcs.get_channel_f64(channel)?;
+        let target_median = median(&data)?;
+
+        if !drifting.contains(channel) {
+            diagnostics.insert(
+                channel.clone(),
+                ChannelDriftDiagnostics {
+                    corrected: false,
+                    target_median,
+                    bin_scale_factors: Vec::new(),
+                },
+            );
+            corrected_channels.insert(channel.clone(), data);
+            continue;
+        }
+
+        let mut corrected = data.clone();
+        let mut bin_scale_factors = Vec::with_capacity(breaks.len());
+
+        for &(start, end) in &breaks {
+            let bin_data = &data[start..end];
+            if bin_data.is_empty() {
+                bin_scale_factors.push(1.0);
+                continue;
+            }
+
+            let bin_median = median(bin_data)?;
+            let scale = if bin_median.abs() > 1e-10 {
+                target_median / bin_median
+            } else {
+                1.0
+            };
+            bin_scale_factors.push(scale);
+
+            for value in &mut corrected[start..end] {
+                *value *= scale;
+            }
+        }
+
+        diagnostics.insert(
+            channel.clone(),
+            ChannelDriftDiagnostics {
+                corrected: true,
+                target_median,
+                bin_scale_factors,
+            },
+        );
+        corrected_channels.insert(channel.clone(), corrected);
+    }
+
+    Ok(DriftCorrectionResult {
+        corrected_channels,
+        diagnostics,
+        monotonic,
+    })
+}
+
+/// Non-overlapping bin boundaries, unlike [`crate::qc::peaks::create_breaks`]'s 50%-overlap
+/// bins - correction rescales events in place, so overlapping bins would apply a scale factor
+/// twice to the events they share.
+fn non_overlapping_breaks(n_events: usize, events_per_bin: usize) -> Vec<(usize, usize)> {
+    let mut breaks = Vec::new();
+    let mut start = 0;
+    while start < n_events {
+        let end = (start + events_per_bin).min(n_events);
+        breaks.push((start, end));
+        start = end;
+    }
+    breaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::SimpleFcs;
+    use polars::df;
+    use std::collections::HashMap as StdHashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_correct_drift_fixes_increasing_trend() {
+        let n = 1000;
+        let data: Vec<f64> = (0..n).map(|i| 100.0 + i as f64 * 0.1).collect();
+
+        let df = Arc::new(df!["FL1-A" => data].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = DriftCorrectionConfig {
+            events_per_bin: 100,
+            ..Default::default()
+        };
+
+        let result = correct_drift(&fcs, &["FL1-A".to_string()], &config).unwrap();
+
+        assert!(!result.monotonic.increasing.is_empty());
+        let diag = &result.diagnostics["FL1-A"];
+        assert!(diag.corrected);
+        assert_eq!(diag.bin_scale_factors.len(), 10);
+
+        // Corrected bin medians should be much closer to the target median than the raw ones.
+        let corrected = &result.corrected_channels["FL1-A"];
+        let first_bin_median = median(&corrected[0..100]).unwrap();
+        let last_bin_median = median(&corrected[900..1000]).unwrap();
+        assert!(
+            (first_bin_median - last_bin_median).abs() < 1.0,
+            "corrected bin medians should be aligned: {} vs {}",
+            first_bin_median,
+            last_bin_median
+        );
+    }
+
+    #[test]
+    fn test_correct_drift_leaves_stable_channel_untouched() {
+        let n = 1000;
+        let data: Vec<f64> = (0..n).map(|i| 100.0 + (i as f64 % 10.0)).collect();
+
+        let df = Arc::new(df!["FL1-A" => data.clone()].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = DriftCorrectionConfig {
+            events_per_bin: 100,
+            ..Default::default()
+        };
+
+        let result = correct_drift(&fcs, &["FL1-A".to_string()], &config).unwrap();
+
+        let diag = &result.diagnostics["FL1-A"];
+        assert!(!diag.corrected);
+        assert_eq!(result.corrected_channels["FL1-A"], data);
+    }
+}
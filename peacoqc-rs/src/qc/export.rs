@@ -390,10 +390,13 @@ mod tests {
             percentage_removed: 40.0,
             it_percentage: Some(20.0),
             mad_percentage: Some(20.0),
+            isolation_forest_percentage: None,
             consecutive_percentage: 0.0,
             peaks: HashMap::new(),
             n_bins: 10,
             events_per_bin: 50,
+            bin_size_strategy: crate::qc::BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
         }
     }
 
@@ -473,10 +476,13 @@ mod tests {
             percentage_removed: 0.0,
             it_percentage: None,
             mad_percentage: None,
+            isolation_forest_percentage: None,
             consecutive_percentage: 0.0,
             peaks: HashMap::new(),
             n_bins: 0,
             events_per_bin: 0,
+            bin_size_strategy: crate::qc::BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
         };
 
         assert!(export_csv_boolean(&result, &path, None).is_err());
@@ -0,0 +1,362 @@
+//! flowAI-style quality control
+//!
+//! Where [`crate::qc::peacoqc`] bins events by *count* and looks for peak-trajectory
+//! instability, flowAI bins events by *time* and looks directly at acquisition health:
+//! flow rate anomalies, signal (fluorescence) drift, and dynamic-range saturation. Both
+//! algorithms consume and produce the same [`PeacoQCData`]/boolean-mask shapes, so callers
+//! can run either, or both, and combine (e.g. AND together) their masks.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::stats::median_mad::median_mad_scaled;
+use std::collections::HashMap;
+
+/// Configuration for flowAI-style quality control
+#[derive(Debug, Clone)]
+pub struct FlowAIConfig {
+    /// Fluorescence/scatter channels to check for signal acquisition instability and
+    /// dynamic-range saturation
+    pub channels: Vec<String>,
+
+    /// Name of the time channel (auto-detected from `channel_names()` if `None`, matching
+    /// any channel whose name contains "TIME")
+    pub time_channel: Option<String>,
+
+    /// Width of each time bin, in the same units as the time channel (default: 100, matching
+    /// flowAI's default `timeCh` bin width of 100 ticks)
+    pub second_fraction: f64,
+
+    /// MAD threshold multiplier for the flow rate check (default: 6.0, matching PeacoQC's
+    /// default MAD threshold for consistency across this crate's algorithms)
+    ///
+    /// **Tradeoff**: the lower this is, the more strict the flow rate check is, and the more
+    /// bins get flagged as rate anomalies.
+    pub flow_rate_mad: f64,
+
+    /// MAD threshold multiplier for the signal acquisition stability check (default: 6.0)
+    pub signal_mad: f64,
+
+    /// Fraction of a channel's `(min, max)` range treated as "at the detector limit" for the
+    /// dynamic-range check (default: 0.0, i.e. only events exactly at the limit)
+    pub dynamic_range_margin: f64,
+}
+
+impl Default for FlowAIConfig {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            time_channel: None,
+            second_fraction: 100.0,
+            flow_rate_mad: 6.0,
+            signal_mad: 6.0,
+            dynamic_range_margin: 0.0,
+        }
+    }
+}
+
+/// Result of flowAI-style quality control
+#[derive(Debug)]
+pub struct FlowAIResult {
+    /// Combined boolean mask of good cells (true = keep, false = remove); the AND of
+    /// `flow_rate_mask`, `signal_mask`, and `dynamic_range_mask`
+    pub good_cells: Vec<bool>,
+
+    /// Per-event mask from the flow rate check (false = event falls in a time bin whose
+    /// acquisition rate is a MAD outlier)
+    pub flow_rate_mask: Vec<bool>,
+
+    /// Per-event mask from the signal acquisition stability check (false = event falls in a
+    /// time bin where some channel's median signal is a MAD outlier)
+    pub signal_mask: Vec<bool>,
+
+    /// Per-event mask from the dynamic-range check (false = event is at a channel's detector
+    /// limit)
+    pub dynamic_range_mask: Vec<bool>,
+
+    /// Percentage contribution of each check to the total events removed
+    pub contribution: HashMap<String, f64>,
+
+    /// Percentage of events removed overall
+    pub percentage_removed: f64,
+}
+
+/// Find the time channel by name, matching any channel containing "TIME" (case-insensitive)
+fn find_time_channel<T: PeacoQCData>(fcs: &T) -> Option<String> {
+    fcs.channel_names()
+        .into_iter()
+        .find(|name| name.to_uppercase().contains("TIME"))
+}
+
+/// Assign each event to a time bin of width `second_fraction`
+fn time_bins(time_values: &[f64], second_fraction: f64) -> Vec<usize> {
+    let t0 = time_values.iter().copied().fold(f64::INFINITY, f64::min);
+    time_values
+        .iter()
+        .map(|&t| (((t - t0) / second_fraction).floor().max(0.0)) as usize)
+        .collect()
+}
+
+/// Flags events in time bins whose acquisition rate (events per bin) is a MAD outlier
+fn flow_rate_check(bins: &[usize], n_bins: usize, mad_threshold: f64) -> Result<Vec<bool>> {
+    let mut counts = vec![0.0f64; n_bins];
+    for &bin in bins {
+        counts[bin] += 1.0;
+    }
+
+    let (median, mad) = median_mad_scaled(&counts)?;
+    if mad == 0.0 {
+        return Ok(vec![true; bins.len()]);
+    }
+
+    let lower = median - mad_threshold * mad;
+    let upper = median + mad_threshold * mad;
+    let good_bins: Vec<bool> = counts.iter().map(|&c| c >= lower && c <= upper).collect();
+
+    Ok(bins.iter().map(|&bin| good_bins[bin]).collect())
+}
+
+/// Flags events in time bins whose median signal, in any checked channel, is a MAD outlier
+fn signal_stability_check<T: PeacoQCData>(
+    fcs: &T,
+    channels: &[String],
+    bins: &[usize],
+    n_bins: usize,
+    mad_threshold: f64,
+) -> Result<(Vec<bool>, HashMap<String, f64>)> {
+    let mut good_bins = vec![true; n_bins];
+    let mut contribution = HashMap::new();
+
+    for channel in channels {
+        let values = fcs.get_channel_f64(channel)?;
+
+        let mut bin_values: Vec<Vec<f64>> = vec![Vec::new(); n_bins];
+        for (&bin, &value) in bins.iter().zip(values.iter()) {
+            bin_values[bin].push(value);
+        }
+
+        let bin_medians: Vec<f64> = bin_values
+            .iter()
+            .map(|values| crate::stats::median(values).unwrap_or(f64::NAN))
+            .collect();
+        let finite_medians: Vec<f64> = bin_medians.iter().copied().filter(|v| v.is_finite()).collect();
+        if finite_medians.len() < 3 {
+            continue;
+        }
+
+        let (median, mad) = median_mad_scaled(&finite_medians)?;
+        if mad == 0.0 {
+            continue;
+        }
+
+        let lower = median - mad_threshold * mad;
+        let upper = median + mad_threshold * mad;
+
+        let mut n_flagged = 0;
+        for (bin, &bin_median) in bin_medians.iter().enumerate() {
+            if bin_median.is_finite() && (bin_median < lower || bin_median > upper) {
+                good_bins[bin] = false;
+                n_flagged += 1;
+            }
+        }
+
+        contribution.insert(channel.clone(), (n_flagged as f64 / n_bins as f64) * 100.0);
+    }
+
+    let mask = bins.iter().map(|&bin| good_bins[bin]).collect();
+    Ok((mask, contribution))
+}
+
+/// Flags events sitting at a channel's detector limit (min or max of its range)
+fn dynamic_range_check<T: PeacoQCData>(
+    fcs: &T,
+    channels: &[String],
+    n_events: usize,
+    margin: f64,
+) -> Result<Vec<bool>> {
+    let mut mask = vec![true; n_events];
+
+    for channel in channels {
+        let values = fcs.get_channel_f64(channel)?;
+        let Some((min_range, max_range)) = fcs.get_channel_range(channel) else {
+            continue;
+        };
+        let span = (max_range - min_range).max(0.0);
+        let lower = min_range + span * margin;
+        let upper = max_range - span * margin;
+
+        for (i, &value) in values.iter().enumerate() {
+            if value <= lower || value >= upper {
+                mask[i] = false;
+            }
+        }
+    }
+
+    Ok(mask)
+}
+
+/// Run flowAI-style quality control: flow rate anomaly detection, signal acquisition
+/// stability, and dynamic-range saturation
+///
+/// Each check produces its own event-level mask; `good_cells` is their combination (an event
+/// must pass all three to be kept), matching how [`crate::qc::peacoqc::peacoqc`] combines its
+/// own checks. Run flowAI and PeacoQC independently and `AND` their `good_cells` masks
+/// together to compare or combine both methods against the same file.
+///
+/// # Errors
+/// Returns `Err` if `config.channels` is empty, if the time channel can't be found (see
+/// [`FlowAIConfig::time_channel`]), or if a channel's data can't be read.
+pub fn flow_ai<T: PeacoQCData>(fcs: &T, config: &FlowAIConfig) -> Result<FlowAIResult> {
+    if config.channels.is_empty() {
+        return Err(PeacoQCError::ConfigError(
+            "No channels specified for flowAI quality control".to_string(),
+        ));
+    }
+
+    let n_events = fcs.n_events();
+    if n_events == 0 {
+        return Err(PeacoQCError::InsufficientData { min: 1, actual: 0 });
+    }
+
+    let time_channel = match &config.time_channel {
+        Some(channel) => channel.clone(),
+        None => find_time_channel(fcs)
+            .ok_or_else(|| PeacoQCError::ConfigError("Time channel not found".to_string()))?,
+    };
+
+    let time_values = fcs.get_channel_f64(&time_channel)?;
+    let bins = time_bins(&time_values, config.second_fraction);
+    let n_bins = bins.iter().copied().max().map_or(0, |max_bin| max_bin + 1);
+
+    let flow_rate_mask = flow_rate_check(&bins, n_bins, config.flow_rate_mad)?;
+    let (signal_mask, mut contribution) =
+        signal_stability_check(fcs, &config.channels, &bins, n_bins, config.signal_mad)?;
+    let dynamic_range_mask =
+        dynamic_range_check(fcs, &config.channels, n_events, config.dynamic_range_margin)?;
+
+    let good_cells: Vec<bool> = (0..n_events)
+        .map(|i| flow_rate_mask[i] && signal_mask[i] && dynamic_range_mask[i])
+        .collect();
+
+    let n_removed = good_cells.iter().filter(|&&keep| !keep).count();
+    let percentage_removed = (n_removed as f64 / n_events as f64) * 100.0;
+
+    contribution.insert(
+        "flow_rate".to_string(),
+        (flow_rate_mask.iter().filter(|&&keep| !keep).count() as f64 / n_events as f64) * 100.0,
+    );
+    contribution.insert(
+        "dynamic_range".to_string(),
+        (dynamic_range_mask.iter().filter(|&&keep| !keep).count() as f64 / n_events as f64) * 100.0,
+    );
+
+    Ok(FlowAIResult {
+        good_cells,
+        flow_rate_mask,
+        signal_mask,
+        dynamic_range_mask,
+        contribution,
+        percentage_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::{ParameterMetadata, SimpleFcs};
+    use polars::df;
+    use std::collections::HashMap as StdHashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_flow_ai_flags_flow_rate_spike() {
+        // 20 time bins holding 4-6 events each (so the per-bin counts have a nonzero MAD), plus
+        // a burst of 20 extra events crammed into one bin.
+        let mut time: Vec<f64> = (0..20)
+            .flat_map(|b| (0..4 + b % 3).map(move |k| b as f64 * 5.0 + k as f64 * 0.5))
+            .collect();
+        time.extend(std::iter::repeat(50.0).take(20));
+        let n = time.len();
+
+        let df = Arc::new(
+            df![
+                "Time" => time,
+                "FL1-A" => vec![100.0; n],
+            ]
+            .unwrap(),
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = FlowAIConfig {
+            channels: vec!["FL1-A".to_string()],
+            second_fraction: 5.0,
+            flow_rate_mad: 3.0,
+            ..Default::default()
+        };
+
+        let result = flow_ai(&fcs, &config).expect("flow_ai should succeed");
+        assert_eq!(result.good_cells.len(), n);
+        assert!(
+            result.flow_rate_mask.iter().filter(|&&keep| !keep).count() > 0,
+            "Should flag the flow rate spike"
+        );
+    }
+
+    #[test]
+    fn test_flow_ai_flags_dynamic_range_saturation() {
+        let n = 50;
+        let time: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let mut fl1 = vec![100.0; n];
+        fl1[0] = 0.0; // sits at the channel minimum
+        fl1[1] = 262144.0; // sits at the channel maximum
+
+        let df = Arc::new(
+            df![
+                "Time" => time,
+                "FL1-A" => fl1,
+            ]
+            .unwrap(),
+        );
+
+        let mut metadata = StdHashMap::new();
+        metadata.insert(
+            "FL1-A".to_string(),
+            ParameterMetadata {
+                min_range: 0.0,
+                max_range: 262144.0,
+                name: "FL1-A".to_string(),
+            },
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: metadata,
+        };
+
+        let config = FlowAIConfig {
+            channels: vec!["FL1-A".to_string()],
+            second_fraction: 5.0,
+            ..Default::default()
+        };
+
+        let result = flow_ai(&fcs, &config).expect("flow_ai should succeed");
+        assert_eq!(
+            result.dynamic_range_mask.iter().filter(|&&keep| !keep).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_flow_ai_errors_without_channels() {
+        let df = Arc::new(df!["Time" => vec![0.0, 1.0]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+        let config = FlowAIConfig::default();
+        assert!(flow_ai(&fcs, &config).is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! flowCut-style QC: segment-based mean drift detection
+//!
+//! Where [`crate::qc::peacoqc`] bins by count and clusters density *peaks* per bin, flowCut
+//! divides events into fixed-size segments and looks directly at each segment's summary
+//! statistics for a channel - its mean, spread, and rate of change from the segment before
+//! it - flagging segments whose statistics drift too far from the rest of the file. This is
+//! a coarser, cheaper check than PeacoQC's, and some labs standardize on it when migrating
+//! from R's `flowCut` package, so it's offered here as a distinct [`flow_cut`] entry point
+//! rather than folded into [`crate::qc::peacoqc::QCMode`].
+//!
+//! # The eight measures
+//!
+//! For each channel, every segment is scored against the channel's own other segments on
+//! eight measures - four "level" measures (is this segment's summary statistic unusual?), two
+//! "drift" measures (did it change abruptly from the segment before it?), and two saturation
+//! measures (how many of its events sit at the channel's range limits?):
+//!
+//! 1. Mean level
+//! 2. Median level
+//! 3. Spread (SD) level
+//! 4. Spread (IQR) level
+//! 5. Mean-to-mean derivative (this segment vs. the previous one)
+//! 6. Spread-to-spread derivative
+//! 7. Fraction of events at or below the channel's minimum
+//! 8. Fraction of events at or above the channel's maximum
+//!
+//! A segment is flagged bad for a channel if any of its eight measures is a MAD outlier
+//! relative to that measure's distribution across every segment; a segment is bad overall if
+//! any checked channel flags it.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::qc::consecutive::{ConsecutiveConfig, remove_short_regions};
+use crate::stats::median_mad::median_mad_scaled;
+use std::collections::HashMap;
+
+/// Configuration for flowCut-style QC
+#[derive(Debug, Clone)]
+pub struct FlowCutConfig {
+    /// Channels to analyze
+    pub channels: Vec<String>,
+
+    /// Number of events examined together as one segment (default: 500, matching flowCut's
+    /// default `Segment` parameter)
+    pub events_per_segment: usize,
+
+    /// MAD threshold multiplier for all eight measures (default: 6.0, matching this crate's
+    /// other MAD-based checks)
+    ///
+    /// **Tradeoff**: the lower this is, the more strict flowCut is, and the more segments get
+    /// flagged as drifted.
+    pub measure_threshold: f64,
+
+    /// Minimum number of consecutive good segments to keep; shorter good runs between bad
+    /// segments are also marked bad (default: 5, matching [`crate::qc::consecutive`]'s default)
+    pub consecutive_segments: usize,
+}
+
+impl Default for FlowCutConfig {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            events_per_segment: 500,
+            measure_threshold: 6.0,
+            consecutive_segments: 5,
+        }
+    }
+}
+
+/// Result of flowCut-style QC
+#[derive(Debug)]
+pub struct FlowCutResult {
+    /// Boolean mask of good cells (true = keep, false = remove)
+    pub good_cells: Vec<bool>,
+
+    /// Per-segment mask (true = segment flagged as drifted)
+    pub bad_segments: Vec<bool>,
+
+    /// Percentage contribution of each channel to the segments flagged
+    pub contribution: HashMap<String, f64>,
+
+    /// Percentage of events removed overall
+    pub percentage_removed: f64,
+}
+
+/// The eight per-segment measures for one channel; see the module docs
+struct SegmentMeasures {
+    mean: Vec<f64>,
+    median: Vec<f64>,
+    sd: Vec<f64>,
+    iqr: Vec<f64>,
+    mean_derivative: Vec<f64>,
+    sd_derivative: Vec<f64>,
+    fraction_at_min: Vec<f64>,
+    fraction_at_max: Vec<f64>,
+}
+
+fn segment_measures(
+    values: &[f64],
+    breaks: &[(usize, usize)],
+    channel_range: Option<(f64, f64)>,
+) -> Result<SegmentMeasures> {
+    let mut mean = Vec::with_capacity(breaks.len());
+    let mut median = Vec::with_capacity(breaks.len());
+    let mut sd = Vec::with_capacity(breaks.len());
+    let mut iqr = Vec::with_capacity(breaks.len());
+    let mut fraction_at_min = Vec::with_capacity(breaks.len());
+    let mut fraction_at_max = Vec::with_capacity(breaks.len());
+
+    let (range_min, range_max) = channel_range.unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+
+    for &(start, end) in breaks {
+        let segment = &values[start..end];
+        let n = segment.len() as f64;
+
+        let segment_mean = segment.iter().sum::<f64>() / n;
+        let segment_sd = (segment.iter().map(|v| (v - segment_mean).powi(2)).sum::<f64>() / n).sqrt();
+
+        let mut sorted = segment.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let segment_median = crate::stats::median(&sorted).unwrap_or(segment_mean);
+        let q1 = sorted[(sorted.len() as f64 * 0.25) as usize];
+        let q3 = sorted[((sorted.len() as f64 * 0.75) as usize).min(sorted.len() - 1)];
+
+        mean.push(segment_mean);
+        median.push(segment_median);
+        sd.push(segment_sd);
+        iqr.push(q3 - q1);
+        fraction_at_min.push(segment.iter().filter(|&&v| v <= range_min).count() as f64 / n);
+        fraction_at_max.push(segment.iter().filter(|&&v| v >= range_max).count() as f64 / n);
+    }
+
+    let mean_derivative = derivative(&mean);
+    let sd_derivative = derivative(&sd);
+
+    Ok(SegmentMeasures {
+        mean,
+        median,
+        sd,
+        iqr,
+        mean_derivative,
+        sd_derivative,
+        fraction_at_min,
+        fraction_at_max,
+    })
+}
+
+/// Splits `n_events` into consecutive, non-overlapping segments of `events_per_segment` events
+/// each. Unlike [`crate::qc::peaks::create_breaks`]'s overlapping sliding windows, flowCut's
+/// segments are adjacent and don't share events, so a segment's mean derivative from the one
+/// before it is meaningful. Any remainder shorter than `events_per_segment` is folded into the
+/// last segment rather than left as its own undersized segment.
+fn create_segments(n_events: usize, events_per_segment: usize) -> Vec<(usize, usize)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+
+    while start < n_events {
+        let end = (start + events_per_segment).min(n_events);
+        if n_events - end < events_per_segment {
+            segments.push((start, n_events));
+            break;
+        }
+        segments.push((start, end));
+        start = end;
+    }
+
+    segments
+}
+
+/// `[0, |x1 - x0|, |x2 - x1|, ...]`, i.e. the same length as `values`
+fn derivative(values: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; values.len()];
+    for i in 1..values.len() {
+        out[i] = (values[i] - values[i - 1]).abs();
+    }
+    out
+}
+
+/// Flags segments where `measure` is a MAD outlier relative to its own distribution
+fn flag_outlier_segments(measure: &[f64], threshold: f64) -> Result<Vec<bool>> {
+    if measure.len() < 3 {
+        return Ok(vec![false; measure.len()]);
+    }
+
+    let (median, mad) = median_mad_scaled(measure)?;
+    if mad == 0.0 {
+        return Ok(vec![false; measure.len()]);
+    }
+
+    let lower = median - threshold * mad;
+    let upper = median + threshold * mad;
+    Ok(measure.iter().map(|&v| v < lower || v > upper).collect())
+}
+
+/// Run flowCut-style QC: segment-based mean drift detection across eight per-channel measures
+///
+/// # Errors
+/// Returns `Err` if `config.channels` is empty, if there are fewer than
+/// `config.events_per_segment` events, or if a channel's data can't be read.
+pub fn flow_cut<T: PeacoQCData>(fcs: &T, config: &FlowCutConfig) -> Result<FlowCutResult> {
+    if config.channels.is_empty() {
+        return Err(PeacoQCError::ConfigError(
+            "No channels specified for flowCut quality control".to_string(),
+        ));
+    }
+
+    let n_events = fcs.n_events();
+    if n_events < config.events_per_segment {
+        return Err(PeacoQCError::InsufficientData {
+            min: config.events_per_segment,
+            actual: n_events,
+        });
+    }
+
+    let breaks = create_segments(n_events, config.events_per_segment);
+    let n_segments = breaks.len();
+
+    let mut bad_segments = vec![false; n_segments];
+    let mut contribution = HashMap::new();
+
+    for channel in &config.channels {
+        let values = fcs.get_channel_f64(channel)?;
+        let measures = segment_measures(&values, &breaks, fcs.get_channel_range(channel))?;
+
+        let mut channel_bad = vec![false; n_segments];
+        for measure in [
+            &measures.mean,
+            &measures.median,
+            &measures.sd,
+            &measures.iqr,
+            &measures.mean_derivative,
+            &measures.sd_derivative,
+            &measures.fraction_at_min,
+            &measures.fraction_at_max,
+        ] {
+            let flagged = flag_outlier_segments(measure, config.measure_threshold)?;
+            for (i, &is_bad) in flagged.iter().enumerate() {
+                channel_bad[i] |= is_bad;
+            }
+        }
+
+        let n_flagged = channel_bad.iter().filter(|&&bad| bad).count();
+        contribution.insert(
+            channel.clone(),
+            (n_flagged as f64 / n_segments as f64) * 100.0,
+        );
+
+        for (i, &is_bad) in channel_bad.iter().enumerate() {
+            bad_segments[i] |= is_bad;
+        }
+    }
+
+    let good_segments: Vec<bool> = bad_segments.iter().map(|&bad| !bad).collect();
+    let consecutive_config = ConsecutiveConfig {
+        consecutive_bins: config.consecutive_segments,
+        ..Default::default()
+    };
+    // `remove_short_regions` expects an outlier mask (true = bad), so invert back to bad_segments
+    // after filtering out isolated good segments too short to trust.
+    let filtered_bad_segments: Vec<bool> = remove_short_regions(
+        &good_segments.iter().map(|&good| !good).collect::<Vec<_>>(),
+        &consecutive_config,
+    )?;
+
+    let mut good_cells = vec![true; n_events];
+    for (segment_idx, &(start, end)) in breaks.iter().enumerate() {
+        if filtered_bad_segments[segment_idx] {
+            for cell in good_cells.iter_mut().take(end).skip(start) {
+                *cell = false;
+            }
+        }
+    }
+
+    let n_removed = good_cells.iter().filter(|&&keep| !keep).count();
+    let percentage_removed = (n_removed as f64 / n_events as f64) * 100.0;
+
+    Ok(FlowCutResult {
+        good_cells,
+        bad_segments: filtered_bad_segments,
+        contribution,
+        percentage_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::{ParameterMetadata, SimpleFcs};
+    use polars::df;
+    use std::collections::HashMap as StdHashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_flow_cut_flags_drifted_segment() {
+        // 10 segments of 100 events each, with segment means a unit or two apart (so each
+        // measure has a nonzero MAD), except one segment whose mean jumps.
+        let mut fl1 = Vec::new();
+        for segment in 0..10 {
+            let base = if segment == 5 { 5000.0 } else { 100.0 + (segment % 3) as f64 };
+            fl1.extend(std::iter::repeat(base).take(100));
+        }
+        let n = fl1.len();
+
+        let df = Arc::new(df!["FL1-A" => fl1].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = FlowCutConfig {
+            channels: vec!["FL1-A".to_string()],
+            events_per_segment: 100,
+            measure_threshold: 3.0,
+            consecutive_segments: 1,
+        };
+
+        let result = flow_cut(&fcs, &config).expect("flow_cut should succeed");
+        assert_eq!(result.good_cells.len(), n);
+        assert!(result.bad_segments[5], "Drifted segment should be flagged");
+        assert!(
+            result.good_cells[500..600].iter().all(|&keep| !keep),
+            "Every event in the drifted segment should be removed"
+        );
+    }
+
+    #[test]
+    fn test_flow_cut_stable_data_has_no_bad_segments() {
+        let fl1 = vec![100.0; 1000];
+        let df = Arc::new(df!["FL1-A" => fl1].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+
+        let config = FlowCutConfig {
+            channels: vec!["FL1-A".to_string()],
+            events_per_segment: 100,
+            ..Default::default()
+        };
+
+        let result = flow_cut(&fcs, &config).expect("flow_cut should succeed");
+        assert!(result.bad_segments.iter().all(|&bad| !bad));
+        assert_eq!(result.percentage_removed, 0.0);
+    }
+
+    #[test]
+    fn test_flow_cut_errors_without_channels() {
+        let df = Arc::new(df!["FL1-A" => vec![0.0; 200]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: StdHashMap::new(),
+        };
+        let config = FlowCutConfig::default();
+        assert!(flow_cut(&fcs, &config).is_err());
+    }
+
+    #[test]
+    fn test_flow_cut_dynamic_range_metadata_still_read() {
+        let df = Arc::new(df!["FL1-A" => vec![100.0; 200]].unwrap());
+        let mut metadata = StdHashMap::new();
+        metadata.insert(
+            "FL1-A".to_string(),
+            ParameterMetadata {
+                min_range: 0.0,
+                max_range: 262144.0,
+                name: "FL1-A".to_string(),
+            },
+        );
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: metadata,
+        };
+
+        let config = FlowCutConfig {
+            channels: vec!["FL1-A".to_string()],
+            events_per_segment: 100,
+            ..Default::default()
+        };
+
+        assert!(flow_cut(&fcs, &config).is_ok());
+    }
+}
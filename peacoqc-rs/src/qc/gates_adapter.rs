@@ -0,0 +1,213 @@
+//! Adapter from `PeacoQCResult` to `flow-gates` time-interval gates
+//!
+//! [`peacoqc`](crate::peacoqc) returns a per-event good/bad mask, which is normally applied by
+//! discarding bad events outright. That's destructive: once applied, the original events are
+//! gone and the decision can't be inspected or toggled later. This module instead turns the
+//! contiguous runs of good events into one Rectangle gate per run on the Time axis (plus a
+//! Boolean OR combining them, when there's more than one), so QC becomes a node a caller can add
+//! to a [`flow_gates::GateHierarchy`] like any other gate.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::qc::PeacoQCResult;
+use flow_gates::{BooleanOperation, Gate, GateGeometry, GateNode};
+use std::sync::Arc;
+
+/// Contiguous `[start, end)` event-index runs where `good_cells` is `true`
+fn good_runs(good_cells: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &good) in good_cells.iter().enumerate() {
+        match (good, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s, good_cells.len()));
+    }
+    runs
+}
+
+/// Build one Rectangle gate per contiguous run of QC-good events in `result`, spanning
+/// `time_channel` on the x-axis (from the first to the last good event's Time value in that
+/// run) and the full range of `y_channel` on the y-axis, plus a Boolean OR gate combining all
+/// runs when there's more than one.
+///
+/// `id_prefix` is used to derive stable, unique IDs for the generated gates (`{id_prefix}_run_0`,
+/// `{id_prefix}_run_1`, ..., `{id_prefix}_or`).
+///
+/// # Errors
+/// Returns an error if `time_channel`'s event count doesn't match `result.good_cells`, if
+/// `y_channel` has no known range, or if `result.good_cells` contains no good events.
+pub fn qc_result_to_time_gates<T: PeacoQCData>(
+    fcs: &T,
+    result: &PeacoQCResult,
+    time_channel: &str,
+    y_channel: &str,
+    id_prefix: &str,
+) -> Result<Vec<Gate>> {
+    let time_values = fcs.get_channel_f64(time_channel)?;
+    if time_values.len() != result.good_cells.len() {
+        return Err(PeacoQCError::ConfigError(format!(
+            "Time channel '{}' has {} events, but the QC result has {}",
+            time_channel,
+            time_values.len(),
+            result.good_cells.len()
+        )));
+    }
+
+    let (y_min, y_max) = fcs
+        .get_channel_range(y_channel)
+        .ok_or_else(|| PeacoQCError::ChannelNotFound(y_channel.to_string()))?;
+
+    let runs = good_runs(&result.good_cells);
+    if runs.is_empty() {
+        return Err(PeacoQCError::InsufficientData { min: 1, actual: 0 });
+    }
+
+    let gates: Vec<Gate> = runs
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, end))| {
+            let t_start = time_values[start] as f32;
+            let t_end = time_values[end - 1] as f32;
+
+            let mut min_node = GateNode::new(format!("{id_prefix}_run_{i}_min"));
+            min_node.set_coordinate(Arc::from(time_channel), t_start);
+            min_node.set_coordinate(Arc::from(y_channel), y_min as f32);
+
+            let mut max_node = GateNode::new(format!("{id_prefix}_run_{i}_max"));
+            max_node.set_coordinate(Arc::from(time_channel), t_end);
+            max_node.set_coordinate(Arc::from(y_channel), y_max as f32);
+
+            Gate::new(
+                format!("{id_prefix}_run_{i}"),
+                format!("QC good ({start}-{end})"),
+                GateGeometry::Rectangle {
+                    min: min_node,
+                    max: max_node,
+                },
+                time_channel,
+                y_channel,
+            )
+        })
+        .collect();
+
+    if gates.len() == 1 {
+        return Ok(gates);
+    }
+
+    let or_gate = Gate::new(
+        format!("{id_prefix}_or"),
+        "QC good (all runs)".to_string(),
+        GateGeometry::Boolean {
+            operation: BooleanOperation::Or,
+            operands: gates.iter().map(|g| Arc::clone(&g.id)).collect(),
+        },
+        time_channel,
+        y_channel,
+    );
+
+    let mut all = gates;
+    all.push(or_gate);
+    Ok(all)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::{ParameterMetadata, SimpleFcs};
+    use crate::qc::BinSizeStrategy;
+    use polars::df;
+    use std::collections::HashMap;
+    use std::sync::Arc as StdArc;
+
+    fn test_fcs() -> SimpleFcs {
+        let df = StdArc::new(
+            df![
+                "Time" => &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+                "FSC-A" => &[10.0, 20.0, 30.0, 40.0, 50.0, 60.0],
+            ]
+            .unwrap(),
+        );
+        let mut parameter_metadata = HashMap::new();
+        parameter_metadata.insert(
+            "FSC-A".to_string(),
+            ParameterMetadata {
+                min_range: 0.0,
+                max_range: 262144.0,
+                name: "FSC-A".to_string(),
+            },
+        );
+        SimpleFcs {
+            data_frame: df,
+            parameter_metadata,
+        }
+    }
+
+    fn test_result(good_cells: Vec<bool>) -> PeacoQCResult {
+        PeacoQCResult {
+            good_cells,
+            percentage_removed: 0.0,
+            it_percentage: None,
+            mad_percentage: None,
+            isolation_forest_percentage: None,
+            consecutive_percentage: 0.0,
+            peaks: HashMap::new(),
+            n_bins: 1,
+            events_per_bin: 6,
+            bin_size_strategy: BinSizeStrategy::RHeuristic,
+            channel_contribution: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_single_run_produces_one_gate() {
+        let fcs = test_fcs();
+        let result = test_result(vec![true, true, true, true, true, true]);
+
+        let gates = qc_result_to_time_gates(&fcs, &result, "Time", "FSC-A", "qc").unwrap();
+
+        assert_eq!(gates.len(), 1);
+        match &gates[0].geometry {
+            GateGeometry::Rectangle { min, max } => {
+                assert_eq!(min.get_coordinate("Time"), Some(0.0));
+                assert_eq!(max.get_coordinate("Time"), Some(5.0));
+                assert_eq!(min.get_coordinate("FSC-A"), Some(0.0));
+                assert_eq!(max.get_coordinate("FSC-A"), Some(262144.0));
+            }
+            other => panic!("expected Rectangle geometry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_runs_produce_or_gate() {
+        let fcs = test_fcs();
+        let result = test_result(vec![true, true, false, false, true, true]);
+
+        let gates = qc_result_to_time_gates(&fcs, &result, "Time", "FSC-A", "qc").unwrap();
+
+        assert_eq!(gates.len(), 3);
+        let or_gate = gates.last().unwrap();
+        match &or_gate.geometry {
+            GateGeometry::Boolean { operation, operands } => {
+                assert_eq!(*operation, BooleanOperation::Or);
+                assert_eq!(operands.len(), 2);
+            }
+            other => panic!("expected Boolean geometry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_all_bad_errors() {
+        let fcs = test_fcs();
+        let result = test_result(vec![false; 6]);
+
+        assert!(qc_result_to_time_gates(&fcs, &result, "Time", "FSC-A", "qc").is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! Multichannel Isolation Forest anomaly detection
+//!
+//! `isolation_tree` builds a single, deterministic tree that splits on SD
+//! reduction and looks for the largest homogeneous group of bins -- it's
+//! effective, but a clog or other correlated anomaly that shifts several
+//! channels together by a modest amount can still slip past it, and past
+//! per-channel MAD, if no single channel or split crosses either threshold
+//! on its own.
+//!
+//! This module instead builds a standard randomized isolation forest (Liu et
+//! al., 2008) over the same joint per-bin feature matrix (all channels/clusters
+//! at once): many trees, each built from random feature/split choices over a
+//! random subsample of bins, so a bin's *combined* position across all
+//! channels determines how quickly it gets isolated. Anomalies isolate in
+//! fewer splits on average, giving a continuous anomaly score rather than a
+//! single largest-node cut.
+
+use crate::error::{PeacoQCError, Result};
+use crate::qc::isolation_tree::{build_feature_matrix, channel_of_feature};
+use crate::qc::peaks::ChannelPeakFrame;
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Configuration for the multichannel Isolation Forest QC mode
+#[derive(Debug, Clone)]
+pub struct IsolationForestConfig {
+    /// Number of trees in the forest (default: 100)
+    pub n_trees: usize,
+
+    /// Expected proportion of bins that are anomalous (default: 0.05)
+    ///
+    /// **Tradeoff**: Raising this flags more bins as outliers regardless of
+    /// how sharply their anomaly scores actually separate from the rest.
+    pub contamination: f64,
+
+    /// Number of bins subsampled to build each tree (default: 256, matching
+    /// the original Isolation Forest paper's recommendation)
+    pub sample_size: usize,
+
+    /// RNG seed, so a run can be reproduced exactly
+    pub seed: u64,
+}
+
+impl Default for IsolationForestConfig {
+    fn default() -> Self {
+        Self {
+            n_trees: 100,
+            contamination: 0.05,
+            sample_size: 256,
+            seed: 42,
+        }
+    }
+}
+
+/// Result of Isolation Forest anomaly detection
+#[derive(Debug)]
+pub struct IsolationForestResult {
+    /// Boolean mask indicating outlier bins (true = outlier)
+    pub outlier_bins: Vec<bool>,
+
+    /// Anomaly score per bin in `[0, 1]`; higher means more anomalous
+    /// (matches Liu et al.'s `s(x, n)` scoring function)
+    pub anomaly_scores: Vec<f64>,
+
+    /// Score threshold above which a bin is flagged, derived from `contamination`
+    pub threshold: f64,
+}
+
+struct ForestNode {
+    split_feature: usize,
+    split_value: f64,
+    left: usize,
+    right: usize,
+}
+
+enum Node {
+    Internal(ForestNode),
+    Leaf { size: usize },
+}
+
+struct IsolationTree {
+    nodes: Vec<Node>,
+}
+
+impl IsolationTree {
+    fn build(
+        data: &[Vec<f64>],
+        rows: &[usize],
+        depth: usize,
+        max_depth: usize,
+        rng: &mut StdRng,
+        feature_weights: &[f64],
+    ) -> Self {
+        let mut nodes = Vec::new();
+        Self::build_node(data, rows, depth, max_depth, rng, &mut nodes, feature_weights);
+        Self { nodes }
+    }
+
+    /// Recursively builds nodes into the flat `nodes` arena, returning the index of the node just built
+    fn build_node(
+        data: &[Vec<f64>],
+        rows: &[usize],
+        depth: usize,
+        max_depth: usize,
+        rng: &mut StdRng,
+        nodes: &mut Vec<Node>,
+        feature_weights: &[f64],
+    ) -> usize {
+        let n_features = data[0].len();
+
+        if rows.len() <= 1 || depth >= max_depth {
+            nodes.push(Node::Leaf { size: rows.len() });
+            return nodes.len() - 1;
+        }
+
+        // Pick a random feature with non-degenerate range among these rows, weighted so a
+        // channel's columns are drawn more or less often according to its channel weight
+        // (e.g. a channel dominated by spillover spread can be down-weighted to isolate on
+        // it less often); give up after a handful of tries and fall back to a leaf if every
+        // feature is constant here.
+        for _ in 0..n_features.max(1) {
+            let feature = weighted_feature_index(feature_weights, rng);
+            let (min, max) = rows
+                .iter()
+                .map(|&r| data[r][feature])
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                    (min.min(v), max.max(v))
+                });
+
+            if min >= max {
+                continue;
+            }
+
+            let split_value = rng.random_range(min..max);
+            let left_rows: Vec<usize> = rows
+                .iter()
+                .copied()
+                .filter(|&r| data[r][feature] < split_value)
+                .collect();
+            let right_rows: Vec<usize> = rows
+                .iter()
+                .copied()
+                .filter(|&r| data[r][feature] >= split_value)
+                .collect();
+
+            if left_rows.is_empty() || right_rows.is_empty() {
+                continue;
+            }
+
+            // Reserve this node's slot before recursing so left/right indices are known.
+            let this_idx = nodes.len();
+            nodes.push(Node::Leaf { size: rows.len() }); // placeholder, replaced below
+            let left = Self::build_node(data, &left_rows, depth + 1, max_depth, rng, nodes, feature_weights);
+            let right = Self::build_node(data, &right_rows, depth + 1, max_depth, rng, nodes, feature_weights);
+            nodes[this_idx] = Node::Internal(ForestNode {
+                split_feature: feature,
+                split_value,
+                left,
+                right,
+            });
+            return this_idx;
+        }
+
+        nodes.push(Node::Leaf { size: rows.len() });
+        nodes.len() - 1
+    }
+
+    /// Path length for a single point, starting from the root (node 0)
+    fn path_length(&self, point: &[f64]) -> f64 {
+        let mut node_idx = 0;
+        let mut depth = 0.0;
+
+        loop {
+            match &self.nodes[node_idx] {
+                Node::Leaf { size } => return depth + avg_path_length(*size),
+                Node::Internal(node) => {
+                    depth += 1.0;
+                    node_idx = if point[node.split_feature] < node.split_value {
+                        node.left
+                    } else {
+                        node.right
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Average path length of an unsuccessful search in a Binary Search Tree of `n` nodes
+/// (Liu et al.'s `c(n)`), used to normalize raw path lengths into anomaly scores
+fn avg_path_length(n: usize) -> f64 {
+    if n <= 1 {
+        return 0.0;
+    }
+    let n = n as f64;
+    2.0 * ((n - 1.0).ln() + 0.5772156649) - (2.0 * (n - 1.0)) / n
+}
+
+/// Pick a feature column index with probability proportional to `feature_weights`, falling
+/// back to a uniform pick if the weights are empty or don't sum to a usable total
+fn weighted_feature_index(feature_weights: &[f64], rng: &mut StdRng) -> usize {
+    let total: f64 = feature_weights.iter().sum();
+    if !(total > 0.0) {
+        return rng.random_range(0..feature_weights.len());
+    }
+
+    let mut draw = rng.random_range(0.0..total);
+    for (i, &weight) in feature_weights.iter().enumerate() {
+        if draw < weight {
+            return i;
+        }
+        draw -= weight;
+    }
+    feature_weights.len() - 1
+}
+
+/// Detect anomalous bins using a multichannel Isolation Forest
+///
+/// # Algorithm
+/// 1. Build the joint feature matrix (bins × channel/cluster columns), same as `isolation_tree`
+/// 2. Build `n_trees` randomized trees, each over a random subsample of up to `sample_size` bins
+/// 3. Score each bin as `2^(-avg_path_length / c(sample_size))` (Liu et al.'s anomaly score)
+/// 4. Flag the top `contamination` fraction of bins by score as outliers
+pub fn isolation_forest_detect(
+    peak_results: &HashMap<String, ChannelPeakFrame>,
+    n_bins: usize,
+    config: &IsolationForestConfig,
+    channel_weights: &HashMap<String, f64>,
+) -> Result<IsolationForestResult> {
+    if peak_results.is_empty() {
+        return Err(PeacoQCError::NoPeaksDetected);
+    }
+
+    let (feature_matrix, feature_names) = build_feature_matrix(peak_results, n_bins, channel_weights)?;
+
+    if n_bins < 2 {
+        return Err(PeacoQCError::InsufficientData { min: 2, actual: n_bins });
+    }
+
+    // Bias which feature a tree splits on by channel weight, same intent as the SD-gain scaling
+    // in `isolation_tree`: a down-weighted channel (e.g. one dominated by spillover spread)
+    // gets isolated on less often, so it contributes fewer false anomalies to the forest.
+    let feature_weights: Vec<f64> = feature_names
+        .iter()
+        .map(|name| {
+            channel_weights
+                .get(channel_of_feature(name))
+                .copied()
+                .unwrap_or(1.0)
+        })
+        .collect();
+
+    let sample_size = config.sample_size.min(n_bins);
+    let max_depth = (sample_size as f64).log2().ceil() as usize;
+    let all_rows: Vec<usize> = (0..n_bins).collect();
+
+    let mut seed_rng = StdRng::seed_from_u64(config.seed);
+    let tree_seeds: Vec<u64> = (0..config.n_trees).map(|_| seed_rng.random()).collect();
+
+    let build_tree = |seed: u64| -> IsolationTree {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut sample = all_rows.clone();
+        // Partial Fisher-Yates shuffle: only need the first `sample_size` elements in random order
+        for i in 0..sample_size.min(sample.len().saturating_sub(1)) {
+            let j = rng.random_range(i..sample.len());
+            sample.swap(i, j);
+        }
+        sample.truncate(sample_size);
+        IsolationTree::build(&feature_matrix, &sample, 0, max_depth, &mut rng, &feature_weights)
+    };
+
+    #[cfg(feature = "parallel")]
+    let trees: Vec<IsolationTree> = tree_seeds.into_par_iter().map(build_tree).collect();
+    #[cfg(not(feature = "parallel"))]
+    let trees: Vec<IsolationTree> = tree_seeds.into_iter().map(build_tree).collect();
+
+    let normalization = avg_path_length(sample_size);
+    let anomaly_scores: Vec<f64> = feature_matrix
+        .iter()
+        .map(|point| {
+            let avg_path = trees.iter().map(|tree| tree.path_length(point)).sum::<f64>()
+                / trees.len() as f64;
+            if normalization > 0.0 {
+                2f64.powf(-avg_path / normalization)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut sorted_scores = anomaly_scores.clone();
+    sorted_scores.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let n_outliers = ((n_bins as f64) * config.contamination).ceil() as usize;
+    let threshold = if n_outliers == 0 {
+        f64::INFINITY
+    } else {
+        sorted_scores[n_outliers.min(n_bins) - 1]
+    };
+
+    let outlier_bins: Vec<bool> = anomaly_scores.iter().map(|&s| s >= threshold).collect();
+
+    Ok(IsolationForestResult {
+        outlier_bins,
+        anomaly_scores,
+        threshold,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qc::peaks::PeakInfo;
+
+    fn make_peaks(channel_values: &[(&str, Vec<f64>)]) -> HashMap<String, ChannelPeakFrame> {
+        let mut peak_results = HashMap::new();
+        for (channel, values) in channel_values {
+            let peaks = values
+                .iter()
+                .enumerate()
+                .map(|(bin, &peak_value)| PeakInfo { bin, peak_value, cluster: 1 })
+                .collect();
+            peak_results.insert((*channel).to_string(), ChannelPeakFrame { peaks });
+        }
+        peak_results
+    }
+
+    #[test]
+    fn test_isolation_forest_flags_correlated_anomaly() {
+        // A bin that is only mildly high in each individual channel but shifted
+        // across all channels at once -- the kind of correlated clog that a
+        // per-channel check could miss, but a joint feature matrix should not.
+        let n = 100;
+        let mut fl1 = vec![100.0; n];
+        let mut fl2 = vec![200.0; n];
+        let mut fl3 = vec![300.0; n];
+        for v in [&mut fl1, &mut fl2, &mut fl3] {
+            v[50] += 15.0;
+        }
+
+        let peaks = make_peaks(&[("FL1-A", fl1), ("FL2-A", fl2), ("FL3-A", fl3)]);
+
+        let config = IsolationForestConfig {
+            n_trees: 100,
+            contamination: 0.05,
+            sample_size: n,
+            seed: 7,
+        };
+
+        let result = isolation_forest_detect(&peaks, n, &config, &HashMap::new()).unwrap();
+
+        assert!(result.outlier_bins[50], "correlated shift should be flagged");
+        assert!(result.anomaly_scores[50] > result.anomaly_scores[0]);
+    }
+
+    #[test]
+    fn test_isolation_forest_contamination_bounds_outlier_count() {
+        let n = 200;
+        let values = (0..n).map(|i| 100.0 + i as f64 * 0.1).collect::<Vec<_>>();
+        let peaks = make_peaks(&[("FL1-A", values)]);
+
+        let config = IsolationForestConfig {
+            n_trees: 50,
+            contamination: 0.1,
+            sample_size: n,
+            seed: 1,
+        };
+
+        let result = isolation_forest_detect(&peaks, n, &config, &HashMap::new()).unwrap();
+        let n_outliers = result.outlier_bins.iter().filter(|&&x| x).count();
+
+        assert!(n_outliers > 0);
+        assert!(n_outliers <= (n as f64 * config.contamination).ceil() as usize + 1);
+    }
+
+    #[test]
+    fn test_isolation_forest_errors_without_peaks() {
+        let peaks = HashMap::new();
+        let config = IsolationForestConfig::default();
+        assert!(isolation_forest_detect(&peaks, 100, &config, &HashMap::new()).is_err());
+    }
+}
@@ -15,6 +15,7 @@
 
 use crate::error::{PeacoQCError, Result};
 use crate::qc::peaks::ChannelPeakFrame;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -135,6 +136,7 @@ pub fn isolation_tree_detect(
     peak_results: &HashMap<String, ChannelPeakFrame>,
     n_bins: usize,
     config: &IsolationTreeConfig,
+    channel_weights: &HashMap<String, f64>,
 ) -> Result<IsolationTreeResult> {
     // Check if we have enough bins
     if n_bins < config.force_it {
@@ -153,13 +155,13 @@ pub fn isolation_tree_detect(
     // Use GPU if available (batched operations provide speedup even for smaller datasets)
     #[cfg(feature = "gpu")]
     let (feature_matrix, feature_names) = if is_gpu_available() {
-        build_feature_matrix_gpu(peak_results, n_bins)?
+        build_feature_matrix_gpu(peak_results, n_bins, channel_weights)?
     } else {
-        build_feature_matrix(peak_results, n_bins)?
+        build_feature_matrix(peak_results, n_bins, channel_weights)?
     };
 
     #[cfg(not(feature = "gpu"))]
-    let (feature_matrix, feature_names) = build_feature_matrix(peak_results, n_bins)?;
+    let (feature_matrix, feature_names) = build_feature_matrix(peak_results, n_bins, channel_weights)?;
     let n_features = feature_matrix[0].len();
 
     eprintln!(
@@ -167,9 +169,22 @@ pub fn isolation_tree_detect(
         n_bins, n_features
     );
 
+    // A column's measured SD-gain is scaled by its channel's weight before it's allowed to win
+    // a split or update `gain_limit`, so down-weighted channels (e.g. ones dominated by
+    // spillover spread) need a proportionally stronger split to still drive bin removal.
+    let feature_weights: Vec<f64> = feature_names
+        .iter()
+        .map(|name| {
+            channel_weights
+                .get(channel_of_feature(name))
+                .copied()
+                .unwrap_or(1.0)
+        })
+        .collect();
+
     // Build the SD-based isolation tree
     let (tree, selection) =
-        build_isolation_tree_sd(&feature_matrix, &feature_names, config.it_limit)?;
+        build_isolation_tree_sd(&feature_matrix, &feature_names, &feature_weights, config.it_limit)?;
 
     // Find the largest leaf node (node with most datapoints and a path_length)
     let largest_node = tree
@@ -220,12 +235,20 @@ pub fn isolation_tree_detect(
 ///
 /// Returns: (matrix, feature_names) where matrix is Vec<Vec<f64>> (bins × features)
 /// Feature names are formatted as "{channel}_cluster_{cluster_id}"
+///
+/// Channels mapped to a weight of `0.0` or less in `channel_weights` are dropped entirely (no
+/// columns are emitted for them); channels absent from the map default to a weight of `1.0`.
 pub fn build_feature_matrix(
     peak_results: &HashMap<String, ChannelPeakFrame>,
     n_bins: usize,
+    channel_weights: &HashMap<String, f64>,
 ) -> Result<(Vec<Vec<f64>>, Vec<String>)> {
     // Get channels in consistent order
-    let mut channel_names: Vec<String> = peak_results.keys().cloned().collect();
+    let mut channel_names: Vec<String> = peak_results
+        .keys()
+        .filter(|channel| channel_weights.get(*channel).copied().unwrap_or(1.0) > 0.0)
+        .cloned()
+        .collect();
     channel_names.sort();
 
     // Collect all clusters per channel (matching R's ExtractPeakValues)
@@ -282,6 +305,14 @@ pub fn build_feature_matrix(
     Ok((matrix, feature_names))
 }
 
+/// Recovers the channel name from a `"{channel}_cluster_{cluster_id}"` feature name, as produced
+/// by [`build_feature_matrix`]
+pub(crate) fn channel_of_feature(feature_name: &str) -> &str {
+    feature_name
+        .rsplit_once("_cluster_")
+        .map_or(feature_name, |(channel, _)| channel)
+}
+
 /// Build SD-based isolation tree (matches R's isolationTreeSD)
 ///
 /// Returns: (tree_nodes, selection_matrix)
@@ -290,6 +321,7 @@ pub fn build_feature_matrix(
 fn build_isolation_tree_sd(
     data: &[Vec<f64>],
     feature_names: &[String],
+    feature_weights: &[f64],
     initial_gain_limit: f64,
 ) -> Result<(Vec<TreeNode>, Vec<Vec<bool>>)> {
     let n_bins = data.len();
@@ -338,7 +370,7 @@ fn build_isolation_tree_sd(
         }
 
         // Find best split across all columns
-        let best_split = find_best_split_parallel(data, &rows, feature_names, gain_limit);
+        let best_split = find_best_split_parallel(data, &rows, feature_weights, gain_limit);
 
         match best_split {
             Some((col_idx, split_value, gain)) => {
@@ -441,15 +473,20 @@ fn build_isolation_tree_sd(
 fn find_best_split_parallel(
     data: &[Vec<f64>],
     rows: &[usize],
-    _feature_names: &[String],
+    feature_weights: &[f64],
     gain_limit: f64,
 ) -> Option<(usize, f64, f64)> {
     let n_features = data[0].len();
 
     // Process each column in parallel
+    #[cfg(feature = "parallel")]
     let column_results: Vec<Option<(usize, f64, f64)>> = (0..n_features)
         .into_par_iter()
-        .map(|col| find_best_split_for_column(data, rows, col, gain_limit))
+        .map(|col| find_best_split_for_column(data, rows, col, feature_weights[col], gain_limit))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let column_results: Vec<Option<(usize, f64, f64)>> = (0..n_features)
+        .map(|col| find_best_split_for_column(data, rows, col, feature_weights[col], gain_limit))
         .collect();
 
     // Find the best split across all columns
@@ -479,6 +516,7 @@ fn find_best_split_for_column(
     data: &[Vec<f64>],
     rows: &[usize],
     col: usize,
+    weight: f64,
     gain_limit: f64,
 ) -> Option<(usize, f64, f64)> {
     // Get and sort values for this column
@@ -507,9 +545,10 @@ fn find_best_split_for_column(
         let sd_1 = if i == 1 { 0.0 } else { std_dev(left) };
         let sd_2 = if i == n - 1 { 0.0 } else { std_dev(right) };
 
-        // Gain formula (R: line 321)
+        // Gain formula (R: line 321), scaled by this column's channel weight so
+        // down-weighted channels need a proportionally stronger split to win
         let mean_child_sd = (sd_1 + sd_2) / 2.0;
-        let gain = (base_sd - mean_child_sd) / base_sd;
+        let gain = (base_sd - mean_child_sd) / base_sd * weight;
 
         if gain.is_finite() && gain >= best_gain {
             best_gain = gain;
@@ -586,7 +625,7 @@ mod tests {
             it_limit: 0.6,
         };
 
-        let result = isolation_tree_detect(&peak_results, 200, &config).unwrap();
+        let result = isolation_tree_detect(&peak_results, 200, &config, &HashMap::new()).unwrap();
 
         // The outlier region (bins 50-59) should be marked as outliers
         // and the majority of bins should be marked as good
@@ -622,7 +661,7 @@ mod tests {
         peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks: peaks1 });
         peak_results.insert("FL2-A".to_string(), ChannelPeakFrame { peaks: peaks2 });
 
-        let (matrix, names) = build_feature_matrix(&peak_results, 5).unwrap();
+        let (matrix, names) = build_feature_matrix(&peak_results, 5, &HashMap::new()).unwrap();
 
         assert_eq!(matrix.len(), 5); // 5 bins
         // NEW: Should have 2 columns (one per cluster per channel)
@@ -45,6 +45,12 @@ pub struct MADResult {
 
     /// Percentage contribution of each channel to outlier detection
     pub contribution: HashMap<String, f64>,
+
+    /// Per-channel bin-level outlier mask (true = this channel flagged the bin)
+    ///
+    /// Lets a caller identify which detector triggered removal of a given bin, rather
+    /// than only seeing the combined [`Self::outlier_bins`] mask.
+    pub channel_outlier_bins: HashMap<String, Vec<bool>>,
 }
 
 /// Apply smoothing to peak trajectory before MAD detection
@@ -198,11 +204,17 @@ fn mad_outliers_single_channel(
 /// * `existing_outliers` - Boolean mask where true = bin passed IT (still candidate for MAD)
 /// * `n_bins` - Total number of bins
 /// * `config` - MAD configuration
+/// * `channel_weights` - Per-channel weight (default `1.0` for channels not present in the map).
+///   A weight of `0.0` or less excludes the channel from the vote entirely; other weights scale
+///   that channel's effective `mad_threshold` (`config.mad_threshold / weight`), so a
+///   down-weighted channel (e.g. one dominated by spillover spread) needs a proportionally
+///   larger deviation to be flagged.
 pub fn mad_outlier_method(
     peak_results: &HashMap<String, ChannelPeakFrame>,
     existing_outliers: &[bool],
     n_bins: usize,
     config: &MADConfig,
+    channel_weights: &HashMap<String, f64>,
 ) -> Result<MADResult> {
     if peak_results.is_empty() {
         return Err(PeacoQCError::NoPeaksDetected);
@@ -212,8 +224,12 @@ pub fn mad_outlier_method(
     // Structure: (channel, cluster) -> Vec<f64> (full-length trajectory)
     let mut cluster_trajectories: Vec<(String, usize, Vec<f64>)> = Vec::new();
 
-    // Get channel names in sorted order for consistent processing
-    let mut channel_names: Vec<&String> = peak_results.keys().collect();
+    // Get channel names in sorted order for consistent processing, excluding any channel
+    // weighted out entirely
+    let mut channel_names: Vec<&String> = peak_results
+        .keys()
+        .filter(|channel| channel_weights.get(*channel).copied().unwrap_or(1.0) > 0.0)
+        .collect();
     channel_names.sort();
 
     for channel in channel_names {
@@ -270,6 +286,7 @@ pub fn mad_outlier_method(
     // R: to_remove_bins_df <- apply(peak_frame, 2, MADOutliers, MAD)
     let mut outlier_bins_per_cluster: Vec<Vec<bool>> = Vec::new();
     let mut contribution = HashMap::new();
+    let mut channel_outlier_bins: HashMap<String, Vec<bool>> = HashMap::new();
 
     for (channel, _cluster_id, trajectory) in &cluster_trajectories {
         // Filter to bins that passed IT (matching R: peak_frame <- peaks[outlier_bins, , drop = FALSE])
@@ -301,10 +318,11 @@ pub fn mad_outlier_method(
             continue;
         }
 
-        // Apply MAD outlier detection with smoothing
+        // Apply MAD outlier detection with smoothing, scaled by this channel's weight
+        let weight = channel_weights.get(channel.as_str()).copied().unwrap_or(1.0);
         let cluster_outliers = mad_outliers_single_channel(
             &filtered_trajectory,
-            config.mad_threshold,
+            config.mad_threshold / weight,
             config.smooth_param,
         )?;
 
@@ -329,6 +347,15 @@ pub fn mad_outlier_method(
             .entry(channel.clone())
             .and_modify(|e| *e += contrib_pct)
             .or_insert(contrib_pct);
+
+        let channel_bins = channel_outlier_bins
+            .entry(channel.clone())
+            .or_insert_with(|| vec![false; n_bins]);
+        for (bin_idx, &is_outlier) in full_outliers.iter().enumerate() {
+            if is_outlier {
+                channel_bins[bin_idx] = true;
+            }
+        }
     }
 
     // Combine: a bin is an outlier if ANY cluster marks it
@@ -353,6 +380,7 @@ pub fn mad_outlier_method(
     Ok(MADResult {
         outlier_bins,
         contribution,
+        channel_outlier_bins,
     })
 }
 
@@ -428,7 +456,7 @@ mod tests {
             smooth_param: 0.2,  // Less smoothing
         };
 
-        let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config).unwrap();
+        let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config, &HashMap::new()).unwrap();
 
         // Should detect outlier(s) near bin 25 with extreme value
         let n_outliers = result.outlier_bins.iter().filter(|&&x| x).count();
@@ -456,7 +484,7 @@ mod tests {
         let existing_outliers = vec![true; 50];
         let config = MADConfig::default();
 
-        let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config).unwrap();
+        let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config, &HashMap::new()).unwrap();
 
         // Stable data should have no outliers (MAD = 0)
         let n_outliers = result.outlier_bins.iter().filter(|&&x| x).count();
@@ -2,41 +2,89 @@ use crate::PeacoQCData;
 use crate::error::{PeacoQCError, Result};
 use std::collections::HashMap;
 
+/// Where a channel's range comes from when checking for margin events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarginRangeSource {
+    /// Use the range from the FCS file's PnR keyword, via [`PeacoQCData::get_channel_range`]
+    /// (falling back to the observed data range, widened to the detector's typical maximum,
+    /// if the keyword isn't available)
+    Keyword,
+
+    /// Derive the range from the observed data (min/max of the channel's own values)
+    Data,
+
+    /// Use an explicit `(min, max)` range, ignoring both the keyword and the data
+    Explicit(f64, f64),
+}
+
+/// Per-channel margin removal behavior
+#[derive(Debug, Clone, Copy)]
+pub struct MarginChannelSpec {
+    /// Where this channel's range comes from
+    pub range: MarginRangeSource,
+
+    /// Whether to check for events piled up at the minimum of the range
+    pub remove_min: bool,
+
+    /// Whether to check for events piled up at the maximum of the range
+    pub remove_max: bool,
+}
+
+impl Default for MarginChannelSpec {
+    fn default() -> Self {
+        Self {
+            range: MarginRangeSource::Keyword,
+            remove_min: true,
+            remove_max: true,
+        }
+    }
+}
+
 /// Configuration for margin removal
 #[derive(Debug, Clone)]
 pub struct MarginConfig {
     /// Channels to check for margin events
     pub channels: Vec<String>,
 
-    /// Override channel specifications (minRange, maxRange)
-    pub channel_specifications: Option<HashMap<String, (f64, f64)>>,
-
-    /// Channels to check for minimum margins (defaults to all channels)
-    pub remove_min: Option<Vec<String>>,
-
-    /// Channels to check for maximum margins (defaults to all channels)
-    pub remove_max: Option<Vec<String>>,
+    /// Per-channel overrides; a channel not present here uses [`MarginChannelSpec::default`]
+    /// (PnR range, both min and max margins checked)
+    pub per_channel: HashMap<String, MarginChannelSpec>,
 }
 
 impl Default for MarginConfig {
     fn default() -> Self {
         Self {
             channels: Vec::new(),
-            channel_specifications: None,
-            remove_min: None,
-            remove_max: None,
+            per_channel: HashMap::new(),
         }
     }
 }
 
+/// Per-channel margin removal breakdown, as reported in [`MarginResult::margin_matrix`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginChannelReport {
+    /// Number of events removed at the low margin
+    pub min_removed: usize,
+
+    /// Number of events removed at the high margin
+    pub max_removed: usize,
+
+    /// Low-margin threshold used (events at or below this value were removed, if `remove_min`)
+    pub min_threshold: f64,
+
+    /// High-margin threshold used (events strictly above this value were removed, if `remove_max`)
+    pub max_threshold: f64,
+}
+
 /// Result of margin removal analysis
 #[derive(Debug)]
 pub struct MarginResult {
     /// Boolean mask indicating which events to keep (true = keep, false = remove)
     pub mask: Vec<bool>,
 
-    /// Number of events removed per channel (min and max)
-    pub margin_matrix: HashMap<String, (usize, usize)>, // (min_removed, max_removed)
+    /// Per-channel removal counts and thresholds, so a single saturating detector
+    /// responsible for most of the removal can be spotted
+    pub margin_matrix: HashMap<String, MarginChannelReport>,
 
     /// Total percentage removed
     pub percentage_removed: f64,
@@ -66,71 +114,73 @@ pub fn remove_margins<T: PeacoQCData>(fcs: &T, config: &MarginConfig) -> Result<
         ));
     }
 
+    // Read each channel in chunks rather than materializing the whole column up front - see
+    // PeacoQCData::for_each_channel_chunk.
+    const CHUNK_SIZE: usize = 8192;
+
     let n_events = fcs.n_events();
     let mut mask = vec![true; n_events];
     let mut margin_matrix = HashMap::new();
 
-    // Get lists of channels to check for min/max margins
-    let remove_min = config.remove_min.as_ref().unwrap_or(&config.channels);
-    let remove_max = config.remove_max.as_ref().unwrap_or(&config.channels);
-
     for channel in &config.channels {
-        // Get channel data
-        let values = fcs.get_channel_f64(channel)?;
-
-        // Calculate min/max from data
-        let data_min = values.iter().copied().fold(f64::INFINITY, f64::min);
-        let data_max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-
-        // Get or override parameter ranges
-        let (min_range, max_range) = if let Some(specs) = &config.channel_specifications {
-            if let Some(&(min, max)) = specs.get(channel) {
-                (min, max)
-            } else {
-                // Get from FCS metadata via trait
-                fcs.get_channel_range(channel).unwrap_or_else(|| {
-                    // Fallback to data range
-                    (data_min.min(0.0), data_max.max(262144.0))
-                })
+        // First pass: data min/max, needed to resolve the margin thresholds below.
+        let mut data_min = f64::INFINITY;
+        let mut data_max = f64::NEG_INFINITY;
+        fcs.for_each_channel_chunk(channel, CHUNK_SIZE, |chunk| {
+            for &v in chunk {
+                data_min = data_min.min(v);
+                data_max = data_max.max(v);
             }
-        } else {
-            // Get from FCS metadata via trait
-            fcs.get_channel_range(channel).unwrap_or_else(|| {
+        })?;
+
+        let spec = config.per_channel.get(channel).copied().unwrap_or_default();
+
+        // Resolve this channel's range from its configured source
+        let (min_range, max_range) = match spec.range {
+            MarginRangeSource::Explicit(min, max) => (min, max),
+            MarginRangeSource::Data => (data_min, data_max),
+            MarginRangeSource::Keyword => fcs.get_channel_range(channel).unwrap_or_else(|| {
+                // Fallback to data range
                 (data_min.min(0.0), data_max.max(262144.0))
-            })
+            }),
         };
 
         let mut min_removed = 0;
         let mut max_removed = 0;
+        let min_threshold = min_range.min(0.0).max(data_min);
+        let max_threshold = max_range.min(data_max);
 
-        // Check minimum margins
-        if remove_min.contains(channel) {
-            let threshold = min_range.min(0.0).max(data_min);
+        // Second pass: build the mask, again chunk by chunk.
+        let mut offset = 0;
+        fcs.for_each_channel_chunk(channel, CHUNK_SIZE, |chunk| {
+            for (i, &v) in chunk.iter().enumerate() {
+                let idx = offset + i;
 
-            for (i, &v) in values.iter().enumerate() {
-                if v <= threshold {
-                    mask[i] = false;
+                // Check minimum margins
+                if spec.remove_min && v <= min_threshold {
+                    mask[idx] = false;
                     min_removed += 1;
                 }
-            }
-        }
-
-        // Check maximum margins
-        // R: max_margin_ev <- e[, d] > min(meta[d, "maxRange"], max(e[, d]))
-        // Note: R uses > (strictly greater than), not >=
-        if remove_max.contains(channel) {
-            let threshold = max_range.min(data_max);
 
-            for (i, &v) in values.iter().enumerate() {
-                // Remove events strictly above the threshold (matching R's > operator)
-                if v > threshold && mask[i] {
-                    mask[i] = false;
+                // Check maximum margins (R uses strictly-greater-than, not >=)
+                // R: max_margin_ev <- e[, d] > min(meta[d, "maxRange"], max(e[, d]))
+                if spec.remove_max && v > max_threshold && mask[idx] {
+                    mask[idx] = false;
                     max_removed += 1;
                 }
             }
-        }
+            offset += chunk.len();
+        })?;
 
-        margin_matrix.insert(channel.clone(), (min_removed, max_removed));
+        margin_matrix.insert(
+            channel.clone(),
+            MarginChannelReport {
+                min_removed,
+                max_removed,
+                min_threshold,
+                max_threshold,
+            },
+        );
     }
 
     let n_removed = mask.iter().filter(|&&x| !x).count();
@@ -202,4 +252,100 @@ mod tests {
         assert_eq!(result.mask.iter().filter(|&&x| !x).count(), 2);
         assert!(result.percentage_removed > 0.0);
     }
+
+    #[test]
+    fn test_remove_margins_upper_only() {
+        // Only the upper margin should be checked on FSC-A, so the event at 0.0
+        // must survive while the event at 262144.0, strictly above the 262143.0
+        // range maximum, is still removed.
+        let df = Arc::new(df![
+            "FSC-A" => &[100.0, 200.0, 300.0, 0.0, 262144.0, 150.0],
+        ]
+        .unwrap());
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "FSC-A".to_string(),
+            ParameterMetadata {
+                min_range: 0.0,
+                max_range: 262143.0,
+                name: "FSC-A".to_string(),
+            },
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: metadata,
+        };
+
+        let mut per_channel = HashMap::new();
+        per_channel.insert(
+            "FSC-A".to_string(),
+            MarginChannelSpec {
+                range: MarginRangeSource::Keyword,
+                remove_min: false,
+                remove_max: true,
+            },
+        );
+
+        let config = MarginConfig {
+            channels: vec!["FSC-A".to_string()],
+            per_channel,
+        };
+
+        let result = remove_margins(&fcs, &config).unwrap();
+
+        assert_eq!(result.mask, vec![true, true, true, true, false, true]);
+        assert_eq!(result.margin_matrix["FSC-A"].min_removed, 0);
+        assert_eq!(result.margin_matrix["FSC-A"].max_removed, 1);
+    }
+
+    #[test]
+    fn test_remove_margins_explicit_range() {
+        // An explicit range overrides both the PnR keyword and the observed data,
+        // e.g. treating the Time channel's own min/max as an out-of-band cutoff.
+        let df = Arc::new(df![
+            "Time" => &[1.0, 2.0, 3.0, 4.0, 5.0],
+        ]
+        .unwrap());
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "Time".to_string(),
+            ParameterMetadata {
+                min_range: 0.0,
+                max_range: 100.0,
+                name: "Time".to_string(),
+            },
+        );
+
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: metadata,
+        };
+
+        let mut per_channel = HashMap::new();
+        per_channel.insert(
+            "Time".to_string(),
+            MarginChannelSpec {
+                range: MarginRangeSource::Explicit(0.0, 3.0),
+                remove_min: false,
+                remove_max: true,
+            },
+        );
+
+        let config = MarginConfig {
+            channels: vec!["Time".to_string()],
+            per_channel,
+        };
+
+        let result = remove_margins(&fcs, &config).unwrap();
+
+        // Values strictly above the explicit max of 3.0 are removed, ignoring the
+        // PnR keyword's max_range of 100.0.
+        assert_eq!(result.mask, vec![true, true, true, false, false]);
+        assert_eq!(result.margin_matrix["Time"].min_removed, 0);
+        assert_eq!(result.margin_matrix["Time"].max_removed, 2);
+        assert_eq!(result.margin_matrix["Time"].max_threshold, 3.0);
+    }
 }
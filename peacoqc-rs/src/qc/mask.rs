@@ -0,0 +1,266 @@
+//! Portable save/reapply of QC masks
+//!
+//! Lets a QC pass be computed once (e.g. centrally, on a well-resourced machine)
+//! and its result carried elsewhere -- a different tool, a later session, or a
+//! machine without the full QC pipeline -- and reapplied to the same data.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use peacoqc_rs::{PeacoQCConfig, PeacoQCData, SavedQCMask, peacoqc, reapply};
+//!
+//! # fn example<T: PeacoQCData + Clone>(fcs: T) -> peacoqc_rs::Result<()> {
+//! let config = PeacoQCConfig { channels: fcs.channel_names(), ..Default::default() };
+//! let result = peacoqc(&fcs, &config)?;
+//!
+//! let saved = SavedQCMask::new(&fcs, &result.good_cells, &config);
+//! saved.save("qc_mask.json")?;
+//!
+//! // ...later, or in another tool...
+//! let saved = SavedQCMask::load("qc_mask.json")?;
+//! let mask = reapply(&fcs, &saved)?;
+//! # let _ = mask;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+use crate::qc::PeacoQCConfig;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "file-io")]
+use std::fs::File;
+#[cfg(feature = "file-io")]
+use std::io::BufWriter;
+#[cfg(feature = "file-io")]
+use std::path::Path;
+
+/// A lightweight fingerprint of the data a QC mask was computed against.
+///
+/// This isn't a cryptographic file identity check -- `PeacoQCData` gives no access
+/// to a unique FCS file GUID -- it only confirms the event count and channel set
+/// match closely enough that reapplying the mask makes sense.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChannelFingerprint {
+    /// Number of events in the data the mask was computed against
+    pub n_events: usize,
+    /// Sorted channel names present in that data
+    pub channels: Vec<String>,
+}
+
+impl ChannelFingerprint {
+    /// Compute a fingerprint from the current state of `fcs`
+    pub fn of<T: PeacoQCData>(fcs: &T) -> Self {
+        let mut channels = fcs.channel_names();
+        channels.sort();
+        Self {
+            n_events: fcs.n_events(),
+            channels,
+        }
+    }
+}
+
+/// Simplified, serializable snapshot of the [`PeacoQCConfig`] used to produce a saved mask
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQCConfig {
+    /// Channels the QC pass analyzed
+    pub channels: Vec<String>,
+    /// QC mode used, as its debug representation (e.g. "All", "MadOnly")
+    pub determine_good_cells: String,
+    /// MAD threshold
+    pub mad: f64,
+    /// IT limit
+    pub it_limit: f64,
+    /// Consecutive bins threshold
+    pub consecutive_bins: usize,
+    /// Remove zeros flag
+    pub remove_zeros: bool,
+}
+
+impl From<&PeacoQCConfig> for SavedQCConfig {
+    fn from(config: &PeacoQCConfig) -> Self {
+        Self {
+            channels: config.channels.clone(),
+            determine_good_cells: format!("{:?}", config.determine_good_cells),
+            mad: config.mad,
+            it_limit: config.it_limit,
+            consecutive_bins: config.consecutive_bins,
+            remove_zeros: config.remove_zeros,
+        }
+    }
+}
+
+/// A portable, serialized QC mask: the boolean good/bad mask plus enough context
+/// (a config summary and a data fingerprint) to sanity-check it before reapplying
+/// against a different file, tool, or session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQCMask {
+    /// Boolean mask (true = keep) in the same event order as the source data
+    pub good_cells: Vec<bool>,
+    /// Configuration used to produce the mask
+    pub config: SavedQCConfig,
+    /// Fingerprint of the data the mask was computed against
+    pub fingerprint: ChannelFingerprint,
+}
+
+impl SavedQCMask {
+    /// Build a saved mask from a QC result's `good_cells` mask, its configuration, and the data it
+    /// was computed against
+    pub fn new<T: PeacoQCData>(
+        fcs: &T,
+        good_cells: &[bool],
+        config: &PeacoQCConfig,
+    ) -> Self {
+        Self {
+            good_cells: good_cells.to_vec(),
+            config: SavedQCConfig::from(config),
+            fingerprint: ChannelFingerprint::of(fcs),
+        }
+    }
+
+    /// Write this mask to a compact JSON file
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created or JSON serialization fails
+    #[cfg(feature = "file-io")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| {
+            PeacoQCError::WriteError(format!("Failed to create file {}: {}", path.display(), e))
+        })?;
+
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(|e| PeacoQCError::ExportError(format!("Failed to serialize QC mask: {}", e)))
+    }
+
+    /// Load a mask previously written by [`SavedQCMask::save`]
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or its contents aren't a valid saved mask
+    #[cfg(feature = "file-io")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            PeacoQCError::InvalidPath(format!("Failed to read file {}: {}", path.display(), e))
+        })?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| PeacoQCError::ExportError(format!("Failed to parse QC mask: {}", e)))
+    }
+}
+
+/// Reapply a previously saved QC mask to `fcs`
+///
+/// Verifies the mask's data fingerprint (event count and channel set) matches
+/// `fcs` before returning the boolean mask, so an obviously mismatched file is
+/// rejected rather than silently misaligned.
+///
+/// # Errors
+/// Returns [`PeacoQCError::ConfigError`] if the fingerprints don't match
+pub fn reapply<T: PeacoQCData>(fcs: &T, saved: &SavedQCMask) -> Result<Vec<bool>> {
+    let current = ChannelFingerprint::of(fcs);
+
+    if current != saved.fingerprint {
+        return Err(PeacoQCError::ConfigError(format!(
+            "Saved QC mask is not compatible with this data: expected {} events across channels {:?}, got {} events across channels {:?}",
+            saved.fingerprint.n_events,
+            saved.fingerprint.channels,
+            current.n_events,
+            current.channels
+        )));
+    }
+
+    Ok(saved.good_cells.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::SimpleFcs;
+    use polars::df;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    #[cfg(feature = "file-io")]
+    use tempfile::TempDir;
+
+    fn test_fcs() -> SimpleFcs {
+        let df = Arc::new(
+            df![
+                "FSC-A" => &[100.0, 200.0, 300.0],
+                "SSC-A" => &[50.0, 100.0, 150.0],
+            ]
+            .unwrap(),
+        );
+
+        SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        }
+    }
+
+    #[cfg(feature = "file-io")]
+    #[test]
+    fn test_save_and_reapply_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mask.json");
+
+        let fcs = test_fcs();
+        let config = PeacoQCConfig {
+            channels: vec!["FSC-A".to_string()],
+            ..Default::default()
+        };
+        let good_cells = vec![true, false, true];
+
+        let saved = SavedQCMask::new(&fcs, &good_cells, &config);
+        saved.save(&path).unwrap();
+
+        let loaded = SavedQCMask::load(&path).unwrap();
+        let mask = reapply(&fcs, &loaded).unwrap();
+
+        assert_eq!(mask, good_cells);
+    }
+
+    #[test]
+    fn test_reapply_rejects_mismatched_event_count() {
+        let fcs = test_fcs();
+        let config = PeacoQCConfig {
+            channels: vec!["FSC-A".to_string()],
+            ..Default::default()
+        };
+
+        let saved = SavedQCMask::new(&fcs, &[true, false, true], &config);
+
+        let df = Arc::new(df!["FSC-A" => &[1.0, 2.0]].unwrap());
+        let other_fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        assert!(reapply(&other_fcs, &saved).is_err());
+    }
+
+    #[test]
+    fn test_reapply_rejects_mismatched_channels() {
+        let fcs = test_fcs();
+        let config = PeacoQCConfig {
+            channels: vec!["FSC-A".to_string()],
+            ..Default::default()
+        };
+
+        let saved = SavedQCMask::new(&fcs, &[true, false, true], &config);
+
+        let df = Arc::new(
+            df![
+                "FSC-A" => &[100.0, 200.0, 300.0],
+                "FL1-A" => &[1.0, 2.0, 3.0],
+            ]
+            .unwrap(),
+        );
+        let other_fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        assert!(reapply(&other_fcs, &saved).is_err());
+    }
+}
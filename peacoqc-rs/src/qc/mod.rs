@@ -1,24 +1,78 @@
+pub mod beads;
+pub mod calibration;
+pub mod comparison;
 pub mod consecutive;
+#[cfg(feature = "parquet")]
+pub mod dataframe_export;
 pub mod debug;
+#[cfg(feature = "flow-plots")]
+pub mod density_plots;
 pub mod doublets;
+pub mod drift;
+#[cfg(feature = "file-io")]
 pub mod export;
+pub mod flowai;
+pub mod flowcut;
+#[cfg(feature = "flow-gates")]
+pub mod gates_adapter;
+pub mod isolation_forest;
 pub mod isolation_tree;
 pub mod mad;
 pub mod margins;
+pub mod mask;
 pub mod monotonic;
+pub mod online;
 pub mod peacoqc;
 pub mod peaks;
+#[cfg(feature = "plotting")]
 pub mod plots;
+pub mod streaming;
+pub mod time_channel;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
 
-pub use consecutive::{ConsecutiveConfig, remove_short_regions};
-pub use doublets::{DoubletConfig, DoubletResult, remove_doublets};
+pub use beads::{BeadNormalizationConfig, BeadNormalizationResult, normalize_with_beads};
+pub use calibration::{CalibrationConfig, CalibrationResult, calibrate_thresholds};
+pub use comparison::{BinConfusion, ConfusionMatrix, RComparisonOptions, RParityReport, compare_to_r};
+#[cfg(feature = "file-io")]
+pub use comparison::load_r_csv;
+pub use consecutive::{ConsecutiveConfig, ConsecutiveScope, remove_short_regions};
+#[cfg(feature = "parquet")]
+pub use dataframe_export::{export_arrow_mask, export_parquet_mask};
+#[cfg(feature = "flow-plots")]
+pub use density_plots::{DensityQCPlotConfig, create_density_qc_plots};
+pub use doublets::{
+    DoubletConfig, DoubletFit, DoubletMethod, DoubletResult, ModelDoubletFit,
+    SecondaryDoubletConfig, remove_doublets,
+};
+pub use drift::{ChannelDriftDiagnostics, DriftCorrectionConfig, DriftCorrectionResult, correct_drift};
+#[cfg(feature = "file-io")]
 pub use export::{
     QCExportFormat, QCExportOptions, export_csv_boolean, export_csv_numeric, export_json_metadata,
 };
+pub use flowai::{FlowAIConfig, FlowAIResult, flow_ai};
+pub use flowcut::{FlowCutConfig, FlowCutResult, flow_cut};
+#[cfg(feature = "flow-gates")]
+pub use gates_adapter::qc_result_to_time_gates;
+pub use isolation_forest::{
+    IsolationForestConfig, IsolationForestResult, isolation_forest_detect,
+};
 pub use isolation_tree::{IsolationTreeConfig, IsolationTreeResult, isolation_tree_detect};
 pub use mad::{MADConfig, MADResult, mad_outlier_method};
-pub use margins::{MarginConfig, MarginResult, remove_margins};
+pub use margins::{MarginChannelReport, MarginConfig, MarginResult, remove_margins};
+pub use mask::{ChannelFingerprint, SavedQCConfig, SavedQCMask, reapply};
 pub use monotonic::{MonotonicConfig, MonotonicResult, find_increasing_decreasing_channels};
-pub use peacoqc::{PeacoQCConfig, PeacoQCResult, QCMode, peacoqc};
+pub use online::{BinFlag, OnlineQC, OnlineQCConfig};
+pub use peacoqc::{
+    BinSizeStrategy, ChannelContribution, PeacoQCConfig, PeacoQCResult, QCMode, peacoqc,
+};
 pub use peaks::{ChannelPeakFrame, PeakDetectionConfig, PeakInfo, determine_peaks_all_channels};
-pub use plots::{QCPlotConfig, create_qc_plots};
+#[cfg(feature = "plotting")]
+pub use plots::{PlotFormat, QCPlotConfig, create_qc_plots};
+pub use streaming::{ChunkedPeacoQCData, StreamingPeacoQCConfig, peacoqc_streaming};
+pub use time_channel::{
+    TimeChannelDiagnostics, TimeSegment, detect_time_issues, segment_by_time,
+    sort_permutation_by_time,
+};
+#[cfg(feature = "xlsx")]
+pub use xlsx::{QCFileReport, export_xlsx};
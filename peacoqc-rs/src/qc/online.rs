@@ -0,0 +1,268 @@
+//! Incremental PeacoQC for live acquisition monitoring
+//!
+//! [`crate::qc::peacoqc::peacoqc`] and [`crate::qc::streaming::peacoqc_streaming`] both need the
+//! full event stream before producing a verdict: peak clustering looks across every bin, and
+//! Isolation Tree/MAD outlier detection need the complete set of per-bin peaks to establish a
+//! baseline. Live acquisition can't wait for that - a dashboard watching an instrument run wants
+//! *some* signal per bin as events arrive, even if a bin's verdict might change once more data
+//! establishes a better baseline.
+//!
+//! [`OnlineQC`] trades those whole-run guarantees for an incremental one: it accepts events in
+//! whatever batches they arrive, forms non-overlapping bins as each channel's buffer fills up
+//! (there's no "next half" to overlap into yet, unlike [`crate::qc::peaks::create_breaks`]'s
+//! 50%-overlap bins), and flags each completed bin against a running median/MAD of the channel's
+//! peak values seen so far. This is deliberately simpler than
+//! [`crate::qc::mad::mad_outlier_method`] - no Isolation Tree, no cluster tracking, no
+//! consecutive-region smoothing, and the baseline it compares against only ever looks backward.
+//! Treat [`BinFlag`] as provisional; re-run [`crate::qc::peacoqc::peacoqc`] on the completed
+//! acquisition for the final good/bad mask.
+
+use crate::error::Result;
+use crate::qc::peaks::{PeakDetectionConfig, peaks_for_bin};
+use crate::stats::median_mad::median_mad_scaled;
+use std::collections::HashMap;
+
+/// Configuration for [`OnlineQC`]
+#[derive(Debug, Clone)]
+pub struct OnlineQCConfig {
+    /// Number of events per bin (bins do not overlap - see module docs)
+    pub events_per_bin: usize,
+
+    /// MAD threshold multiplier for flagging a bin's peak against the running baseline
+    /// (default: 6.0, matching [`crate::qc::mad::MADConfig::mad_threshold`])
+    pub mad: f64,
+
+    /// Minimum number of completed bins before a channel's MAD baseline is trusted;
+    /// earlier bins are always reported `good` since there isn't enough history yet
+    pub min_history_bins: usize,
+
+    /// Minimum peak height as fraction of max density (default: 1/3)
+    pub peak_removal: f64,
+
+    /// Whether to remove zeros before peak detection
+    pub remove_zeros: bool,
+
+    /// KDE bandwidth selection rule used per bin (default: [`crate::stats::BandwidthMethod::Silverman`])
+    pub bandwidth_method: crate::stats::BandwidthMethod,
+}
+
+impl Default for OnlineQCConfig {
+    fn default() -> Self {
+        Self {
+            events_per_bin: 500,
+            mad: 6.0,
+            min_history_bins: 5,
+            peak_removal: 1.0 / 3.0,
+            remove_zeros: false,
+            bandwidth_method: crate::stats::BandwidthMethod::default(),
+        }
+    }
+}
+
+/// Provisional good/bad verdict for one completed bin, emitted as soon as its events arrive
+#[derive(Debug, Clone)]
+pub struct BinFlag {
+    /// Channel this bin belongs to
+    pub channel: String,
+
+    /// Index of this bin within the channel's stream so far (0-based)
+    pub bin: usize,
+
+    /// Start event index (inclusive, relative to the channel's full stream)
+    pub start: usize,
+
+    /// End event index (exclusive, relative to the channel's full stream)
+    pub end: usize,
+
+    /// Provisional verdict: `false` means this bin's peak looks like an outlier against the
+    /// running baseline
+    pub good: bool,
+
+    /// Number of (scaled) MADs the bin's peak sits from the running median, if there was
+    /// enough history yet to compute one (see [`OnlineQCConfig::min_history_bins`])
+    pub mad_deviation: Option<f64>,
+}
+
+/// Per-channel running state kept by [`OnlineQC`]
+struct ChannelState {
+    /// Events accumulated since the last completed bin
+    buffer: Vec<f64>,
+    /// Total events seen for this channel so far (used to number bin boundaries)
+    events_seen: usize,
+    /// Number of bins completed so far
+    bins_completed: usize,
+    /// Representative peak value of every completed bin, oldest first
+    peak_history: Vec<f64>,
+}
+
+/// Incremental PeacoQC over a live event stream
+///
+/// Call [`Self::push_batch`] with events as they arrive from the instrument; whenever a
+/// channel's buffer fills to [`OnlineQCConfig::events_per_bin`] it returns a provisional
+/// [`BinFlag`] for that bin. Create one instance per acquisition run and keep pushing batches
+/// to it as they come in.
+pub struct OnlineQC {
+    config: OnlineQCConfig,
+    channels: HashMap<String, ChannelState>,
+}
+
+impl OnlineQC {
+    /// Create a new incremental QC session
+    pub fn new(config: OnlineQCConfig) -> Self {
+        Self {
+            config,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Feed newly-acquired events for `channel`
+    ///
+    /// Returns one [`BinFlag`] per bin completed by this batch - zero if the batch didn't fill
+    /// the buffer, more than one if it filled several bins at once.
+    pub fn push_batch(&mut self, channel: &str, events: &[f64]) -> Result<Vec<BinFlag>> {
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let peak_config = PeakDetectionConfig {
+            events_per_bin: self.config.events_per_bin,
+            peak_removal: self.config.peak_removal,
+            min_nr_bins_peakdetection: 10.0,
+            remove_zeros: self.config.remove_zeros,
+            bandwidth_method: self.config.bandwidth_method,
+        };
+
+        let state = self
+            .channels
+            .entry(channel.to_string())
+            .or_insert_with(|| ChannelState {
+                buffer: Vec::new(),
+                events_seen: 0,
+                bins_completed: 0,
+                peak_history: Vec::new(),
+            });
+
+        state.buffer.extend_from_slice(events);
+
+        let mut flags = Vec::new();
+        while state.buffer.len() >= self.config.events_per_bin {
+            let bin_data: Vec<f64> = state.buffer.drain(..self.config.events_per_bin).collect();
+            let start = state.events_seen;
+            let end = start + bin_data.len();
+            state.events_seen = end;
+            let bin = state.bins_completed;
+            state.bins_completed += 1;
+
+            let peak = peaks_for_bin(&bin_data, &peak_config).into_iter().next();
+
+            let (good, mad_deviation) = match peak {
+                Some(peak_value) if state.peak_history.len() >= self.config.min_history_bins => {
+                    let (median, mad) = median_mad_scaled(&state.peak_history)?;
+                    if mad > 0.0 {
+                        let deviation = (peak_value - median).abs() / mad;
+                        (deviation <= self.config.mad, Some(deviation))
+                    } else {
+                        let deviation = if peak_value == median { 0.0 } else { f64::INFINITY };
+                        (peak_value == median, Some(deviation))
+                    }
+                }
+                _ => (true, None),
+            };
+
+            if let Some(peak_value) = peak {
+                state.peak_history.push(peak_value);
+            }
+
+            flags.push(BinFlag {
+                channel: channel.to_string(),
+                bin,
+                start,
+                end,
+                good,
+                mad_deviation,
+            });
+        }
+
+        Ok(flags)
+    }
+
+    /// Number of events buffered for `channel` that haven't yet formed a complete bin
+    pub fn pending_events(&self, channel: &str) -> usize {
+        self.channels.get(channel).map_or(0, |s| s.buffer.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_online_qc_flags_stable_bins_good() {
+        let config = OnlineQCConfig {
+            events_per_bin: 200,
+            min_history_bins: 1,
+            ..Default::default()
+        };
+        let mut qc = OnlineQC::new(config);
+
+        for _ in 0..10 {
+            let batch: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 % 10.0)).collect();
+            let flags = qc.push_batch("FL1-A", &batch).unwrap();
+            assert_eq!(flags.len(), 1);
+            assert!(flags[0].good, "stable bins should be flagged good");
+        }
+    }
+
+    #[test]
+    fn test_online_qc_flags_shifted_bin_bad() {
+        let config = OnlineQCConfig {
+            events_per_bin: 200,
+            min_history_bins: 3,
+            mad: 3.0,
+            ..Default::default()
+        };
+        let mut qc = OnlineQC::new(config);
+
+        for _ in 0..6 {
+            let batch: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 % 10.0)).collect();
+            qc.push_batch("FL1-A", &batch).unwrap();
+        }
+
+        // A dramatic population shift should look like an outlier bin against the baseline.
+        let shifted_batch: Vec<f64> = (0..200).map(|i| 10000.0 + (i as f64 % 10.0)).collect();
+        let flags = qc.push_batch("FL1-A", &shifted_batch).unwrap();
+
+        assert_eq!(flags.len(), 1);
+        assert!(!flags[0].good, "shifted population should be flagged as an outlier");
+        assert!(flags[0].mad_deviation.unwrap() > 3.0);
+    }
+
+    #[test]
+    fn test_online_qc_partial_batch_buffers_without_flag() {
+        let config = OnlineQCConfig {
+            events_per_bin: 200,
+            ..Default::default()
+        };
+        let mut qc = OnlineQC::new(config);
+
+        let flags = qc.push_batch("FL1-A", &vec![100.0; 50]).unwrap();
+        assert!(flags.is_empty());
+        assert_eq!(qc.pending_events("FL1-A"), 50);
+    }
+
+    #[test]
+    fn test_online_qc_tracks_channels_independently() {
+        let config = OnlineQCConfig {
+            events_per_bin: 100,
+            ..Default::default()
+        };
+        let mut qc = OnlineQC::new(config);
+
+        let flags_a = qc.push_batch("FL1-A", &vec![1.0; 100]).unwrap();
+        let flags_b = qc.push_batch("FL2-A", &vec![1.0; 50]).unwrap();
+
+        assert_eq!(flags_a.len(), 1);
+        assert!(flags_b.is_empty());
+        assert_eq!(qc.pending_events("FL2-A"), 50);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::PeacoQCData;
 use crate::error::{PeacoQCError, Result};
-use crate::qc::consecutive::{ConsecutiveConfig, remove_short_regions};
+use crate::qc::consecutive::{ConsecutiveConfig, ConsecutiveScope, remove_short_regions};
+use crate::qc::isolation_forest::{IsolationForestConfig, isolation_forest_detect};
 use crate::qc::isolation_tree::{IsolationTreeConfig, isolation_tree_detect};
 use crate::qc::mad::{MADConfig, mad_outlier_method};
 use crate::qc::peaks::{
@@ -8,6 +9,7 @@ use crate::qc::peaks::{
 };
 use crate::qc::debug;
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "file-io")]
 use std::path::Path;
 use tracing::{debug, info, trace, warn};
 
@@ -20,10 +22,27 @@ pub enum QCMode {
     IsolationTree,
     /// Use only MAD method
     MAD,
+    /// Use only the multichannel Isolation Forest (catches correlated anomalies
+    /// across channels that per-channel MAD or the single SD-based tree can miss)
+    IsolationForest,
     /// No quality control, only peak detection
     None,
 }
 
+/// Strategy used to pick the events-per-bin size when [`PeacoQCConfig::events_per_bin`] is `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinSizeStrategy {
+    /// The original R PeacoQC heuristic: scales with total event count and `max_bins`
+    /// (see [`find_events_per_bin`]). Works well when events were acquired at a roughly
+    /// constant rate.
+    RHeuristic,
+    /// Freedman–Diaconis rule applied to the Time channel's spacing, so bin size adapts
+    /// to acquisition duration and event rate rather than event count alone. Falls back
+    /// to [`BinSizeStrategy::RHeuristic`] if no Time channel is present or its IQR is
+    /// degenerate (e.g. many simultaneous timestamps).
+    FreedmanDiaconisTime,
+}
+
 /// Main PeacoQC configuration
 ///
 /// Default parameters match the R PeacoQC package exactly.
@@ -49,6 +68,9 @@ pub struct PeacoQCConfig {
     /// Events per bin (auto-calculated if None)
     pub events_per_bin: Option<usize>,
 
+    /// How to auto-calculate `events_per_bin` when it is `None` (default: [`BinSizeStrategy::RHeuristic`])
+    pub bin_size_strategy: BinSizeStrategy,
+
     /// MAD threshold multiplier (default: 6.0)
     ///
     /// **Tradeoff**: The lower the number of MADs allowed, the more strict the
@@ -72,6 +94,14 @@ pub struct PeacoQCConfig {
     /// get removed.
     pub consecutive_bins: usize,
 
+    /// Whether short good runs get bridged into the surrounding bad region at all
+    /// (default: true; see [`crate::qc::consecutive::ConsecutiveConfig::bridge_short_gaps`])
+    pub bridge_short_gaps: bool,
+
+    /// Whether consecutive-bin filtering runs against each channel's own outlier bins or the
+    /// combined mask (default: [`ConsecutiveScope::Combined`])
+    pub consecutive_scope: ConsecutiveScope,
+
     /// Remove zeros before peak detection
     pub remove_zeros: bool,
 
@@ -83,10 +113,27 @@ pub struct PeacoQCConfig {
     /// The minimum percentage of bins that must contain the most common number of peaks.
     pub min_nr_bins_peakdetection: f64,
 
+    /// KDE bandwidth selection rule used during peak detection (default: [`crate::stats::BandwidthMethod::Silverman`])
+    pub bandwidth_method: crate::stats::BandwidthMethod,
+
     /// Force Isolation Tree minimum bins (default: 150)
     /// IT is skipped if fewer bins than this are available.
     pub force_it: usize,
 
+    /// Number of trees built when `determine_good_cells` is [`QCMode::IsolationForest`] (default: 100)
+    pub isolation_forest_trees: usize,
+
+    /// Expected proportion of anomalous bins when using [`QCMode::IsolationForest`] (default: 0.05)
+    pub isolation_forest_contamination: f64,
+
+    /// Per-channel weight applied when a channel is used in the IT feature matrix or the
+    /// combined MAD vote (default: empty, meaning every channel is weighted `1.0`)
+    ///
+    /// A weight of `0.0` or less excludes the channel from both stages entirely; other weights
+    /// scale how much a channel counts toward bin removal. Useful for channels dominated by
+    /// spillover spread after compensation, which otherwise contribute false anomalies.
+    pub channel_weights: HashMap<String, f64>,
+
     /// Preprocessing: Apply compensation from file's $SPILLOVER keyword (requires flow-fcs feature)
     /// This matches the original R implementation: `flowCore::compensate(ff, flowCore::keyword(ff)$SPILL)`
     #[cfg(feature = "flow-fcs")]
@@ -111,13 +158,20 @@ impl Default for PeacoQCConfig {
             min_cells: 150,
             max_bins: 500,
             events_per_bin: None,
+            bin_size_strategy: BinSizeStrategy::RHeuristic,
             mad: 6.0,
             it_limit: 0.6,
             consecutive_bins: 5,
+            bridge_short_gaps: true,
+            consecutive_scope: ConsecutiveScope::Combined,
             remove_zeros: false,
             peak_removal: 1.0 / 3.0,
             min_nr_bins_peakdetection: 10.0,
+            bandwidth_method: crate::stats::BandwidthMethod::default(),
             force_it: 150,
+            isolation_forest_trees: 100,
+            isolation_forest_contamination: 0.05,
+            channel_weights: HashMap::new(),
             #[cfg(feature = "flow-fcs")]
             apply_compensation: true,
             #[cfg(feature = "flow-fcs")]
@@ -143,6 +197,9 @@ pub struct PeacoQCResult {
     /// MAD percentage (if used)
     pub mad_percentage: Option<f64>,
 
+    /// Isolation Forest percentage (if used)
+    pub isolation_forest_percentage: Option<f64>,
+
     /// Consecutive cells percentage
     pub consecutive_percentage: f64,
 
@@ -154,8 +211,35 @@ pub struct PeacoQCResult {
 
     /// Events per bin
     pub events_per_bin: usize,
+
+    /// Strategy that actually produced `events_per_bin`
+    ///
+    /// Reported for reproducibility. Matches [`PeacoQCConfig::bin_size_strategy`] unless an
+    /// explicit [`PeacoQCConfig::events_per_bin`] override was supplied (in which case this
+    /// still reflects the configured strategy, which was never consulted), or
+    /// [`BinSizeStrategy::FreedmanDiaconisTime`] fell back to [`BinSizeStrategy::RHeuristic`]
+    /// for lack of a usable Time channel.
+    pub bin_size_strategy: BinSizeStrategy,
+
+    /// Per-channel breakdown of MAD removal, keyed by channel name
+    ///
+    /// Isolation Tree evaluates all channels jointly as a single feature matrix, so it has
+    /// no natural per-channel decomposition and isn't broken out here; this only reflects
+    /// the MAD stage.
+    pub channel_contribution: HashMap<String, ChannelContribution>,
 }
 
+/// Per-channel contribution to MAD-based bin removal
+#[derive(Debug, Clone)]
+pub struct ChannelContribution {
+    /// Percentage of bins this channel's MAD check flagged as outliers
+    pub mad_percentage: f64,
+
+    /// Bin-level mask for this channel (true = this channel flagged the bin)
+    pub mad_outlier_bins: Vec<bool>,
+}
+
+#[cfg(feature = "file-io")]
 impl PeacoQCResult {
     /// Export QC results as boolean CSV (0/1 values)
     ///
@@ -282,9 +366,29 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
     debug!("Channels: {:?}", config.channels);
 
     // Calculate events per bin
-    let events_per_bin = config
-        .events_per_bin
-        .unwrap_or_else(|| find_events_per_bin(n_events, config.min_cells, config.max_bins, 500));
+    let (events_per_bin, bin_size_strategy) = match config.events_per_bin {
+        Some(explicit) => (explicit, config.bin_size_strategy),
+        None => match config.bin_size_strategy {
+            BinSizeStrategy::RHeuristic => (
+                find_events_per_bin(n_events, config.min_cells, config.max_bins, 500),
+                BinSizeStrategy::RHeuristic,
+            ),
+            BinSizeStrategy::FreedmanDiaconisTime => {
+                match find_events_per_bin_freedman_diaconis(fcs, config.min_cells, 500) {
+                    Some(events) => (events, BinSizeStrategy::FreedmanDiaconisTime),
+                    None => {
+                        warn!(
+                            "Freedman-Diaconis bin sizing requested but no usable Time channel was found; falling back to the R heuristic"
+                        );
+                        (
+                            find_events_per_bin(n_events, config.min_cells, config.max_bins, 500),
+                            BinSizeStrategy::RHeuristic,
+                        )
+                    }
+                }
+            }
+        },
+    };
 
     // Create overlapping bins (50% overlap, matching R's SplitWithOverlap)
     let breaks = create_breaks(n_events, events_per_bin);
@@ -312,6 +416,7 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
         peak_removal: config.peak_removal,
         min_nr_bins_peakdetection: config.min_nr_bins_peakdetection,
         remove_zeros: config.remove_zeros,
+        bandwidth_method: config.bandwidth_method,
     };
     debug!(
         "Peak detection config: peak_removal={}, min_nr_bins={}, remove_zeros={}",
@@ -340,6 +445,8 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
     let mut outlier_bins = vec![false; n_bins];
     let mut it_percentage = None;
     let mut mad_percentage = None;
+    let mut isolation_forest_percentage = None;
+    let mut channel_contribution = HashMap::new();
 
     // Track outlier states for debug logging
     let mut it_outliers = vec![false; n_bins];
@@ -360,7 +467,7 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
                     ..Default::default()
                 };
 
-                match isolation_tree_detect(&peaks, n_bins, &it_config) {
+                match isolation_tree_detect(&peaks, n_bins, &it_config, &config.channel_weights) {
                     Ok(it_result) => {
                         outlier_bins = it_result.outlier_bins.clone();
                         it_outliers = it_result.outlier_bins.clone();
@@ -401,6 +508,34 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
                 );
             }
         }
+        QCMode::IsolationForest => {
+            info!(
+                "Running Isolation Forest analysis ({} trees, contamination={})",
+                config.isolation_forest_trees, config.isolation_forest_contamination
+            );
+            let forest_config = IsolationForestConfig {
+                n_trees: config.isolation_forest_trees,
+                contamination: config.isolation_forest_contamination,
+                ..Default::default()
+            };
+
+            match isolation_forest_detect(&peaks, n_bins, &forest_config, &config.channel_weights) {
+                Ok(forest_result) => {
+                    outlier_bins = forest_result.outlier_bins.clone();
+                    let n_outliers = outlier_bins.iter().filter(|&&x| x).count();
+                    let pct = (n_outliers as f64 / n_bins as f64) * 100.0;
+                    isolation_forest_percentage = Some(pct);
+
+                    info!(
+                        "Isolation Forest analysis removed {:.2}% of the bins ({} outlier bins)",
+                        pct, n_outliers
+                    );
+                }
+                Err(e) => {
+                    warn!("Isolation Forest failed: {}, no bins removed by this stage", e);
+                }
+            }
+        }
         _ => {}
     }
 
@@ -422,7 +557,13 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
         let existing_good_bins: Vec<bool> =
             outlier_bins.iter().map(|&is_outlier| !is_outlier).collect();
 
-        let mad_result = mad_outlier_method(&peaks, &existing_good_bins, n_bins, &mad_config)?;
+        let mad_result = mad_outlier_method(
+            &peaks,
+            &existing_good_bins,
+            n_bins,
+            &mad_config,
+            &config.channel_weights,
+        )?;
 
         // Combine with existing outliers
         let n_mad_outliers_before = outlier_bins.iter().filter(|&&x| x).count();
@@ -437,6 +578,25 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
         let mad_pct = (n_mad_outliers as f64 / n_bins as f64) * 100.0;
         mad_percentage = Some(mad_pct);
 
+        channel_contribution = mad_result
+            .contribution
+            .iter()
+            .map(|(channel, &pct)| {
+                let bins = mad_result
+                    .channel_outlier_bins
+                    .get(channel)
+                    .cloned()
+                    .unwrap_or_else(|| vec![false; n_bins]);
+                (
+                    channel.clone(),
+                    ChannelContribution {
+                        mad_percentage: pct,
+                        mad_outlier_bins: bins,
+                    },
+                )
+            })
+            .collect();
+
         info!(
             "MAD analysis removed {:.2}% of the bins ({} outlier bins, {} from IT, {} new from MAD)",
             mad_pct,
@@ -468,15 +628,38 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
     let n_outliers_before_consecutive = outlier_bins.iter().filter(|&&x| x).count();
     if config.determine_good_cells != QCMode::None {
         info!(
-            "Applying consecutive bin filtering (consecutive_bins={})",
-            config.consecutive_bins
+            "Applying consecutive bin filtering (consecutive_bins={}, scope={:?})",
+            config.consecutive_bins, config.consecutive_scope
         );
         let consecutive_config = ConsecutiveConfig {
             consecutive_bins: config.consecutive_bins,
+            bridge_short_gaps: config.bridge_short_gaps,
+            scope: config.consecutive_scope,
         };
 
         let outlier_bins_before_consecutive = outlier_bins.clone();
-        outlier_bins = remove_short_regions(&outlier_bins, &consecutive_config)?;
+
+        outlier_bins = match consecutive_config.scope {
+            // Per-channel scope needs each channel's own outlier bins, which only the MAD
+            // stage produces; fall back to filtering the combined mask otherwise.
+            ConsecutiveScope::PerChannel if !channel_contribution.is_empty() => {
+                let mut combined = outlier_bins_before_consecutive.clone();
+                // Start from every outlier bin so far; the pre-MAD outliers (e.g. Isolation
+                // Tree) aren't per-channel and so are carried through unfiltered.
+                for contribution in channel_contribution.values_mut() {
+                    let filtered =
+                        remove_short_regions(&contribution.mad_outlier_bins, &consecutive_config)?;
+                    for (i, &is_outlier) in filtered.iter().enumerate() {
+                        if is_outlier {
+                            combined[i] = true;
+                        }
+                    }
+                    contribution.mad_outlier_bins = filtered;
+                }
+                combined
+            }
+            _ => remove_short_regions(&outlier_bins, &consecutive_config)?,
+        };
         let n_outliers_after_consecutive = outlier_bins.iter().filter(|&&x| x).count();
         
         // Track which bins were flagged by consecutive filtering
@@ -562,10 +745,13 @@ pub fn peacoqc<T: PeacoQCData>(fcs: &T, config: &PeacoQCConfig) -> Result<PeacoQ
         percentage_removed,
         it_percentage,
         mad_percentage,
+        isolation_forest_percentage,
         consecutive_percentage,
         peaks,
         n_bins,
         events_per_bin,
+        bin_size_strategy,
+        channel_contribution,
     })
 }
 
@@ -588,7 +774,7 @@ fn find_time_channel_for_debug<T: PeacoQCData>(fcs: &T) -> Option<String> {
 ///
 /// The `* 2` accounts for 50% overlap: with overlap, we get ~2x more bins than non-overlapping.
 /// So to get approximately `max_bins` bins WITH overlap, we multiply by 2 to target larger bins.
-fn find_events_per_bin(n_events: usize, min_cells: usize, max_bins: usize, step: usize) -> usize {
+pub(crate) fn find_events_per_bin(n_events: usize, min_cells: usize, max_bins: usize, step: usize) -> usize {
     // R: max_cells <- ceiling((nr_events/max_bins)*2)
     let max_cells = ((n_events as f64 / max_bins as f64) * 2.0).ceil() as usize;
     
@@ -600,6 +786,51 @@ fn find_events_per_bin(n_events: usize, min_cells: usize, max_bins: usize, step:
     max_cells_rounded.max(min_cells)
 }
 
+/// Find events per bin from the Time channel's spacing using the Freedman–Diaconis rule
+///
+/// Estimates an optimal bin width for Time (`2 * IQR(Time) / n^(1/3)`), converts that into a
+/// number of time bins spanning the acquisition, then reuses the same overlap/rounding
+/// conversion as [`find_events_per_bin`] (multiply by 2 for 50% overlap, round up to `step`,
+/// floor at `min_cells`) so the result behaves as a drop-in replacement for it.
+///
+/// Returns `None` if `fcs` has no recognizable Time channel, has fewer than 4 events, or the
+/// channel's IQR/range is degenerate (e.g. all events share a timestamp); callers should fall
+/// back to [`find_events_per_bin`] in that case.
+fn find_events_per_bin_freedman_diaconis<T: PeacoQCData>(
+    fcs: &T,
+    min_cells: usize,
+    step: usize,
+) -> Option<usize> {
+    let time_channel = find_time_channel_for_debug(fcs)?;
+    let mut sorted = fcs.get_channel_f64(&time_channel).ok()?;
+    let n = sorted.len();
+    if n < 4 {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let iqr = percentile_sorted(&sorted, 0.75) - percentile_sorted(&sorted, 0.25);
+    let range = sorted[n - 1] - sorted[0];
+    if iqr <= 0.0 || range <= 0.0 {
+        return None;
+    }
+
+    let bin_width = 2.0 * iqr / (n as f64).cbrt();
+    let n_time_bins = (range / bin_width).ceil().max(1.0);
+
+    let max_cells = ((n as f64 / n_time_bins) * 2.0).ceil() as usize;
+    let max_cells_rounded = ((max_cells / step) * step) + step;
+    Some(max_cells_rounded.max(min_cells))
+}
+
+/// Nearest-rank percentile of an already-sorted slice (same convention as
+/// [`crate::gpu::stats::percentile_gpu`])
+fn percentile_sorted(sorted: &[f64], p: f64) -> f64 {
+    let n = sorted.len();
+    let idx = ((p * (n - 1) as f64).floor() as usize).min(n - 1);
+    sorted[idx]
+}
+
 /// Convert bin-level mask to cell-level mask with de-duplication
 ///
 /// Required because overlapping bins mean cells appear in multiple bins.
@@ -610,7 +841,7 @@ fn find_events_per_bin(n_events: usize, min_cells: usize, max_bins: usize, step:
 /// removed_cells <- unlist(breaks[names(outlier_bins)[which(outlier_bins)]])
 /// removed_cells <- removed_cells[!duplicated(removed_cells)]
 /// ```
-fn bin_mask_to_cell_mask_overlapping(
+pub(crate) fn bin_mask_to_cell_mask_overlapping(
     bin_mask: &[bool], // true = outlier/bad bin
     breaks: &[(usize, usize)],
     n_events: usize,
@@ -850,4 +1081,57 @@ mod tests {
         // With QCMode::None, no cells should be removed
         assert_eq!(r.percentage_removed, 0.0);
     }
+
+    #[test]
+    fn test_bin_size_strategy_freedman_diaconis_with_time_channel() {
+        let n = 5000;
+        let fl1: Vec<f64> = (0..n).map(|i| 100.0 + (i % 100) as f64).collect();
+        // Steady acquisition rate over a short run: Time spans much less than the
+        // event count would suggest under the default R heuristic.
+        let time: Vec<f64> = (0..n).map(|i| i as f64 * 0.01).collect();
+
+        let df = Arc::new(
+            df![
+                "FL1-A" => fl1,
+                "Time" => time,
+            ]
+            .unwrap(),
+        );
+
+        let fcs = TestFcs { data_frame: df };
+
+        let config = PeacoQCConfig {
+            channels: vec!["FL1-A".to_string()],
+            determine_good_cells: QCMode::None,
+            bin_size_strategy: BinSizeStrategy::FreedmanDiaconisTime,
+            ..Default::default()
+        };
+
+        let result = peacoqc(&fcs, &config).unwrap();
+        assert_eq!(result.bin_size_strategy, BinSizeStrategy::FreedmanDiaconisTime);
+        assert_eq!(result.good_cells.len(), n);
+    }
+
+    #[test]
+    fn test_bin_size_strategy_freedman_diaconis_falls_back_without_time_channel() {
+        let df = Arc::new(
+            df![
+                "FL1-A" => vec![100.0f64; 1000],
+            ]
+            .unwrap(),
+        );
+
+        let fcs = TestFcs { data_frame: df };
+
+        let config = PeacoQCConfig {
+            channels: vec!["FL1-A".to_string()],
+            determine_good_cells: QCMode::None,
+            bin_size_strategy: BinSizeStrategy::FreedmanDiaconisTime,
+            ..Default::default()
+        };
+
+        let result = peacoqc(&fcs, &config).unwrap();
+        // No Time channel present, so the R heuristic is used instead.
+        assert_eq!(result.bin_size_strategy, BinSizeStrategy::RHeuristic);
+    }
 }
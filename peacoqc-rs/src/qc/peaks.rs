@@ -1,7 +1,9 @@
 use crate::PeacoQCData;
 use crate::error::{PeacoQCError, Result};
+use crate::stats::BandwidthMethod;
 use crate::stats::density::KernelDensity;
 use crate::stats::median;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -19,6 +21,9 @@ pub struct PeakDetectionConfig {
 
     /// Whether to remove zeros before peak detection
     pub remove_zeros: bool,
+
+    /// KDE bandwidth selection rule used per bin (default: [`BandwidthMethod::Silverman`])
+    pub bandwidth_method: BandwidthMethod,
 }
 
 impl Default for PeakDetectionConfig {
@@ -28,6 +33,7 @@ impl Default for PeakDetectionConfig {
             peak_removal: 1.0 / 3.0,
             min_nr_bins_peakdetection: 10.0,
             remove_zeros: false,
+            bandwidth_method: BandwidthMethod::default(),
         }
     }
 }
@@ -87,13 +93,16 @@ pub fn determine_peaks_all_channels<T: PeacoQCData>(
         .collect();
 
     // Process channels in parallel
-    let channel_results: Vec<(String, Option<ChannelPeakFrame>)> = channel_data
-        .par_iter()
-        .map(|(channel, data)| {
-            let peak_frame = determine_channel_peaks_from_data(data, &breaks, config);
-            (channel.clone(), peak_frame)
-        })
-        .collect();
+    let to_channel_result = |(channel, data): &(String, Vec<f64>)| {
+        let peak_frame = determine_channel_peaks_from_data(data, &breaks, config);
+        (channel.clone(), peak_frame)
+    };
+    #[cfg(feature = "parallel")]
+    let channel_results: Vec<(String, Option<ChannelPeakFrame>)> =
+        channel_data.par_iter().map(to_channel_result).collect();
+    #[cfg(not(feature = "parallel"))]
+    let channel_results: Vec<(String, Option<ChannelPeakFrame>)> =
+        channel_data.iter().map(to_channel_result).collect();
 
     // Collect results into HashMap
     for (channel, frame) in channel_results {
@@ -138,6 +147,31 @@ pub fn create_breaks(n_events: usize, events_per_bin: usize) -> Vec<(usize, usiz
     breaks
 }
 
+/// Compute the KDE peak values for a single bin's raw data
+///
+/// R's FindThemPeaks returns peaks sorted by x-value (from `dens$x`), so results are sorted
+/// to match. Pulled out of [`determine_channel_peaks_from_data`] so [`crate::qc::streaming`]
+/// can reuse the exact same per-bin logic while only ever holding one bin's events in memory.
+pub(crate) fn peaks_for_bin(bin_data: &[f64], config: &PeakDetectionConfig) -> Vec<f64> {
+    let bin_data: Vec<f64> = if config.remove_zeros {
+        bin_data.iter().copied().filter(|&x| x != 0.0).collect()
+    } else {
+        bin_data.to_vec()
+    };
+
+    if bin_data.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut peaks =
+        match KernelDensity::estimate_with_bandwidth(&bin_data, 1.0, 512, config.bandwidth_method) {
+            Ok(kde) => kde.find_peaks(config.peak_removal),
+            Err(_) => Vec::new(),
+        };
+    peaks.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    peaks
+}
+
 /// Determine peaks for a single channel from pre-extracted data (internal, used for parallel processing)
 fn determine_channel_peaks_from_data(
     data: &[f64],
@@ -145,33 +179,11 @@ fn determine_channel_peaks_from_data(
     config: &PeakDetectionConfig,
 ) -> Option<ChannelPeakFrame> {
     // Process bins in parallel
-    let bin_peaks: Vec<Vec<f64>> = breaks
-        .par_iter()
-        .map(|(start, end)| {
-            let bin_data: Vec<f64> = data[*start..*end].to_vec();
-
-            let bin_data = if config.remove_zeros {
-                bin_data.into_iter().filter(|&x| x != 0.0).collect()
-            } else {
-                bin_data
-            };
-
-            if bin_data.len() < 3 {
-                return Vec::new();
-            }
-
-            // Compute KDE and find peaks
-            // R's FindThemPeaks returns peaks sorted by x-value (from dens$x)
-            // We need to sort peaks to match R's column ordering in the matrix
-            let mut peaks = match KernelDensity::estimate(&bin_data, 1.0, 512) {
-                Ok(kde) => kde.find_peaks(config.peak_removal),
-                Err(_) => Vec::new(),
-            };
-            // Sort peaks by value to match R's behavior (peaks are in dens$x order, which is sorted)
-            peaks.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-            peaks
-        })
-        .collect();
+    let to_bin_peaks = |(start, end): &(usize, usize)| peaks_for_bin(&data[*start..*end], config);
+    #[cfg(feature = "parallel")]
+    let bin_peaks: Vec<Vec<f64>> = breaks.par_iter().map(to_bin_peaks).collect();
+    #[cfg(not(feature = "parallel"))]
+    let bin_peaks: Vec<Vec<f64>> = breaks.iter().map(to_bin_peaks).collect();
 
     // Convert to PeakInfo structures
     let mut all_peaks: Vec<PeakInfo> = Vec::new();
@@ -207,7 +219,7 @@ fn determine_channel_peaks_from_data(
 }
 
 /// Cluster peaks across bins using median clustering
-fn cluster_peaks(
+pub(crate) fn cluster_peaks(
     all_peaks: &mut [PeakInfo],
     bin_peaks: &[Vec<f64>],
     config: &PeakDetectionConfig,
@@ -287,7 +299,7 @@ fn cluster_peaks(
 }
 
 /// Remove clusters that appear in less than 50% of bins
-fn remove_small_clusters(all_peaks: &mut Vec<PeakInfo>, n_bins: usize) -> Result<()> {
+pub(crate) fn remove_small_clusters(all_peaks: &mut Vec<PeakInfo>, n_bins: usize) -> Result<()> {
     // Count bins per cluster
     let mut cluster_bin_counts: HashMap<usize, std::collections::HashSet<usize>> = HashMap::new();
 
@@ -8,24 +8,70 @@
 use crate::PeacoQCData;
 use crate::error::{PeacoQCError, Result};
 use crate::qc::peacoqc::PeacoQCResult;
+use plotters::coord::Shift;
 use plotters::prelude::*;
 use plotters::style::{BLACK, RGBAColor, RGBColor, WHITE};
 use std::path::Path;
 
+/// Output image format for [`create_qc_plots`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotFormat {
+    /// Rasterized bitmap (default)
+    Png,
+    /// Vector image - scales cleanly for large spectral panels
+    Svg,
+}
+
+/// A named preset bundling [`QCPlotConfig`]'s color fields
+///
+/// Applied via [`QCPlotConfig::with_color_scheme`]; individual color fields can still be
+/// overridden afterward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    /// The original PeacoQC-style palette (light purple / grey / black / red / blue)
+    Default,
+    /// Higher-contrast palette chosen to stay distinguishable under the common color vision
+    /// deficiencies (based on the Okabe-Ito palette)
+    ColorblindSafe,
+    /// Light foreground colors intended for a dark plot background
+    Dark,
+}
+
 /// Configuration for QC plots
 #[derive(Debug, Clone)]
 pub struct QCPlotConfig {
-    /// Output image width in pixels
+    /// Output image width in pixels (ignored if [`Self::plot_width`] is set - see there)
     pub width: u32,
 
-    /// Output image height in pixels
+    /// Output image height in pixels (ignored if [`Self::plot_height`] is set - see there)
     pub height: u32,
 
-    /// Number of columns in the plot grid
-    pub n_cols: usize,
+    /// Width of a single subplot, in pixels. When set, [`Self::width`] is derived from this and
+    /// the resolved grid's column count instead of being used directly - useful for keeping
+    /// individual channel plots readable regardless of how many channels are being plotted.
+    pub plot_width: Option<u32>,
 
-    /// Number of rows in the plot grid
-    pub n_rows: usize,
+    /// Height of a single subplot, in pixels; see [`Self::plot_width`]
+    pub plot_height: Option<u32>,
+
+    /// Explicit `(rows, cols)` grid layout. When `None` (the default), a roughly-square grid
+    /// is chosen automatically based on the number of channels being plotted - see
+    /// [`calculate_grid_dimensions`].
+    pub grid: Option<(usize, usize)>,
+
+    /// Which channels to plot, and in what order. `None` (the default) plots every channel
+    /// PeacoQC ran on, in the order [`PeacoQCResult::peaks`] returns them. Channels not present
+    /// in the result are silently skipped, so a caller can pass a fixed panel layout across
+    /// files that don't all QC the same channels.
+    pub channels: Option<Vec<String>>,
+
+    /// Maximum number of points drawn per channel scatter plot (default: 10,000). Events beyond
+    /// this are downsampled by taking an even stride through the channel, same as before this
+    /// was made configurable.
+    pub max_points_per_channel: usize,
+
+    /// Output image format (default: [`PlotFormat::Png`])
+    pub format: PlotFormat,
 
     /// Color for unstable regions (RGBA)
     pub unstable_color: RGBColor,
@@ -54,8 +100,12 @@ impl Default for QCPlotConfig {
         Self {
             width: 2400,
             height: 1800,
-            n_cols: 4,
-            n_rows: 6,
+            plot_width: None,
+            plot_height: None,
+            grid: None,
+            channels: None,
+            max_points_per_channel: 10_000,
+            format: PlotFormat::Png,
             unstable_color: RGBColor(200, 150, 255), // Light purple
             good_color: RGBColor(128, 128, 128),     // Grey
             median_color: RGBColor(0, 0, 0),         // Black
@@ -67,6 +117,37 @@ impl Default for QCPlotConfig {
     }
 }
 
+impl QCPlotConfig {
+    /// Apply a named color palette, overriding this config's individual color fields
+    pub fn with_color_scheme(mut self, scheme: ColorScheme) -> Self {
+        match scheme {
+            ColorScheme::Default => {
+                self.unstable_color = RGBColor(200, 150, 255);
+                self.good_color = RGBColor(128, 128, 128);
+                self.median_color = RGBColor(0, 0, 0);
+                self.smoothed_spline_color = RGBColor(255, 0, 0);
+                self.mad_threshold_color = RGBColor(0, 0, 255);
+            }
+            ColorScheme::ColorblindSafe => {
+                // Okabe-Ito palette
+                self.unstable_color = RGBColor(230, 159, 0); // Orange
+                self.good_color = RGBColor(128, 128, 128); // Grey
+                self.median_color = RGBColor(0, 0, 0); // Black
+                self.smoothed_spline_color = RGBColor(0, 114, 178); // Blue
+                self.mad_threshold_color = RGBColor(213, 94, 0); // Vermillion
+            }
+            ColorScheme::Dark => {
+                self.unstable_color = RGBColor(120, 80, 160);
+                self.good_color = RGBColor(180, 180, 180);
+                self.median_color = RGBColor(255, 255, 255);
+                self.smoothed_spline_color = RGBColor(255, 100, 100);
+                self.mad_threshold_color = RGBColor(100, 150, 255);
+            }
+        }
+        self
+    }
+}
+
 /// Find the time channel name
 fn find_time_channel<T: PeacoQCData>(fcs: &T) -> Option<String> {
     fcs.channel_names().into_iter().find(|name| {
@@ -123,7 +204,7 @@ fn get_channel_data<T: PeacoQCData>(fcs: &T, channel: &str) -> Result<Vec<f64>>
 }
 
 /// Calculate median value per bin for a channel
-fn calculate_median_per_bin(values: &[f64], events_per_bin: usize) -> Vec<(usize, f64)> {
+pub(crate) fn calculate_median_per_bin(values: &[f64], events_per_bin: usize) -> Vec<(usize, f64)> {
     let mut medians = Vec::new();
     let n_bins = (values.len() + events_per_bin - 1) / events_per_bin;
 
@@ -151,7 +232,7 @@ fn calculate_median_per_bin(values: &[f64], events_per_bin: usize) -> Vec<(usize
 
 /// Calculate grid dimensions for a given number of plots
 /// Returns (n_rows, n_cols) that is relatively square and can fit all plots
-fn calculate_grid_dimensions(n_plots: usize) -> (usize, usize) {
+pub(crate) fn calculate_grid_dimensions(n_plots: usize) -> (usize, usize) {
     if n_plots == 0 {
         return (1, 1);
     }
@@ -174,7 +255,7 @@ fn calculate_grid_dimensions(n_plots: usize) -> (usize, usize) {
 }
 
 /// Find unstable regions (ranges of cell indices where good_cells is false)
-fn find_unstable_regions(good_cells: &[bool]) -> Vec<(usize, usize)> {
+pub(crate) fn find_unstable_regions(good_cells: &[bool]) -> Vec<(usize, usize)> {
     let mut regions = Vec::new();
     let mut in_unstable = false;
     let mut start = 0;
@@ -216,12 +297,15 @@ pub fn create_qc_plots<T: PeacoQCData>(
 ) -> Result<()> {
     let output_path = output_path.as_ref();
 
-    // Find time channel
-    let time_channel = find_time_channel(fcs)
-        .ok_or_else(|| PeacoQCError::ConfigError("Time channel not found".to_string()))?;
-
-    // Get channels to plot (those that were QC'd)
-    let channels: Vec<String> = qc_result.peaks.keys().cloned().collect();
+    // Get channels to plot (those that were QC'd, filtered/ordered per config.channels)
+    let channels: Vec<String> = match &config.channels {
+        Some(requested) => requested
+            .iter()
+            .filter(|c| qc_result.peaks.contains_key(*c))
+            .cloned()
+            .collect(),
+        None => qc_result.peaks.keys().cloned().collect(),
+    };
 
     if channels.is_empty() {
         return Err(PeacoQCError::ConfigError("No channels to plot".to_string()));
@@ -230,11 +314,43 @@ pub fn create_qc_plots<T: PeacoQCData>(
     // Calculate total number of plots needed (1 time plot + N channel plots)
     let n_plots = 1 + channels.len();
 
-    // Calculate grid dimensions dynamically based on number of plots
-    let (n_rows, n_cols) = calculate_grid_dimensions(n_plots);
+    // Calculate grid dimensions: explicit override, or auto-computed
+    let (n_rows, n_cols) = config.grid.unwrap_or_else(|| calculate_grid_dimensions(n_plots));
+
+    // Per-plot size takes precedence over the fixed total width/height
+    let width = config.plot_width.map(|w| w * n_cols as u32).unwrap_or(config.width);
+    let height = config.plot_height.map(|h| h * n_rows as u32).unwrap_or(config.height);
+
+    match config.format {
+        PlotFormat::Png => {
+            let root = BitMapBackend::new(output_path, (width, height)).into_drawing_area();
+            render_qc_plots(root, fcs, qc_result, &config, &channels, n_rows, n_cols)
+        }
+        PlotFormat::Svg => {
+            let root = SVGBackend::new(output_path, (width, height)).into_drawing_area();
+            render_qc_plots(root, fcs, qc_result, &config, &channels, n_rows, n_cols)
+        }
+    }
+}
+
+/// Shared plotting body for [`create_qc_plots`], generic over the plotters backend so both PNG
+/// and SVG output can be produced without duplicating the drawing logic
+fn render_qc_plots<T: PeacoQCData, DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    fcs: &T,
+    qc_result: &PeacoQCResult,
+    config: &QCPlotConfig,
+    channels: &[String],
+    n_rows: usize,
+    n_cols: usize,
+) -> Result<()>
+where
+    DB::ErrorType: std::fmt::Debug,
+{
+    // Find time channel
+    let time_channel = find_time_channel(fcs)
+        .ok_or_else(|| PeacoQCError::ConfigError("Time channel not found".to_string()))?;
 
-    // Create drawing area
-    let root = BitMapBackend::new(output_path, (config.width, config.height)).into_drawing_area();
     root.fill(&WHITE)
         .map_err(|e| PeacoQCError::ExportError(format!("Failed to fill background: {:?}", e)))?;
 
@@ -425,8 +541,8 @@ pub fn create_qc_plots<T: PeacoQCData>(
         }
 
         // Draw scatter plot of good values (sample for performance)
-        let sample_size = 10000.min(n_events);
-        let step = n_events / sample_size;
+        let sample_size = config.max_points_per_channel.min(n_events);
+        let step = n_events.div_ceil(sample_size.max(1));
         let mut good_points = Vec::new();
 
         for i in (0..n_events).step_by(step.max(1)) {
@@ -651,6 +767,21 @@ mod tests {
         assert_eq!(medians[1], (1, 3.5));
     }
 
+    #[test]
+    fn test_config_grid_override_takes_precedence() {
+        let config = QCPlotConfig { grid: Some((2, 7)), ..Default::default() };
+        assert_eq!(config.grid, Some((2, 7)));
+        // an explicit grid should be used as-is, not run through calculate_grid_dimensions
+        assert_ne!(config.grid.unwrap(), calculate_grid_dimensions(9));
+    }
+
+    #[test]
+    fn test_color_scheme_overrides_individual_colors() {
+        let default_colors = QCPlotConfig::default();
+        let colorblind = QCPlotConfig::default().with_color_scheme(ColorScheme::ColorblindSafe);
+        assert_ne!(default_colors.unstable_color, colorblind.unstable_color);
+    }
+
     #[test]
     fn test_calculate_grid_dimensions() {
         // Test various plot counts
@@ -0,0 +1,224 @@
+//! Detection and handling of non-monotonic Time channels
+//!
+//! PeacoQC's binning assumes events arrive in roughly increasing Time order. Files with a
+//! device clock reset mid-acquisition, or that concatenate multiple acquisitions into one FCS
+//! file, break that assumption silently: bins end up mixing events from unrelated points in
+//! time, which can look like drift or instability that was never really there. This module
+//! detects that situation and lets a caller either segment the file into separate acquisition
+//! runs (each processed independently) or compute a sort permutation to restore Time order.
+
+use crate::PeacoQCData;
+use crate::error::{PeacoQCError, Result};
+
+/// One contiguous run of events between two Time resets, as `[start, end)` event indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSegment {
+    /// Index of the first event in this run
+    pub start: usize,
+    /// Index one past the last event in this run
+    pub end: usize,
+}
+
+impl TimeSegment {
+    /// Number of events in this run
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether this run contains no events
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Diagnostics from [`detect_time_issues`]
+#[derive(Debug, Clone)]
+pub struct TimeChannelDiagnostics {
+    /// `true` if Time never decreases from one event to the next
+    pub is_monotonic: bool,
+
+    /// Event indices where Time dropped relative to the previous event (clock resets or
+    /// acquisition boundaries)
+    pub reset_indices: Vec<usize>,
+
+    /// Contiguous acquisition runs implied by `reset_indices`; a single segment spanning the
+    /// whole file when `is_monotonic` is `true`
+    pub segments: Vec<TimeSegment>,
+}
+
+impl TimeChannelDiagnostics {
+    /// Number of resets detected (`segments.len() - 1`)
+    pub fn n_resets(&self) -> usize {
+        self.reset_indices.len()
+    }
+}
+
+fn find_time_channel<T: PeacoQCData>(fcs: &T) -> Option<String> {
+    fcs.channel_names()
+        .into_iter()
+        .find(|name| name.to_uppercase().contains("TIME"))
+}
+
+/// Detect whether `fcs`'s Time channel is monotonic, and if not, where it resets
+///
+/// # Errors
+/// Returns an error if `fcs` has no recognizable Time channel.
+pub fn detect_time_issues<T: PeacoQCData>(fcs: &T) -> Result<TimeChannelDiagnostics> {
+    let time_channel = find_time_channel(fcs).ok_or_else(|| {
+        PeacoQCError::ChannelNotFound("No Time channel found".to_string())
+    })?;
+    let time_values = fcs.get_channel_f64(&time_channel)?;
+
+    let mut reset_indices = Vec::new();
+    for i in 1..time_values.len() {
+        if time_values[i] < time_values[i - 1] {
+            reset_indices.push(i);
+        }
+    }
+
+    let mut segments = Vec::with_capacity(reset_indices.len() + 1);
+    let mut start = 0;
+    for &reset in &reset_indices {
+        segments.push(TimeSegment { start, end: reset });
+        start = reset;
+    }
+    segments.push(TimeSegment {
+        start,
+        end: time_values.len(),
+    });
+
+    Ok(TimeChannelDiagnostics {
+        is_monotonic: reset_indices.is_empty(),
+        reset_indices,
+        segments,
+    })
+}
+
+/// Compute the permutation of event indices that sorts `fcs` into increasing Time order
+///
+/// Applying this permutation is left to the caller, since [`crate::FcsFilter`] only supports
+/// masking, not reordering events - callers with access to their own data representation can
+/// use it to physically re-sort rows before running PeacoQC.
+///
+/// # Errors
+/// Returns an error if `fcs` has no recognizable Time channel.
+pub fn sort_permutation_by_time<T: PeacoQCData>(fcs: &T) -> Result<Vec<usize>> {
+    let time_channel = find_time_channel(fcs).ok_or_else(|| {
+        PeacoQCError::ChannelNotFound("No Time channel found".to_string())
+    })?;
+    let time_values = fcs.get_channel_f64(&time_channel)?;
+
+    let mut indices: Vec<usize> = (0..time_values.len()).collect();
+    indices.sort_by(|&a, &b| {
+        time_values[a]
+            .partial_cmp(&time_values[b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(indices)
+}
+
+/// Split `fcs` into its separate acquisition runs, one per [`TimeSegment`] in `diagnostics`
+///
+/// Each run is built with [`crate::FcsFilter::filter`], so PeacoQC (or any other analysis) can
+/// then be run against each run independently rather than binning across a Time reset.
+///
+/// # Errors
+/// Returns an error if any segment's mask fails to apply.
+pub fn segment_by_time<T: PeacoQCData + crate::FcsFilter>(
+    fcs: &T,
+    diagnostics: &TimeChannelDiagnostics,
+) -> Result<Vec<T>> {
+    let n_events = fcs.n_events();
+    diagnostics
+        .segments
+        .iter()
+        .map(|segment| {
+            let mask: Vec<bool> = (0..n_events)
+                .map(|i| i >= segment.start && i < segment.end)
+                .collect();
+            fcs.filter(&mask)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fcs::SimpleFcs;
+    use polars::df;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_detect_time_issues_monotonic() {
+        let df = Arc::new(df!["Time" => &[1.0, 2.0, 3.0, 4.0, 5.0]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let diagnostics = detect_time_issues(&fcs).unwrap();
+
+        assert!(diagnostics.is_monotonic);
+        assert_eq!(diagnostics.n_resets(), 0);
+        assert_eq!(diagnostics.segments, vec![TimeSegment { start: 0, end: 5 }]);
+    }
+
+    #[test]
+    fn test_detect_time_issues_with_reset() {
+        // Clock resets between index 2 and 3, then again between 4 and 5.
+        let df = Arc::new(df!["Time" => &[1.0, 2.0, 3.0, 0.5, 1.5, 0.2]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let diagnostics = detect_time_issues(&fcs).unwrap();
+
+        assert!(!diagnostics.is_monotonic);
+        assert_eq!(diagnostics.reset_indices, vec![3, 5]);
+        assert_eq!(
+            diagnostics.segments,
+            vec![
+                TimeSegment { start: 0, end: 3 },
+                TimeSegment { start: 3, end: 5 },
+                TimeSegment { start: 5, end: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_permutation_by_time() {
+        let df = Arc::new(df!["Time" => &[3.0, 1.0, 2.0]].unwrap());
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let permutation = sort_permutation_by_time(&fcs).unwrap();
+
+        assert_eq!(permutation, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_segment_by_time() {
+        let df = Arc::new(
+            df![
+                "Time" => &[1.0, 2.0, 0.5, 1.5],
+                "FSC-A" => &[10.0, 20.0, 30.0, 40.0],
+            ]
+            .unwrap(),
+        );
+        let fcs = SimpleFcs {
+            data_frame: df,
+            parameter_metadata: HashMap::new(),
+        };
+
+        let diagnostics = detect_time_issues(&fcs).unwrap();
+        let runs = segment_by_time(&fcs, &diagnostics).unwrap();
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].n_events(), 2);
+        assert_eq!(runs[1].n_events(), 2);
+    }
+}
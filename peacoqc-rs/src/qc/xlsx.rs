@@ -0,0 +1,159 @@
+//! Excel (xlsx) export of QC results
+//!
+//! Unlike [`crate::qc::export`]'s per-event CSV/JSON exports, this produces a single workbook
+//! summarizing a whole batch of files - the format wet-lab collaborators who don't use
+//! pandas/R tend to actually open.
+
+use crate::error::{PeacoQCError, Result};
+use crate::qc::{PeacoQCConfig, PeacoQCResult};
+use rust_xlsxwriter::{Workbook, Worksheet};
+use std::path::Path;
+
+/// One file's QC result and the configuration that produced it, as input to [`export_xlsx`]
+pub struct QCFileReport<'a> {
+    /// Name shown in the summary sheet and used to derive this file's per-file sheet name
+    pub file_name: String,
+    /// QC result for this file
+    pub result: &'a PeacoQCResult,
+    /// QC configuration used to produce `result`
+    pub config: &'a PeacoQCConfig,
+}
+
+/// Export a batch of QC results as a single Excel workbook
+///
+/// The workbook has one summary sheet listing every file's headline metrics, followed by one
+/// sheet per file with its configuration, per-channel MAD removal breakdown, and a bin table.
+///
+/// # Errors
+/// Returns an error if `reports` is empty, or if the workbook cannot be built or saved.
+pub fn export_xlsx(reports: &[QCFileReport], path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+
+    if reports.is_empty() {
+        return Err(PeacoQCError::ExportError(
+            "Cannot export an empty batch of QC results".to_string(),
+        ));
+    }
+
+    let mut workbook = Workbook::new();
+    write_summary_sheet(workbook.add_worksheet(), reports)?;
+
+    for report in reports {
+        let sheet_name = xlsx_sheet_name(&report.file_name);
+        let sheet = workbook.add_worksheet();
+        sheet
+            .set_name(&sheet_name)
+            .map_err(|e| PeacoQCError::ExportError(format!("Invalid sheet name for {}: {e}", report.file_name)))?;
+        write_file_sheet(sheet, report)?;
+    }
+
+    workbook.save(path).map_err(|e| {
+        PeacoQCError::WriteError(format!("Failed to save workbook {}: {}", path.display(), e))
+    })?;
+
+    Ok(())
+}
+
+fn write_summary_sheet(sheet: &mut Worksheet, reports: &[QCFileReport]) -> Result<()> {
+    let headers = [
+        "File", "Events (before)", "Events (after)", "% removed", "IT %", "MAD %",
+        "Consecutive %", "Bins", "Events/bin",
+    ];
+    write_row(sheet, 0, &headers)?;
+
+    for (i, report) in reports.iter().enumerate() {
+        let row = (i + 1) as u32;
+        let result = report.result;
+        let n_events_before = result.good_cells.len();
+        let n_events_after = result.good_cells.iter().filter(|&&good| good).count();
+
+        write_cell(sheet, row, 0, &report.file_name)?;
+        write_cell(sheet, row, 1, n_events_before as f64)?;
+        write_cell(sheet, row, 2, n_events_after as f64)?;
+        write_cell(sheet, row, 3, result.percentage_removed)?;
+        write_cell(sheet, row, 4, result.it_percentage.unwrap_or(0.0))?;
+        write_cell(sheet, row, 5, result.mad_percentage.unwrap_or(0.0))?;
+        write_cell(sheet, row, 6, result.consecutive_percentage)?;
+        write_cell(sheet, row, 7, result.n_bins as f64)?;
+        write_cell(sheet, row, 8, result.events_per_bin as f64)?;
+    }
+
+    Ok(())
+}
+
+fn write_file_sheet(sheet: &mut Worksheet, report: &QCFileReport) -> Result<()> {
+    let mut row = 0u32;
+
+    write_cell(sheet, row, 0, "Configuration")?;
+    row += 1;
+    let config = report.config;
+    for (label, value) in [
+        ("QC mode".to_string(), format!("{:?}", config.determine_good_cells)),
+        ("MAD threshold".to_string(), config.mad.to_string()),
+        ("IT limit".to_string(), config.it_limit.to_string()),
+        ("Consecutive bins".to_string(), config.consecutive_bins.to_string()),
+        ("Remove zeros".to_string(), config.remove_zeros.to_string()),
+    ] {
+        write_cell(sheet, row, 0, &label)?;
+        write_cell(sheet, row, 1, &value)?;
+        row += 1;
+    }
+
+    let mut channels: Vec<&String> = report.result.channel_contribution.keys().collect();
+    channels.sort();
+
+    row += 1;
+    write_cell(sheet, row, 0, "Per-channel MAD removal")?;
+    row += 1;
+    write_row(sheet, row, &["Channel", "MAD % of bins flagged"])?;
+    row += 1;
+    for channel in &channels {
+        let contribution = &report.result.channel_contribution[*channel];
+        write_cell(sheet, row, 0, channel.as_str())?;
+        write_cell(sheet, row, 1, contribution.mad_percentage)?;
+        row += 1;
+    }
+
+    row += 1;
+    write_cell(sheet, row, 0, "Bin table")?;
+    row += 1;
+    let mut header = vec!["Bin".to_string()];
+    header.extend(channels.iter().map(|c| format!("{c} outlier")));
+    write_row(sheet, row, &header.iter().map(String::as_str).collect::<Vec<_>>())?;
+    row += 1;
+    for bin in 0..report.result.n_bins {
+        write_cell(sheet, row, 0, bin as f64)?;
+        for (col, channel) in channels.iter().enumerate() {
+            let contribution = &report.result.channel_contribution[*channel];
+            let is_outlier = contribution.mad_outlier_bins.get(bin).copied().unwrap_or(false);
+            write_cell(sheet, row, (col + 1) as u16, is_outlier)?;
+        }
+        row += 1;
+    }
+
+    Ok(())
+}
+
+fn write_row(sheet: &mut Worksheet, row: u32, values: &[&str]) -> Result<()> {
+    for (col, value) in values.iter().enumerate() {
+        write_cell(sheet, row, col as u16, *value)?;
+    }
+    Ok(())
+}
+
+fn write_cell(sheet: &mut Worksheet, row: u32, col: u16, value: impl rust_xlsxwriter::IntoExcelData) -> Result<()> {
+    sheet
+        .write(row, col, value)
+        .map_err(|e| PeacoQCError::ExportError(format!("Failed to write cell ({row}, {col}): {e}")))?;
+    Ok(())
+}
+
+/// Sanitize a file name into a valid Excel sheet name: strip characters Excel forbids
+/// (`\ / ? * [ ] :`) and truncate to Excel's 31-character sheet-name limit
+fn xlsx_sheet_name(file_name: &str) -> String {
+    let cleaned: String = file_name
+        .chars()
+        .map(|c| if "\\/?*[]:".contains(c) { '_' } else { c })
+        .collect();
+    cleaned.chars().take(31).collect()
+}
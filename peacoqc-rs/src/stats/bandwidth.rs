@@ -0,0 +1,365 @@
+//! Bandwidth selection rules for [`crate::stats::KernelDensity`]
+//!
+//! Peak detection quality is sensitive to the KDE bandwidth: too wide and distinct peaks
+//! merge together, too narrow and noise is mistaken for peaks. This module implements the
+//! rules R's `density()`/`bw.*` family expose, from cheapest to most robust.
+
+use crate::error::{PeacoQCError, Result};
+use std::f64::consts::PI;
+
+/// Bandwidth selection rule for kernel density estimation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BandwidthMethod {
+    /// Silverman's rule of thumb (R's `bw.nrd0`): `0.9 * min(sd, IQR/1.34) * n^(-1/5)`
+    ///
+    /// Cheap and the historical default here, but tends to oversmooth multimodal data.
+    #[default]
+    Silverman,
+    /// Scott's rule (R's `bw.nrd`): `1.06 * min(sd, IQR/1.34) * n^(-1/5)`
+    ///
+    /// Same form as Silverman with a larger constant, so it smooths slightly more.
+    Scott,
+    /// Sheather-Jones "solve-the-equation" plug-in bandwidth (R's `bw.SJ(method = "ste")`)
+    ///
+    /// Estimates bandwidth from the data's own curvature instead of assuming normality,
+    /// which usually gives sharper, more reliable peaks for flow cytometry channels.
+    /// Falls back to [`BandwidthMethod::Silverman`] if the plug-in functionals degenerate
+    /// (e.g. too many tied values).
+    SheatherJones,
+    /// Botev et al.'s (2010) improved Sheather-Jones diffusion estimator
+    ///
+    /// Unlike [`BandwidthMethod::SheatherJones`], it doesn't need a normal-reference pilot
+    /// bandwidth, so it holds up better on strongly multimodal or heavy-tailed channels.
+    /// Falls back to [`BandwidthMethod::Silverman`] if the fixed-point solve doesn't converge.
+    ImprovedSheatherJones,
+}
+
+/// Select a KDE bandwidth for `data` using the given rule
+pub fn select_bandwidth(data: &[f64], method: BandwidthMethod) -> Result<f64> {
+    match method {
+        BandwidthMethod::Silverman => bw_silverman(data),
+        BandwidthMethod::Scott => bw_scott(data),
+        BandwidthMethod::SheatherJones => bw_sheather_jones(data),
+        BandwidthMethod::ImprovedSheatherJones => bw_isj(data),
+    }
+}
+
+/// Silverman's rule of thumb (R's `bw.nrd0`)
+pub(crate) fn bw_silverman(data: &[f64]) -> Result<f64> {
+    bw_normal_reference(data, 0.9)
+}
+
+/// Scott's rule (R's `bw.nrd`)
+pub(crate) fn bw_scott(data: &[f64]) -> Result<f64> {
+    bw_normal_reference(data, 1.06)
+}
+
+fn bw_normal_reference(data: &[f64], constant: f64) -> Result<f64> {
+    let n = data.len() as f64;
+    let scale = normal_reference_scale(data)?;
+    Ok(constant * scale * n.powf(-0.2))
+}
+
+/// `min(sd, IQR/1.34)`, the robust scale estimate shared by Silverman and Scott's rules
+fn normal_reference_scale(data: &[f64]) -> Result<f64> {
+    let std_dev = standard_deviation(data)?;
+    let iqr = interquartile_range(data)?;
+    Ok(std_dev.min(iqr / 1.34))
+}
+
+/// Sheather-Jones "solve-the-equation" plug-in bandwidth
+///
+/// Follows the structure of R's `bw.SJ(method = "ste")`: a pilot bandwidth `b` estimates the
+/// (fixed) sixth-derivative functional `TD`, then the bandwidth `h` solving
+/// `h = (c1 / phi4(alpha2(h)))^(1/5)` is found where `alpha2(h) = 1.357 * (phi4(h)/TD)^(1/7)`.
+/// R solves this with a bracketing root-finder; here it's solved by fixed-point iteration
+/// starting from Silverman's bandwidth, which converges quickly in practice.
+fn bw_sheather_jones(data: &[f64]) -> Result<f64> {
+    let n = data.len();
+    if n < 2 {
+        return Err(PeacoQCError::InsufficientData { min: 2, actual: n });
+    }
+    let nf = n as f64;
+    let scale = normal_reference_scale(data)?;
+    if !(scale > 0.0) {
+        return bw_silverman(data);
+    }
+
+    let b = 0.912 * scale * nf.powf(-1.0 / 9.0);
+    // R negates the raw phi6 functional to get a positive curvature estimate (`TD <- -bw.phi6(...)`).
+    let td = -phi_functional(data, b, 6);
+    if !(td > 0.0) {
+        return bw_silverman(data);
+    }
+
+    let c1 = 1.0 / (2.0 * PI.sqrt() * nf);
+    let mut h = bw_silverman(data)?;
+    for _ in 0..40 {
+        let sd_h = phi_functional(data, h, 4);
+        if !(sd_h > 0.0) {
+            break;
+        }
+        let alpha2 = 1.357 * (sd_h / td).powf(1.0 / 7.0);
+        let sd_alpha2 = phi_functional(data, alpha2, 4);
+        if !(sd_alpha2 > 0.0) {
+            break;
+        }
+        let new_h = (c1 / sd_alpha2).powf(0.2);
+        // Plain fixed-point iteration ping-pongs around the root here; averaging with the
+        // previous estimate damps that oscillation so it converges in a handful of iterations.
+        let damped = 0.5 * (h + new_h);
+        let converged = (damped - h).abs() < 1e-6 * h.max(1e-12);
+        h = damped;
+        if converged {
+            break;
+        }
+    }
+
+    Ok(h)
+}
+
+/// Roughness functional `phi_r(h) = 1/(n(n-1)h^(r+1)) * sum_{i,j} He_r((x_i-x_j)/h) * phi((x_i-x_j)/h)`
+/// (the sum includes the `i == j` terms, as R's does)
+/// used by [`bw_sheather_jones`], for `r` in `{4, 6}` (the even-order Hermite polynomials R's
+/// `bw.phi4`/`bw.phi6` implement).
+fn phi_functional(data: &[f64], h: f64, order: i32) -> f64 {
+    let n = data.len();
+    let mut sum = 0.0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let u = (data[i] - data[j]) / h;
+            let delta = u * u;
+            sum += 2.0 * hermite_term(order, delta);
+        }
+    }
+    // i == j contributes He_r(0) once per point.
+    sum += n as f64 * hermite_term(order, 0.0);
+
+    sum / (n as f64 * (n as f64 - 1.0) * h.powi(order + 1) * (2.0 * PI).sqrt())
+}
+
+/// `He_r(u) * exp(-u^2/2)` where `delta = u^2`, for `r` in `{4, 6}`
+fn hermite_term(order: i32, delta: f64) -> f64 {
+    let he = match order {
+        4 => delta * delta - 6.0 * delta + 3.0,
+        6 => delta * delta * delta - 15.0 * delta * delta + 45.0 * delta - 15.0,
+        _ => unreachable!("phi_functional only supports order 4 or 6"),
+    };
+    he * (-0.5 * delta).exp()
+}
+
+/// Botev, Grotowski & Kroese's (2010) improved Sheather-Jones diffusion bandwidth
+///
+/// Bins the data onto a grid, takes its discrete cosine transform, and solves the diffusion
+/// fixed-point equation for the rescaled bandwidth `t*` (see the paper's `fixed_point`
+/// recursion). Falls back to [`BandwidthMethod::Silverman`] if the bracketing search for a
+/// root doesn't find a sign change, which can happen for very small or degenerate samples.
+fn bw_isj(data: &[f64]) -> Result<f64> {
+    const N_GRID: usize = 512;
+
+    let n = data.len();
+    if n < 2 {
+        return Err(PeacoQCError::InsufficientData { min: 2, actual: n });
+    }
+
+    let data_min = data.iter().cloned().fold(f64::INFINITY, f64::min);
+    let data_max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = data_max - data_min;
+    if !(range > 0.0) {
+        return bw_silverman(data);
+    }
+    // Pad the grid so mass near the edges isn't clipped, matching Botev's reference implementation.
+    let pad = range * 0.1;
+    let grid_min = data_min - pad;
+    let grid_max = data_max + pad;
+    let grid_range = grid_max - grid_min;
+
+    let mut counts = vec![0.0f64; N_GRID];
+    for &x in data {
+        let idx = (((x - grid_min) / grid_range) * N_GRID as f64) as usize;
+        counts[idx.min(N_GRID - 1)] += 1.0;
+    }
+    let nf = n as f64;
+    let weights: Vec<f64> = counts.iter().map(|&c| c / nf).collect();
+
+    let dct = dct2(&weights);
+    let i_vals: Vec<f64> = (1..N_GRID).map(|i| (i * i) as f64).collect();
+    let a2: Vec<f64> = dct[1..].iter().map(|&a| (a / 2.0).powi(2)).collect();
+
+    let g = |t: f64| diffusion_fixed_point(t, nf, &i_vals, &a2);
+    let t_star = match find_root_bisection(g, 1e-9, 0.1, 100) {
+        Some(t) => t,
+        None => {
+            let scale = bw_silverman(data)?;
+            return Ok(scale);
+        }
+    };
+
+    Ok(t_star.sqrt() * grid_range)
+}
+
+/// Botev et al.'s recursive fixed-point functional; its root in `t` is the rescaled
+/// diffusion-estimator bandwidth `t*`
+fn diffusion_fixed_point(t: f64, n: f64, i_vals: &[f64], a2: &[f64]) -> f64 {
+    let l = 7;
+    let mut f = diffusion_functional(l, t, i_vals, a2);
+    for s in (2..l).rev() {
+        let k0 = (1..2 * s)
+            .step_by(2)
+            .map(|x| x as f64)
+            .product::<f64>()
+            / (2.0 * PI).sqrt();
+        let const_term = (1.0 + (0.5f64).powf(s as f64 + 0.5)) / 3.0;
+        let time = (2.0 * const_term * k0 / n / f).powf(2.0 / (3.0 + 2.0 * s as f64));
+        f = diffusion_functional(s, time, i_vals, a2);
+    }
+    (2.0 * n * PI.sqrt() * f).powf(-2.0 / 5.0) - t
+}
+
+fn diffusion_functional(s: i32, time: f64, i_vals: &[f64], a2: &[f64]) -> f64 {
+    2.0 * PI.powi(2 * s)
+        * i_vals
+            .iter()
+            .zip(a2)
+            .map(|(&i, &a)| i.powi(s) * a * (-i * PI.powi(2) * time).exp())
+            .sum::<f64>()
+}
+
+/// Type-II discrete cosine transform (unnormalized, matching SciPy's default convention)
+fn dct2(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let nf = n as f64;
+    (0..n)
+        .map(|k| {
+            let sum: f64 = data
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| {
+                    x * (PI * k as f64 * (2.0 * i as f64 + 1.0) / (2.0 * nf))
+                        .cos()
+                })
+                .sum();
+            2.0 * sum
+        })
+        .collect()
+}
+
+/// Bisection root-find of `f` on `[lo, hi]`, doubling `hi` until a sign change brackets a root
+fn find_root_bisection(f: impl Fn(f64) -> f64, mut lo: f64, mut hi: f64, iters: usize) -> Option<f64> {
+    let mut f_lo = f(lo);
+    let mut f_hi = f(hi);
+    let mut doublings = 0;
+    while f_lo * f_hi > 0.0 && doublings < 20 {
+        hi *= 2.0;
+        f_hi = f(hi);
+        doublings += 1;
+    }
+    if f_lo * f_hi > 0.0 {
+        return None;
+    }
+
+    for _ in 0..iters {
+        let mid = 0.5 * (lo + hi);
+        let f_mid = f(mid);
+        if f_lo * f_mid <= 0.0 {
+            hi = mid;
+            f_hi = f_mid;
+        } else {
+            lo = mid;
+            f_lo = f_mid;
+        }
+        let _ = f_hi;
+    }
+
+    Some(0.5 * (lo + hi))
+}
+
+/// Calculate standard deviation
+fn standard_deviation(data: &[f64]) -> Result<f64> {
+    if data.is_empty() {
+        return Err(PeacoQCError::StatsError("Empty data".to_string()));
+    }
+
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    let variance = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
+
+    Ok(variance.sqrt())
+}
+
+/// Calculate interquartile range (IQR = Q3 - Q1)
+fn interquartile_range(data: &[f64]) -> Result<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let n = sorted.len();
+    if n < 4 {
+        return Ok(sorted[n - 1] - sorted[0]);
+    }
+
+    let q1_idx = n / 4;
+    let q3_idx = 3 * n / 4;
+
+    Ok(sorted[q3_idx] - sorted[q1_idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bimodal_sample() -> Vec<f64> {
+        let mut data = Vec::new();
+        for i in 0..200 {
+            let jitter = (i % 7) as f64 * 0.05;
+            data.push(0.0 + jitter);
+            data.push(5.0 + jitter);
+        }
+        data
+    }
+
+    #[test]
+    fn test_silverman_positive() {
+        let data = bimodal_sample();
+        let bw = select_bandwidth(&data, BandwidthMethod::Silverman).unwrap();
+        assert!(bw > 0.0);
+    }
+
+    #[test]
+    fn test_scott_wider_than_silverman() {
+        let data = bimodal_sample();
+        let silverman = select_bandwidth(&data, BandwidthMethod::Silverman).unwrap();
+        let scott = select_bandwidth(&data, BandwidthMethod::Scott).unwrap();
+        // Same functional form, larger constant.
+        assert!(scott > silverman);
+    }
+
+    #[test]
+    fn test_sheather_jones_positive() {
+        let data = bimodal_sample();
+        let bw = select_bandwidth(&data, BandwidthMethod::SheatherJones).unwrap();
+        assert!(bw > 0.0);
+        assert!(bw.is_finite());
+    }
+
+    #[test]
+    fn test_improved_sheather_jones_positive() {
+        let data = bimodal_sample();
+        let bw = select_bandwidth(&data, BandwidthMethod::ImprovedSheatherJones).unwrap();
+        assert!(bw > 0.0);
+        assert!(bw.is_finite());
+    }
+
+    #[test]
+    fn test_bandwidth_methods_handle_degenerate_data() {
+        let data = vec![1.0; 50];
+        for method in [
+            BandwidthMethod::Silverman,
+            BandwidthMethod::Scott,
+            BandwidthMethod::SheatherJones,
+            BandwidthMethod::ImprovedSheatherJones,
+        ] {
+            // Should fall back gracefully rather than panicking or returning NaN.
+            let bw = select_bandwidth(&data, method).unwrap();
+            assert!(bw.is_finite());
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::{PeacoQCError, Result};
+use crate::stats::bandwidth::{BandwidthMethod, select_bandwidth};
 use realfft::RealFftPlanner;
 use realfft::num_complex::Complex;
 
@@ -8,7 +9,7 @@ use crate::gpu::{is_gpu_available, kde_fft_gpu};
 /// Kernel Density Estimation using Gaussian kernel with FFT acceleration
 ///
 /// This is a simplified implementation of R's density() function
-/// with automatic bandwidth selection using Silverman's rule of thumb.
+/// with automatic bandwidth selection (see [`BandwidthMethod`], default Silverman's rule).
 /// Uses FFT-based convolution for O(n log n) performance instead of O(n*m).
 pub struct KernelDensity {
     pub x: Vec<f64>, // Grid points
@@ -16,13 +17,31 @@ pub struct KernelDensity {
 }
 
 impl KernelDensity {
-    /// Compute kernel density estimate using FFT-based convolution
+    /// Compute kernel density estimate using FFT-based convolution and Silverman's rule
+    ///
+    /// Shorthand for [`Self::estimate_with_bandwidth`] with [`BandwidthMethod::Silverman`].
     ///
     /// # Arguments
     /// * `data` - Input data
     /// * `adjust` - Bandwidth adjustment factor (default: 1.0)
     /// * `n_points` - Number of grid points (default: 512)
     pub fn estimate(data: &[f64], adjust: f64, n_points: usize) -> Result<Self> {
+        Self::estimate_with_bandwidth(data, adjust, n_points, BandwidthMethod::Silverman)
+    }
+
+    /// Compute kernel density estimate using FFT-based convolution
+    ///
+    /// # Arguments
+    /// * `data` - Input data
+    /// * `adjust` - Bandwidth adjustment factor (default: 1.0)
+    /// * `n_points` - Number of grid points (default: 512)
+    /// * `method` - Bandwidth selection rule (see [`BandwidthMethod`])
+    pub fn estimate_with_bandwidth(
+        data: &[f64],
+        adjust: f64,
+        n_points: usize,
+        method: BandwidthMethod,
+    ) -> Result<Self> {
         if data.is_empty() {
             return Err(PeacoQCError::StatsError("Empty data for KDE".to_string()));
         }
@@ -37,14 +56,8 @@ impl KernelDensity {
             });
         }
 
-        // Calculate bandwidth using Silverman's rule of thumb
         let n = clean_data.len() as f64;
-        let std_dev = standard_deviation(&clean_data)?;
-        let iqr = interquartile_range(&clean_data)?;
-
-        // Silverman's rule: bw = 0.9 * min(sd, IQR/1.34) * n^(-1/5)
-        let bw_factor = 0.9 * std_dev.min(iqr / 1.34) * n.powf(-0.2);
-        let bandwidth = bw_factor * adjust;
+        let bandwidth = select_bandwidth(&clean_data, method)? * adjust;
 
         // Create grid
         let data_min = clean_data.iter().cloned().fold(f64::INFINITY, f64::min);
@@ -227,34 +240,6 @@ fn gaussian_kernel(u: f64) -> f64 {
     INV_SQRT_2PI * (-0.5 * u * u).exp()
 }
 
-/// Calculate standard deviation
-fn standard_deviation(data: &[f64]) -> Result<f64> {
-    if data.is_empty() {
-        return Err(PeacoQCError::StatsError("Empty data".to_string()));
-    }
-
-    let mean = data.iter().sum::<f64>() / data.len() as f64;
-    let variance = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
-
-    Ok(variance.sqrt())
-}
-
-/// Calculate interquartile range (IQR = Q3 - Q1)
-fn interquartile_range(data: &[f64]) -> Result<f64> {
-    let mut sorted = data.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    let n = sorted.len();
-    if n < 4 {
-        return Ok(sorted[n - 1] - sorted[0]);
-    }
-
-    let q1_idx = n / 4;
-    let q3_idx = 3 * n / 4;
-
-    Ok(sorted[q3_idx] - sorted[q1_idx])
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
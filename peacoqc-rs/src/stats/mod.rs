@@ -1,7 +1,9 @@
+pub mod bandwidth;
 pub mod density;
 pub mod median_mad;
 pub mod spline;
 
+pub use bandwidth::BandwidthMethod;
 pub use density::KernelDensity;
 pub use median_mad::{median, median_mad};
 pub use spline::smooth_spline;
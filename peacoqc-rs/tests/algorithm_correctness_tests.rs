@@ -75,7 +75,7 @@ fn test_isolation_tree_split_selection() {
         force_it: 10,
     };
 
-    let result = isolation_tree_detect(&peak_results, 20, &config).unwrap();
+    let result = isolation_tree_detect(&peak_results, 20, &config, &HashMap::new()).unwrap();
     
     // IT should identify the split and create a tree
     assert!(result.tree.len() > 1, "IT should create a tree with multiple nodes");
@@ -223,6 +223,7 @@ fn test_consecutive_bins_removes_short_regions() {
 
     let config = ConsecutiveConfig {
         consecutive_bins: 5,
+        ..Default::default()
     };
 
     let result = remove_short_regions(&outlier_bins, &config).unwrap();
@@ -296,7 +297,7 @@ fn test_mad_edge_cases() {
     let existing_outliers = vec![true, true, true];
     let config = MADConfig::default();
 
-    let result = mad_outlier_method(&peak_results, &existing_outliers, 3, &config);
+    let result = mad_outlier_method(&peak_results, &existing_outliers, 3, &config, &HashMap::new());
     // Should handle small datasets (may succeed or fail gracefully)
     assert!(result.is_ok() || result.is_err(), "MAD should handle small datasets gracefully");
 }
@@ -310,7 +311,7 @@ fn test_feature_matrix_empty_clusters() {
     let mut peak_results = HashMap::new();
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks: Vec::new() });
 
-    let result = build_feature_matrix(&peak_results, 10);
+    let result = build_feature_matrix(&peak_results, 10, &HashMap::new());
     // Current implementation may return Ok with empty matrix or Err
     // Both are acceptable - empty matrix will cause IT to fail downstream, which is fine
     match result {
@@ -90,20 +90,20 @@ fn test_gpu_feature_matrix_correctness() {
     let n_bins = 100;
 
     // Run CPU version
-    let (matrix_cpu, names_cpu) = build_feature_matrix(&peak_results, n_bins).unwrap();
+    let (matrix_cpu, names_cpu) = build_feature_matrix(&peak_results, n_bins, &HashMap::new()).unwrap();
 
     // Run GPU version (if available)
     #[cfg(feature = "gpu")]
     use peacoqc_rs::gpu::{build_feature_matrix_gpu, is_gpu_available};
     #[cfg(feature = "gpu")]
     let (matrix_gpu, names_gpu) = if is_gpu_available() {
-        build_feature_matrix_gpu(&peak_results, n_bins).unwrap()
+        build_feature_matrix_gpu(&peak_results, n_bins, &HashMap::new()).unwrap()
     } else {
-        build_feature_matrix(&peak_results, n_bins).unwrap()
+        build_feature_matrix(&peak_results, n_bins, &HashMap::new()).unwrap()
     };
 
     #[cfg(not(feature = "gpu"))]
-    let (matrix_gpu, names_gpu) = build_feature_matrix(&peak_results, n_bins).unwrap();
+    let (matrix_gpu, names_gpu) = build_feature_matrix(&peak_results, n_bins, &HashMap::new()).unwrap();
 
     // Compare results
     assert_eq!(matrix_cpu.len(), matrix_gpu.len());
@@ -121,3 +121,55 @@ fn test_gpu_feature_matrix_correctness() {
         }
     }
 }
+
+#[test]
+#[cfg(feature = "gpu")]
+fn test_median_mad_batched_gpu_matches_cpu() {
+    use peacoqc_rs::gpu::median_mad_batched_gpu;
+    use peacoqc_rs::stats::median_mad;
+
+    let channel_a: Vec<f64> = (0..1000).map(|i| (i as f64 / 37.0).sin() * 50.0 + 100.0).collect();
+    let channel_b: Vec<f64> = (0..1000).map(|i| (i as f64 * 1.3) % 200.0).collect();
+    let channels: Vec<&[f64]> = vec![&channel_a, &channel_b];
+
+    let batched = median_mad_batched_gpu(&channels);
+    assert_eq!(batched.len(), channels.len());
+
+    for (data, result) in channels.iter().zip(batched.iter()) {
+        let (median_cpu, mad_cpu) = median_mad(data).unwrap();
+        let (median_batched, mad_batched) = result.as_ref().unwrap();
+        assert!((median_cpu - median_batched).abs() < 1e-10);
+        assert!((mad_cpu - mad_batched).abs() < 1e-10);
+    }
+}
+
+#[test]
+#[cfg(feature = "gpu")]
+fn test_smooth_spline_batched_gpu_matches_cpu() {
+    use peacoqc_rs::gpu::{smooth_spline_batched_gpu, SplineContext};
+    use peacoqc_rs::stats::smooth_spline;
+
+    let x_a: Vec<f64> = (0..20).map(|i| i as f64).collect();
+    let y_a: Vec<f64> = (0..20).map(|i| (i as f64) * 2.0 + 1.0).collect();
+    let x_b: Vec<f64> = (0..15).map(|i| i as f64).collect();
+    let y_b: Vec<f64> = vec![
+        1.0, 5.0, 2.0, 8.0, 1.5, 6.0, 3.0, 7.0, 2.5, 5.5, 1.0, 5.0, 2.0, 8.0, 1.5,
+    ];
+
+    let contexts = vec![
+        SplineContext { x: &x_a, y: &y_a, spar: 0.5 },
+        SplineContext { x: &x_b, y: &y_b, spar: 0.5 },
+    ];
+
+    let batched = smooth_spline_batched_gpu(&contexts);
+    assert_eq!(batched.len(), contexts.len());
+
+    for (ctx, result) in contexts.iter().zip(batched.iter()) {
+        let cpu = smooth_spline(ctx.x, ctx.y, ctx.spar).unwrap();
+        let gpu = result.as_ref().unwrap();
+        assert_eq!(cpu.len(), gpu.len());
+        for (a, b) in cpu.iter().zip(gpu.iter()) {
+            assert!((a - b).abs() < 1e-10);
+        }
+    }
+}
@@ -209,7 +209,8 @@ mod tests {
         let result = peacoqc_rs::peacoqc(&fcs, &config).unwrap();
 
         // Build feature matrix and verify structure
-        let (matrix, feature_names) = build_feature_matrix(&result.peaks, result.n_bins).unwrap();
+        let (matrix, feature_names) =
+            build_feature_matrix(&result.peaks, result.n_bins, &std::collections::HashMap::new()).unwrap();
 
         // Verify: should have one column per cluster per channel
         // Should have more columns than channels (because clusters > 1 per channel)
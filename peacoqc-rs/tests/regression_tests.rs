@@ -54,7 +54,7 @@ fn test_feature_matrix_structure_per_cluster() {
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks: peaks1 });
     peak_results.insert("FL2-A".to_string(), ChannelPeakFrame { peaks: peaks2 });
 
-    let (matrix, feature_names) = build_feature_matrix(&peak_results, 10).unwrap();
+    let (matrix, feature_names) = build_feature_matrix(&peak_results, 10, &HashMap::new()).unwrap();
 
     // Should have 4 columns: FL1-A_cluster_1, FL1-A_cluster_2, FL2-A_cluster_1, FL2-A_cluster_2
     assert_eq!(matrix[0].len(), 4, "Feature matrix should have 4 columns (2 channels × 2 clusters)");
@@ -86,7 +86,7 @@ fn test_feature_matrix_cluster_median_defaults() {
     let mut peak_results = HashMap::new();
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks });
 
-    let (matrix, _) = build_feature_matrix(&peak_results, 5).unwrap();
+    let (matrix, _) = build_feature_matrix(&peak_results, 5, &HashMap::new()).unwrap();
 
     // All bins should have values
     for bin_idx in 0..5 {
@@ -123,7 +123,7 @@ fn test_isolation_tree_feature_matrix() {
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks: peaks1 });
 
     // Build feature matrix
-    let (matrix, feature_names) = build_feature_matrix(&peak_results, 20).unwrap();
+    let (matrix, feature_names) = build_feature_matrix(&peak_results, 20, &HashMap::new()).unwrap();
     
     // Verify structure
     assert_eq!(matrix.len(), 20, "Should have 20 bins");
@@ -136,7 +136,7 @@ fn test_isolation_tree_feature_matrix() {
         force_it: 10, // Lower threshold for testing
     };
 
-    let result = isolation_tree_detect(&peak_results, 20, &config);
+    let result = isolation_tree_detect(&peak_results, 20, &config, &HashMap::new());
     assert!(result.is_ok(), "IT should succeed with correct feature matrix");
     
     let result = result.unwrap();
@@ -166,7 +166,7 @@ fn test_mad_filters_to_it_passed_bins() {
         smooth_param: 0.5,
     };
 
-    let result = mad_outlier_method(&peak_results, &existing_outliers, 10, &config);
+    let result = mad_outlier_method(&peak_results, &existing_outliers, 10, &config, &HashMap::new());
     assert!(result.is_ok(), "MAD should succeed");
 
     let result = result.unwrap();
@@ -252,7 +252,7 @@ fn test_isolation_tree_finds_largest_group() {
         force_it: 10,
     };
 
-    let result = isolation_tree_detect(&peak_results, 20, &config).unwrap();
+    let result = isolation_tree_detect(&peak_results, 20, &config, &HashMap::new()).unwrap();
     
     // IT should identify one group as larger/more homogeneous
     // The largest node should have most bins
@@ -292,7 +292,7 @@ fn test_mad_uses_spline_smoothing() {
         smooth_param: 0.5,
     };
 
-    let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config).unwrap();
+    let result = mad_outlier_method(&peak_results, &existing_outliers, 50, &config, &HashMap::new()).unwrap();
 
     // With smoothing, small spikes shouldn't all be detected as outliers
     // (unless they're extreme)
@@ -320,7 +320,7 @@ fn test_feature_matrix_missing_peaks() {
     let mut peak_results = HashMap::new();
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks });
 
-    let (matrix, _) = build_feature_matrix(&peak_results, 10).unwrap();
+    let (matrix, _) = build_feature_matrix(&peak_results, 10, &HashMap::new()).unwrap();
 
     // All bins should have values (cluster median for missing ones)
     for bin_idx in 0..10 {
@@ -365,7 +365,7 @@ fn test_feature_matrix_multiple_channels_clusters() {
     peak_results.insert("FL1-A".to_string(), ChannelPeakFrame { peaks: peaks1 });
     peak_results.insert("FL2-A".to_string(), ChannelPeakFrame { peaks: peaks2 });
 
-    let (matrix, feature_names) = build_feature_matrix(&peak_results, 5).unwrap();
+    let (matrix, feature_names) = build_feature_matrix(&peak_results, 5, &HashMap::new()).unwrap();
 
     // Should have 5 columns: FL1-A_cluster_1, FL1-A_cluster_2, FL2-A_cluster_1, FL2-A_cluster_2, FL2-A_cluster_3
     assert_eq!(matrix[0].len(), 5, "Should have 5 features (2+3 clusters)");
@@ -390,7 +390,7 @@ fn test_isolation_tree_empty_features() {
     let peak_results = HashMap::new();
     let config = IsolationTreeConfig::default();
 
-    let result = isolation_tree_detect(&peak_results, 10, &config);
+    let result = isolation_tree_detect(&peak_results, 10, &config, &HashMap::new());
     assert!(result.is_err(), "IT should fail with no peaks");
 }
 
@@ -413,7 +413,7 @@ fn test_isolation_tree_force_it_threshold() {
         force_it: 150,
     };
 
-    let result = isolation_tree_detect(&peak_results, 100, &config);
+    let result = isolation_tree_detect(&peak_results, 100, &config, &HashMap::new());
     assert!(result.is_err(), "IT should fail when bins < force_it");
 }
 
@@ -426,7 +426,7 @@ fn test_mad_empty_trajectory() {
     let existing_outliers = vec![true; 10];
     let config = MADConfig::default();
 
-    let result = mad_outlier_method(&peak_results, &existing_outliers, 10, &config);
+    let result = mad_outlier_method(&peak_results, &existing_outliers, 10, &config, &HashMap::new());
     assert!(result.is_err(), "MAD should fail with no peaks");
 }
 
@@ -451,6 +451,7 @@ fn test_consecutive_bins_filtering() {
 
     let config = ConsecutiveConfig {
         consecutive_bins: 5,
+        ..Default::default()
     };
 
     let filtered = remove_short_regions(&outlier_bins, &config).unwrap();
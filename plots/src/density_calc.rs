@@ -148,8 +148,9 @@ pub fn calculate_density_per_pixel_cancelable(
 pub fn calculate_density_per_pixel_batch(
     requests: &[(Vec<(f32, f32)>, DensityPlotOptions)],
 ) -> Vec<Vec<RawPixelData>> {
-    calculate_density_per_pixel_batch_cancelable(requests, || false)
-        .expect("calculate_density_per_pixel_batch_cancelable returned None when cancellation is disabled")
+    calculate_density_per_pixel_batch_cancelable(requests, || false).expect(
+        "calculate_density_per_pixel_batch_cancelable returned None when cancellation is disabled",
+    )
 }
 
 /// Calculate density for multiple plots in batch with cancellation support
@@ -0,0 +1,147 @@
+//! Binning and kernel density estimation for [`crate::plots::HistogramPlot`]
+
+use crate::options::HistogramNormalization;
+use std::ops::RangeInclusive;
+
+/// One bin of a computed histogram
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistogramBin {
+    /// Lower edge of the bin, in the same (possibly transformed) space as the input values
+    pub start: f32,
+    /// Upper edge of the bin
+    pub end: f32,
+    /// Bin height, after applying the requested [`HistogramNormalization`]
+    pub value: f32,
+}
+
+/// Bin `values` into `n_bins` equal-width bins spanning `range`, applying `normalization`.
+/// Values outside `range` are dropped, same as points outside a density plot's axis range.
+pub fn compute_histogram(
+    values: &[f32],
+    range: RangeInclusive<f32>,
+    n_bins: usize,
+    normalization: HistogramNormalization,
+) -> Vec<HistogramBin> {
+    let n_bins = n_bins.max(1);
+    let start = *range.start();
+    let end = *range.end();
+    let width = ((end - start) / n_bins as f32).max(f32::MIN_POSITIVE);
+
+    let mut counts = vec![0u32; n_bins];
+    for &v in values {
+        if v < start || v > end {
+            continue;
+        }
+        let idx = (((v - start) / width) as usize).min(n_bins - 1);
+        counts[idx] += 1;
+    }
+
+    let n = values.len() as f32;
+    let max_count = counts.iter().copied().max().unwrap_or(0) as f32;
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let bin_start = start + i as f32 * width;
+            let value = match normalization {
+                HistogramNormalization::Count => count as f32,
+                HistogramNormalization::PercentMax => {
+                    if max_count > 0.0 {
+                        100.0 * count as f32 / max_count
+                    } else {
+                        0.0
+                    }
+                }
+                HistogramNormalization::Density => {
+                    if n > 0.0 {
+                        count as f32 / (n * width)
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            HistogramBin { start: bin_start, end: bin_start + width, value }
+        })
+        .collect()
+}
+
+/// Silverman's rule of thumb bandwidth (R's `bw.nrd0`): `0.9 * min(sd, IQR/1.34) * n^(-1/5)`
+pub fn silverman_bandwidth(values: &[f32]) -> f32 {
+    let n = values.len() as f32;
+    if n < 2.0 {
+        return 1.0;
+    }
+
+    let mean = values.iter().sum::<f32>() / n;
+    let variance = values.iter().map(|&v| (v - mean).powi(2)).sum::<f32>() / n;
+    let std_dev = variance.sqrt();
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let q1 = sorted[(sorted.len() / 4).min(sorted.len() - 1)];
+    let q3 = sorted[(3 * sorted.len() / 4).min(sorted.len() - 1)];
+    let iqr = q3 - q1;
+
+    let scale = if iqr > 0.0 { std_dev.min(iqr / 1.34) } else { std_dev };
+    if scale > 0.0 { 0.9 * scale * n.powf(-0.2) } else { 1.0 }
+}
+
+/// Evaluate a Gaussian KDE for `values` on `n_points` evenly spaced points spanning `range`,
+/// scaled so its area under the curve matches `normalization` (matching the histogram it's
+/// meant to overlay: counts for [`HistogramNormalization::Count`], the raw density curve for
+/// [`HistogramNormalization::Density`], and rescaled to the tallest bar for
+/// [`HistogramNormalization::PercentMax`]).
+pub fn compute_kde_curve(
+    values: &[f32],
+    range: RangeInclusive<f32>,
+    n_points: usize,
+    bandwidth: Option<f32>,
+    bin_width: f32,
+    normalization: HistogramNormalization,
+) -> Vec<(f32, f32)> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    let n = values.len() as f32;
+    let bandwidth = bandwidth.unwrap_or_else(|| silverman_bandwidth(values)).max(f32::MIN_POSITIVE);
+    let start = *range.start();
+    let end = *range.end();
+    let step = (end - start) / (n_points.max(1) as f32 - 1.0).max(1.0);
+    let norm = 1.0 / (n * bandwidth * (2.0 * std::f32::consts::PI).sqrt());
+
+    let mut curve: Vec<(f32, f32)> = (0..n_points.max(1))
+        .map(|i| {
+            let x = start + i as f32 * step;
+            let density = values
+                .iter()
+                .map(|&xi| {
+                    let u = (x - xi) / bandwidth;
+                    (-0.5 * u * u).exp()
+                })
+                .sum::<f32>()
+                * norm;
+            (x, density)
+        })
+        .collect();
+
+    match normalization {
+        HistogramNormalization::Density => {}
+        HistogramNormalization::Count => {
+            for (_, y) in &mut curve {
+                *y *= n * bin_width;
+            }
+        }
+        HistogramNormalization::PercentMax => {
+            let max_density = curve.iter().map(|(_, y)| *y).fold(0.0f32, f32::max);
+            if max_density > 0.0 {
+                for (_, y) in &mut curve {
+                    *y = 100.0 * *y / max_density;
+                }
+            }
+        }
+    }
+
+    curve
+}
@@ -29,23 +29,28 @@
 //! The library is organized into several modules:
 //!
 //! - `options`: Plot configuration types using the builder pattern
-//! - `plots`: Plot implementations (currently `DensityPlot`)
+//! - `plots`: Plot implementations (`DensityPlot`, `HistogramPlot`)
 //! - `render`: Rendering infrastructure and progress reporting
-//! - `density`: Density calculation algorithms
+//! - `density_calc`: Density calculation algorithms
+//! - `histogram_calc`: Binning and KDE calculation for histogram plots
 //! - `colormap`: Color map implementations
 //! - `helpers`: Helper functions for common initialization patterns
 
 pub mod colormap;
 pub mod density_calc;
 pub mod helpers;
+pub mod histogram_calc;
 pub mod options;
 pub mod plots;
 pub mod render;
 
 // Re-export commonly used types
 pub use colormap::ColorMaps;
-pub use options::{AxisOptions, BasePlotOptions, DensityPlotOptions, PlotOptions};
-pub use plots::{DensityPlot, Plot, PlotType};
+pub use options::{
+    AxisOptions, BasePlotOptions, DensityPlotOptions, HistogramNormalization,
+    HistogramPlotOptions, PlotOptions,
+};
+pub use plots::{DensityPlot, HistogramPlot, HistogramSeries, Plot, PlotType};
 pub use render::{ProgressCallback, ProgressInfo, RenderConfig};
 
 // Type aliases
@@ -84,7 +89,10 @@ pub fn create_axis_specs(
             let (nice_min, nice_max) = nice_bounds(*min, *max);
             nice_min..nice_max
         }
-        TransformType::Arcsinh { cofactor: _ } | TransformType::Biexponential { .. } => {
+        TransformType::Arcsinh { cofactor: _ }
+        | TransformType::Biexponential { .. }
+        | TransformType::Logicle { .. }
+        | TransformType::Custom(_) => {
             // Keep the transformed range but we'll format nicely in the formatter
             *plot_range_x.start()..*plot_range_x.end()
         }
@@ -97,7 +105,10 @@ pub fn create_axis_specs(
             let (nice_min, nice_max) = nice_bounds(*min, *max);
             nice_min..nice_max
         }
-        TransformType::Arcsinh { cofactor: _ } | TransformType::Biexponential { .. } => {
+        TransformType::Arcsinh { cofactor: _ }
+        | TransformType::Biexponential { .. }
+        | TransformType::Logicle { .. }
+        | TransformType::Custom(_) => {
             // Keep the transformed range but we'll format nicely in the formatter
             *plot_range_y.start()..*plot_range_y.end()
         }
@@ -0,0 +1,103 @@
+use crate::options::{AxisOptions, BasePlotOptions, PlotOptions};
+use derive_builder::Builder;
+use plotters::style::RGBColor;
+
+/// How histogram bin heights are scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistogramNormalization {
+    /// Raw event counts per bin (default)
+    #[default]
+    Count,
+    /// Each bin as a percentage of the tallest bin
+    PercentMax,
+    /// Probability density (bin height * bin width sums to the fraction of values inside the
+    /// axis range, which is 1 when none fall outside it)
+    Density,
+}
+
+/// Options for single-channel histogram plots
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use flow_plots::options::HistogramPlotOptions;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let options = HistogramPlotOptions::new()
+///     .bins(100usize)
+///     .show_kde(true)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into, strip_option), default)]
+pub struct HistogramPlotOptions {
+    /// Base plot options (layout, dimensions, etc.)
+    #[builder(default)]
+    pub base: BasePlotOptions,
+
+    /// Channel axis configuration (range, transform, label)
+    #[builder(default)]
+    pub axis: AxisOptions,
+
+    /// Number of equal-width bins across the axis range
+    #[builder(default = "50")]
+    pub bins: usize,
+
+    /// How bin heights are scaled
+    #[builder(default)]
+    pub normalization: HistogramNormalization,
+
+    /// Overlay a Gaussian KDE smoothing curve on top of the bars
+    #[builder(default = "true")]
+    pub show_kde: bool,
+
+    /// KDE bandwidth override. `None` (the default) selects it automatically via Silverman's
+    /// rule of thumb, same as [`crate::histogram_calc::silverman_bandwidth`].
+    pub kde_bandwidth: Option<f32>,
+
+    /// Fill color for the histogram bars
+    #[builder(default = "RGBColor(70, 130, 180)")]
+    pub bar_color: RGBColor,
+
+    /// Line color for the KDE overlay
+    #[builder(default = "RGBColor(200, 30, 30)")]
+    pub kde_color: RGBColor,
+
+    /// For [`crate::plots::HistogramPlot::render_overlaid`]: vertically offset each series
+    /// after the first by this fraction of the tallest bar, so overlapping distributions stay
+    /// visually distinguishable (a "ridge plot" / joyplot). `0.0` (the default) stacks every
+    /// series on the same baseline.
+    #[builder(default = "0.0")]
+    pub stagger: f32,
+}
+
+impl Default for HistogramPlotOptions {
+    fn default() -> Self {
+        Self {
+            base: BasePlotOptions::default(),
+            axis: AxisOptions::default(),
+            bins: 50,
+            normalization: HistogramNormalization::default(),
+            show_kde: true,
+            kde_bandwidth: None,
+            bar_color: RGBColor(70, 130, 180),
+            kde_color: RGBColor(200, 30, 30),
+            stagger: 0.0,
+        }
+    }
+}
+
+impl PlotOptions for HistogramPlotOptions {
+    fn base(&self) -> &BasePlotOptions {
+        &self.base
+    }
+}
+
+impl HistogramPlotOptions {
+    /// Create a new builder for HistogramPlotOptions
+    pub fn new() -> HistogramPlotOptionsBuilder {
+        HistogramPlotOptionsBuilder::default()
+    }
+}
@@ -1,10 +1,12 @@
 pub mod axis;
 pub mod base;
 pub mod density;
+pub mod histogram;
 
 pub use axis::{AxisOptions, AxisOptionsBuilder};
 pub use base::{BasePlotOptions, BasePlotOptionsBuilder};
 pub use density::{DensityPlotOptions, DensityPlotOptionsBuilder};
+pub use histogram::{HistogramNormalization, HistogramPlotOptions, HistogramPlotOptionsBuilder};
 
 /// Trait for plot options types
 ///
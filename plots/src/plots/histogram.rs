@@ -0,0 +1,178 @@
+use crate::PlotBytes;
+use crate::histogram_calc::{compute_histogram, compute_kde_curve};
+use crate::options::HistogramPlotOptions;
+use crate::plots::traits::Plot;
+use crate::render::RenderConfig;
+use crate::render::histogram_backend::{RenderedHistogramSeries, render_histogram, render_overlaid_histogram};
+use anyhow::Result;
+use plotters::style::RGBColor;
+
+/// Default colors assigned to series in [`HistogramPlot::render_overlaid`] that don't specify
+/// their own via [`HistogramSeries::with_color`] — the colorblind-safe Okabe-Ito palette, cycled
+/// by series index.
+const DEFAULT_SERIES_PALETTE: &[RGBColor] = &[
+    RGBColor(230, 159, 0),   // orange
+    RGBColor(86, 180, 233),  // sky blue
+    RGBColor(0, 158, 115),   // bluish green
+    RGBColor(240, 228, 66),  // yellow
+    RGBColor(0, 114, 178),   // blue
+    RGBColor(213, 94, 0),    // vermillion
+    RGBColor(204, 121, 167), // reddish purple
+];
+
+/// One labeled distribution within an overlaid histogram (see [`HistogramPlot::render_overlaid`]):
+/// one file, sample, or gated population plotted alongside the others on the same axes.
+#[derive(Clone, Debug)]
+pub struct HistogramSeries {
+    /// Values to bin, already in the same (possibly transformed) space as the shared
+    /// [`HistogramPlotOptions::axis`] used for the whole overlay.
+    pub values: Vec<f32>,
+    /// Legend label. Series with no label are still drawn, just omitted from the legend.
+    pub label: Option<String>,
+    /// Fill/line color for this series. `None` picks a color from
+    /// [`DEFAULT_SERIES_PALETTE`] by series index.
+    pub color: Option<RGBColor>,
+}
+
+impl HistogramSeries {
+    /// Create a new labeled series
+    pub fn new(label: impl Into<String>, values: Vec<f32>) -> Self {
+        Self { values, label: Some(label.into()), color: None }
+    }
+
+    /// Set an explicit color for this series, overriding the default palette
+    pub fn with_color(mut self, color: RGBColor) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Single-channel histogram, with optional KDE smoothing overlay
+///
+/// Bins one channel's values (already in the same, possibly transformed, space as
+/// [`HistogramPlotOptions::axis`]'s range) and draws them as bars, matching
+/// [`crate::plots::DensityPlot`]'s convention of taking pre-transformed data plus a transform
+/// only for axis label formatting.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use flow_plots::plots::histogram::HistogramPlot;
+/// use flow_plots::plots::traits::Plot;
+/// use flow_plots::options::HistogramPlotOptions;
+/// use flow_plots::render::RenderConfig;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let plot = HistogramPlot::new();
+/// let options = HistogramPlotOptions::new().bins(80usize).build()?;
+/// let data: Vec<f32> = vec![1.0, 2.0, 2.5, 3.0];
+/// let mut render_config = RenderConfig::default();
+/// let bytes = plot.render(data, &options, &mut render_config)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct HistogramPlot;
+
+impl HistogramPlot {
+    /// Create a new HistogramPlot instance
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Overlay several series (different files or gated populations) on one histogram, each with
+    /// its own color and an optional legend label — the comparison view used for stimulation/FMO
+    /// analysis. All series share `options`: the same axis range, bin count, normalization, and
+    /// KDE settings; only color and label vary per series.
+    ///
+    /// When [`HistogramPlotOptions::stagger`] is non-zero, each series after the first is offset
+    /// upward by that fraction of the tallest bar across all series, producing a ridge plot
+    /// instead of stacking every series on the same baseline.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use flow_plots::plots::histogram::{HistogramPlot, HistogramSeries};
+    /// use flow_plots::options::HistogramPlotOptions;
+    /// use flow_plots::render::RenderConfig;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let plot = HistogramPlot::new();
+    /// let options = HistogramPlotOptions::new().stagger(0.15f32).build()?;
+    /// let series = vec![
+    ///     HistogramSeries::new("Unstimulated", vec![1.0, 1.2, 1.5]),
+    ///     HistogramSeries::new("Stimulated", vec![2.0, 2.4, 2.6]),
+    /// ];
+    /// let mut render_config = RenderConfig::default();
+    /// let bytes = plot.render_overlaid(&series, &options, &mut render_config)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render_overlaid(
+        &self,
+        series: &[HistogramSeries],
+        options: &HistogramPlotOptions,
+        render_config: &mut RenderConfig,
+    ) -> Result<PlotBytes> {
+        let rendered: Vec<RenderedHistogramSeries> = series
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let bins = compute_histogram(&s.values, options.axis.range.clone(), options.bins, options.normalization);
+
+                let kde_curve = options.show_kde.then(|| {
+                    let bin_width = bins.first().map(|b| b.end - b.start).unwrap_or(1.0);
+                    compute_kde_curve(
+                        &s.values,
+                        options.axis.range.clone(),
+                        200,
+                        options.kde_bandwidth,
+                        bin_width,
+                        options.normalization,
+                    )
+                });
+
+                let color = s
+                    .color
+                    .unwrap_or_else(|| DEFAULT_SERIES_PALETTE[i % DEFAULT_SERIES_PALETTE.len()]);
+
+                RenderedHistogramSeries { bins, kde_curve, color, label: s.label.clone() }
+            })
+            .collect();
+
+        render_overlaid_histogram(&rendered, options, render_config)
+    }
+}
+
+impl Default for HistogramPlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plot for HistogramPlot {
+    type Options = HistogramPlotOptions;
+    type Data = Vec<f32>;
+
+    fn render(
+        &self,
+        data: Self::Data,
+        options: &Self::Options,
+        render_config: &mut RenderConfig,
+    ) -> Result<PlotBytes> {
+        let bins = compute_histogram(&data, options.axis.range.clone(), options.bins, options.normalization);
+
+        let kde_curve = options.show_kde.then(|| {
+            let bin_width = bins.first().map(|b| b.end - b.start).unwrap_or(1.0);
+            compute_kde_curve(
+                &data,
+                options.axis.range.clone(),
+                200,
+                options.kde_bandwidth,
+                bin_width,
+                options.normalization,
+            )
+        });
+
+        render_histogram(&bins, kde_curve.as_deref(), options, render_config)
+    }
+}
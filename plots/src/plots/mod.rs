@@ -1,7 +1,9 @@
 pub mod density;
+pub mod histogram;
 pub mod traits;
 
 pub use density::DensityPlot;
+pub use histogram::{HistogramPlot, HistogramSeries};
 pub use traits::Plot;
 
 /// Plot type enumeration
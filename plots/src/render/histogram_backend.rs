@@ -0,0 +1,165 @@
+use crate::PlotBytes;
+use crate::create_axis_specs;
+use crate::histogram_calc::HistogramBin;
+use crate::options::{HistogramPlotOptions, PlotOptions};
+use crate::render::RenderConfig;
+use crate::render::plotters_backend::format_transform_value;
+use anyhow::Result;
+use flow_fcs::TransformType;
+use plotters::prelude::*;
+
+/// A single series' computed bins, KDE curve, and draw color/label, ready for the shared
+/// rendering path in [`render_overlaid_histogram`]
+pub struct RenderedHistogramSeries {
+    /// Computed bins for this series
+    pub bins: Vec<HistogramBin>,
+    /// Optional KDE overlay curve for this series
+    pub kde_curve: Option<Vec<(f32, f32)>>,
+    /// Fill/line color for this series' bars and KDE curve
+    pub color: RGBColor,
+    /// Legend label. `None` draws the series without a legend entry.
+    pub label: Option<String>,
+}
+
+/// Render a single-channel histogram (bars, plus an optional KDE overlay line) to a JPEG image
+pub fn render_histogram(
+    bins: &[HistogramBin],
+    kde_curve: Option<&[(f32, f32)]>,
+    options: &HistogramPlotOptions,
+    render_config: &mut RenderConfig,
+) -> Result<PlotBytes> {
+    let series = [RenderedHistogramSeries {
+        bins: bins.to_vec(),
+        kde_curve: kde_curve.map(|c| c.to_vec()),
+        color: options.bar_color,
+        label: None,
+    }];
+
+    render_overlaid_histogram(&series, options, render_config)
+}
+
+/// Render one or more histogram series overlaid on the same axes, staggering each series after
+/// the first upward by `options.stagger` (a fraction of the tallest bar) and drawing a legend
+/// when any series carries a label
+pub fn render_overlaid_histogram(
+    series: &[RenderedHistogramSeries],
+    options: &HistogramPlotOptions,
+    render_config: &mut RenderConfig,
+) -> Result<PlotBytes> {
+    let base = options.base();
+    let width = base.width;
+    let height = base.height;
+
+    let mut pixel_buffer = vec![255u8; (width * height * 3) as usize];
+
+    {
+        let backend = BitMapBackend::with_buffer(&mut pixel_buffer, (width, height));
+        let root = backend.into_drawing_area();
+        root.fill(&WHITE)
+            .map_err(|e| anyhow::anyhow!("failed to fill plot background: {e}"))?;
+
+        let max_bar = series
+            .iter()
+            .flat_map(|s| s.bins.iter().map(|b| b.value))
+            .chain(
+                series
+                    .iter()
+                    .filter_map(|s| s.kde_curve.as_ref())
+                    .flat_map(|curve| curve.iter().map(|(_, y)| *y)),
+            )
+            .fold(0.0f32, f32::max)
+            .max(1.0);
+
+        let tallest_bar = series
+            .iter()
+            .flat_map(|s| s.bins.iter().map(|b| b.value))
+            .fold(0.0f32, f32::max);
+        let stagger_base = if tallest_bar > 0.0 { tallest_bar } else { max_bar };
+        let stagger_step = stagger_base * options.stagger;
+        let max_y = max_bar + stagger_step * series.len().saturating_sub(1) as f32;
+
+        let (x_spec, y_spec) = create_axis_specs(
+            &options.axis.range,
+            &(0.0..=max_y),
+            &options.axis.transform,
+            &TransformType::Linear,
+        )?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(base.margin)
+            .caption(&base.title, ("sans-serif", 20).into_font())
+            .x_label_area_size(base.x_label_area_size)
+            .y_label_area_size(base.y_label_area_size)
+            .build_cartesian_2d(x_spec.start..x_spec.end, y_spec.start..y_spec.end)
+            .map_err(|e| anyhow::anyhow!("failed to build chart: {e:?}"))?;
+
+        let transform_clone = options.axis.transform.clone();
+        let x_formatter = move |x: &f32| -> String { format_transform_value(&transform_clone, x) };
+
+        {
+            let mut mesh = chart.configure_mesh();
+            mesh.x_label_formatter(&x_formatter);
+            if let Some(ref label) = options.axis.label {
+                mesh.x_desc(label);
+            }
+            mesh.y_desc("Count")
+                .draw()
+                .map_err(|e| anyhow::anyhow!("failed to draw plot mesh: {e:?}"))?;
+        }
+
+        let mut has_labels = false;
+
+        for (i, s) in series.iter().enumerate() {
+            let offset = stagger_step * i as f32;
+            let fill = RGBAColor(s.color.0, s.color.1, s.color.2, 0.6);
+
+            let drawn = chart
+                .draw_series(s.bins.iter().map(|bin| {
+                    Rectangle::new([(bin.start, offset), (bin.end, bin.value + offset)], fill.filled())
+                }))
+                .map_err(|e| anyhow::anyhow!("failed to draw histogram bars: {e:?}"))?;
+
+            if let Some(ref label) = s.label {
+                has_labels = true;
+                let color = s.color;
+                drawn.label(label).legend(move |(x, y)| {
+                    Rectangle::new([(x - 8, y - 4), (x + 8, y + 4)], color.filled())
+                });
+            }
+
+            if let Some(ref curve) = s.kde_curve {
+                chart
+                    .draw_series(LineSeries::new(
+                        curve.iter().map(|(x, y)| (*x, *y + offset)),
+                        s.color.stroke_width(2),
+                    ))
+                    .map_err(|e| anyhow::anyhow!("failed to draw KDE curve: {e:?}"))?;
+            }
+        }
+
+        if has_labels {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(|e| anyhow::anyhow!("failed to draw legend: {e:?}"))?;
+        }
+
+        root.present()
+            .map_err(|e| anyhow::anyhow!("failed to present plotters buffer: {e}"))?;
+    }
+
+    let img: image::RgbImage = image::ImageBuffer::from_vec(width, height, pixel_buffer)
+        .ok_or_else(|| anyhow::anyhow!("plot image buffer had unexpected size"))?;
+
+    let mut encoded_data = Vec::new();
+    let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded_data, 85);
+    encoder
+        .encode(img.as_raw(), width, height, image::ExtendedColorType::Rgb8)
+        .map_err(|e| anyhow::anyhow!("failed to JPEG encode plot: {e}"))?;
+
+    render_config.report_progress(crate::render::ProgressInfo { pixels: Vec::new(), percent: 100.0 });
+
+    Ok(encoded_data)
+}
@@ -1,3 +1,4 @@
+pub mod histogram_backend;
 pub mod plotters_backend;
 pub mod progress;
 
@@ -8,7 +8,7 @@ use flow_fcs::{TransformType, Transformable};
 /// Format a value using the transform type
 ///
 /// This replicates the Formattable::format logic since the trait is not exported.
-fn format_transform_value(transform: &TransformType, value: &f32) -> String {
+pub(crate) fn format_transform_value(transform: &TransformType, value: &f32) -> String {
     match transform {
         TransformType::Linear => format!("{:.1e}", value),
         TransformType::Arcsinh { cofactor } => {
@@ -17,7 +17,9 @@ fn format_transform_value(transform: &TransformType, value: &f32) -> String {
             // Make nice rounded labels in original space
             format!("{:.1e}", original_value)
         }
-        TransformType::Biexponential { .. } => {
+        TransformType::Biexponential { .. }
+        | TransformType::Logicle { .. }
+        | TransformType::Custom(_) => {
             // Convert from transformed space back to original space using inverse transform
             let original_value = transform.inverse_transform(value);
             // Make nice rounded labels in original space
@@ -16,7 +16,7 @@ mod tests {
     use std::fs::File;
     use std::io::Write;
     // Note: Some imports may not be needed but are kept for future use
-    use std::sync::Arc;
+    use std::sync::{Arc, RwLock};
 
     // Helper to create a test FCS struct
     fn create_test_fcs() -> anyhow::Result<Fcs> {
@@ -89,6 +89,7 @@ mod tests {
             parameters: params,
             data_frame: Arc::new(df),
             file_access: flow_fcs::file::AccessWrapper::new(temp_path.to_str().unwrap_or(""))?,
+            channel_range_cache: Arc::new(RwLock::new(None)),
         })
     }
 